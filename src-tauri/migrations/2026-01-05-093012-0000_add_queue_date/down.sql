@@ -0,0 +1,20 @@
+-- queue_date を削除して単一のグローバルキューに戻す
+
+CREATE TABLE task_queue_old (
+    task_id     TEXT PRIMARY KEY NOT NULL,
+    position    INTEGER NOT NULL,
+    added_at    TEXT NOT NULL,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+INSERT INTO task_queue_old (task_id, position, added_at)
+SELECT task_id,
+       ROW_NUMBER() OVER (ORDER BY queue_date, position) - 1,
+       added_at
+FROM task_queue;
+
+DROP TABLE task_queue;
+
+ALTER TABLE task_queue_old RENAME TO task_queue;
+
+CREATE INDEX idx_task_queue_position ON task_queue (position);
@@ -0,0 +1,21 @@
+-- task_queue に対象日（queue_date）を追加し、日付ごとのキューにする
+-- SQLiteではNOT NULLカラムをデフォルト値なしで追加できないため、テーブルを再作成する
+-- 既存のエントリは今日のキューとして扱う
+
+CREATE TABLE task_queue_new (
+    task_id     TEXT PRIMARY KEY NOT NULL,
+    position    INTEGER NOT NULL,
+    added_at    TEXT NOT NULL,
+    queue_date  TEXT NOT NULL,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+INSERT INTO task_queue_new (task_id, position, added_at, queue_date)
+SELECT task_id, position, added_at, date('now', 'localtime')
+FROM task_queue;
+
+DROP TABLE task_queue;
+
+ALTER TABLE task_queue_new RENAME TO task_queue;
+
+CREATE INDEX idx_task_queue_date_position ON task_queue (queue_date, position);
@@ -0,0 +1,2 @@
+DROP TABLE queue_history;
+DROP TABLE app_settings;
@@ -0,0 +1,21 @@
+-- アプリ設定（キー・値）
+CREATE TABLE app_settings (
+    key         TEXT PRIMARY KEY NOT NULL,
+    value       TEXT NOT NULL,
+    updated_at  TEXT NOT NULL
+);
+
+-- キュー履歴（日ごとのキューのスナップショット）
+-- タスク削除後も履歴を残すため、tasksへの外部キーは設定しない
+CREATE TABLE queue_history (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    queue_date  TEXT NOT NULL,
+    task_id     TEXT NOT NULL,
+    task_title  TEXT NOT NULL,
+    position    INTEGER NOT NULL,
+    outcome     TEXT NOT NULL,
+    recorded_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_queue_history_queue_date ON queue_history (queue_date);
+CREATE INDEX idx_queue_history_task_id ON queue_history (task_id);
@@ -0,0 +1,2 @@
+-- tasks に見積もり時間（分）を追加（未設定はNULL）
+ALTER TABLE tasks ADD COLUMN estimated_minutes INTEGER;
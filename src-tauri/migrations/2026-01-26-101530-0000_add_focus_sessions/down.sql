@@ -0,0 +1 @@
+DROP TABLE focus_sessions;
@@ -0,0 +1,20 @@
+-- 集中セッション（ポモドーロ）
+-- タスク削除後もレポートに残すため、tasksへの外部キーは設定しない
+CREATE TABLE focus_sessions (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    task_id         TEXT NOT NULL,
+    task_title      TEXT NOT NULL,
+    queue_date      TEXT NOT NULL,
+    state           TEXT NOT NULL,
+    work_minutes    INTEGER NOT NULL,
+    break_minutes   INTEGER NOT NULL,
+    started_at      TEXT NOT NULL,
+    paused_at       TEXT,
+    paused_seconds  INTEGER NOT NULL DEFAULT 0,
+    ended_at        TEXT,
+    focused_seconds INTEGER NOT NULL DEFAULT 0,
+    completed_task  BOOLEAN NOT NULL DEFAULT 0
+);
+
+CREATE INDEX idx_focus_sessions_task_id ON focus_sessions (task_id);
+CREATE INDEX idx_focus_sessions_queue_date ON focus_sessions (queue_date);
@@ -0,0 +1,22 @@
+-- rank を日ごとの連番 position に戻す
+
+CREATE TABLE task_queue_old (
+    task_id     TEXT PRIMARY KEY NOT NULL,
+    position    INTEGER NOT NULL,
+    added_at    TEXT NOT NULL,
+    queue_date  TEXT NOT NULL,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+INSERT INTO task_queue_old (task_id, position, added_at, queue_date)
+SELECT task_id,
+       ROW_NUMBER() OVER (PARTITION BY queue_date ORDER BY rank) - 1,
+       added_at,
+       queue_date
+FROM task_queue;
+
+DROP TABLE task_queue;
+
+ALTER TABLE task_queue_old RENAME TO task_queue;
+
+CREATE INDEX idx_task_queue_date_position ON task_queue (queue_date, position);
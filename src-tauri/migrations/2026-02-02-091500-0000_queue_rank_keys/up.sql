@@ -0,0 +1,23 @@
+-- task_queue の position（連番）を並び順キー rank（LexoRank方式の文字列）に置き換える
+-- 同じ日のキュー内で rank は一意（移動・削除時に他の行を振り直さない）
+-- 既存のエントリは日ごとの順序を保ったまま固定長の数字 + "i" のキーに変換する
+
+CREATE TABLE task_queue_new (
+    task_id     TEXT PRIMARY KEY NOT NULL,
+    rank        TEXT NOT NULL,
+    added_at    TEXT NOT NULL,
+    queue_date  TEXT NOT NULL,
+    UNIQUE (queue_date, rank),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+INSERT INTO task_queue_new (task_id, rank, added_at, queue_date)
+SELECT task_id,
+       printf('%06di', ROW_NUMBER() OVER (PARTITION BY queue_date ORDER BY position, added_at) - 1),
+       added_at,
+       queue_date
+FROM task_queue;
+
+DROP TABLE task_queue;
+
+ALTER TABLE task_queue_new RENAME TO task_queue;
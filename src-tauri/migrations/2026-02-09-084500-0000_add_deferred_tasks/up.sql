@@ -0,0 +1,11 @@
+-- 延期（スヌーズ）中のタスク
+-- until_date になるとロールオーバーで今日のキューへ戻し、行を削除する
+CREATE TABLE deferred_tasks (
+    task_id         TEXT PRIMARY KEY NOT NULL,
+    until_date      TEXT NOT NULL,
+    deferred_from   TEXT NOT NULL,
+    deferred_at     TEXT NOT NULL,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_deferred_tasks_until_date ON deferred_tasks (until_date);
@@ -0,0 +1,45 @@
+-- 名前付きキューを削除し、すべてのエントリを1つのキューに戻す
+-- 日ごとの rank の一意性を保つため、既定のキュー以外のエントリは同じ日の末尾に並べ直す
+
+CREATE TABLE task_queue_old (
+    task_id     TEXT PRIMARY KEY NOT NULL,
+    rank        TEXT NOT NULL,
+    added_at    TEXT NOT NULL,
+    queue_date  TEXT NOT NULL,
+    UNIQUE (queue_date, rank),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+INSERT INTO task_queue_old (task_id, rank, added_at, queue_date)
+SELECT task_id,
+       printf('%06di', ROW_NUMBER() OVER (
+           PARTITION BY queue_date
+           ORDER BY queue_id <> 'default', queue_id, rank
+       ) - 1),
+       added_at,
+       queue_date
+FROM task_queue;
+
+DROP TABLE task_queue;
+
+ALTER TABLE task_queue_old RENAME TO task_queue;
+
+CREATE TABLE deferred_tasks_old (
+    task_id         TEXT PRIMARY KEY NOT NULL,
+    until_date      TEXT NOT NULL,
+    deferred_from   TEXT NOT NULL,
+    deferred_at     TEXT NOT NULL,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+INSERT INTO deferred_tasks_old (task_id, until_date, deferred_from, deferred_at)
+SELECT task_id, until_date, deferred_from, deferred_at
+FROM deferred_tasks;
+
+DROP TABLE deferred_tasks;
+
+ALTER TABLE deferred_tasks_old RENAME TO deferred_tasks;
+
+CREATE INDEX idx_deferred_tasks_until_date ON deferred_tasks (until_date);
+
+DROP TABLE queues;
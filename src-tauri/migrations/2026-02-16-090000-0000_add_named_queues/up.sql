@@ -0,0 +1,56 @@
+-- 名前付きキュー（「仕事」「家」などのコンテキスト）
+-- 既存のエントリ・延期中のタスクは既定のキュー（id = 'default'）に所属させる
+-- 1つのタスクが所属できるキューは1つだけ（task_queue の主キーは task_id のまま）
+
+CREATE TABLE queues (
+    id          TEXT PRIMARY KEY NOT NULL,
+    name        TEXT NOT NULL UNIQUE,
+    created_at  TEXT NOT NULL,
+    updated_at  TEXT NOT NULL
+);
+
+INSERT INTO queues (id, name, created_at, updated_at)
+VALUES ('default', 'デフォルト',
+        strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now'),
+        strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now'));
+
+-- rank はキュー・日ごとに一意
+CREATE TABLE task_queue_new (
+    task_id     TEXT PRIMARY KEY NOT NULL,
+    queue_id    TEXT NOT NULL DEFAULT 'default',
+    rank        TEXT NOT NULL,
+    added_at    TEXT NOT NULL,
+    queue_date  TEXT NOT NULL,
+    UNIQUE (queue_id, queue_date, rank),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (queue_id) REFERENCES queues(id)
+);
+
+INSERT INTO task_queue_new (task_id, queue_id, rank, added_at, queue_date)
+SELECT task_id, 'default', rank, added_at, queue_date
+FROM task_queue;
+
+DROP TABLE task_queue;
+
+ALTER TABLE task_queue_new RENAME TO task_queue;
+
+-- 延期中のタスクは延期元のキューへ戻す
+CREATE TABLE deferred_tasks_new (
+    task_id         TEXT PRIMARY KEY NOT NULL,
+    queue_id        TEXT NOT NULL DEFAULT 'default',
+    until_date      TEXT NOT NULL,
+    deferred_from   TEXT NOT NULL,
+    deferred_at     TEXT NOT NULL,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (queue_id) REFERENCES queues(id)
+);
+
+INSERT INTO deferred_tasks_new (task_id, queue_id, until_date, deferred_from, deferred_at)
+SELECT task_id, 'default', until_date, deferred_from, deferred_at
+FROM deferred_tasks;
+
+DROP TABLE deferred_tasks;
+
+ALTER TABLE deferred_tasks_new RENAME TO deferred_tasks;
+
+CREATE INDEX idx_deferred_tasks_until_date ON deferred_tasks (until_date);
@@ -0,0 +1,13 @@
+-- tags に親タグ（名前空間）を追加
+-- name は "work/clientA/billing" のような完全なパスのまま保持し、parent_id で親のパスのタグを指す
+ALTER TABLE tags ADD COLUMN parent_id TEXT;
+
+-- 既存の a/b 形式のタグは、親のパスのタグが存在する場合に紐付ける
+UPDATE tags
+SET parent_id = (
+    SELECT p.id FROM tags p
+    WHERE substr(tags.name, 1, length(p.name) + 1) = p.name || '/'
+      AND instr(substr(tags.name, length(p.name) + 2), '/') = 0
+);
+
+CREATE INDEX idx_tags_parent_id ON tags (parent_id);
@@ -0,0 +1,3 @@
+DROP INDEX idx_tags_group_id;
+ALTER TABLE tags DROP COLUMN group_id;
+DROP TABLE tag_groups;
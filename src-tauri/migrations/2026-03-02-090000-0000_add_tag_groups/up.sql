@@ -0,0 +1,13 @@
+-- タググループ（"サイズ" = S/M/L など）。exclusive のグループのタグは1つのタスクに1つまで
+CREATE TABLE tag_groups (
+    id          TEXT PRIMARY KEY NOT NULL,
+    name        TEXT NOT NULL UNIQUE,
+    exclusive   BOOLEAN NOT NULL DEFAULT 0,
+    created_at  TEXT NOT NULL,
+    updated_at  TEXT NOT NULL
+);
+
+-- タグは最大1つのグループに所属する（グループ削除時はサービス層で所属を解除）
+ALTER TABLE tags ADD COLUMN group_id TEXT;
+
+CREATE INDEX idx_tags_group_id ON tags (group_id);
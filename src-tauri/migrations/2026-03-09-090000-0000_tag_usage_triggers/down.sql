@@ -0,0 +1,9 @@
+DROP TRIGGER tags_usage_after_task_tag_insert;
+DROP TRIGGER tags_usage_after_task_tag_delete;
+DROP TRIGGER tags_usage_after_task_tag_update;
+DROP TRIGGER tags_usage_after_task_status_update;
+DROP TRIGGER tags_usage_before_task_delete;
+ALTER TABLE tags DROP COLUMN draft_count;
+ALTER TABLE tags DROP COLUMN active_count;
+ALTER TABLE tags DROP COLUMN completed_count;
+ALTER TABLE tags DROP COLUMN archived_count;
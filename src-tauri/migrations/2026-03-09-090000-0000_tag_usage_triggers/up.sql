@@ -0,0 +1,78 @@
+-- tags の使用数（usage_count）と、タスクのステータスごとの使用数をトリガーで維持する
+-- 各トリガーは変更された行の分だけ件数を増減する（全件の数え直しは最初の1回だけ）
+-- タスクの削除時は CASCADE で task_tags が消える前（BEFORE DELETE）に減らし、
+-- task_tags のトリガーはタスクが存在する場合のみ件数を変更する（二重に減らさないため）
+
+ALTER TABLE tags ADD COLUMN draft_count INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE tags ADD COLUMN active_count INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE tags ADD COLUMN completed_count INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE tags ADD COLUMN archived_count INTEGER NOT NULL DEFAULT 0;
+
+-- 既存のタグの件数を計算
+UPDATE tags SET
+    usage_count = (SELECT COUNT(*) FROM task_tags tt JOIN tasks t ON t.id = tt.task_id WHERE tt.tag_id = tags.id),
+    draft_count = (SELECT COUNT(*) FROM task_tags tt JOIN tasks t ON t.id = tt.task_id WHERE tt.tag_id = tags.id AND t.status = 'draft'),
+    active_count = (SELECT COUNT(*) FROM task_tags tt JOIN tasks t ON t.id = tt.task_id WHERE tt.tag_id = tags.id AND t.status = 'active'),
+    completed_count = (SELECT COUNT(*) FROM task_tags tt JOIN tasks t ON t.id = tt.task_id WHERE tt.tag_id = tags.id AND t.status = 'completed'),
+    archived_count = (SELECT COUNT(*) FROM task_tags tt JOIN tasks t ON t.id = tt.task_id WHERE tt.tag_id = tags.id AND t.status = 'archived');
+
+CREATE TRIGGER tags_usage_after_task_tag_insert AFTER INSERT ON task_tags
+BEGIN
+    UPDATE tags SET
+        usage_count = usage_count + 1,
+        draft_count = draft_count + ((SELECT status FROM tasks WHERE id = NEW.task_id) = 'draft'),
+        active_count = active_count + ((SELECT status FROM tasks WHERE id = NEW.task_id) = 'active'),
+        completed_count = completed_count + ((SELECT status FROM tasks WHERE id = NEW.task_id) = 'completed'),
+        archived_count = archived_count + ((SELECT status FROM tasks WHERE id = NEW.task_id) = 'archived')
+    WHERE id = NEW.tag_id AND EXISTS (SELECT 1 FROM tasks WHERE id = NEW.task_id);
+END;
+
+CREATE TRIGGER tags_usage_after_task_tag_delete AFTER DELETE ON task_tags
+BEGIN
+    UPDATE tags SET
+        usage_count = usage_count - 1,
+        draft_count = draft_count - ((SELECT status FROM tasks WHERE id = OLD.task_id) = 'draft'),
+        active_count = active_count - ((SELECT status FROM tasks WHERE id = OLD.task_id) = 'active'),
+        completed_count = completed_count - ((SELECT status FROM tasks WHERE id = OLD.task_id) = 'completed'),
+        archived_count = archived_count - ((SELECT status FROM tasks WHERE id = OLD.task_id) = 'archived')
+    WHERE id = OLD.tag_id AND EXISTS (SELECT 1 FROM tasks WHERE id = OLD.task_id);
+END;
+
+CREATE TRIGGER tags_usage_after_task_tag_update AFTER UPDATE ON task_tags
+BEGIN
+    UPDATE tags SET
+        usage_count = usage_count - 1,
+        draft_count = draft_count - ((SELECT status FROM tasks WHERE id = OLD.task_id) = 'draft'),
+        active_count = active_count - ((SELECT status FROM tasks WHERE id = OLD.task_id) = 'active'),
+        completed_count = completed_count - ((SELECT status FROM tasks WHERE id = OLD.task_id) = 'completed'),
+        archived_count = archived_count - ((SELECT status FROM tasks WHERE id = OLD.task_id) = 'archived')
+    WHERE id = OLD.tag_id AND EXISTS (SELECT 1 FROM tasks WHERE id = OLD.task_id);
+    UPDATE tags SET
+        usage_count = usage_count + 1,
+        draft_count = draft_count + ((SELECT status FROM tasks WHERE id = NEW.task_id) = 'draft'),
+        active_count = active_count + ((SELECT status FROM tasks WHERE id = NEW.task_id) = 'active'),
+        completed_count = completed_count + ((SELECT status FROM tasks WHERE id = NEW.task_id) = 'completed'),
+        archived_count = archived_count + ((SELECT status FROM tasks WHERE id = NEW.task_id) = 'archived')
+    WHERE id = NEW.tag_id AND EXISTS (SELECT 1 FROM tasks WHERE id = NEW.task_id);
+END;
+
+CREATE TRIGGER tags_usage_after_task_status_update AFTER UPDATE OF status ON tasks WHEN OLD.status <> NEW.status
+BEGIN
+    UPDATE tags SET
+        draft_count = draft_count - (OLD.status = 'draft') + (NEW.status = 'draft'),
+        active_count = active_count - (OLD.status = 'active') + (NEW.status = 'active'),
+        completed_count = completed_count - (OLD.status = 'completed') + (NEW.status = 'completed'),
+        archived_count = archived_count - (OLD.status = 'archived') + (NEW.status = 'archived')
+    WHERE id IN (SELECT tag_id FROM task_tags WHERE task_id = NEW.id);
+END;
+
+CREATE TRIGGER tags_usage_before_task_delete BEFORE DELETE ON tasks
+BEGIN
+    UPDATE tags SET
+        usage_count = usage_count - 1,
+        draft_count = draft_count - (OLD.status = 'draft'),
+        active_count = active_count - (OLD.status = 'active'),
+        completed_count = completed_count - (OLD.status = 'completed'),
+        archived_count = archived_count - (OLD.status = 'archived')
+    WHERE id IN (SELECT tag_id FROM task_tags WHERE task_id = OLD.id);
+END;
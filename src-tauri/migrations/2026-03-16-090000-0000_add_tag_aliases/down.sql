@@ -0,0 +1,2 @@
+DROP INDEX idx_tag_aliases_tag_id;
+DROP TABLE tag_aliases;
@@ -0,0 +1,8 @@
+-- タグの別名（"mtg" / "会議" → "meeting" など）。タスクへのタグ付けと検索で正式なタグに読み替える
+CREATE TABLE tag_aliases (
+    alias       TEXT PRIMARY KEY NOT NULL,
+    tag_id      TEXT NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+    created_at  TEXT NOT NULL
+);
+
+CREATE INDEX idx_tag_aliases_tag_id ON tag_aliases (tag_id);
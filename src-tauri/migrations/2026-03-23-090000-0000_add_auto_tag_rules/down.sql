@@ -0,0 +1,3 @@
+DROP INDEX idx_auto_tag_rule_tags_tag_id;
+DROP TABLE auto_tag_rule_tags;
+DROP TABLE auto_tag_rules;
@@ -0,0 +1,21 @@
+-- 自動タグ付けルール（タイトル・説明・親タスクの条件に一致したタスクにタグを付ける）
+-- target: title / description / parent_id、match_type: contains / regex / equals
+CREATE TABLE auto_tag_rules (
+    id          TEXT PRIMARY KEY NOT NULL,
+    name        TEXT NOT NULL,
+    target      TEXT NOT NULL,
+    match_type  TEXT NOT NULL,
+    pattern     TEXT NOT NULL,
+    enabled     BOOLEAN NOT NULL DEFAULT 1,
+    created_at  TEXT NOT NULL,
+    updated_at  TEXT NOT NULL
+);
+
+-- ルールに一致したタスクに付けるタグ
+CREATE TABLE auto_tag_rule_tags (
+    rule_id     TEXT NOT NULL REFERENCES auto_tag_rules(id) ON DELETE CASCADE,
+    tag_id      TEXT NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+    PRIMARY KEY (rule_id, tag_id)
+);
+
+CREATE INDEX idx_auto_tag_rule_tags_tag_id ON auto_tag_rule_tags (tag_id);
@@ -0,0 +1,55 @@
+//! 日次ロールオーバーをUIなしで実行するCLI
+//!
+//! 使い方: `tms-rollover <DBファイルパス> [--now YYYY-MM-DDTHH:MM:SS]`
+//!
+//! アプリと同じマイグレーションを適用したうえで `RolloverService::rollover` を実行し、
+//! 結果をJSONで標準出力に書き出す。`--now` で任意の時刻を指定して動作を確認できる。
+
+use std::path::PathBuf;
+
+use chrono::{Local, NaiveDateTime};
+use diesel::prelude::*;
+use diesel::SqliteConnection;
+use tms_v2_lib::service::RolloverService;
+
+const USAGE: &str = "使い方: tms-rollover <DBファイルパス> [--now YYYY-MM-DDTHH:MM:SS]";
+
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+fn run() -> Result<(), String> {
+    let mut args = std::env::args().skip(1);
+    let db_path = PathBuf::from(args.next().ok_or(USAGE)?);
+
+    let mut now = Local::now().naive_local();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--now" => {
+                let value = args.next().ok_or(USAGE)?;
+                now = NaiveDateTime::parse_from_str(&value, "%Y-%m-%dT%H:%M:%S")
+                    .map_err(|e| format!("--now の形式が不正です: {}", e))?;
+            }
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+
+    tms_v2_lib::db::open_and_migrate(&db_path).map_err(|e| e.to_string())?;
+
+    let database_url = db_path.to_str().ok_or("Invalid database path")?;
+    let mut conn = SqliteConnection::establish(database_url).map_err(|e| e.to_string())?;
+    diesel::sql_query("PRAGMA foreign_keys = ON;")
+        .execute(&mut conn)
+        .map_err(|e| e.to_string())?;
+
+    let result = RolloverService::rollover(&mut conn, now).map_err(|e| e.to_string())?;
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&result).map_err(|e| e.to_string())?
+    );
+
+    Ok(())
+}
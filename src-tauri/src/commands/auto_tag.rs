@@ -0,0 +1,69 @@
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use tauri::State;
+
+use crate::commands::quick_switch::refresh_index;
+use crate::models::auto_tag::{
+    AutoTagRuleResponse, CreateAutoTagRuleRequest, UpdateAutoTagRuleRequest,
+};
+use crate::service::{AutoTagService, QuickSwitchState};
+
+/// データベース接続プール型
+pub type DbPool = Pool<ConnectionManager<SqliteConnection>>;
+
+/// 自動タグ付けルール一覧取得（作成順）
+#[tauri::command]
+pub fn list_auto_tag_rules(pool: State<DbPool>) -> Result<Vec<AutoTagRuleResponse>, String> {
+    let mut conn = pool
+        .get()
+        .map_err(|e| format!("データベース接続エラー: {}", e))?;
+    AutoTagService::list_rules(&mut conn).map_err(|e| e.to_string())
+}
+
+/// 自動タグ付けルール作成
+#[tauri::command]
+pub fn create_auto_tag_rule(
+    pool: State<DbPool>,
+    request: CreateAutoTagRuleRequest,
+) -> Result<AutoTagRuleResponse, String> {
+    let mut conn = pool
+        .get()
+        .map_err(|e| format!("データベース接続エラー: {}", e))?;
+    AutoTagService::create_rule(&mut conn, request).map_err(|e| e.to_string())
+}
+
+/// 自動タグ付けルール更新
+#[tauri::command]
+pub fn update_auto_tag_rule(
+    pool: State<DbPool>,
+    rule_id: String,
+    request: UpdateAutoTagRuleRequest,
+) -> Result<AutoTagRuleResponse, String> {
+    let mut conn = pool
+        .get()
+        .map_err(|e| format!("データベース接続エラー: {}", e))?;
+    AutoTagService::update_rule(&mut conn, &rule_id, request).map_err(|e| e.to_string())
+}
+
+/// 自動タグ付けルール削除（付けたタグはタスクに残る）
+#[tauri::command]
+pub fn delete_auto_tag_rule(pool: State<DbPool>, rule_id: String) -> Result<(), String> {
+    let mut conn = pool
+        .get()
+        .map_err(|e| format!("データベース接続エラー: {}", e))?;
+    AutoTagService::delete_rule(&mut conn, &rule_id).map_err(|e| e.to_string())
+}
+
+/// 有効なルールをすべてのタスク（Archivedを除く）に適用し直す（タグを付けたタスク数を返す）
+#[tauri::command]
+pub fn apply_auto_tag_rules(
+    pool: State<DbPool>,
+    index: State<QuickSwitchState>,
+) -> Result<usize, String> {
+    let mut conn = pool
+        .get()
+        .map_err(|e| format!("データベース接続エラー: {}", e))?;
+    let task_ids = AutoTagService::apply_to_all(&mut conn).map_err(|e| e.to_string())?;
+    refresh_index(&index, &mut conn, &task_ids)?;
+    Ok(task_ids.len())
+}
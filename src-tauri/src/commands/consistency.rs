@@ -0,0 +1,26 @@
+use tauri::State;
+
+use crate::commands::quick_switch::refresh_index;
+use crate::models::consistency::ConsistencyReport;
+use crate::service::{ConsistencyService, QuickSwitchState};
+use crate::DbPool;
+
+/// タスクとキューの整合性をチェック（repair = true の場合は修復も行う）
+#[tauri::command]
+pub fn check_consistency(
+    pool: State<DbPool>,
+    index: State<QuickSwitchState>,
+    repair: Option<bool>,
+) -> Result<ConsistencyReport, String> {
+    let mut conn = pool
+        .get()
+        .map_err(|e| format!("データベース接続エラー: {}", e))?;
+
+    if !repair.unwrap_or(false) {
+        return ConsistencyService::check(&mut conn).map_err(|e| e.to_string());
+    }
+
+    let report = ConsistencyService::repair(&mut conn).map_err(|e| e.to_string())?;
+    refresh_index(&index, &mut conn, &report.affected_task_ids())?;
+    Ok(report)
+}
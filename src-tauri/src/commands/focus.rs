@@ -0,0 +1,88 @@
+use chrono::Utc;
+use tauri::State;
+
+use crate::commands::quick_switch::refresh_index;
+use crate::models::focus::FocusSessionResponse;
+use crate::models::queue::DEFAULT_QUEUE_ID;
+use crate::models::settings::FocusSettings;
+use crate::service::{FocusService, QuickSwitchState, SettingsService};
+use crate::DbPool;
+
+/// 指定キュー（省略時は既定のキュー）の今日の先頭のタスクで集中セッションを開始
+#[tauri::command]
+pub fn start_focus_session(
+    pool: State<DbPool>,
+    queue_id: Option<String>,
+) -> Result<FocusSessionResponse, String> {
+    let queue_id = queue_id.unwrap_or_else(|| DEFAULT_QUEUE_ID.to_string());
+    let mut conn = pool
+        .get()
+        .map_err(|e| format!("データベース接続エラー: {}", e))?;
+    FocusService::start_session(&mut conn, &queue_id, Utc::now()).map_err(|e| e.to_string())
+}
+
+/// 進行中の集中セッションを一時停止
+#[tauri::command]
+pub fn pause_focus_session(pool: State<DbPool>) -> Result<FocusSessionResponse, String> {
+    let mut conn = pool
+        .get()
+        .map_err(|e| format!("データベース接続エラー: {}", e))?;
+    FocusService::pause_session(&mut conn, Utc::now()).map_err(|e| e.to_string())
+}
+
+/// 一時停止中の集中セッションを再開
+#[tauri::command]
+pub fn resume_focus_session(pool: State<DbPool>) -> Result<FocusSessionResponse, String> {
+    let mut conn = pool
+        .get()
+        .map_err(|e| format!("データベース接続エラー: {}", e))?;
+    FocusService::resume_session(&mut conn, Utc::now()).map_err(|e| e.to_string())
+}
+
+/// 集中セッションを終了（`complete_task` が true の場合はタスクを完了にする）
+#[tauri::command]
+pub fn finish_focus_session(
+    pool: State<DbPool>,
+    index: State<QuickSwitchState>,
+    complete_task: bool,
+) -> Result<FocusSessionResponse, String> {
+    let mut conn = pool
+        .get()
+        .map_err(|e| format!("データベース接続エラー: {}", e))?;
+    let session = FocusService::finish_session(&mut conn, complete_task, Utc::now())
+        .map_err(|e| e.to_string())?;
+    if session.completed_task {
+        refresh_index(&index, &mut conn, std::slice::from_ref(&session.task_id))?;
+    }
+    Ok(session)
+}
+
+/// 進行中の集中セッションを取得（無い場合はnull）
+#[tauri::command]
+pub fn get_focus_session(pool: State<DbPool>) -> Result<Option<FocusSessionResponse>, String> {
+    let mut conn = pool
+        .get()
+        .map_err(|e| format!("データベース接続エラー: {}", e))?;
+    FocusService::get_current_session(&mut conn, Utc::now()).map_err(|e| e.to_string())
+}
+
+/// 集中セッション設定（作業時間・休憩時間）を取得
+#[tauri::command]
+pub fn get_focus_settings(pool: State<DbPool>) -> Result<FocusSettings, String> {
+    let mut conn = pool
+        .get()
+        .map_err(|e| format!("データベース接続エラー: {}", e))?;
+    SettingsService::get_focus_settings(&mut conn).map_err(|e| e.to_string())
+}
+
+/// 集中セッション設定を更新
+#[tauri::command]
+pub fn update_focus_settings(
+    pool: State<DbPool>,
+    settings: FocusSettings,
+) -> Result<FocusSettings, String> {
+    let mut conn = pool
+        .get()
+        .map_err(|e| format!("データベース接続エラー: {}", e))?;
+    SettingsService::update_focus_settings(&mut conn, settings).map_err(|e| e.to_string())
+}
@@ -1,3 +1,11 @@
+pub mod auto_tag;
+pub mod consistency;
+pub mod focus;
 pub mod queue;
+pub mod quick_switch;
+pub mod rollover;
+pub mod schedule;
+pub mod similarity;
+pub mod stats;
 pub mod tag;
 pub mod task;
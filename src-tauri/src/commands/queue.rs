@@ -1,52 +1,215 @@
+use chrono::{Local, NaiveDate, Utc};
+use diesel::SqliteConnection;
 use tauri::State;
 
+use crate::commands::quick_switch::refresh_index;
 use crate::models::queue::{
-    AddToQueueRequest, CompleteAllQueueResponse, QueueEntry, QueueEntryWithTask,
-    RemoveFromQueueRequest, ReorderQueueRequest, UpdateQueueRequest,
+    AddToQueueRequest, CompleteAllQueueResponse, CreateQueueRequest, DeferQueueEntryRequest,
+    DeferredTask, DeferredTaskWithTitle, EnqueueByFilterRequest, EnqueueByFilterResult,
+    EnqueueSubtreeRequest, MoveQueueEntryRequest, Queue, QueueEntry, QueueSchedule,
+    RemoveFromQueueRequest, RenameQueueRequest, ReorderQueueRequest, UpdateQueueRequest,
+    DEFAULT_QUEUE_ID,
+};
+use crate::models::settings::QueueLimits;
+use crate::models::suggestion::QueueSuggestion;
+use crate::service::{
+    QueueService, QuickSwitchState, ScheduleService, SettingsService, SuggestionService,
 };
-use crate::service::QueueService;
 use crate::DbPool;
 
-/// キュー全体を取得
+/// 名前付きキューの一覧を取得
+#[tauri::command]
+pub fn get_queues(pool: State<DbPool>) -> Result<Vec<Queue>, String> {
+    let mut conn = pool.get().map_err(|e| format!("データベース接続エラー: {}", e))?;
+    QueueService::list_queues(&mut conn).map_err(|e| e.to_string())
+}
+
+/// 名前付きキューを作成
+#[tauri::command]
+pub fn create_queue(pool: State<DbPool>, req: CreateQueueRequest) -> Result<Queue, String> {
+    let mut conn = pool.get().map_err(|e| format!("データベース接続エラー: {}", e))?;
+    QueueService::create_queue(&mut conn, req.name).map_err(|e| e.to_string())
+}
+
+/// 名前付きキューの名前を変更
 #[tauri::command]
-pub fn get_task_queue(pool: State<DbPool>) -> Result<Vec<QueueEntryWithTask>, String> {
+pub fn rename_queue(pool: State<DbPool>, req: RenameQueueRequest) -> Result<Queue, String> {
     let mut conn = pool.get().map_err(|e| format!("データベース接続エラー: {}", e))?;
-    QueueService::get_queue(&mut conn).map_err(|e| e.to_string())
+    QueueService::rename_queue(&mut conn, &req.queue_id, req.name).map_err(|e| e.to_string())
+}
+
+/// 名前付きキューを削除（タスクが残っているキュー・既定のキューは削除できない）
+#[tauri::command]
+pub fn delete_queue(pool: State<DbPool>, queue_id: String) -> Result<(), String> {
+    let mut conn = pool.get().map_err(|e| format!("データベース接続エラー: {}", e))?;
+    QueueService::delete_queue(&mut conn, &queue_id).map_err(|e| e.to_string())
+}
+
+/// 指定日のキューを予定付きで取得（キュー省略時は既定のキュー、日付省略時は今日）
+///
+/// 各エントリの予定開始・終了時刻と見積もり合計を含み、
+/// 合計が1日の作業時間を超える場合は `overloaded` が true になる。
+#[tauri::command]
+pub fn get_task_queue(
+    pool: State<DbPool>,
+    queue_id: Option<String>,
+    date: Option<String>,
+) -> Result<QueueSchedule, String> {
+    let queue_id = queue_id_or_default(queue_id);
+    let queue_date = parse_date(date.as_deref())?;
+    let mut conn = pool.get().map_err(|e| format!("データベース接続エラー: {}", e))?;
+    ScheduleService::get_schedule(&mut conn, &queue_id, queue_date, Local::now().naive_local())
+        .map_err(|e| e.to_string())
 }
 
 /// タスクをキューに追加
 #[tauri::command]
 pub fn add_task_to_queue(
     pool: State<DbPool>,
+    index: State<QuickSwitchState>,
     req: AddToQueueRequest,
 ) -> Result<QueueEntry, String> {
+    let queue_date = parse_date(req.queue_date.as_deref())?;
     let mut conn = pool.get().map_err(|e| format!("データベース接続エラー: {}", e))?;
-    QueueService::add_to_queue(&mut conn, req.task_id).map_err(|e| e.to_string())
+    let entry = QueueService::add_to_queue(
+        &mut conn,
+        &req.queue_id,
+        req.task_id,
+        queue_date,
+        req.override_limits,
+    )
+    .map_err(|e| e.to_string())?;
+    refresh_index(&index, &mut conn, std::slice::from_ref(&entry.task_id))?;
+    Ok(entry)
+}
+
+/// 親タスクの子タスクをまとめてキューに追加（Completedの子タスクはスキップ）
+#[tauri::command]
+pub fn enqueue_subtree(
+    pool: State<DbPool>,
+    index: State<QuickSwitchState>,
+    req: EnqueueSubtreeRequest,
+) -> Result<Vec<QueueEntry>, String> {
+    let queue_date = parse_date(req.queue_date.as_deref())?;
+    let mut conn = pool.get().map_err(|e| format!("データベース接続エラー: {}", e))?;
+    let entries = QueueService::enqueue_subtree(
+        &mut conn,
+        &req.queue_id,
+        req.parent_id.clone(),
+        queue_date,
+        req.override_limits,
+    )
+    .map_err(|e| e.to_string())?;
+
+    let mut task_ids: Vec<String> = entries.iter().map(|e| e.task_id.clone()).collect();
+    task_ids.push(req.parent_id);
+    refresh_index(&index, &mut conn, &task_ids)?;
+    Ok(entries)
+}
+
+/// 検索条件（キーワード・ステータス・タグ）に一致するタスクをまとめてキューに追加
+///
+/// 追加したエントリと、スキップしたタスク（既にキューにある・子タスクを持つ・アーカイブ済み）を返す。
+#[tauri::command]
+pub fn enqueue_by_filter(
+    pool: State<DbPool>,
+    index: State<QuickSwitchState>,
+    req: EnqueueByFilterRequest,
+) -> Result<EnqueueByFilterResult, String> {
+    let queue_date = parse_date(req.queue_date.as_deref())?;
+    let mut conn = pool.get().map_err(|e| format!("データベース接続エラー: {}", e))?;
+    let result = QueueService::enqueue_matching(
+        &mut conn,
+        &req.queue_id,
+        &req.filter,
+        req.sort_by,
+        req.descending,
+        queue_date,
+        req.override_limits,
+    )
+    .map_err(|e| e.to_string())?;
+
+    let task_ids: Vec<String> = result.added.iter().map(|e| e.task_id.clone()).collect();
+    refresh_index(&index, &mut conn, &task_ids)?;
+    Ok(result)
 }
 
 /// タスクをキューから削除
 #[tauri::command]
 pub fn remove_task_from_queue(
     pool: State<DbPool>,
+    index: State<QuickSwitchState>,
     req: RemoveFromQueueRequest,
 ) -> Result<(), String> {
     let mut conn = pool.get().map_err(|e| format!("データベース接続エラー: {}", e))?;
-    QueueService::remove_from_queue(&mut conn, req.task_id, req.target_status)
-        .map_err(|e| e.to_string())
+    QueueService::remove_from_queue(
+        &mut conn,
+        &req.queue_id,
+        req.task_id.clone(),
+        req.target_status,
+    )
+    .map_err(|e| e.to_string())?;
+    refresh_index(&index, &mut conn, &[req.task_id])
 }
 
-/// キュー全体をクリア
+/// キューエントリを指定日まで延期（ステータスは変更しない）
 #[tauri::command]
-pub fn clear_task_queue(pool: State<DbPool>) -> Result<(), String> {
+pub fn defer_queue_entry(
+    pool: State<DbPool>,
+    index: State<QuickSwitchState>,
+    req: DeferQueueEntryRequest,
+) -> Result<DeferredTask, String> {
+    let until = QueueService::parse_queue_date(&req.until).map_err(|e| e.to_string())?;
     let mut conn = pool.get().map_err(|e| format!("データベース接続エラー: {}", e))?;
-    QueueService::clear_queue(&mut conn).map_err(|e| e.to_string())
+    let deferred =
+        QueueService::defer_queue_entry(&mut conn, &req.queue_id, req.task_id.clone(), until)
+            .map_err(|e| e.to_string())?;
+    refresh_index(&index, &mut conn, &[req.task_id])?;
+    Ok(deferred)
 }
 
-/// キュー内の全タスクを完了状態にする
+/// 指定キュー（省略時は既定のキュー）へ戻す予定の延期中のタスク一覧を取得
 #[tauri::command]
-pub fn complete_all_queue(pool: State<DbPool>) -> Result<CompleteAllQueueResponse, String> {
+pub fn get_deferred_tasks(
+    pool: State<DbPool>,
+    queue_id: Option<String>,
+) -> Result<Vec<DeferredTaskWithTitle>, String> {
+    let queue_id = queue_id_or_default(queue_id);
     let mut conn = pool.get().map_err(|e| format!("データベース接続エラー: {}", e))?;
-    let completed_count = QueueService::complete_all_queue(&mut conn).map_err(|e| e.to_string())?;
+    QueueService::get_deferred_tasks(&mut conn, &queue_id).map_err(|e| e.to_string())
+}
+
+/// 指定日のキュー全体をクリア（日付省略時は今日）
+#[tauri::command]
+pub fn clear_task_queue(
+    pool: State<DbPool>,
+    index: State<QuickSwitchState>,
+    queue_id: Option<String>,
+    date: Option<String>,
+) -> Result<(), String> {
+    let queue_id = queue_id_or_default(queue_id);
+    let queue_date = parse_date(date.as_deref())?;
+    let mut conn = pool
+        .get()
+        .map_err(|e| format!("データベース接続エラー: {}", e))?;
+    let queued_ids = queued_task_ids(&mut conn, &queue_id, queue_date)?;
+    QueueService::clear_queue(&mut conn, &queue_id, queue_date).map_err(|e| e.to_string())?;
+    refresh_index(&index, &mut conn, &queued_ids)
+}
+
+/// 今日のキュー内の全タスクを完了状態にする（キュー省略時は既定のキュー）
+#[tauri::command]
+pub fn complete_all_queue(
+    pool: State<DbPool>,
+    index: State<QuickSwitchState>,
+    queue_id: Option<String>,
+) -> Result<CompleteAllQueueResponse, String> {
+    let queue_id = queue_id_or_default(queue_id);
+    let mut conn = pool.get().map_err(|e| format!("データベース接続エラー: {}", e))?;
+    let queued_ids = queued_task_ids(&mut conn, &queue_id, None)?;
+    let completed_count =
+        QueueService::complete_all_queue(&mut conn, &queue_id).map_err(|e| e.to_string())?;
+    refresh_index(&index, &mut conn, &queued_ids)?;
     Ok(CompleteAllQueueResponse { completed_count })
 }
 
@@ -57,16 +220,114 @@ pub fn update_queue_position(
     req: UpdateQueueRequest,
 ) -> Result<QueueEntry, String> {
     let mut conn = pool.get().map_err(|e| format!("データベース接続エラー: {}", e))?;
-    QueueService::update_queue_position(&mut conn, req.task_id, req.new_position)
+    QueueService::update_queue_position(&mut conn, &req.queue_id, req.task_id, req.new_position)
         .map_err(|e| e.to_string())
 }
 
-/// キュー全体を一括で並び替え
+/// 指定日のキュー全体を一括で並び替え（日付省略時は今日）
 #[tauri::command]
 pub fn reorder_task_queue(
     pool: State<DbPool>,
     req: ReorderQueueRequest,
 ) -> Result<Vec<QueueEntry>, String> {
+    let queue_date = parse_date(req.queue_date.as_deref())?;
     let mut conn = pool.get().map_err(|e| format!("データベース接続エラー: {}", e))?;
-    QueueService::reorder_queue(&mut conn, req.task_ids).map_err(|e| e.to_string())
+    QueueService::reorder_queue(
+        &mut conn,
+        &req.queue_id,
+        req.task_ids,
+        queue_date,
+        req.override_limits,
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// キューエントリを別の日・別のキューへ移動
+#[tauri::command]
+pub fn move_queue_entry(
+    pool: State<DbPool>,
+    index: State<QuickSwitchState>,
+    req: MoveQueueEntryRequest,
+) -> Result<QueueEntry, String> {
+    let target_date =
+        QueueService::parse_queue_date(&req.target_date).map_err(|e| e.to_string())?;
+    let mut conn = pool
+        .get()
+        .map_err(|e| format!("データベース接続エラー: {}", e))?;
+    let target_queue_id = req.target_queue_id.as_deref().unwrap_or(&req.queue_id);
+    let entry = QueueService::move_queue_entry(
+        &mut conn,
+        &req.queue_id,
+        req.task_id.clone(),
+        target_queue_id,
+        target_date,
+        req.position,
+        req.override_limits,
+    )
+    .map_err(|e| e.to_string())?;
+    refresh_index(&index, &mut conn, std::slice::from_ref(&entry.task_id))?;
+    Ok(entry)
+}
+
+/// キューの上限（WIP制限）設定を取得
+#[tauri::command]
+pub fn get_queue_limits(pool: State<DbPool>) -> Result<QueueLimits, String> {
+    let mut conn = pool
+        .get()
+        .map_err(|e| format!("データベース接続エラー: {}", e))?;
+    SettingsService::get_queue_limits(&mut conn).map_err(|e| e.to_string())
+}
+
+/// キューの上限（WIP制限）設定を更新
+#[tauri::command]
+pub fn update_queue_limits(
+    pool: State<DbPool>,
+    limits: QueueLimits,
+) -> Result<QueueLimits, String> {
+    let mut conn = pool
+        .get()
+        .map_err(|e| format!("データベース接続エラー: {}", e))?;
+    SettingsService::update_queue_limits(&mut conn, limits).map_err(|e| e.to_string())
+}
+
+/// 今日のキューへの追加候補を提案（キュー省略時は既定のキュー、件数省略時は5件）
+///
+/// Draftプールのタスクをスコア順に返し、各候補に順位付けの理由を付ける。
+#[tauri::command]
+pub fn suggest_queue(
+    pool: State<DbPool>,
+    queue_id: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<QueueSuggestion>, String> {
+    let queue_id = queue_id_or_default(queue_id);
+    let mut conn = pool
+        .get()
+        .map_err(|e| format!("データベース接続エラー: {}", e))?;
+    SuggestionService::suggest_queue(&mut conn, &queue_id, limit.unwrap_or(5), Utc::now())
+        .map_err(|e| e.to_string())
+}
+
+/// キューIDを解決（省略時は既定のキュー）
+fn queue_id_or_default(queue_id: Option<String>) -> String {
+    queue_id.unwrap_or_else(|| DEFAULT_QUEUE_ID.to_string())
+}
+
+/// 日付文字列（YYYY-MM-DD）を解析（省略時はNone = 今日）
+fn parse_date(date: Option<&str>) -> Result<Option<NaiveDate>, String> {
+    date.map(QueueService::parse_queue_date)
+        .transpose()
+        .map_err(|e| e.to_string())
+}
+
+/// 指定日のキュー内のタスクIDを取得（一括操作前のインデックス更新対象の収集用）
+fn queued_task_ids(
+    conn: &mut SqliteConnection,
+    queue_id: &str,
+    queue_date: Option<NaiveDate>,
+) -> Result<Vec<String>, String> {
+    Ok(QueueService::get_queue(conn, queue_id, queue_date)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|entry| entry.task_id)
+        .collect())
 }
@@ -0,0 +1,30 @@
+use diesel::SqliteConnection;
+use tauri::State;
+
+use crate::models::quick_switch::QuickSwitchResult;
+use crate::service::QuickSwitchState;
+
+/// クイックスイッチャー検索（Ctrl+K ジャンプ用）
+#[tauri::command]
+pub fn quick_switch(
+    index: State<QuickSwitchState>,
+    query: String,
+    limit: Option<usize>,
+    include_archived: Option<bool>,
+) -> Result<Vec<QuickSwitchResult>, String> {
+    let index = index.lock().map_err(|e| e.to_string())?;
+    Ok(index.search(&query, limit, include_archived.unwrap_or(false)))
+}
+
+/// 変更されたタスクをインデックスに反映（各ミューテーションコマンドから呼び出す）
+///
+/// タスクを変更するコマンドは、変更したタスクのIDを渡してこの関数を必ず呼ぶこと
+/// （QuickSwitchIndex のドキュメントを参照）
+pub(crate) fn refresh_index(
+    index: &QuickSwitchState,
+    conn: &mut SqliteConnection,
+    task_ids: &[String],
+) -> Result<(), String> {
+    let mut index = index.lock().map_err(|e| e.to_string())?;
+    index.refresh(conn, task_ids).map_err(|e| e.to_string())
+}
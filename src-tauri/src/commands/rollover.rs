@@ -0,0 +1,53 @@
+use chrono::Local;
+use tauri::State;
+
+use crate::commands::quick_switch::refresh_index;
+use crate::models::queue_history::{RolloverResult, TaskSlipCount};
+use crate::models::settings::RolloverSettings;
+use crate::service::{QuickSwitchState, RolloverService, SettingsService};
+use crate::DbPool;
+
+/// 日次ロールオーバーを手動で実行
+#[tauri::command]
+pub fn run_queue_rollover(
+    pool: State<DbPool>,
+    index: State<QuickSwitchState>,
+) -> Result<RolloverResult, String> {
+    let mut conn = pool
+        .get()
+        .map_err(|e| format!("データベース接続エラー: {}", e))?;
+    let result = RolloverService::rollover(&mut conn, Local::now().naive_local())
+        .map_err(|e| e.to_string())?;
+    refresh_index(&index, &mut conn, &result.affected_task_ids())?;
+    Ok(result)
+}
+
+/// ロールオーバー設定を取得
+#[tauri::command]
+pub fn get_rollover_settings(pool: State<DbPool>) -> Result<RolloverSettings, String> {
+    let mut conn = pool
+        .get()
+        .map_err(|e| format!("データベース接続エラー: {}", e))?;
+    SettingsService::get_rollover_settings(&mut conn).map_err(|e| e.to_string())
+}
+
+/// ロールオーバー設定を更新
+#[tauri::command]
+pub fn update_rollover_settings(
+    pool: State<DbPool>,
+    settings: RolloverSettings,
+) -> Result<RolloverSettings, String> {
+    let mut conn = pool
+        .get()
+        .map_err(|e| format!("データベース接続エラー: {}", e))?;
+    SettingsService::update_rollover_settings(&mut conn, settings).map_err(|e| e.to_string())
+}
+
+/// 持ち越されたことのあるタスクを持ち越し回数の多い順に取得
+#[tauri::command]
+pub fn get_slipped_tasks(pool: State<DbPool>) -> Result<Vec<TaskSlipCount>, String> {
+    let mut conn = pool
+        .get()
+        .map_err(|e| format!("データベース接続エラー: {}", e))?;
+    RolloverService::get_slipped_tasks(&mut conn).map_err(|e| e.to_string())
+}
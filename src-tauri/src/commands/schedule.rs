@@ -0,0 +1,26 @@
+use tauri::State;
+
+use crate::models::settings::WorkdaySettings;
+use crate::service::SettingsService;
+use crate::DbPool;
+
+/// 作業時間設定（作業開始時刻・1日の作業時間）を取得
+#[tauri::command]
+pub fn get_workday_settings(pool: State<DbPool>) -> Result<WorkdaySettings, String> {
+    let mut conn = pool
+        .get()
+        .map_err(|e| format!("データベース接続エラー: {}", e))?;
+    SettingsService::get_workday_settings(&mut conn).map_err(|e| e.to_string())
+}
+
+/// 作業時間設定を更新
+#[tauri::command]
+pub fn update_workday_settings(
+    pool: State<DbPool>,
+    settings: WorkdaySettings,
+) -> Result<WorkdaySettings, String> {
+    let mut conn = pool
+        .get()
+        .map_err(|e| format!("データベース接続エラー: {}", e))?;
+    SettingsService::update_workday_settings(&mut conn, settings).map_err(|e| e.to_string())
+}
@@ -0,0 +1,20 @@
+use tauri::State;
+
+use crate::models::similarity::SimilarTask;
+use crate::service::SimilarityService;
+use crate::DbPool;
+
+/// 類似タスクを検索（task_id または text を基準にする）
+#[tauri::command]
+pub fn find_similar_tasks(
+    pool: State<DbPool>,
+    task_id: Option<String>,
+    text: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<SimilarTask>, String> {
+    let mut conn = pool
+        .get()
+        .map_err(|e| format!("データベース接続エラー: {}", e))?;
+    SimilarityService::find_similar_tasks(&mut conn, task_id.as_deref(), text.as_deref(), limit)
+        .map_err(|e| e.to_string())
+}
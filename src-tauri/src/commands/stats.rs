@@ -0,0 +1,74 @@
+use tauri::State;
+
+use crate::models::focus::TaskFocusTotal;
+use crate::models::queue_history::QueueHistoryEntry;
+use crate::models::stats::{CompletionStreak, DailyQueueStats, WeeklyTagTotal};
+use crate::service::{QueueService, StatsService};
+use crate::DbPool;
+
+/// 指定日のキュー履歴を取得
+#[tauri::command]
+pub fn get_queue_history(
+    pool: State<DbPool>,
+    date: String,
+) -> Result<Vec<QueueHistoryEntry>, String> {
+    let date = QueueService::parse_queue_date(&date).map_err(|e| e.to_string())?;
+    let mut conn = pool
+        .get()
+        .map_err(|e| format!("データベース接続エラー: {}", e))?;
+    StatsService::get_queue_history(&mut conn, date).map_err(|e| e.to_string())
+}
+
+/// 期間内の日ごとの完了数・完了率を取得
+#[tauri::command]
+pub fn get_daily_queue_stats(
+    pool: State<DbPool>,
+    from: String,
+    to: String,
+) -> Result<Vec<DailyQueueStats>, String> {
+    let from = QueueService::parse_queue_date(&from).map_err(|e| e.to_string())?;
+    let to = QueueService::parse_queue_date(&to).map_err(|e| e.to_string())?;
+    let mut conn = pool
+        .get()
+        .map_err(|e| format!("データベース接続エラー: {}", e))?;
+    StatsService::get_daily_stats(&mut conn, from, to).map_err(|e| e.to_string())
+}
+
+/// 連続完了日数を取得
+#[tauri::command]
+pub fn get_completion_streak(pool: State<DbPool>) -> Result<CompletionStreak, String> {
+    let mut conn = pool
+        .get()
+        .map_err(|e| format!("データベース接続エラー: {}", e))?;
+    StatsService::get_completion_streak(&mut conn).map_err(|e| e.to_string())
+}
+
+/// 期間内の週ごと・タグごとの完了タスク数を取得
+#[tauri::command]
+pub fn get_weekly_tag_totals(
+    pool: State<DbPool>,
+    from: String,
+    to: String,
+) -> Result<Vec<WeeklyTagTotal>, String> {
+    let from = QueueService::parse_queue_date(&from).map_err(|e| e.to_string())?;
+    let to = QueueService::parse_queue_date(&to).map_err(|e| e.to_string())?;
+    let mut conn = pool
+        .get()
+        .map_err(|e| format!("データベース接続エラー: {}", e))?;
+    StatsService::get_weekly_tag_totals(&mut conn, from, to).map_err(|e| e.to_string())
+}
+
+/// 期間内のタスクごとの集中時間を取得（集中セッションの記録から集計）
+#[tauri::command]
+pub fn get_focus_totals(
+    pool: State<DbPool>,
+    from: String,
+    to: String,
+) -> Result<Vec<TaskFocusTotal>, String> {
+    let from = QueueService::parse_queue_date(&from).map_err(|e| e.to_string())?;
+    let to = QueueService::parse_queue_date(&to).map_err(|e| e.to_string())?;
+    let mut conn = pool
+        .get()
+        .map_err(|e| format!("データベース接続エラー: {}", e))?;
+    StatsService::get_focus_totals(&mut conn, from, to).map_err(|e| e.to_string())
+}
@@ -2,17 +2,21 @@ use diesel::prelude::*;
 use diesel::r2d2::{ConnectionManager, Pool};
 use tauri::State;
 
-use crate::models::tag::{CreateTagRequest, Tag, UpdateTagRequest};
-use crate::service::TagService;
+use crate::commands::quick_switch::refresh_index;
+use crate::models::tag::{
+    CreateTagGroupRequest, CreateTagRequest, Tag, TagAlias, TagGroup, TagGroupWithTags,
+    TagTreeNode, UpdateTagGroupRequest, UpdateTagRequest,
+};
+use crate::service::{QuickSwitchState, TagService};
 
 /// データベース接続プール型
 pub type DbPool = Pool<ConnectionManager<SqliteConnection>>;
 
-/// 全タグ取得
+/// 全タグ取得（名前空間のツリー、子孫を含めた使用数付き）
 #[tauri::command]
-pub fn list_tags(pool: State<DbPool>) -> Result<Vec<Tag>, String> {
+pub fn list_tags(pool: State<DbPool>) -> Result<Vec<TagTreeNode>, String> {
     let mut conn = pool.get().map_err(|e| format!("データベース接続エラー: {}", e))?;
-    TagService::list_tags(&mut conn).map_err(|e| e.to_string())
+    TagService::list_tag_tree(&mut conn).map_err(|e| e.to_string())
 }
 
 /// タグ作成
@@ -26,16 +30,114 @@ pub fn create_tag(pool: State<DbPool>, request: CreateTagRequest) -> Result<Tag,
 #[tauri::command]
 pub fn update_tag(
     pool: State<DbPool>,
+    index: State<QuickSwitchState>,
     tag_id: String,
     request: UpdateTagRequest,
 ) -> Result<Tag, String> {
     let mut conn = pool.get().map_err(|e| format!("データベース接続エラー: {}", e))?;
-    TagService::update_tag(&mut conn, &tag_id, request).map_err(|e| e.to_string())
+    // 名前を変更すると子孫のタグの名前も変わる（統合した場合は統合先のタグになる）
+    let task_ids =
+        TagService::tagged_task_ids(&mut conn, vec![tag_id.clone()]).map_err(|e| e.to_string())?;
+    let tag = TagService::update_tag(&mut conn, &tag_id, request).map_err(|e| e.to_string())?;
+    refresh_index(&index, &mut conn, &task_ids)?;
+    Ok(tag)
+}
+
+/// タグ統合（統合元のタグを統合先へまとめ、統合先の使用数を返す）
+#[tauri::command]
+pub fn merge_tags(
+    pool: State<DbPool>,
+    index: State<QuickSwitchState>,
+    source_ids: Vec<String>,
+    target_id: String,
+) -> Result<i32, String> {
+    let mut conn = pool.get().map_err(|e| format!("データベース接続エラー: {}", e))?;
+    let task_ids =
+        TagService::tagged_task_ids(&mut conn, source_ids.clone()).map_err(|e| e.to_string())?;
+    let usage_count =
+        TagService::merge_tags(&mut conn, &source_ids, &target_id).map_err(|e| e.to_string())?;
+    refresh_index(&index, &mut conn, &task_ids)?;
+    Ok(usage_count)
 }
 
 /// タグ削除
 #[tauri::command]
-pub fn delete_tag(pool: State<DbPool>, tag_id: String) -> Result<(), String> {
+pub fn delete_tag(
+    pool: State<DbPool>,
+    index: State<QuickSwitchState>,
+    tag_id: String,
+) -> Result<(), String> {
+    let mut conn = pool.get().map_err(|e| format!("データベース接続エラー: {}", e))?;
+    let tag = TagService::get_tag(&mut conn, &tag_id).map_err(|e| e.to_string())?;
+    TagService::delete_tag(&mut conn, &tag_id).map_err(|e| e.to_string())?;
+
+    let mut index = index.lock().map_err(|e| e.to_string())?;
+    index.remove_tag(&tag.name);
+    Ok(())
+}
+
+/// タグの別名を追加（タスクへのタグ付けと検索で正式なタグに読み替えられる）
+#[tauri::command]
+pub fn add_tag_alias(
+    pool: State<DbPool>,
+    tag_id: String,
+    alias: String,
+) -> Result<TagAlias, String> {
+    let mut conn = pool
+        .get()
+        .map_err(|e| format!("データベース接続エラー: {}", e))?;
+    TagService::add_alias(&mut conn, &tag_id, &alias).map_err(|e| e.to_string())
+}
+
+/// タグの別名を削除
+#[tauri::command]
+pub fn remove_tag_alias(pool: State<DbPool>, alias: String) -> Result<(), String> {
+    let mut conn = pool.get().map_err(|e| format!("データベース接続エラー: {}", e))?;
+    TagService::remove_alias(&mut conn, &alias).map_err(|e| e.to_string())
+}
+
+/// 全タググループ取得（所属するタグ付き）
+#[tauri::command]
+pub fn list_tag_groups(pool: State<DbPool>) -> Result<Vec<TagGroupWithTags>, String> {
+    let mut conn = pool.get().map_err(|e| format!("データベース接続エラー: {}", e))?;
+    TagService::list_tag_groups(&mut conn).map_err(|e| e.to_string())
+}
+
+/// タググループ作成
+#[tauri::command]
+pub fn create_tag_group(
+    pool: State<DbPool>,
+    request: CreateTagGroupRequest,
+) -> Result<TagGroup, String> {
+    let mut conn = pool.get().map_err(|e| format!("データベース接続エラー: {}", e))?;
+    TagService::create_tag_group(&mut conn, request).map_err(|e| e.to_string())
+}
+
+/// タググループ更新
+#[tauri::command]
+pub fn update_tag_group(
+    pool: State<DbPool>,
+    group_id: String,
+    request: UpdateTagGroupRequest,
+) -> Result<TagGroup, String> {
+    let mut conn = pool.get().map_err(|e| format!("データベース接続エラー: {}", e))?;
+    TagService::update_tag_group(&mut conn, &group_id, request).map_err(|e| e.to_string())
+}
+
+/// タググループ削除（所属していたタグはグループから外れる）
+#[tauri::command]
+pub fn delete_tag_group(pool: State<DbPool>, group_id: String) -> Result<(), String> {
+    let mut conn = pool.get().map_err(|e| format!("データベース接続エラー: {}", e))?;
+    TagService::delete_tag_group(&mut conn, &group_id).map_err(|e| e.to_string())
+}
+
+/// タグの所属グループを設定（group_idを省略するとグループから外す）
+#[tauri::command]
+pub fn set_tag_group(
+    pool: State<DbPool>,
+    tag_id: String,
+    group_id: Option<String>,
+) -> Result<Tag, String> {
     let mut conn = pool.get().map_err(|e| format!("データベース接続エラー: {}", e))?;
-    TagService::delete_tag(&mut conn, &tag_id).map_err(|e| e.to_string())
+    TagService::set_tag_group(&mut conn, &tag_id, group_id.as_deref()).map_err(|e| e.to_string())
 }
@@ -2,23 +2,47 @@ use diesel::prelude::*;
 use diesel::r2d2::{ConnectionManager, Pool};
 use tauri::State;
 
+use crate::commands::quick_switch::refresh_index;
 use crate::models::task::{
     CreateTaskRequest, DuplicateTaskRequest, ListTasksPaginatedParams, PaginatedTaskResponse,
-    SearchTasksParams, TaskHierarchyResponse, TaskResponse, UpdateTaskRequestInput,
+    SearchTasksParams, TaskHierarchyDelta, TaskHierarchyResponse, TaskResponse,
+    UpdateTaskRequestInput,
 };
-use crate::service::TaskService;
+use crate::service::{QuickSwitchState, SimilarityService, TaskService};
 
 /// データベース接続プール型
 pub type DbPool = Pool<ConnectionManager<SqliteConnection>>;
 
 /// タスクを作成
+///
+/// `check_duplicates` が true の場合、重複の可能性がある既存タスクを
+/// `duplicateWarnings` として返す（作成自体は行われる）。
+/// `auto_create_tags` が true の場合、存在しないタグを作成して関連付け、
+/// 作成したタグ名を `createdTags` として返す。
 #[tauri::command]
 pub fn create_task(
     pool: State<DbPool>,
+    index: State<QuickSwitchState>,
     req: CreateTaskRequest,
+    check_duplicates: Option<bool>,
+    auto_create_tags: Option<bool>,
 ) -> Result<TaskResponse, String> {
     let mut conn = pool.get().map_err(|e| format!("データベース接続エラー: {}", e))?;
-    TaskService::create_task(&mut conn, req).map_err(|e| e.to_string())
+    let mut task =
+        TaskService::create_task_with_options(&mut conn, req, auto_create_tags.unwrap_or(false))
+            .map_err(|e| e.to_string())?;
+    refresh_index(&index, &mut conn, std::slice::from_ref(&task.id))?;
+
+    if check_duplicates.unwrap_or(false) {
+        task.duplicate_warnings = SimilarityService::check_duplicates(
+            &mut conn,
+            &task.title,
+            task.description.as_deref(),
+            Some(&task.id),
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    Ok(task)
 }
 
 /// タスクを複製する
@@ -27,19 +51,25 @@ pub fn create_task(
 /// 複製されたタスクは以下の特性を持つ：
 /// - タイトルに `_YYYYMMDD_HHmmss` サフィックスが追加される
 /// - ステータスは Draft に設定される
-/// - 説明、タグは元のタスクと同じ
+/// - 説明、タグ、見積もり時間は元のタスクと同じ
 /// - 親子関係は新しいタスクで再現される
 #[tauri::command]
 pub fn duplicate_task(
     pool: State<DbPool>,
+    index: State<QuickSwitchState>,
     req: DuplicateTaskRequest,
 ) -> Result<TaskResponse, String> {
     let mut conn = pool
         .get()
         .map_err(|e| format!("データベース接続エラー: {}", e))?;
 
-    TaskService::duplicate_task(&mut conn, &req.task_id, req.new_title)
-        .map_err(|e| e.to_string())
+    let task = TaskService::duplicate_task(&mut conn, &req.task_id, req.new_title)
+        .map_err(|e| e.to_string())?;
+
+    let mut changed_ids = task.children_ids.clone();
+    changed_ids.push(task.id.clone());
+    refresh_index(&index, &mut conn, &changed_ids)?;
+    Ok(task)
 }
 
 /// タスクを取得
@@ -50,21 +80,51 @@ pub fn get_task(pool: State<DbPool>, task_id: String) -> Result<TaskResponse, St
 }
 
 /// タスクを更新
+///
+/// `auto_create_tags` が true の場合、存在しないタグを作成して関連付け、
+/// 作成したタグ名を `createdTags` として返す。
 #[tauri::command]
 pub fn update_task(
     pool: State<DbPool>,
+    index: State<QuickSwitchState>,
     task_id: String,
     req: UpdateTaskRequestInput,
+    auto_create_tags: Option<bool>,
 ) -> Result<TaskResponse, String> {
     let mut conn = pool.get().map_err(|e| format!("データベース接続エラー: {}", e))?;
-    TaskService::update_task(&mut conn, &task_id, req).map_err(|e| e.to_string())
+    let task = TaskService::update_task_with_options(
+        &mut conn,
+        &task_id,
+        req,
+        auto_create_tags.unwrap_or(false),
+    )
+    .map_err(|e| e.to_string())?;
+    refresh_index(&index, &mut conn, &[task_id])?;
+    Ok(task)
+}
+
+/// タスクの見積もり時間（分）を設定（Noneで未設定に戻す）
+#[tauri::command]
+pub fn set_task_estimate(
+    pool: State<DbPool>,
+    task_id: String,
+    estimated_minutes: Option<i32>,
+) -> Result<TaskResponse, String> {
+    let mut conn = pool.get().map_err(|e| format!("データベース接続エラー: {}", e))?;
+    TaskService::set_task_estimate(&mut conn, &task_id, estimated_minutes)
+        .map_err(|e| e.to_string())
 }
 
 /// タスクを削除（論理削除: Draft → Archived）
 #[tauri::command]
-pub fn delete_task(pool: State<DbPool>, task_id: String) -> Result<(), String> {
+pub fn delete_task(
+    pool: State<DbPool>,
+    index: State<QuickSwitchState>,
+    task_id: String,
+) -> Result<(), String> {
     let mut conn = pool.get().map_err(|e| format!("データベース接続エラー: {}", e))?;
-    TaskService::delete_task(&mut conn, &task_id).map_err(|e| e.to_string())
+    TaskService::delete_task(&mut conn, &task_id).map_err(|e| e.to_string())?;
+    refresh_index(&index, &mut conn, &[task_id])
 }
 
 /// タスクを完全に削除（物理削除: データベースから削除）
@@ -73,9 +133,14 @@ pub fn delete_task(pool: State<DbPool>, task_id: String) -> Result<(), String> {
 /// - Archivedステータスのタスクのみ物理削除可能
 /// - 子タスクも自動的に削除される（CASCADE）
 #[tauri::command]
-pub fn delete_task_permanently(pool: State<DbPool>, task_id: String) -> Result<(), String> {
+pub fn delete_task_permanently(
+    pool: State<DbPool>,
+    index: State<QuickSwitchState>,
+    task_id: String,
+) -> Result<(), String> {
     let mut conn = pool.get().map_err(|e| format!("データベース接続エラー: {}", e))?;
-    TaskService::delete_task_permanently(&mut conn, &task_id).map_err(|e| e.to_string())
+    TaskService::delete_task_permanently(&mut conn, &task_id).map_err(|e| e.to_string())?;
+    refresh_index(&index, &mut conn, &[task_id])
 }
 
 /// タスクを復元（Archived → Draft）
@@ -84,9 +149,15 @@ pub fn delete_task_permanently(pool: State<DbPool>, task_id: String) -> Result<(
 /// - Archivedステータスのタスクのみ復元可能
 /// - タスクのステータスをDraftに変更し、updated_atを更新する
 #[tauri::command]
-pub fn restore_task(pool: State<DbPool>, task_id: String) -> Result<TaskResponse, String> {
+pub fn restore_task(
+    pool: State<DbPool>,
+    index: State<QuickSwitchState>,
+    task_id: String,
+) -> Result<TaskResponse, String> {
     let mut conn = pool.get().map_err(|e| format!("データベース接続エラー: {}", e))?;
-    TaskService::restore_task(&mut conn, &task_id).map_err(|e| e.to_string())
+    let task = TaskService::restore_task(&mut conn, &task_id).map_err(|e| e.to_string())?;
+    refresh_index(&index, &mut conn, &[task_id])?;
+    Ok(task)
 }
 
 /// タスク一覧を取得（ステータスフィルタ対応）
@@ -138,6 +209,16 @@ pub fn get_task_hierarchy(
     TaskService::get_hierarchy(&mut conn).map_err(|e| e.to_string())
 }
 
+/// タスク階層の差分を取得（since 以降に変更されたサブツリーのみ）
+#[tauri::command]
+pub fn get_task_hierarchy_changes(
+    pool: State<DbPool>,
+    since: String,
+) -> Result<TaskHierarchyDelta, String> {
+    let mut conn = pool.get().map_err(|e| format!("データベース接続エラー: {}", e))?;
+    TaskService::get_hierarchy_since(&mut conn, &since).map_err(|e| e.to_string())
+}
+
 /// タスク検索（フィルタ・キーワード対応、ページネーション対応）
 #[tauri::command]
 pub fn search_tasks(
@@ -147,6 +228,7 @@ pub fn search_tasks(
     tags: Option<Vec<String>>,
     limit: Option<i64>,
     offset: Option<i64>,
+    cursor: Option<String>,
 ) -> Result<PaginatedTaskResponse, String> {
     let mut conn = pool.get().map_err(|e| format!("データベース接続エラー: {}", e))?;
     let params = SearchTasksParams {
@@ -155,6 +237,7 @@ pub fn search_tasks(
         tags,
         limit,
         offset,
+        cursor,
     };
     TaskService::search_tasks(&mut conn, params).map_err(|e| e.to_string())
 }
@@ -50,6 +50,302 @@ CREATE INDEX idx_tags_name ON tags (name);
 CREATE INDEX idx_task_tags_task_id ON task_tags (task_id);
 CREATE INDEX idx_task_tags_tag_id ON task_tags (tag_id);
 CREATE INDEX idx_task_queue_position ON task_queue (position);
+"),
+        // 日付ごとのキュー（task_queue.queue_date）
+        M::up("
+CREATE TABLE task_queue_new (
+    task_id     TEXT PRIMARY KEY NOT NULL,
+    position    INTEGER NOT NULL,
+    added_at    TEXT NOT NULL,
+    queue_date  TEXT NOT NULL,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+INSERT INTO task_queue_new (task_id, position, added_at, queue_date)
+SELECT task_id, position, added_at, date('now', 'localtime')
+FROM task_queue;
+
+DROP TABLE task_queue;
+
+ALTER TABLE task_queue_new RENAME TO task_queue;
+
+CREATE INDEX idx_task_queue_date_position ON task_queue (queue_date, position);
+"),
+        // アプリ設定とキュー履歴（日次ロールオーバー）
+        M::up("
+-- アプリ設定（キー・値）
+CREATE TABLE app_settings (
+    key         TEXT PRIMARY KEY NOT NULL,
+    value       TEXT NOT NULL,
+    updated_at  TEXT NOT NULL
+);
+
+-- キュー履歴（日ごとのキューのスナップショット）
+-- タスク削除後も履歴を残すため、tasksへの外部キーは設定しない
+CREATE TABLE queue_history (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    queue_date  TEXT NOT NULL,
+    task_id     TEXT NOT NULL,
+    task_title  TEXT NOT NULL,
+    position    INTEGER NOT NULL,
+    outcome     TEXT NOT NULL,
+    recorded_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_queue_history_queue_date ON queue_history (queue_date);
+CREATE INDEX idx_queue_history_task_id ON queue_history (task_id);
+"),
+        // タスクの見積もり時間（分）
+        M::up("
+ALTER TABLE tasks ADD COLUMN estimated_minutes INTEGER;
+"),
+        // 集中セッション（ポモドーロ）
+        M::up("
+CREATE TABLE focus_sessions (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    task_id         TEXT NOT NULL,
+    task_title      TEXT NOT NULL,
+    queue_date      TEXT NOT NULL,
+    state           TEXT NOT NULL,
+    work_minutes    INTEGER NOT NULL,
+    break_minutes   INTEGER NOT NULL,
+    started_at      TEXT NOT NULL,
+    paused_at       TEXT,
+    paused_seconds  INTEGER NOT NULL DEFAULT 0,
+    ended_at        TEXT,
+    focused_seconds INTEGER NOT NULL DEFAULT 0,
+    completed_task  BOOLEAN NOT NULL DEFAULT 0
+);
+
+CREATE INDEX idx_focus_sessions_task_id ON focus_sessions (task_id);
+CREATE INDEX idx_focus_sessions_queue_date ON focus_sessions (queue_date);
+"),
+        // キューの並び順キー（position → rank、同じ日のキュー内で一意）
+        M::up("
+CREATE TABLE task_queue_new (
+    task_id     TEXT PRIMARY KEY NOT NULL,
+    rank        TEXT NOT NULL,
+    added_at    TEXT NOT NULL,
+    queue_date  TEXT NOT NULL,
+    UNIQUE (queue_date, rank),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+INSERT INTO task_queue_new (task_id, rank, added_at, queue_date)
+SELECT task_id,
+       printf('%06di', ROW_NUMBER() OVER (PARTITION BY queue_date ORDER BY position, added_at) - 1),
+       added_at,
+       queue_date
+FROM task_queue;
+
+DROP TABLE task_queue;
+
+ALTER TABLE task_queue_new RENAME TO task_queue;
+"),
+        // 延期（スヌーズ）中のタスク
+        M::up("
+CREATE TABLE deferred_tasks (
+    task_id         TEXT PRIMARY KEY NOT NULL,
+    until_date      TEXT NOT NULL,
+    deferred_from   TEXT NOT NULL,
+    deferred_at     TEXT NOT NULL,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_deferred_tasks_until_date ON deferred_tasks (until_date);
+"),
+        // 名前付きキュー（既存のエントリ・延期中のタスクは既定のキューに所属）
+        M::up("
+CREATE TABLE queues (
+    id          TEXT PRIMARY KEY NOT NULL,
+    name        TEXT NOT NULL UNIQUE,
+    created_at  TEXT NOT NULL,
+    updated_at  TEXT NOT NULL
+);
+
+INSERT INTO queues (id, name, created_at, updated_at)
+VALUES ('default', 'デフォルト',
+        strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now'),
+        strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now'));
+
+-- rank はキュー・日ごとに一意
+CREATE TABLE task_queue_new (
+    task_id     TEXT PRIMARY KEY NOT NULL,
+    queue_id    TEXT NOT NULL DEFAULT 'default',
+    rank        TEXT NOT NULL,
+    added_at    TEXT NOT NULL,
+    queue_date  TEXT NOT NULL,
+    UNIQUE (queue_id, queue_date, rank),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (queue_id) REFERENCES queues(id)
+);
+
+INSERT INTO task_queue_new (task_id, queue_id, rank, added_at, queue_date)
+SELECT task_id, 'default', rank, added_at, queue_date
+FROM task_queue;
+
+DROP TABLE task_queue;
+
+ALTER TABLE task_queue_new RENAME TO task_queue;
+
+-- 延期中のタスクは延期元のキューへ戻す
+CREATE TABLE deferred_tasks_new (
+    task_id         TEXT PRIMARY KEY NOT NULL,
+    queue_id        TEXT NOT NULL DEFAULT 'default',
+    until_date      TEXT NOT NULL,
+    deferred_from   TEXT NOT NULL,
+    deferred_at     TEXT NOT NULL,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (queue_id) REFERENCES queues(id)
+);
+
+INSERT INTO deferred_tasks_new (task_id, queue_id, until_date, deferred_from, deferred_at)
+SELECT task_id, 'default', until_date, deferred_from, deferred_at
+FROM deferred_tasks;
+
+DROP TABLE deferred_tasks;
+
+ALTER TABLE deferred_tasks_new RENAME TO deferred_tasks;
+
+CREATE INDEX idx_deferred_tasks_until_date ON deferred_tasks (until_date);
+"),
+        // タグの名前空間（name は完全なパスのまま、parent_id で親のパスのタグを指す）
+        M::up("
+ALTER TABLE tags ADD COLUMN parent_id TEXT;
+
+-- 既存の a/b 形式のタグは、親のパスのタグが存在する場合に紐付ける
+UPDATE tags
+SET parent_id = (
+    SELECT p.id FROM tags p
+    WHERE substr(tags.name, 1, length(p.name) + 1) = p.name || '/'
+      AND instr(substr(tags.name, length(p.name) + 2), '/') = 0
+);
+
+CREATE INDEX idx_tags_parent_id ON tags (parent_id);
+"),
+        // タググループ（exclusive のグループのタグは1つのタスクに1つまで）
+        M::up("
+CREATE TABLE tag_groups (
+    id          TEXT PRIMARY KEY NOT NULL,
+    name        TEXT NOT NULL UNIQUE,
+    exclusive   BOOLEAN NOT NULL DEFAULT 0,
+    created_at  TEXT NOT NULL,
+    updated_at  TEXT NOT NULL
+);
+
+-- タグは最大1つのグループに所属する（グループ削除時はサービス層で所属を解除）
+ALTER TABLE tags ADD COLUMN group_id TEXT;
+
+CREATE INDEX idx_tags_group_id ON tags (group_id);
+"),
+        // タグの使用数（ステータスごとの件数を含む）をトリガーで維持
+        M::up("
+
+ALTER TABLE tags ADD COLUMN draft_count INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE tags ADD COLUMN active_count INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE tags ADD COLUMN completed_count INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE tags ADD COLUMN archived_count INTEGER NOT NULL DEFAULT 0;
+
+-- 既存のタグの件数を計算
+UPDATE tags SET
+    usage_count = (SELECT COUNT(*) FROM task_tags tt JOIN tasks t ON t.id = tt.task_id WHERE tt.tag_id = tags.id),
+    draft_count = (SELECT COUNT(*) FROM task_tags tt JOIN tasks t ON t.id = tt.task_id WHERE tt.tag_id = tags.id AND t.status = 'draft'),
+    active_count = (SELECT COUNT(*) FROM task_tags tt JOIN tasks t ON t.id = tt.task_id WHERE tt.tag_id = tags.id AND t.status = 'active'),
+    completed_count = (SELECT COUNT(*) FROM task_tags tt JOIN tasks t ON t.id = tt.task_id WHERE tt.tag_id = tags.id AND t.status = 'completed'),
+    archived_count = (SELECT COUNT(*) FROM task_tags tt JOIN tasks t ON t.id = tt.task_id WHERE tt.tag_id = tags.id AND t.status = 'archived');
+
+CREATE TRIGGER tags_usage_after_task_tag_insert AFTER INSERT ON task_tags
+BEGIN
+    UPDATE tags SET
+        usage_count = usage_count + 1,
+        draft_count = draft_count + ((SELECT status FROM tasks WHERE id = NEW.task_id) = 'draft'),
+        active_count = active_count + ((SELECT status FROM tasks WHERE id = NEW.task_id) = 'active'),
+        completed_count = completed_count + ((SELECT status FROM tasks WHERE id = NEW.task_id) = 'completed'),
+        archived_count = archived_count + ((SELECT status FROM tasks WHERE id = NEW.task_id) = 'archived')
+    WHERE id = NEW.tag_id AND EXISTS (SELECT 1 FROM tasks WHERE id = NEW.task_id);
+END;
+
+CREATE TRIGGER tags_usage_after_task_tag_delete AFTER DELETE ON task_tags
+BEGIN
+    UPDATE tags SET
+        usage_count = usage_count - 1,
+        draft_count = draft_count - ((SELECT status FROM tasks WHERE id = OLD.task_id) = 'draft'),
+        active_count = active_count - ((SELECT status FROM tasks WHERE id = OLD.task_id) = 'active'),
+        completed_count = completed_count - ((SELECT status FROM tasks WHERE id = OLD.task_id) = 'completed'),
+        archived_count = archived_count - ((SELECT status FROM tasks WHERE id = OLD.task_id) = 'archived')
+    WHERE id = OLD.tag_id AND EXISTS (SELECT 1 FROM tasks WHERE id = OLD.task_id);
+END;
+
+CREATE TRIGGER tags_usage_after_task_tag_update AFTER UPDATE ON task_tags
+BEGIN
+    UPDATE tags SET
+        usage_count = usage_count - 1,
+        draft_count = draft_count - ((SELECT status FROM tasks WHERE id = OLD.task_id) = 'draft'),
+        active_count = active_count - ((SELECT status FROM tasks WHERE id = OLD.task_id) = 'active'),
+        completed_count = completed_count - ((SELECT status FROM tasks WHERE id = OLD.task_id) = 'completed'),
+        archived_count = archived_count - ((SELECT status FROM tasks WHERE id = OLD.task_id) = 'archived')
+    WHERE id = OLD.tag_id AND EXISTS (SELECT 1 FROM tasks WHERE id = OLD.task_id);
+    UPDATE tags SET
+        usage_count = usage_count + 1,
+        draft_count = draft_count + ((SELECT status FROM tasks WHERE id = NEW.task_id) = 'draft'),
+        active_count = active_count + ((SELECT status FROM tasks WHERE id = NEW.task_id) = 'active'),
+        completed_count = completed_count + ((SELECT status FROM tasks WHERE id = NEW.task_id) = 'completed'),
+        archived_count = archived_count + ((SELECT status FROM tasks WHERE id = NEW.task_id) = 'archived')
+    WHERE id = NEW.tag_id AND EXISTS (SELECT 1 FROM tasks WHERE id = NEW.task_id);
+END;
+
+CREATE TRIGGER tags_usage_after_task_status_update AFTER UPDATE OF status ON tasks WHEN OLD.status <> NEW.status
+BEGIN
+    UPDATE tags SET
+        draft_count = draft_count - (OLD.status = 'draft') + (NEW.status = 'draft'),
+        active_count = active_count - (OLD.status = 'active') + (NEW.status = 'active'),
+        completed_count = completed_count - (OLD.status = 'completed') + (NEW.status = 'completed'),
+        archived_count = archived_count - (OLD.status = 'archived') + (NEW.status = 'archived')
+    WHERE id IN (SELECT tag_id FROM task_tags WHERE task_id = NEW.id);
+END;
+
+CREATE TRIGGER tags_usage_before_task_delete BEFORE DELETE ON tasks
+BEGIN
+    UPDATE tags SET
+        usage_count = usage_count - 1,
+        draft_count = draft_count - (OLD.status = 'draft'),
+        active_count = active_count - (OLD.status = 'active'),
+        completed_count = completed_count - (OLD.status = 'completed'),
+        archived_count = archived_count - (OLD.status = 'archived')
+    WHERE id IN (SELECT tag_id FROM task_tags WHERE task_id = OLD.id);
+END;
+"),
+        // タグの別名（タスクへのタグ付けと検索で正式なタグに読み替える）
+        M::up("
+CREATE TABLE tag_aliases (
+    alias       TEXT PRIMARY KEY NOT NULL,
+    tag_id      TEXT NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+    created_at  TEXT NOT NULL
+);
+
+CREATE INDEX idx_tag_aliases_tag_id ON tag_aliases (tag_id);
+"),
+        // 自動タグ付けルール（target: title / description / parent_id、match_type: contains / regex / equals）
+        M::up("
+CREATE TABLE auto_tag_rules (
+    id          TEXT PRIMARY KEY NOT NULL,
+    name        TEXT NOT NULL,
+    target      TEXT NOT NULL,
+    match_type  TEXT NOT NULL,
+    pattern     TEXT NOT NULL,
+    enabled     BOOLEAN NOT NULL DEFAULT 1,
+    created_at  TEXT NOT NULL,
+    updated_at  TEXT NOT NULL
+);
+
+-- ルールに一致したタスクに付けるタグ
+CREATE TABLE auto_tag_rule_tags (
+    rule_id     TEXT NOT NULL REFERENCES auto_tag_rules(id) ON DELETE CASCADE,
+    tag_id      TEXT NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+    PRIMARY KEY (rule_id, tag_id)
+);
+
+CREATE INDEX idx_auto_tag_rule_tags_tag_id ON auto_tag_rule_tags (tag_id);
 "),
     ]);
 }
@@ -59,6 +355,11 @@ pub type DbConnection = Arc<Mutex<Connection>>;
 // initialize_database 関数は tauri::AppHandle を受け取るように変更
 pub fn initialize_database(app_handle: AppHandle) -> Result<DbConnection> {
     let db_path = get_db_path(&app_handle)?;
+    open_and_migrate(&db_path)
+}
+
+/// 指定パスのデータベースを開いてマイグレーションを適用（CLIからも使用）
+pub fn open_and_migrate(db_path: &std::path::Path) -> Result<DbConnection> {
     let mut conn = Connection::open(db_path)?;
 
     MIGRATIONS.to_latest(&mut conn).map_err(|_e| {
@@ -33,12 +33,45 @@ pub enum ServiceError {
     #[error("このタグはすでに存在しています")]
     DuplicateEntry(String),
 
+    #[error("指定されたタグの別名が見つかりません")]
+    TagAliasNotFound(String),
+
+    #[error("この名前はすでにタグまたはタグの別名として使われています")]
+    DuplicateTagAlias(String),
+
+    #[error("指定された自動タグ付けルールが見つかりません")]
+    AutoTagRuleNotFound(String),
+
+    #[error("指定されたタググループが見つかりません")]
+    TagGroupNotFound(String),
+
+    #[error("同じ名前のタググループがすでに存在しています")]
+    DuplicateTagGroupName(String),
+
+    #[error("排他グループのタグは1つのタスクに1つまでです（{0}）")]
+    ExclusiveTagConflict(String),
+
     #[error("キューにこのタスクが見つかりません")]
     QueueEntryNotFound(String),
 
     #[error("このタスクはすでにキューに登録されています")]
     DuplicateQueueEntry(String),
 
+    #[error("指定されたキューが見つかりません")]
+    QueueNotFound(String),
+
+    #[error("同じ名前のキューがすでに存在しています")]
+    DuplicateQueueName(String),
+
+    #[error("キューの上限を超えています（{0}）")]
+    QueueLimitExceeded(String),
+
+    #[error("すでに進行中の集中セッションがあります")]
+    FocusSessionInProgress(i32),
+
+    #[error("進行中の集中セッションがありません")]
+    FocusSessionNotFound,
+
     #[error("タスクのステータスが不正です")]
     InvalidTaskStatus(String),
 
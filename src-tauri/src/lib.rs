@@ -9,7 +9,8 @@ pub mod service;
 use diesel::r2d2::{ConnectionManager, Pool, CustomizeConnection};
 use diesel::SqliteConnection;
 use std::path::PathBuf;
-use tauri::Manager;
+use std::time::Duration;
+use tauri::{Emitter, Manager};
 
 /// データベース接続プール型
 pub type DbPool = Pool<ConnectionManager<SqliteConnection>>;
@@ -46,6 +47,37 @@ fn init_db_pool(db_path: PathBuf) -> Result<DbPool, String> {
         .map_err(|e| format!("Failed to create pool: {}", e))
 }
 
+/// 正常終了を記録する（次回起動時の整合性修復の要否判定に使う）
+fn end_db_session(app_handle: &tauri::AppHandle) -> Result<(), String> {
+    let pool = app_handle.state::<DbPool>();
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+    service::ConsistencyService::end_session(&mut conn).map_err(|e| e.to_string())
+}
+
+/// 日付変更（ロールオーバー）のチェック間隔
+const ROLLOVER_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// 日付が変わっていればロールオーバーを実行し、フロントエンドへ通知する
+///
+/// 1日の開始時刻（day_start_hour）は設定で変更できるため、
+/// 一定間隔で「今日」を再計算して前回実行日と比較する。
+fn run_scheduled_rollover(app_handle: &tauri::AppHandle, pool: &DbPool) -> Result<(), String> {
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+    let result =
+        service::RolloverService::rollover_if_due(&mut conn, chrono::Local::now().naive_local())
+            .map_err(|e| e.to_string())?;
+
+    if let Some(result) = result {
+        let index = app_handle.state::<service::QuickSwitchState>();
+        commands::quick_switch::refresh_index(&index, &mut conn, &result.affected_task_ids())?;
+        app_handle
+            .emit("queue-rolled-over", &result)
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 #[tauri::command]
 fn greet(name: &str) -> String {
@@ -67,52 +99,172 @@ pub fn run() {
             // 接続プールを作成
             let pool = init_db_pool(db_path).map_err(|e| e.to_string())?;
 
-            // アプリケーションステートに接続プールを登録
+            // 起動時に日次ロールオーバーと（前回が異常終了の場合は）整合性の修復を実行し、
+            // クイックスイッチャー用インデックスを構築
+            let quick_switch_index = {
+                let mut conn = pool.get().map_err(|e| e.to_string())?;
+                // 失敗しても起動は続ける（定期チェックで再試行する）
+                if let Err(e) = service::RolloverService::rollover_if_due(
+                    &mut conn,
+                    chrono::Local::now().naive_local(),
+                ) {
+                    eprintln!("日次ロールオーバーに失敗しました: {}", e);
+                }
+                if let Some(report) = service::ConsistencyService::begin_session(&mut conn)? {
+                    eprintln!(
+                        "前回の異常終了後の不整合を{}件修復しました",
+                        report.issues.len()
+                    );
+                }
+                service::QuickSwitchIndex::build(&mut conn)?
+            };
+
+            // アプリケーションステートに接続プールとインデックスを登録
+            let rollover_pool = pool.clone();
             app.manage(pool);
+            app.manage(service::QuickSwitchState::new(quick_switch_index));
+
+            // 起動中に日付が変わった場合のロールオーバー
+            let app_handle = app.handle().clone();
+            std::thread::spawn(move || loop {
+                std::thread::sleep(ROLLOVER_CHECK_INTERVAL);
+                if let Err(e) = run_scheduled_rollover(&app_handle, &rollover_pool) {
+                    eprintln!("日次ロールオーバーに失敗しました: {}", e);
+                }
+            });
 
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             // Utility
             greet,
-            // Task Management (12 commands)
+            // Task Management (14 commands)
             // - CRUD operations for tasks
             // - Search and filter functionality (including lightweight ID-only search)
-            // - Hierarchical task retrieval
+            // - Hierarchical task retrieval (full and incremental)
             // - Physical deletion and restore for archived tasks
             // - Pagination support for list operations
             // - Task duplication with recursive child handling
+            // - Estimated duration (minutes) for queue scheduling
             commands::task::create_task,
             commands::task::duplicate_task,
             commands::task::get_task,
             commands::task::update_task,
+            commands::task::set_task_estimate,
             commands::task::delete_task,
             commands::task::delete_task_permanently,
             commands::task::restore_task,
             commands::task::list_tasks,
             commands::task::list_tasks_paginated,
             commands::task::get_task_hierarchy,
+            commands::task::get_task_hierarchy_changes,
             commands::task::search_tasks,
             commands::task::search_task_ids,
-            // Task Queue Management (7 commands)
-            // - Daily task queue operations
+            // Task Queue Management (19 commands)
+            // - Named queues (contexts such as "Work" / "Home"), each keyed by date
+            // - Date-keyed daily task queue operations (plan future days)
             // - Position management and reordering
             // - Batch operations (complete all, clear all)
+            // - Projected start/end times and planned load (get_task_queue)
+            // - WIP limits (max entries, max total estimate, per-tag caps)
+            // - Deferring entries until a later date (restored by the daily rollover)
+            commands::queue::get_queues,
+            commands::queue::create_queue,
+            commands::queue::rename_queue,
+            commands::queue::delete_queue,
             commands::queue::get_task_queue,
             commands::queue::add_task_to_queue,
+            commands::queue::enqueue_subtree,
+            commands::queue::enqueue_by_filter,
             commands::queue::remove_task_from_queue,
+            commands::queue::defer_queue_entry,
+            commands::queue::get_deferred_tasks,
             commands::queue::clear_task_queue,
             commands::queue::complete_all_queue,
             commands::queue::update_queue_position,
             commands::queue::reorder_task_queue,
-            // Tag Management (4 commands)
+            commands::queue::move_queue_entry,
+            commands::queue::get_queue_limits,
+            commands::queue::update_queue_limits,
+            commands::queue::suggest_queue,
+            // Daily Rollover (4 commands)
+            // - Carry over unfinished queue entries to today (also runs at startup / day start)
+            // - Day-start hour and carry-over settings
+            // - Slip counts per task
+            commands::rollover::run_queue_rollover,
+            commands::rollover::get_rollover_settings,
+            commands::rollover::update_rollover_settings,
+            commands::rollover::get_slipped_tasks,
+            // Workday Schedule (2 commands)
+            // - Workday start time and working hours used for queue ETAs
+            commands::schedule::get_workday_settings,
+            commands::schedule::update_workday_settings,
+            // Focus Sessions (7 commands)
+            // - Pomodoro sessions bound to the head of today's queue
+            // - Start / pause / resume / finish (optionally completing the task)
+            // - Work and break length settings
+            commands::focus::start_focus_session,
+            commands::focus::pause_focus_session,
+            commands::focus::resume_focus_session,
+            commands::focus::finish_focus_session,
+            commands::focus::get_focus_session,
+            commands::focus::get_focus_settings,
+            commands::focus::update_focus_settings,
+            // Quick Switcher (1 command)
+            // - In-memory fuzzy search over titles, short IDs and tags
+            commands::quick_switch::quick_switch,
+            // Similarity (1 command)
+            // - Related / likely-duplicate task detection (TF-IDF)
+            commands::similarity::find_similar_tasks,
+            // Queue History & Statistics (5 commands)
+            // - Per-day queue sessions (planned / completed / returned / archived)
+            // - Completion rate, streak and weekly totals per tag
+            // - Focused minutes per task
+            commands::stats::get_queue_history,
+            commands::stats::get_daily_queue_stats,
+            commands::stats::get_completion_streak,
+            commands::stats::get_weekly_tag_totals,
+            commands::stats::get_focus_totals,
+            // Consistency (1 command)
+            // - Detect / repair task status and queue invariant violations
+            //   (also repaired automatically at startup after an abnormal exit)
+            commands::consistency::check_consistency,
+            // Tag Management (12 commands)
             // - Tag CRUD operations
-            // - Usage count tracking
+            // - Hierarchical namespaces (work/clientA/billing), renames cascade to descendants
+            // - Merging tags (also via rename into an existing name)
+            // - Aliases resolved to the canonical tag on input and search
+            // - Tag groups (exclusive groups allow one tag per task)
+            // - Usage count tracking (rolled up over namespaces)
             commands::tag::list_tags,
             commands::tag::create_tag,
             commands::tag::update_tag,
             commands::tag::delete_tag,
+            commands::tag::merge_tags,
+            commands::tag::add_tag_alias,
+            commands::tag::remove_tag_alias,
+            commands::tag::list_tag_groups,
+            commands::tag::create_tag_group,
+            commands::tag::update_tag_group,
+            commands::tag::delete_tag_group,
+            commands::tag::set_tag_group,
+            // Auto Tagging (5 commands)
+            // - Rules matching title / description (substring or regex) or parent task
+            // - No custom-field rules: tasks have no custom fields
+            // - Applied on task create/update, or re-applied to all tasks in batch
+            commands::auto_tag::list_auto_tag_rules,
+            commands::auto_tag::create_auto_tag_rule,
+            commands::auto_tag::update_auto_tag_rule,
+            commands::auto_tag::delete_auto_tag_rule,
+            commands::auto_tag::apply_auto_tag_rules,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app_handle, event| {
+            if let tauri::RunEvent::Exit = event {
+                if let Err(e) = end_db_session(app_handle) {
+                    eprintln!("終了処理に失敗しました: {}", e);
+                }
+            }
+        });
 }
@@ -0,0 +1,172 @@
+use chrono::Utc;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::schema::auto_tag_rules;
+
+/// 自動タグ付けルールで判定する項目
+///
+/// タスクにはカスタムフィールドが無いため、カスタムフィールドを判定する項目は用意していない
+/// （未知の項目を指定したリクエストはデシリアライズ時にエラーになる）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AutoTagTarget {
+    /// タイトル
+    Title,
+    /// 説明（未設定は空文字として判定）
+    Description,
+    /// 親タスクのID（親が無い場合は空文字として判定）
+    ParentId,
+}
+
+impl AutoTagTarget {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AutoTagTarget::Title => "title",
+            AutoTagTarget::Description => "description",
+            AutoTagTarget::ParentId => "parent_id",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "title" => Some(AutoTagTarget::Title),
+            "description" => Some(AutoTagTarget::Description),
+            "parent_id" => Some(AutoTagTarget::ParentId),
+            _ => None,
+        }
+    }
+}
+
+/// 自動タグ付けルールの一致方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AutoTagMatch {
+    /// 部分一致（大文字小文字を区別しない）
+    Contains,
+    /// 正規表現
+    Regex,
+    /// 完全一致
+    Equals,
+}
+
+impl AutoTagMatch {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AutoTagMatch::Contains => "contains",
+            AutoTagMatch::Regex => "regex",
+            AutoTagMatch::Equals => "equals",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "contains" => Some(AutoTagMatch::Contains),
+            "regex" => Some(AutoTagMatch::Regex),
+            "equals" => Some(AutoTagMatch::Equals),
+            _ => None,
+        }
+    }
+}
+
+/// 自動タグ付けルール（データベースモデル）
+#[derive(Debug, Clone, Queryable, Selectable, Insertable)]
+#[diesel(table_name = auto_tag_rules)]
+pub struct AutoTagRule {
+    pub id: String,
+    pub name: String,
+    /// 判定する項目（AutoTagTarget の文字列表現）
+    pub target: String,
+    /// 一致方法（AutoTagMatch の文字列表現）
+    pub match_type: String,
+    pub pattern: String,
+    pub enabled: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl AutoTagRule {
+    pub fn new(
+        name: String,
+        target: AutoTagTarget,
+        match_type: AutoTagMatch,
+        pattern: String,
+        enabled: bool,
+    ) -> Self {
+        let now = Utc::now().to_rfc3339();
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            name,
+            target: target.as_str().to_string(),
+            match_type: match_type.as_str().to_string(),
+            pattern,
+            enabled,
+            created_at: now.clone(),
+            updated_at: now,
+        }
+    }
+
+    /// 付けるタグ名を加えたレスポンスに変換
+    pub fn with_tags(self, tags: Vec<String>) -> AutoTagRuleResponse {
+        AutoTagRuleResponse {
+            target: AutoTagTarget::parse(&self.target).unwrap_or(AutoTagTarget::Title),
+            match_type: AutoTagMatch::parse(&self.match_type).unwrap_or(AutoTagMatch::Contains),
+            id: self.id,
+            name: self.name,
+            pattern: self.pattern,
+            tags,
+            enabled: self.enabled,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
+
+/// 自動タグ付けルールレスポンス
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoTagRuleResponse {
+    pub id: String,
+    pub name: String,
+    pub target: AutoTagTarget,
+    pub match_type: AutoTagMatch,
+    pub pattern: String,
+    /// 一致したタスクに付けるタグ名（名前順）
+    pub tags: Vec<String>,
+    pub enabled: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// 自動タグ付けルール作成リクエスト
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateAutoTagRuleRequest {
+    pub name: String,
+    pub target: AutoTagTarget,
+    pub match_type: AutoTagMatch,
+    pub pattern: String,
+    /// 付けるタグ名（別名も指定できる）
+    pub tags: Vec<String>,
+    /// 有効にするか（省略時は有効）
+    #[serde(default)]
+    pub enabled: Option<bool>,
+}
+
+/// 自動タグ付けルール更新リクエスト
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateAutoTagRuleRequest {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub target: Option<AutoTagTarget>,
+    #[serde(default)]
+    pub match_type: Option<AutoTagMatch>,
+    #[serde(default)]
+    pub pattern: Option<String>,
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+    #[serde(default)]
+    pub enabled: Option<bool>,
+}
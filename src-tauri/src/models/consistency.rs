@@ -0,0 +1,48 @@
+use serde::Serialize;
+
+/// 整合性チェックで検出する不整合の種別
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConsistencyIssueKind {
+    /// Activeの（子タスクを持たない）タスクがキューにも延期中のタスクにも無い
+    ActiveNotQueued,
+    /// Completed/Archivedのタスクが今日以降のキューまたは延期中のタスクに残っている
+    ClosedTaskQueued,
+    /// 親タスクのステータスが子タスクから計算したステータスと一致しない
+    ParentStatusMismatch,
+    /// キューの並び順キー（rank）が不正（形式違反・長すぎる）
+    InvalidQueueRank,
+}
+
+/// 検出した不整合
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsistencyIssue {
+    pub kind: ConsistencyIssueKind,
+    /// 対象のタスクID（InvalidQueueRankの場合はrankが不正なエントリのタスク）
+    pub task_id: String,
+    pub message: String,
+}
+
+/// 整合性チェックの結果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsistencyReport {
+    /// 検出した不整合（修復した場合は修復した不整合）
+    pub issues: Vec<ConsistencyIssue>,
+    /// 修復を実行したか
+    pub repaired: bool,
+}
+
+impl ConsistencyReport {
+    /// 修復でステータスやキューが変化したタスクID（インデックス更新用）
+    pub fn affected_task_ids(&self) -> Vec<String> {
+        if !self.repaired {
+            return Vec::new();
+        }
+        let mut task_ids: Vec<String> = self.issues.iter().map(|i| i.task_id.clone()).collect();
+        task_ids.sort();
+        task_ids.dedup();
+        task_ids
+    }
+}
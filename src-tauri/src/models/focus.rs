@@ -0,0 +1,108 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::schema::focus_sessions;
+
+/// 集中セッションの状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FocusSessionState {
+    /// 計測中
+    Running,
+    /// 一時停止中
+    Paused,
+    /// 終了済み
+    Finished,
+}
+
+impl FocusSessionState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FocusSessionState::Running => "running",
+            FocusSessionState::Paused => "paused",
+            FocusSessionState::Finished => "finished",
+        }
+    }
+}
+
+/// 集中セッションのフェーズ（経過時間から算出）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FocusPhase {
+    /// 作業時間中
+    Work,
+    /// 休憩時間中
+    Break,
+    /// 休憩時間も終了（終了待ち）
+    Over,
+}
+
+/// 集中セッション（データベースモデル）
+#[derive(Debug, Clone, Queryable, Selectable)]
+#[diesel(table_name = focus_sessions)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct FocusSession {
+    pub id: i32,
+    pub task_id: String,
+    /// 開始時点のタスクタイトル
+    pub task_title: String,
+    pub queue_date: String,
+    pub state: String,
+    pub work_minutes: i32,
+    pub break_minutes: i32,
+    pub started_at: String,
+    pub paused_at: Option<String>,
+    /// 一時停止していた合計秒数（再開済みの分）
+    pub paused_seconds: i32,
+    pub ended_at: Option<String>,
+    /// 集中した秒数（終了時に確定、作業時間が上限）
+    pub focused_seconds: i32,
+    /// 終了時にタスクを完了したか
+    pub completed_task: bool,
+}
+
+/// 新規集中セッション（挿入用）
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = focus_sessions)]
+pub struct NewFocusSession {
+    pub task_id: String,
+    pub task_title: String,
+    pub queue_date: String,
+    pub state: String,
+    pub work_minutes: i32,
+    pub break_minutes: i32,
+    pub started_at: String,
+}
+
+/// 集中セッションレスポンス（現在のフェーズ・残り時間付き）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FocusSessionResponse {
+    pub id: i32,
+    pub task_id: String,
+    pub task_title: String,
+    pub queue_date: String,
+    pub state: FocusSessionState,
+    pub phase: FocusPhase,
+    pub work_minutes: i32,
+    pub break_minutes: i32,
+    pub started_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ended_at: Option<String>,
+    /// 集中した秒数（作業時間が上限）
+    pub focused_seconds: i64,
+    /// 現在のフェーズの残り秒数（Overの場合は0）
+    pub remaining_seconds: i64,
+    pub completed_task: bool,
+}
+
+/// タスクごとの集中時間
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskFocusTotal {
+    pub task_id: String,
+    /// 最後のセッション時点のタスクタイトル
+    pub task_title: String,
+    pub session_count: usize,
+    pub focused_minutes: i64,
+}
@@ -1,4 +1,13 @@
+pub mod auto_tag;
+pub mod consistency;
+pub mod focus;
 pub mod queue;
+pub mod queue_history;
+pub mod quick_switch;
+pub mod settings;
+pub mod similarity;
+pub mod stats;
+pub mod suggestion;
 pub mod tag;
 pub mod task;
 
@@ -1,8 +1,62 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use crate::schema::task_queue;
+use crate::models::task::SearchTasksParams;
+use crate::schema::{deferred_tasks, queues, task_queue};
+
+/// キュー対象日の文字列形式
+pub const QUEUE_DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// 予定開始・終了時刻の文字列形式（ローカル時刻）
+pub const SCHEDULE_TIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// 既定のキューのID（マイグレーションで作成され、削除できない）
+pub const DEFAULT_QUEUE_ID: &str = "default";
+
+/// リクエストでキューIDが省略された場合の値
+fn default_queue_id() -> String {
+    DEFAULT_QUEUE_ID.to_string()
+}
+
+/// 名前付きキュー（「仕事」「家」などのコンテキスト）
+#[derive(Debug, Clone, Queryable, Selectable, Insertable, Serialize, Deserialize)]
+#[diesel(table_name = queues)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+#[serde(rename_all = "camelCase")]
+pub struct Queue {
+    pub id: String,
+    pub name: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl Queue {
+    pub fn new(name: String) -> Self {
+        let now = Utc::now().to_rfc3339();
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            name,
+            created_at: now.clone(),
+            updated_at: now,
+        }
+    }
+}
+
+/// キュー作成リクエスト
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateQueueRequest {
+    pub name: String,
+}
+
+/// キュー名変更リクエスト
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenameQueueRequest {
+    pub queue_id: String,
+    pub name: String,
+}
 
 /// タスクキューエントリ（データベースモデル）
 #[derive(Debug, Clone, Queryable, Selectable, Serialize, Deserialize)]
@@ -11,8 +65,13 @@ use crate::schema::task_queue;
 #[serde(rename_all = "camelCase")]
 pub struct QueueEntry {
     pub task_id: String,
-    pub position: i32,
+    /// 所属するキューのID
+    pub queue_id: String,
+    /// 同じキュー・同じ日の中での並び順キー（辞書順、service::rank を参照）
+    pub rank: String,
     pub added_at: String,
+    /// キューの対象日（YYYY-MM-DD）
+    pub queue_date: String,
 }
 
 /// 新規キューエントリ（挿入用）
@@ -20,17 +79,21 @@ pub struct QueueEntry {
 #[diesel(table_name = task_queue)]
 pub struct NewQueueEntry {
     pub task_id: String,
-    pub position: i32,
+    pub queue_id: String,
+    pub rank: String,
     pub added_at: String,
+    pub queue_date: String,
 }
 
 impl NewQueueEntry {
-    pub fn new(task_id: String, position: i32) -> Self {
+    pub fn new(queue_id: String, task_id: String, rank: String, queue_date: NaiveDate) -> Self {
         let now: DateTime<Utc> = Utc::now();
         Self {
             task_id,
-            position,
+            queue_id,
+            rank,
             added_at: now.to_rfc3339(),
+            queue_date: queue_date.format(QUEUE_DATE_FORMAT).to_string(),
         }
     }
 }
@@ -40,24 +103,158 @@ impl NewQueueEntry {
 #[serde(rename_all = "camelCase")]
 pub struct QueueEntryWithTask {
     pub task_id: String,
+    pub queue_id: String,
+    /// キュー内の位置（0始まり、rank順に数えた値）
     pub position: i32,
+    pub rank: String,
     pub added_at: String,
+    pub queue_date: String,
     pub task_title: String,
     pub task_status: String,
     pub task_description: Option<String>,
+    /// タスクの見積もり時間（分）
+    #[serde(default)]
+    pub estimated_minutes: Option<i32>,
+    /// 予定開始時刻（ローカル、YYYY-MM-DDTHH:MM:SS）。過去の日のキューではNone
+    #[serde(default)]
+    pub scheduled_start: Option<String>,
+    /// 予定終了時刻（見積もり未設定のエントリはNone）
+    #[serde(default)]
+    pub scheduled_end: Option<String>,
+}
+
+/// キューの予定（get_task_queue のレスポンス）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueSchedule {
+    pub queue_id: String,
+    pub queue_date: String,
+    /// 予定開始・終了時刻付きのキューエントリ（position順）
+    pub entries: Vec<QueueEntryWithTask>,
+    /// 見積もり時間の合計（分）
+    pub total_estimated_minutes: i64,
+    /// 見積もり未設定のエントリ数（合計には含まれない）
+    pub unestimated_count: usize,
+    /// 設定された1日の作業時間（分）
+    pub working_minutes: u32,
+    /// 合計が作業時間を超えているか
+    pub overloaded: bool,
+    /// 作業時間を超過している分数（超過していなければ0）
+    pub overload_minutes: i64,
+    /// 最後のエントリの予定終了時刻
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub projected_end: Option<String>,
 }
 
 /// タスクキュー追加リクエスト
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AddToQueueRequest {
+    /// 対象のキューID（省略時は既定のキュー）
+    #[serde(default = "default_queue_id")]
+    pub queue_id: String,
+    pub task_id: String,
+    /// 対象日（YYYY-MM-DD、省略時は今日）
+    #[serde(default)]
+    pub queue_date: Option<String>,
+    /// キューの上限（WIP制限）を無視して追加する
+    #[serde(default)]
+    pub override_limits: bool,
+}
+
+/// 親タスクの子タスクをまとめてキューに追加するリクエスト
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnqueueSubtreeRequest {
+    /// 対象のキューID（省略時は既定のキュー）
+    #[serde(default = "default_queue_id")]
+    pub queue_id: String,
+    pub parent_id: String,
+    /// 対象日（YYYY-MM-DD、省略時は今日）
+    #[serde(default)]
+    pub queue_date: Option<String>,
+    /// キューの上限（WIP制限）を無視して追加する
+    #[serde(default)]
+    pub override_limits: bool,
+}
+
+/// 検索条件で一括追加する際の並び順のキー
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EnqueueSortKey {
+    /// 作成日時（古い順）
+    #[default]
+    Created,
+    /// 更新日時（古い順）
+    Updated,
+    /// タイトル
+    Title,
+    /// 見積もり時間（短い順、未設定は最後）
+    Estimate,
+}
+
+/// 検索条件に一致するタスクをまとめてキューに追加するリクエスト
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnqueueByFilterRequest {
+    /// 対象のキューID（省略時は既定のキュー）
+    #[serde(default = "default_queue_id")]
+    pub queue_id: String,
+    /// 検索条件（q・status・tags、limit・offset・cursorは無視される）
+    pub filter: SearchTasksParams,
+    #[serde(default)]
+    pub sort_by: EnqueueSortKey,
+    /// trueの場合は並び順を逆にする
+    #[serde(default)]
+    pub descending: bool,
+    /// 対象日（YYYY-MM-DD、省略時は今日）
+    #[serde(default)]
+    pub queue_date: Option<String>,
+    /// キューの上限（WIP制限）を無視して追加する
+    #[serde(default)]
+    pub override_limits: bool,
+}
+
+/// 一括追加でスキップした理由
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EnqueueSkipReason {
+    /// 既にいずれかのキュー（いずれかの日）にある
+    AlreadyQueued,
+    /// アーカイブ以外の子タスクを持つ親タスク（BR-015）
+    HasChildren,
+    /// アーカイブ済み
+    Archived,
+    /// 完了済み（Activeに戻さない）
+    Completed,
+}
+
+/// 一括追加でスキップしたタスク
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkippedQueueTask {
     pub task_id: String,
+    pub title: String,
+    pub reason: EnqueueSkipReason,
+}
+
+/// 検索条件による一括追加の結果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnqueueByFilterResult {
+    /// 追加されたキューエントリ（追加順）
+    pub added: Vec<QueueEntry>,
+    /// スキップしたタスクと理由（並び順）
+    pub skipped: Vec<SkippedQueueTask>,
 }
 
 /// タスクキュー削除リクエスト
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RemoveFromQueueRequest {
+    /// 対象のキューID（省略時は既定のキュー）
+    #[serde(default = "default_queue_id")]
+    pub queue_id: String,
     pub task_id: String,
     pub target_status: String, // "draft" または "completed"
 }
@@ -66,6 +263,9 @@ pub struct RemoveFromQueueRequest {
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateQueueRequest {
+    /// 対象のキューID（省略時は既定のキュー）
+    #[serde(default = "default_queue_id")]
+    pub queue_id: String,
     pub task_id: String,
     pub new_position: i32,
 }
@@ -74,7 +274,78 @@ pub struct UpdateQueueRequest {
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ReorderQueueRequest {
+    /// 対象のキューID（省略時は既定のキュー）
+    #[serde(default = "default_queue_id")]
+    pub queue_id: String,
     pub task_ids: Vec<String>, // 新しい順序でのタスクIDリスト
+    /// 対象日（YYYY-MM-DD、省略時は今日）
+    #[serde(default)]
+    pub queue_date: Option<String>,
+    /// キューの上限（WIP制限）を超えていても並び替える
+    #[serde(default)]
+    pub override_limits: bool,
+}
+
+/// 別の日・別のキューへの移動リクエスト
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MoveQueueEntryRequest {
+    /// 対象のキューID（省略時は既定のキュー）
+    #[serde(default = "default_queue_id")]
+    pub queue_id: String,
+    pub task_id: String,
+    /// 移動先のキューID（省略時は移動元と同じキュー）
+    #[serde(default)]
+    pub target_queue_id: Option<String>,
+    /// 移動先の日付（YYYY-MM-DD）
+    pub target_date: String,
+    /// 移動先での位置（省略時は末尾）
+    #[serde(default)]
+    pub position: Option<i32>,
+    /// 移動先のキューの上限（WIP制限）を無視して移動する
+    #[serde(default)]
+    pub override_limits: bool,
+}
+
+/// キューエントリの延期リクエスト
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeferQueueEntryRequest {
+    /// 対象のキューID（省略時は既定のキュー）
+    #[serde(default = "default_queue_id")]
+    pub queue_id: String,
+    pub task_id: String,
+    /// 再びキューに戻す日（YYYY-MM-DD、明日以降）
+    pub until: String,
+}
+
+/// 延期中のタスク（データベースモデル）
+#[derive(Debug, Clone, Queryable, Selectable, Insertable, Serialize)]
+#[diesel(table_name = deferred_tasks)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+#[serde(rename_all = "camelCase")]
+pub struct DeferredTask {
+    pub task_id: String,
+    /// 戻す先のキューID（延期元のキュー）
+    pub queue_id: String,
+    /// 今日のキューへ戻す日（YYYY-MM-DD）
+    pub until_date: String,
+    /// 延期元のキューの日付（YYYY-MM-DD）
+    pub deferred_from: String,
+    pub deferred_at: String,
+}
+
+/// 延期中のタスク（タスク情報含む）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeferredTaskWithTitle {
+    pub task_id: String,
+    pub queue_id: String,
+    pub task_title: String,
+    pub task_status: String,
+    pub until_date: String,
+    pub deferred_from: String,
+    pub deferred_at: String,
 }
 
 /// キュー内全タスク完了レスポンス
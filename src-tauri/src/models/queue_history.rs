@@ -0,0 +1,136 @@
+use chrono::Utc;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::schema::queue_history;
+
+/// キュー履歴の結果種別
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QueueOutcome {
+    /// 完了した
+    Completed,
+    /// アーカイブされた
+    Archived,
+    /// キューから外してDraftに戻された
+    ReturnedToDraft,
+    /// 未完了のまま翌日以降へ持ち越された
+    CarriedOver,
+    /// 指定日まで延期された
+    Deferred,
+}
+
+impl QueueOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QueueOutcome::Completed => "completed",
+            QueueOutcome::Archived => "archived",
+            QueueOutcome::ReturnedToDraft => "returned_to_draft",
+            QueueOutcome::CarriedOver => "carried_over",
+            QueueOutcome::Deferred => "deferred",
+        }
+    }
+}
+
+/// キュー履歴エントリ（データベースモデル）
+#[derive(Debug, Clone, Queryable, Selectable, Serialize)]
+#[diesel(table_name = queue_history)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+#[serde(rename_all = "camelCase")]
+pub struct QueueHistoryEntry {
+    pub id: i32,
+    pub queue_date: String,
+    pub task_id: String,
+    /// 記録時点のタスクタイトル
+    pub task_title: String,
+    pub position: i32,
+    pub outcome: String,
+    pub recorded_at: String,
+}
+
+/// 新規キュー履歴エントリ（挿入用）
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = queue_history)]
+pub struct NewQueueHistoryEntry {
+    pub queue_date: String,
+    pub task_id: String,
+    pub task_title: String,
+    pub position: i32,
+    pub outcome: String,
+    pub recorded_at: String,
+}
+
+impl NewQueueHistoryEntry {
+    pub fn new(
+        queue_date: String,
+        task_id: String,
+        task_title: String,
+        position: i32,
+        outcome: QueueOutcome,
+    ) -> Self {
+        Self {
+            queue_date,
+            task_id,
+            task_title,
+            position,
+            outcome: outcome.as_str().to_string(),
+            recorded_at: Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// 持ち越されたタスク
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CarriedOverTask {
+    pub task_id: String,
+    pub task_title: String,
+    /// 持ち越し元の日付
+    pub from_date: String,
+    /// これまでに持ち越された回数（今回を含む）
+    pub slip_count: i64,
+}
+
+/// ロールオーバー結果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RolloverResult {
+    /// ロールオーバー後の「今日」（YYYY-MM-DD）
+    pub today: String,
+    /// 履歴に記録した過去の日付
+    pub rolled_over_dates: Vec<String>,
+    /// 今日のキューへ持ち越したタスク
+    pub carried_over: Vec<CarriedOverTask>,
+    /// 完了・アーカイブ済みでキューから外したタスク数
+    pub finished_count: usize,
+    /// Activeに変更した今日のキューのタスクID（事前に計画されていたタスク等）
+    pub activated_task_ids: Vec<String>,
+    /// 延期期限を迎えて今日のキューへ戻したタスクID
+    pub restored_task_ids: Vec<String>,
+    /// 延期期限を迎えたがキューへ戻せなかったタスクID（子タスクを持つ・完了済み等、プールに戻る）
+    pub released_task_ids: Vec<String>,
+}
+
+impl RolloverResult {
+    /// ステータスやキューが変化したタスクID（インデックス更新用）
+    pub fn affected_task_ids(&self) -> Vec<String> {
+        self.carried_over
+            .iter()
+            .map(|t| t.task_id.clone())
+            .chain(self.activated_task_ids.iter().cloned())
+            .chain(self.restored_task_ids.iter().cloned())
+            .chain(self.released_task_ids.iter().cloned())
+            .collect()
+    }
+}
+
+/// タスクごとの持ち越し回数
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskSlipCount {
+    pub task_id: String,
+    pub task_title: String,
+    pub slip_count: i64,
+    /// 最後に持ち越された日（持ち越し元の日付）
+    pub last_slipped_date: String,
+}
@@ -0,0 +1,19 @@
+use serde::Serialize;
+
+use crate::models::task::TaskStatus;
+
+/// クイックスイッチャー検索結果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickSwitchResult {
+    pub task_id: String,
+    /// 短縮ID（UUID先頭8文字）
+    pub short_id: String,
+    pub title: String,
+    pub status: TaskStatus,
+    pub tags: Vec<String>,
+    /// ランキングスコア（一致度 + 新しさ）
+    pub score: i32,
+    /// タイトル中の一致文字位置（文字単位、ハイライト表示用）
+    pub title_highlights: Vec<usize>,
+}
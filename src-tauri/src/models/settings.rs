@@ -0,0 +1,74 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// 日次ロールオーバー設定
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RolloverSettings {
+    /// 1日の開始時刻（0〜23時、デフォルト: 0）。この時刻より前は前日として扱う
+    pub day_start_hour: u32,
+    /// 持ち越したタスクを今日のキューの先頭に入れるか（falseの場合は末尾）
+    pub carry_over_to_top: bool,
+}
+
+/// 1日の作業時間設定（キューの予定計算用）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkdaySettings {
+    /// 作業開始時刻（HH:MM）。未設定の場合は現在時刻から予定を計算する
+    #[serde(default)]
+    pub workday_start: Option<String>,
+    /// 1日の作業時間（分、デフォルト: 480）
+    pub working_minutes: u32,
+}
+
+impl Default for WorkdaySettings {
+    fn default() -> Self {
+        Self {
+            workday_start: None,
+            working_minutes: 8 * 60,
+        }
+    }
+}
+
+/// 集中セッション（ポモドーロ）設定
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FocusSettings {
+    /// 作業時間（分、デフォルト: 25）
+    pub work_minutes: u32,
+    /// 休憩時間（分、デフォルト: 5）
+    pub break_minutes: u32,
+}
+
+impl Default for FocusSettings {
+    fn default() -> Self {
+        Self {
+            work_minutes: 25,
+            break_minutes: 5,
+        }
+    }
+}
+
+/// キューの上限（WIP制限）設定。いずれも1日分のキューに対する上限
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueLimits {
+    /// 最大エントリ数（Noneは無制限）
+    #[serde(default)]
+    pub max_entries: Option<u32>,
+    /// 見積もり時間の合計の上限（分、Noneは無制限）
+    #[serde(default)]
+    pub max_total_minutes: Option<u32>,
+    /// タグごとの最大エントリ数（タグ名 → 上限）
+    #[serde(default)]
+    pub tag_caps: BTreeMap<String, u32>,
+}
+
+impl QueueLimits {
+    /// 上限が1つも設定されていないか
+    pub fn is_unlimited(&self) -> bool {
+        self.max_entries.is_none() && self.max_total_minutes.is_none() && self.tag_caps.is_empty()
+    }
+}
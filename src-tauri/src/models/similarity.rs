@@ -0,0 +1,14 @@
+use serde::Serialize;
+
+use crate::models::task::TaskStatus;
+
+/// 類似タスク（find_similar_tasks / 重複チェック用）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimilarTask {
+    pub task_id: String,
+    pub title: String,
+    pub status: TaskStatus,
+    /// 類似度（TF-IDFベクトルのコサイン類似度、0.0〜1.0）
+    pub score: f64,
+}
@@ -0,0 +1,45 @@
+use serde::Serialize;
+
+/// 日ごとのキュー集計
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyQueueStats {
+    /// 対象日（YYYY-MM-DD）
+    pub date: String,
+    /// 計画されたタスク数（その日のキューに入ったタスク、重複除く）
+    pub planned: usize,
+    pub completed: usize,
+    pub returned_to_draft: usize,
+    pub archived: usize,
+    pub carried_over: usize,
+    /// 延期されたタスク数
+    pub deferred: usize,
+    /// まだキューに残っているタスク数
+    pub remaining: usize,
+    /// 完了率（completed / planned、計画が無い日は0.0）
+    pub completion_rate: f64,
+    /// 集中セッションで集中した時間（分）
+    pub focused_minutes: i64,
+}
+
+/// 連続完了日数（1件以上完了した日が続いている日数）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompletionStreak {
+    /// 現在の連続日数（今日まだ完了が無い場合は昨日までで数える）
+    pub current: u32,
+    /// 過去最長の連続日数
+    pub longest: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_completed_date: Option<String>,
+}
+
+/// 週ごと・タグごとの完了タスク数
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeeklyTagTotal {
+    /// 週の開始日（月曜日、YYYY-MM-DD）
+    pub week_start: String,
+    pub tag: String,
+    pub completed: usize,
+}
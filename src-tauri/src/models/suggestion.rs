@@ -0,0 +1,42 @@
+use serde::Serialize;
+
+/// キュー提案の理由の種別
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SuggestionReasonKind {
+    /// 過去に未完了のまま持ち越された
+    Slipped,
+    /// 集中セッションで作業した実績がある（作業途中）
+    InProgress,
+    /// 親タスクが進行中（兄弟タスクがキューにある等）
+    ParentActive,
+    /// 長期間更新されていない
+    Stale,
+    /// 見積もりが短く、すぐ終わる
+    QuickWin,
+    /// 上記に該当しない（作成からの経過日数のみ）
+    Waiting,
+}
+
+/// キュー提案の理由
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SuggestionReason {
+    pub kind: SuggestionReasonKind,
+    /// 表示用の説明（例: "14日間更新なし"）
+    pub message: String,
+}
+
+/// 今日のキューへの追加候補（suggest_queue API用）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueSuggestion {
+    pub task_id: String,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_minutes: Option<i32>,
+    /// 順位付けのスコア（大きいほど優先）
+    pub score: f64,
+    /// スコアの根拠（寄与の大きい順）
+    pub reasons: Vec<SuggestionReason>,
+}
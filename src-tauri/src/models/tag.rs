@@ -2,9 +2,20 @@ use chrono::Utc;
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use crate::schema::tags;
+use crate::schema::{tag_aliases, tag_groups, tags};
+
+/// タグの名前空間の区切り文字（"work/clientA/billing"）
+pub const TAG_PATH_SEPARATOR: char = '/';
+
+/// タグを自動作成する際に順に割り当てる色（フロントエンドのプリセットカラーと同じ）
+pub const AUTO_TAG_COLORS: [&str; 8] = [
+    "#ef4444", "#f97316", "#eab308", "#22c55e", "#3b82f6", "#6366f1", "#a855f7", "#ec4899",
+];
 
 /// Tag エンティティ（DB SELECT結果 → API レスポンス用）
+///
+/// `name` は名前空間を含む完全なパス（"work/clientA/billing"）。
+/// `usage_count` とステータスごとの件数は、task_tags / tasks のトリガーで維持される。
 #[derive(Debug, Clone, Serialize, Queryable, Selectable)]
 #[diesel(table_name = tags)]
 #[serde(rename_all = "camelCase")]
@@ -18,6 +29,33 @@ pub struct Tag {
     // updated_at はレスポンスに含めない（OpenAPI仕様に未定義）
     #[serde(skip)]
     pub updated_at: String,
+    /// 親タグ（名前空間）のID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<String>,
+    /// 所属するタググループのID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_id: Option<String>,
+    /// ステータスごとの使用数（usage_count の内訳）
+    pub draft_count: i32,
+    pub active_count: i32,
+    pub completed_count: i32,
+    pub archived_count: i32,
+}
+
+/// タグツリーのノード（list_tags の返却値）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TagTreeNode {
+    #[serde(flatten)]
+    pub tag: Tag,
+    /// パスの末尾の名前（"work/clientA" の場合は "clientA"）
+    pub label: String,
+    /// 自身と子孫のタグが付いたタスク数（同じタスクは1回だけ数える）
+    pub total_usage_count: i32,
+    /// タグの別名（名前順）
+    pub aliases: Vec<String>,
+    /// 子タグ（名前順）
+    pub children: Vec<TagTreeNode>,
 }
 
 /// 新規タグ作成用（DB INSERT用）
@@ -30,19 +68,23 @@ pub struct NewTag {
     pub usage_count: i32,
     pub created_at: String,
     pub updated_at: String,
+    pub parent_id: Option<String>,
+    pub group_id: Option<String>,
 }
 
 impl NewTag {
-    /// CreateTagRequestから作成
+    /// CreateTagRequestから作成（親タグはサービス層で設定）
     pub fn from_request(req: CreateTagRequest) -> Self {
         let now = Utc::now().to_rfc3339();
         Self {
             id: uuid::Uuid::new_v4().to_string(),
             name: req.name,
             color: req.color,
-            usage_count: 0, // 初期値は0
+            usage_count: 0, // 以降はトリガーで更新
             created_at: now.clone(),
             updated_at: now,
+            parent_id: None,
+            group_id: None,
         }
     }
 }
@@ -67,6 +109,10 @@ pub struct UpdateTagRequest {
     pub color: Option<String>,
     #[serde(skip)]
     pub updated_at: Option<String>,
+    /// 変更後の名前のタグが既に存在する場合、そのタグへ統合する
+    #[serde(default)]
+    #[diesel(skip_update)]
+    pub merge: bool,
 }
 
 impl UpdateTagRequest {
@@ -76,3 +122,70 @@ impl UpdateTagRequest {
         self
     }
 }
+
+/// タグの別名（"mtg" → "meeting" など）
+///
+/// タスクへのタグ付けとタグでの絞り込みで、別名は正式なタグに読み替えられる。
+#[derive(Debug, Clone, Serialize, Queryable, Selectable, Insertable)]
+#[diesel(table_name = tag_aliases)]
+#[serde(rename_all = "camelCase")]
+pub struct TagAlias {
+    pub alias: String,
+    pub tag_id: String,
+    pub created_at: String,
+}
+
+/// タググループ（"サイズ" = S/M/L など）
+#[derive(Debug, Clone, Serialize, Queryable, Selectable, Insertable)]
+#[diesel(table_name = tag_groups)]
+#[serde(rename_all = "camelCase")]
+pub struct TagGroup {
+    pub id: String,
+    pub name: String,
+    /// 排他グループか（1つのタスクに付けられるのはグループ内のタグ1つまで）
+    pub exclusive: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl TagGroup {
+    pub fn new(name: String, exclusive: bool) -> Self {
+        let now = Utc::now().to_rfc3339();
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            name,
+            exclusive,
+            created_at: now.clone(),
+            updated_at: now,
+        }
+    }
+}
+
+/// タググループと所属するタグ（list_tag_groups の返却値）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TagGroupWithTags {
+    #[serde(flatten)]
+    pub group: TagGroup,
+    /// 所属するタグ（名前順）
+    pub tags: Vec<Tag>,
+}
+
+/// タググループ作成リクエスト
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateTagGroupRequest {
+    pub name: String,
+    #[serde(default)]
+    pub exclusive: bool,
+}
+
+/// タググループ更新リクエスト
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateTagGroupRequest {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub exclusive: Option<bool>,
+}
@@ -2,6 +2,7 @@ use chrono::Utc;
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use crate::models::similarity::SimilarTask;
 use crate::schema::tasks;
 
 /// タスクのステータス
@@ -52,6 +53,8 @@ pub struct Task {
     pub parent_id: Option<String>,
     pub created_at: String, // ISO 8601形式の文字列
     pub updated_at: String, // ISO 8601形式の文字列
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_minutes: Option<i32>, // 見積もり時間（分）
 }
 
 impl Task {
@@ -74,6 +77,9 @@ impl Task {
             created_at: self.created_at,
             updated_at: self.updated_at,
             children_ids: Vec::new(), // 初期化（後でサービス層で設定）
+            estimated_minutes: self.estimated_minutes,
+            duplicate_warnings: Vec::new(),
+            created_tags: Vec::new(),
         }
     }
 }
@@ -95,6 +101,15 @@ pub struct TaskResponse {
     pub created_at: String,
     pub updated_at: String,
     pub children_ids: Vec<String>, // 子タスクのIDリスト
+    /// 見積もり時間（分、未設定はNone）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_minutes: Option<i32>,
+    /// 重複の可能性がある既存タスク（create_taskで重複チェック指定時のみ）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub duplicate_warnings: Vec<SimilarTask>,
+    /// 新規作成されたタグ名（create_task / update_task でタグの自動作成指定時のみ）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub created_tags: Vec<String>,
 }
 
 /// 階層構造を持つタスクレスポンス（get_hierarchy API用）
@@ -111,9 +126,25 @@ pub struct TaskHierarchyResponse {
     pub parent_id: Option<String>,
     pub created_at: String,
     pub updated_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimated_minutes: Option<i32>,
     pub children: Vec<TaskHierarchyResponse>, // 子タスクの配列（再帰的）
 }
 
+/// 階層の差分レスポンス（get_hierarchy_since API用）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskHierarchyDelta {
+    /// since 以降に変更されたサブツリー（ルート単位で置き換える）
+    pub changed: Vec<TaskHierarchyResponse>,
+    /// since 以降にプールから外れたルートタスクID（完了・アーカイブ・延期等）
+    pub removed_root_ids: Vec<String>,
+    /// since 以降に変更された子タスクのID（同じIDのルートがあれば子に移動したので取り除く）
+    pub moved_under_parent_ids: Vec<String>,
+    /// 次回の since に指定する時刻
+    pub synced_at: String,
+}
+
 /// 新規タスク作成用（DB INSERT用）
 #[derive(Debug, Insertable)]
 #[diesel(table_name = tasks)]
@@ -125,6 +156,7 @@ pub struct NewTask {
     pub parent_id: Option<String>,
     pub created_at: String,
     pub updated_at: String,
+    pub estimated_minutes: Option<i32>,
 }
 
 impl NewTask {
@@ -139,6 +171,7 @@ impl NewTask {
             parent_id: req.parent_id,
             created_at: now.clone(),
             updated_at: now,
+            estimated_minutes: req.estimated_minutes,
         }
     }
 }
@@ -154,6 +187,9 @@ pub struct CreateTaskRequest {
     pub tags: Vec<String>,
     #[serde(default)]
     pub parent_id: Option<String>,
+    /// 見積もり時間（分）
+    #[serde(default)]
+    pub estimated_minutes: Option<i32>,
 }
 
 /// タスク複製リクエスト（API受信用）
@@ -220,7 +256,7 @@ pub struct SearchTasksParams {
     /// ステータスフィルタ
     #[serde(default)]
     pub status: Option<String>,
-    /// タグフィルタ（OR条件）
+    /// タグフィルタ（同じグループ内はOR条件、グループ同士はAND条件）
     #[serde(default)]
     pub tags: Option<Vec<String>>,
     /// 1ページあたりの件数（デフォルト: 100）
@@ -229,6 +265,9 @@ pub struct SearchTasksParams {
     /// スキップする件数（デフォルト: 0）
     #[serde(default)]
     pub offset: Option<i64>,
+    /// ページングカーソル（指定時はoffsetより優先）
+    #[serde(default)]
+    pub cursor: Option<String>,
 }
 
 /// タスク一覧取得（ページネーション対応）パラメータ（API受信用）
@@ -244,6 +283,9 @@ pub struct ListTasksPaginatedParams {
     /// オフセット（デフォルト0）
     #[serde(default)]
     pub offset: Option<i64>,
+    /// ページングカーソル（指定時はoffsetより優先）
+    #[serde(default)]
+    pub cursor: Option<String>,
 }
 
 /// ページネーション付きタスク一覧レスポンス
@@ -254,4 +296,96 @@ pub struct PaginatedTaskResponse {
     pub tasks: Vec<TaskResponse>,
     /// 総件数（フィルタ適用後）
     pub total: i64,
+    /// 次ページ取得用カーソル（次ページがない場合はNone）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    /// 前ページ取得用カーソル（前ページがない場合はNone）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prev_cursor: Option<String>,
+}
+
+impl PaginatedTaskResponse {
+    /// 空のレスポンスを作成
+    pub fn empty() -> Self {
+        Self {
+            tasks: Vec::new(),
+            total: 0,
+            next_cursor: None,
+            prev_cursor: None,
+        }
+    }
+}
+
+/// カーソルの進行方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CursorDirection {
+    /// 境界より後ろ（次ページ）
+    After,
+    /// 境界より前（前ページ）
+    Before,
+}
+
+/// キーセットページネーション用カーソル
+///
+/// 並び順 (created_at DESC, id DESC) 上の境界位置を表す。
+/// クライアントには `encode()` した不透明な文字列として渡す。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskCursor {
+    pub created_at: String,
+    pub id: String,
+    pub direction: CursorDirection,
+}
+
+impl TaskCursor {
+    /// タスクの位置を境界とするカーソルを作成
+    pub fn new(task: &Task, direction: CursorDirection) -> Self {
+        Self {
+            created_at: task.created_at.clone(),
+            id: task.id.clone(),
+            direction,
+        }
+    }
+
+    /// 不透明なカーソル文字列にエンコード
+    pub fn encode(&self) -> String {
+        let direction = match self.direction {
+            CursorDirection::After => "a",
+            CursorDirection::Before => "b",
+        };
+        format!("{}|{}|{}", direction, self.created_at, self.id)
+            .bytes()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    /// カーソル文字列をデコード（不正な形式の場合はNone）
+    pub fn decode(cursor: &str) -> Option<Self> {
+        let bytes = cursor
+            .as_bytes()
+            .chunks(2)
+            .map(|pair| match pair {
+                [hi, lo] => u8::from_str_radix(std::str::from_utf8(&[*hi, *lo]).ok()?, 16).ok(),
+                _ => None,
+            })
+            .collect::<Option<Vec<u8>>>()?;
+        let raw = String::from_utf8(bytes).ok()?;
+
+        let mut parts = raw.splitn(3, '|');
+        let direction = match parts.next()? {
+            "a" => CursorDirection::After,
+            "b" => CursorDirection::Before,
+            _ => return None,
+        };
+        let created_at = parts.next()?.to_string();
+        let id = parts.next()?.to_string();
+        if created_at.is_empty() || id.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            created_at,
+            id,
+            direction,
+        })
+    }
 }
@@ -1,5 +1,100 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    app_settings (key) {
+        key -> Text,
+        value -> Text,
+        updated_at -> Text,
+    }
+}
+
+diesel::table! {
+    auto_tag_rule_tags (rule_id, tag_id) {
+        rule_id -> Text,
+        tag_id -> Text,
+    }
+}
+
+diesel::table! {
+    auto_tag_rules (id) {
+        id -> Text,
+        name -> Text,
+        target -> Text,
+        match_type -> Text,
+        pattern -> Text,
+        enabled -> Bool,
+        created_at -> Text,
+        updated_at -> Text,
+    }
+}
+
+diesel::table! {
+    deferred_tasks (task_id) {
+        task_id -> Text,
+        queue_id -> Text,
+        until_date -> Text,
+        deferred_from -> Text,
+        deferred_at -> Text,
+    }
+}
+
+diesel::table! {
+    focus_sessions (id) {
+        id -> Integer,
+        task_id -> Text,
+        task_title -> Text,
+        queue_date -> Text,
+        state -> Text,
+        work_minutes -> Integer,
+        break_minutes -> Integer,
+        started_at -> Text,
+        paused_at -> Nullable<Text>,
+        paused_seconds -> Integer,
+        ended_at -> Nullable<Text>,
+        focused_seconds -> Integer,
+        completed_task -> Bool,
+    }
+}
+
+diesel::table! {
+    queue_history (id) {
+        id -> Integer,
+        queue_date -> Text,
+        task_id -> Text,
+        task_title -> Text,
+        position -> Integer,
+        outcome -> Text,
+        recorded_at -> Text,
+    }
+}
+
+diesel::table! {
+    queues (id) {
+        id -> Text,
+        name -> Text,
+        created_at -> Text,
+        updated_at -> Text,
+    }
+}
+
+diesel::table! {
+    tag_aliases (alias) {
+        alias -> Text,
+        tag_id -> Text,
+        created_at -> Text,
+    }
+}
+
+diesel::table! {
+    tag_groups (id) {
+        id -> Text,
+        name -> Text,
+        exclusive -> Bool,
+        created_at -> Text,
+        updated_at -> Text,
+    }
+}
+
 diesel::table! {
     tags (id) {
         id -> Text,
@@ -8,14 +103,22 @@ diesel::table! {
         usage_count -> Integer,
         created_at -> Text,
         updated_at -> Text,
+        parent_id -> Nullable<Text>,
+        group_id -> Nullable<Text>,
+        draft_count -> Integer,
+        active_count -> Integer,
+        completed_count -> Integer,
+        archived_count -> Integer,
     }
 }
 
 diesel::table! {
     task_queue (task_id) {
         task_id -> Text,
-        position -> Integer,
+        queue_id -> Text,
+        rank -> Text,
         added_at -> Text,
+        queue_date -> Text,
     }
 }
 
@@ -35,11 +138,33 @@ diesel::table! {
         parent_id -> Nullable<Text>,
         created_at -> Text,
         updated_at -> Text,
+        estimated_minutes -> Nullable<Integer>,
     }
 }
 
+diesel::joinable!(auto_tag_rule_tags -> auto_tag_rules (rule_id));
+diesel::joinable!(auto_tag_rule_tags -> tags (tag_id));
+diesel::joinable!(deferred_tasks -> queues (queue_id));
+diesel::joinable!(deferred_tasks -> tasks (task_id));
+diesel::joinable!(task_queue -> queues (queue_id));
 diesel::joinable!(task_queue -> tasks (task_id));
+diesel::joinable!(tag_aliases -> tags (tag_id));
+diesel::joinable!(tags -> tag_groups (group_id));
 diesel::joinable!(task_tags -> tags (tag_id));
 diesel::joinable!(task_tags -> tasks (task_id));
 
-diesel::allow_tables_to_appear_in_same_query!(tags, task_queue, task_tags, tasks,);
+diesel::allow_tables_to_appear_in_same_query!(
+    app_settings,
+    auto_tag_rule_tags,
+    auto_tag_rules,
+    deferred_tasks,
+    focus_sessions,
+    queue_history,
+    queues,
+    tag_aliases,
+    tag_groups,
+    tags,
+    task_queue,
+    task_tags,
+    tasks,
+);
@@ -0,0 +1,642 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::Utc;
+use diesel::prelude::*;
+use regex::Regex;
+
+use crate::error::ServiceError;
+use crate::models::auto_tag::{
+    AutoTagMatch, AutoTagRule, AutoTagRuleResponse, AutoTagTarget, CreateAutoTagRuleRequest,
+    UpdateAutoTagRuleRequest,
+};
+use crate::models::task::Task;
+use crate::schema::{auto_tag_rule_tags, auto_tag_rules, tag_groups, tags, task_tags, tasks};
+use crate::service::tag::TagService;
+
+/// 判定用に準備したルール（正規表現はコンパイル済み）
+struct CompiledRule {
+    target: AutoTagTarget,
+    matcher: Matcher,
+    tag_ids: Vec<String>,
+}
+
+enum Matcher {
+    /// 小文字に変換したパターン
+    Contains(String),
+    Regex(Regex),
+    Equals(String),
+}
+
+impl CompiledRule {
+    fn matches(&self, task: &Task) -> bool {
+        let text = match self.target {
+            AutoTagTarget::Title => task.title.as_str(),
+            AutoTagTarget::Description => task.description.as_deref().unwrap_or(""),
+            AutoTagTarget::ParentId => task.parent_id.as_deref().unwrap_or(""),
+        };
+        match self.matcher {
+            Matcher::Contains(ref pattern) => text.to_lowercase().contains(pattern),
+            Matcher::Regex(ref regex) => regex.is_match(text),
+            Matcher::Equals(ref pattern) => text == pattern,
+        }
+    }
+}
+
+/// AutoTagService: 自動タグ付けルールの管理と適用
+///
+/// ルールはタグを付けるだけで、外すことはない。
+/// 判定できるのはタイトル・説明・親タスクのみ。タスクのモデルにカスタムフィールドが無いため、
+/// カスタムフィールドを条件にするルールには対応していない（AutoTagTarget を参照）。
+pub struct AutoTagService;
+
+impl AutoTagService {
+    /// 自動タグ付けルールの一覧を取得（作成順）
+    pub fn list_rules(
+        conn: &mut SqliteConnection,
+    ) -> Result<Vec<AutoTagRuleResponse>, ServiceError> {
+        let rules = auto_tag_rules::table
+            .order(auto_tag_rules::created_at.asc())
+            .load::<AutoTagRule>(conn)?;
+        let mut tags_by_rule = Self::load_rule_tag_names(conn)?;
+
+        Ok(rules
+            .into_iter()
+            .map(|rule| {
+                let tags = tags_by_rule.remove(&rule.id).unwrap_or_default();
+                rule.with_tags(tags)
+            })
+            .collect())
+    }
+
+    /// 自動タグ付けルールをIDで取得
+    pub fn get_rule(
+        conn: &mut SqliteConnection,
+        rule_id: &str,
+    ) -> Result<AutoTagRuleResponse, ServiceError> {
+        let rule = auto_tag_rules::table
+            .find(rule_id)
+            .first::<AutoTagRule>(conn)
+            .optional()?
+            .ok_or_else(|| ServiceError::AutoTagRuleNotFound(rule_id.to_string()))?;
+        let tags = tags::table
+            .inner_join(auto_tag_rule_tags::table)
+            .filter(auto_tag_rule_tags::rule_id.eq(rule_id))
+            .select(tags::name)
+            .order(tags::name.asc())
+            .load::<String>(conn)?;
+        Ok(rule.with_tags(tags))
+    }
+
+    /// 自動タグ付けルールを作成
+    ///
+    /// # Returns
+    /// * `Ok(AutoTagRuleResponse)` - 作成されたルール
+    /// * `Err(ServiceError::InvalidInput)` - 名前・パターンが空、正規表現が不正、タグが未指定
+    /// * `Err(ServiceError::TagNotFound)` - 存在しないタグが指定された
+    pub fn create_rule(
+        conn: &mut SqliteConnection,
+        req: CreateAutoTagRuleRequest,
+    ) -> Result<AutoTagRuleResponse, ServiceError> {
+        let name = Self::validate_name(&req.name)?;
+        Self::validate_pattern(req.match_type, &req.pattern)?;
+        let rule = AutoTagRule::new(
+            name,
+            req.target,
+            req.match_type,
+            req.pattern,
+            req.enabled.unwrap_or(true),
+        );
+
+        conn.transaction::<_, ServiceError, _>(|conn| {
+            let tag_ids = Self::resolve_tag_ids(conn, &req.tags)?;
+            diesel::insert_into(auto_tag_rules::table)
+                .values(&rule)
+                .execute(conn)?;
+            Self::insert_rule_tags(conn, &rule.id, &tag_ids)?;
+            Self::get_rule(conn, &rule.id)
+        })
+    }
+
+    /// 自動タグ付けルールを更新
+    ///
+    /// # Returns
+    /// * `Ok(AutoTagRuleResponse)` - 更新後のルール
+    /// * `Err(ServiceError::AutoTagRuleNotFound)` - ルールが存在しない
+    pub fn update_rule(
+        conn: &mut SqliteConnection,
+        rule_id: &str,
+        req: UpdateAutoTagRuleRequest,
+    ) -> Result<AutoTagRuleResponse, ServiceError> {
+        let rule = Self::get_rule(conn, rule_id)?;
+        let name = match req.name {
+            Some(ref name) => Self::validate_name(name)?,
+            None => rule.name,
+        };
+        let target = req.target.unwrap_or(rule.target);
+        let match_type = req.match_type.unwrap_or(rule.match_type);
+        let pattern = req.pattern.unwrap_or(rule.pattern);
+        Self::validate_pattern(match_type, &pattern)?;
+
+        conn.transaction::<_, ServiceError, _>(|conn| {
+            if let Some(ref tag_names) = req.tags {
+                let tag_ids = Self::resolve_tag_ids(conn, tag_names)?;
+                diesel::delete(
+                    auto_tag_rule_tags::table.filter(auto_tag_rule_tags::rule_id.eq(rule_id)),
+                )
+                .execute(conn)?;
+                Self::insert_rule_tags(conn, rule_id, &tag_ids)?;
+            }
+
+            diesel::update(auto_tag_rules::table.find(rule_id))
+                .set((
+                    auto_tag_rules::name.eq(name),
+                    auto_tag_rules::target.eq(target.as_str()),
+                    auto_tag_rules::match_type.eq(match_type.as_str()),
+                    auto_tag_rules::pattern.eq(pattern),
+                    auto_tag_rules::enabled.eq(req.enabled.unwrap_or(rule.enabled)),
+                    auto_tag_rules::updated_at.eq(Utc::now().to_rfc3339()),
+                ))
+                .execute(conn)?;
+
+            Self::get_rule(conn, rule_id)
+        })
+    }
+
+    /// 自動タグ付けルールを削除（付けたタグはタスクに残る）
+    pub fn delete_rule(conn: &mut SqliteConnection, rule_id: &str) -> Result<(), ServiceError> {
+        Self::get_rule(conn, rule_id)?;
+
+        conn.transaction::<_, ServiceError, _>(|conn| {
+            diesel::delete(
+                auto_tag_rule_tags::table.filter(auto_tag_rule_tags::rule_id.eq(rule_id)),
+            )
+            .execute(conn)?;
+            diesel::delete(auto_tag_rules::table.find(rule_id)).execute(conn)?;
+            Ok(())
+        })
+    }
+
+    /// タスクに有効なルールを適用（create_task / update_task から呼ばれる）
+    ///
+    /// # Returns
+    /// * `Ok(Vec<String>)` - 新たに付けたタグ名（名前順）
+    pub fn apply_to_task(
+        conn: &mut SqliteConnection,
+        task_id: &str,
+    ) -> Result<Vec<String>, ServiceError> {
+        let rules = Self::load_enabled_rules(conn)?;
+        if rules.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let task = tasks::table
+            .find(task_id)
+            .first::<Task>(conn)
+            .optional()?
+            .ok_or_else(|| ServiceError::TaskNotFound(task_id.to_string()))?;
+        Self::apply_rules(conn, &rules, &task)
+    }
+
+    /// 有効なルールをすべてのタスク（Archivedを除く）に適用し直す
+    ///
+    /// # Returns
+    /// * `Ok(Vec<String>)` - タグを付けたタスクのID
+    pub fn apply_to_all(conn: &mut SqliteConnection) -> Result<Vec<String>, ServiceError> {
+        let rules = Self::load_enabled_rules(conn)?;
+        if rules.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        conn.transaction::<_, ServiceError, _>(|conn| {
+            let all_tasks = tasks::table
+                .filter(tasks::status.ne("archived"))
+                .order(tasks::created_at.asc())
+                .load::<Task>(conn)?;
+
+            let mut updated_task_ids = Vec::new();
+            for task in all_tasks {
+                if !Self::apply_rules(conn, &rules, &task)?.is_empty() {
+                    updated_task_ids.push(task.id);
+                }
+            }
+            Ok(updated_task_ids)
+        })
+    }
+
+    /// 一致したルールのタグをタスクに付ける（内部用ヘルパー）
+    ///
+    /// - 既に付いているタグはそのまま
+    /// - 排他グループのタグが既に付いている場合、同じグループのタグは付けない
+    fn apply_rules(
+        conn: &mut SqliteConnection,
+        rules: &[CompiledRule],
+        task: &Task,
+    ) -> Result<Vec<String>, ServiceError> {
+        let current: HashSet<String> = task_tags::table
+            .filter(task_tags::task_id.eq(&task.id))
+            .select(task_tags::tag_id)
+            .load::<String>(conn)?
+            .into_iter()
+            .collect();
+
+        let mut candidates: Vec<String> = Vec::new();
+        for rule in rules.iter().filter(|rule| rule.matches(task)) {
+            for tag_id in &rule.tag_ids {
+                if !current.contains(tag_id) && !candidates.contains(tag_id) {
+                    candidates.push(tag_id.clone());
+                }
+            }
+        }
+        if candidates.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // 排他グループの所属（タグID → グループID）
+        let exclusive_groups: HashMap<String, String> = tags::table
+            .inner_join(tag_groups::table)
+            .filter(tag_groups::exclusive.eq(true))
+            .filter(tags::id.eq_any(current.iter().chain(candidates.iter())))
+            .select((tags::id, tag_groups::id))
+            .load::<(String, String)>(conn)?
+            .into_iter()
+            .collect();
+        let mut occupied: HashSet<&String> = current
+            .iter()
+            .filter_map(|tag_id| exclusive_groups.get(tag_id))
+            .collect();
+
+        let mut added = Vec::new();
+        for tag_id in &candidates {
+            if let Some(group_id) = exclusive_groups.get(tag_id) {
+                if !occupied.insert(group_id) {
+                    continue;
+                }
+            }
+            diesel::insert_into(task_tags::table)
+                .values((
+                    task_tags::task_id.eq(&task.id),
+                    task_tags::tag_id.eq(tag_id),
+                ))
+                .execute(conn)?;
+            added.push(tag_id.clone());
+        }
+
+        Ok(tags::table
+            .filter(tags::id.eq_any(&added))
+            .select(tags::name)
+            .order(tags::name.asc())
+            .load::<String>(conn)?)
+    }
+
+    /// 有効なルールを読み込み、判定用に準備（内部用ヘルパー）
+    fn load_enabled_rules(conn: &mut SqliteConnection) -> Result<Vec<CompiledRule>, ServiceError> {
+        let rules = auto_tag_rules::table
+            .filter(auto_tag_rules::enabled.eq(true))
+            .order(auto_tag_rules::created_at.asc())
+            .load::<AutoTagRule>(conn)?;
+
+        let mut tag_ids_by_rule: HashMap<String, Vec<String>> = HashMap::new();
+        for (rule_id, tag_id) in auto_tag_rule_tags::table
+            .select((auto_tag_rule_tags::rule_id, auto_tag_rule_tags::tag_id))
+            .load::<(String, String)>(conn)?
+        {
+            tag_ids_by_rule.entry(rule_id).or_default().push(tag_id);
+        }
+
+        let mut compiled = Vec::with_capacity(rules.len());
+        for rule in rules {
+            let (Some(target), Some(match_type)) = (
+                AutoTagTarget::parse(&rule.target),
+                AutoTagMatch::parse(&rule.match_type),
+            ) else {
+                continue;
+            };
+            let matcher = match match_type {
+                AutoTagMatch::Contains => Matcher::Contains(rule.pattern.to_lowercase()),
+                AutoTagMatch::Regex => Matcher::Regex(Self::compile_regex(&rule.pattern)?),
+                AutoTagMatch::Equals => Matcher::Equals(rule.pattern),
+            };
+            compiled.push(CompiledRule {
+                target,
+                matcher,
+                tag_ids: tag_ids_by_rule.remove(&rule.id).unwrap_or_default(),
+            });
+        }
+        Ok(compiled)
+    }
+
+    /// ルールごとの付けるタグ名（名前順）を読み込む（内部用ヘルパー）
+    fn load_rule_tag_names(
+        conn: &mut SqliteConnection,
+    ) -> Result<HashMap<String, Vec<String>>, ServiceError> {
+        let mut tags_by_rule: HashMap<String, Vec<String>> = HashMap::new();
+        for (rule_id, tag_name) in auto_tag_rule_tags::table
+            .inner_join(tags::table)
+            .select((auto_tag_rule_tags::rule_id, tags::name))
+            .order(tags::name.asc())
+            .load::<(String, String)>(conn)?
+        {
+            tags_by_rule.entry(rule_id).or_default().push(tag_name);
+        }
+        Ok(tags_by_rule)
+    }
+
+    /// タグ名（別名を含む）をタグIDに解決（内部用ヘルパー）
+    fn resolve_tag_ids(
+        conn: &mut SqliteConnection,
+        tag_names: &[String],
+    ) -> Result<Vec<String>, ServiceError> {
+        if tag_names.is_empty() {
+            return Err(ServiceError::InvalidInput(
+                "付けるタグを1つ以上指定してください".to_string(),
+            ));
+        }
+
+        let tag_names = TagService::resolve_aliases(conn, tag_names)?;
+        let mut tag_ids = Vec::with_capacity(tag_names.len());
+        for tag_name in tag_names {
+            let tag_id = tags::table
+                .filter(tags::name.eq(&tag_name))
+                .select(tags::id)
+                .first::<String>(conn)
+                .optional()?
+                .ok_or(ServiceError::TagNotFound(tag_name))?;
+            tag_ids.push(tag_id);
+        }
+        Ok(tag_ids)
+    }
+
+    fn insert_rule_tags(
+        conn: &mut SqliteConnection,
+        rule_id: &str,
+        tag_ids: &[String],
+    ) -> Result<(), ServiceError> {
+        let rows: Vec<_> = tag_ids
+            .iter()
+            .map(|tag_id| {
+                (
+                    auto_tag_rule_tags::rule_id.eq(rule_id),
+                    auto_tag_rule_tags::tag_id.eq(tag_id),
+                )
+            })
+            .collect();
+        diesel::insert_into(auto_tag_rule_tags::table)
+            .values(&rows)
+            .execute(conn)?;
+        Ok(())
+    }
+
+    fn validate_name(name: &str) -> Result<String, ServiceError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(ServiceError::InvalidInput("ルール名は必須です".to_string()));
+        }
+        Ok(name.to_string())
+    }
+
+    fn validate_pattern(match_type: AutoTagMatch, pattern: &str) -> Result<(), ServiceError> {
+        if pattern.is_empty() {
+            return Err(ServiceError::InvalidInput("パターンは必須です".to_string()));
+        }
+        if match_type == AutoTagMatch::Regex {
+            Self::compile_regex(pattern)?;
+        }
+        Ok(())
+    }
+
+    fn compile_regex(pattern: &str) -> Result<Regex, ServiceError> {
+        Regex::new(pattern)
+            .map_err(|e| ServiceError::InvalidInput(format!("正規表現が不正です: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::tag::{CreateTagGroupRequest, CreateTagRequest};
+    use crate::models::task::{CreateTaskRequest, TaskResponse, UpdateTaskRequestInput};
+    use crate::service::TaskService;
+    use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+
+    pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
+
+    fn setup_test_db() -> SqliteConnection {
+        let mut conn =
+            SqliteConnection::establish(":memory:").expect("Failed to create in-memory database");
+
+        conn.run_pending_migrations(MIGRATIONS)
+            .expect("Failed to run migrations");
+
+        conn
+    }
+
+    fn create_tag(conn: &mut SqliteConnection, name: &str) -> String {
+        TagService::create_tag(
+            conn,
+            CreateTagRequest {
+                name: name.to_string(),
+                color: None,
+            },
+        )
+        .unwrap()
+        .id
+    }
+
+    fn create_rule(
+        conn: &mut SqliteConnection,
+        target: AutoTagTarget,
+        match_type: AutoTagMatch,
+        pattern: &str,
+        tags: &[&str],
+    ) -> Result<AutoTagRuleResponse, ServiceError> {
+        AutoTagService::create_rule(
+            conn,
+            CreateAutoTagRuleRequest {
+                name: format!("ルール: {}", pattern),
+                target,
+                match_type,
+                pattern: pattern.to_string(),
+                tags: tags.iter().map(|t| t.to_string()).collect(),
+                enabled: None,
+            },
+        )
+    }
+
+    fn create_task(
+        conn: &mut SqliteConnection,
+        title: &str,
+        description: Option<&str>,
+        parent_id: Option<&str>,
+    ) -> TaskResponse {
+        TaskService::create_task(
+            conn,
+            CreateTaskRequest {
+                title: title.to_string(),
+                description: description.map(str::to_string),
+                tags: vec![],
+                parent_id: parent_id.map(str::to_string),
+                estimated_minutes: None,
+            },
+        )
+        .unwrap()
+    }
+
+    fn update_task(
+        conn: &mut SqliteConnection,
+        task_id: &str,
+        title: Option<&str>,
+        tags: Option<Vec<String>>,
+    ) -> TaskResponse {
+        TaskService::update_task(
+            conn,
+            task_id,
+            UpdateTaskRequestInput {
+                title: title.map(str::to_string),
+                description: None,
+                status: None,
+                parent_id: None,
+                tags,
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_rules_apply_on_create_and_update() {
+        let mut conn = setup_test_db();
+        create_tag(&mut conn, "bug");
+        create_tag(&mut conn, "frontend");
+        create_tag(&mut conn, "project");
+        let bug_rule = create_rule(
+            &mut conn,
+            AutoTagTarget::Title,
+            AutoTagMatch::Regex,
+            r"^\[BUG\]",
+            &["bug"],
+        )
+        .unwrap();
+        assert_eq!(bug_rule.tags, vec!["bug"]);
+        create_rule(
+            &mut conn,
+            AutoTagTarget::Description,
+            AutoTagMatch::Contains,
+            "css",
+            &["frontend"],
+        )
+        .unwrap();
+
+        let crash = create_task(&mut conn, "[BUG] 保存できない", None, None);
+        assert_eq!(crash.tags, vec!["bug"]);
+        let layout = create_task(&mut conn, "レイアウト修正", Some("CSSが崩れる"), None);
+        assert_eq!(layout.tags, vec!["frontend"]);
+
+        // 親タスクのIDに一致するルール
+        let parent = create_task(&mut conn, "プロジェクト", None, None);
+        create_rule(
+            &mut conn,
+            AutoTagTarget::ParentId,
+            AutoTagMatch::Equals,
+            &parent.id,
+            &["project"],
+        )
+        .unwrap();
+        let child = create_task(&mut conn, "調査", None, Some(&parent.id));
+        assert_eq!(child.tags, vec!["project"]);
+
+        // タイトルを変更するとルールを適用し直す
+        let plain = create_task(&mut conn, "ログイン", None, None);
+        assert!(plain.tags.is_empty());
+        let updated = update_task(&mut conn, &plain.id, Some("[BUG] ログインできない"), None);
+        assert_eq!(updated.tags, vec!["bug"]);
+
+        // タグだけを変更した場合は、外したタグを付け直さない
+        let updated = update_task(&mut conn, &plain.id, None, Some(vec![]));
+        assert!(updated.tags.is_empty());
+
+        // 無効にしたルールは適用しない
+        AutoTagService::update_rule(
+            &mut conn,
+            &bug_rule.id,
+            UpdateAutoTagRuleRequest {
+                name: None,
+                target: None,
+                match_type: None,
+                pattern: None,
+                tags: None,
+                enabled: Some(false),
+            },
+        )
+        .unwrap();
+        let skipped = create_task(&mut conn, "[BUG] 別の不具合", None, None);
+        assert!(skipped.tags.is_empty());
+    }
+
+    #[test]
+    fn test_apply_to_all_respects_exclusive_groups() {
+        let mut conn = setup_test_db();
+        create_tag(&mut conn, "bug");
+        let small = create_tag(&mut conn, "S");
+        let medium = create_tag(&mut conn, "M");
+        let size = TagService::create_tag_group(
+            &mut conn,
+            CreateTagGroupRequest {
+                name: "サイズ".to_string(),
+                exclusive: true,
+            },
+        )
+        .unwrap();
+        TagService::set_tag_group(&mut conn, &small, Some(&size.id)).unwrap();
+        TagService::set_tag_group(&mut conn, &medium, Some(&size.id)).unwrap();
+
+        // ルールより前に作成したタスク
+        let sized = create_task(&mut conn, "[BUG] 大きめの不具合", None, None);
+        update_task(&mut conn, &sized.id, None, Some(vec!["M".to_string()]));
+        let unsized_bug = create_task(&mut conn, "[bug] 小さな不具合", None, None);
+        create_task(&mut conn, "機能追加", None, None);
+
+        let result = create_rule(
+            &mut conn,
+            AutoTagTarget::Title,
+            AutoTagMatch::Regex,
+            "[unclosed",
+            &["bug"],
+        );
+        assert!(matches!(result, Err(ServiceError::InvalidInput(_))));
+        let result = create_rule(
+            &mut conn,
+            AutoTagTarget::Title,
+            AutoTagMatch::Contains,
+            "[bug]",
+            &[],
+        );
+        assert!(matches!(result, Err(ServiceError::InvalidInput(_))));
+
+        create_rule(
+            &mut conn,
+            AutoTagTarget::Title,
+            AutoTagMatch::Contains,
+            "[bug]",
+            &["bug", "S"],
+        )
+        .unwrap();
+
+        let mut updated = AutoTagService::apply_to_all(&mut conn).unwrap();
+        updated.sort();
+        let mut expected = vec![sized.id.clone(), unsized_bug.id.clone()];
+        expected.sort();
+        assert_eq!(updated, expected);
+
+        // 排他グループのタグ（M）が付いているタスクには S を付けない
+        let mut tags = TaskService::get_task(&mut conn, &sized.id).unwrap().tags;
+        tags.sort();
+        assert_eq!(tags, vec!["M", "bug"]);
+        let mut tags = TaskService::get_task(&mut conn, &unsized_bug.id)
+            .unwrap()
+            .tags;
+        tags.sort();
+        assert_eq!(tags, vec!["S", "bug"]);
+
+        // 2回目は付けるタグが無い
+        assert!(AutoTagService::apply_to_all(&mut conn).unwrap().is_empty());
+    }
+}
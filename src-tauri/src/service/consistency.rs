@@ -0,0 +1,478 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::SqliteConnection;
+
+use crate::error::ServiceError;
+use crate::models::consistency::{ConsistencyIssue, ConsistencyIssueKind, ConsistencyReport};
+use crate::models::queue::QueueEntry;
+use crate::models::queue_history::QueueOutcome;
+use crate::models::task::{Task, TaskStatus};
+use crate::schema::{deferred_tasks, task_queue, tasks};
+use crate::service::queue::{date_key, QueueService};
+use crate::service::rank;
+use crate::service::settings::{SettingsService, SESSION_OPEN_KEY};
+use crate::service::task::TaskService;
+
+/// ConsistencyService: タスクのステータスとキューの整合性チェック・修復
+///
+/// `update_task` でステータスを直接変更した場合や、処理の途中でアプリが
+/// 異常終了した場合に崩れうる次の不変条件を検査する。
+/// - Activeの（子タスクを持たない）タスクはキューに入っている（延期中を含む）
+/// - 今日以降のキュー・延期中のタスクにCompleted/Archivedのタスクが無い
+///   （過去の日付のキューはロールオーバーで履歴に記録されるため対象外）
+/// - 親タスクのステータスが子タスクから計算したステータスと一致する
+/// - キューの並び順キー（rank）が正しい形式で、位置が一意に決まる
+pub struct ConsistencyService;
+
+impl ConsistencyService {
+    /// 不整合を検出する（データは変更しない）
+    ///
+    /// # Returns
+    /// * `Ok(ConsistencyReport)` - 検出した不整合（repaired = false）
+    /// * `Err(ServiceError)` - エラー
+    pub fn check(conn: &mut SqliteConnection) -> Result<ConsistencyReport, ServiceError> {
+        let today = date_key(QueueService::today(conn)?);
+
+        let mut issues = Vec::new();
+        issues.extend(
+            Self::closed_queue_entries(conn, &today)?
+                .iter()
+                .map(|(entry, task)| Self::closed_queued_issue(task, Some(entry))),
+        );
+        issues.extend(
+            Self::closed_deferred_tasks(conn)?
+                .iter()
+                .map(|task| Self::closed_queued_issue(task, None)),
+        );
+        issues.extend(
+            Self::active_not_queued(conn)?
+                .iter()
+                .map(Self::active_not_queued_issue),
+        );
+        issues.extend(
+            Self::invalid_rank_entries(conn)?
+                .iter()
+                .map(Self::invalid_rank_issue),
+        );
+        issues.extend(
+            Self::parent_status_mismatches(conn)?
+                .iter()
+                .map(|(task, expected)| Self::parent_status_issue(task, expected)),
+        );
+
+        Ok(ConsistencyReport {
+            issues,
+            repaired: false,
+        })
+    }
+
+    /// 不整合を検出して修復する（1つのトランザクションで実行）
+    ///
+    /// # Returns
+    /// * `Ok(ConsistencyReport)` - 修復した不整合（repaired = true）
+    /// * `Err(ServiceError)` - エラー（何も変更されない）
+    ///
+    /// # Business Logic
+    /// - Completed/Archivedのタスクを今日以降のキューから外す
+    ///   （今日のキューの場合は completed / archived として履歴に記録）、延期も解除する
+    /// - キューにも延期中のタスクにも無いActiveのタスクをDraftに戻す
+    /// - rankが不正なエントリを含むキューのrankを振り直す（並び順は変えない）
+    /// - 上記の後で親タスクのステータスを子タスクから計算し直す
+    pub fn repair(conn: &mut SqliteConnection) -> Result<ConsistencyReport, ServiceError> {
+        let today = date_key(QueueService::today(conn)?);
+
+        conn.transaction::<_, ServiceError, _>(|conn| {
+            let mut issues = Vec::new();
+            let now = Utc::now().to_rfc3339();
+
+            // 1. 完了・アーカイブ済みのタスクをキューから外す
+            for (entry, task) in Self::closed_queue_entries(conn, &today)? {
+                if entry.queue_date == today {
+                    let outcome = if task.status == TaskStatus::Completed.as_str() {
+                        QueueOutcome::Completed
+                    } else {
+                        QueueOutcome::Archived
+                    };
+                    QueueService::record_history(conn, &entry, outcome)?;
+                }
+                diesel::delete(task_queue::table.find(&entry.task_id)).execute(conn)?;
+                issues.push(Self::closed_queued_issue(&task, Some(&entry)));
+            }
+            for task in Self::closed_deferred_tasks(conn)? {
+                diesel::delete(deferred_tasks::table.find(&task.id)).execute(conn)?;
+                issues.push(Self::closed_queued_issue(&task, None));
+            }
+
+            // 2. キューに無いActiveのタスクをDraftに戻す
+            for task in Self::active_not_queued(conn)? {
+                diesel::update(tasks::table.find(&task.id))
+                    .set((
+                        tasks::status.eq(TaskStatus::Draft.as_str()),
+                        tasks::updated_at.eq(&now),
+                    ))
+                    .execute(conn)?;
+                issues.push(Self::active_not_queued_issue(&task));
+            }
+
+            // 3. rankが不正なキューを振り直す
+            let invalid_entries = Self::invalid_rank_entries(conn)?;
+            let queues: BTreeSet<(String, String)> = invalid_entries
+                .iter()
+                .map(|entry| (entry.queue_id.clone(), entry.queue_date.clone()))
+                .collect();
+            for (queue_id, queue_date) in &queues {
+                QueueService::rebalance_queue(conn, queue_id, queue_date)?;
+            }
+            issues.extend(invalid_entries.iter().map(Self::invalid_rank_issue));
+
+            // 4. 親タスクのステータスを計算し直す（上の階層へ波及しなくなるまで繰り返す）
+            loop {
+                let mismatches = Self::parent_status_mismatches(conn)?;
+                if mismatches.is_empty() {
+                    break;
+                }
+                for (task, expected) in mismatches {
+                    diesel::update(tasks::table.find(&task.id))
+                        .set((
+                            tasks::status.eq(expected.as_str()),
+                            tasks::updated_at.eq(&now),
+                        ))
+                        .execute(conn)?;
+                    issues.push(Self::parent_status_issue(&task, &expected));
+                }
+            }
+
+            Ok(ConsistencyReport {
+                issues,
+                repaired: true,
+            })
+        })
+    }
+
+    /// アプリ起動時に呼び出す（前回が異常終了だった場合は修復を実行）
+    ///
+    /// # Returns
+    /// * `Ok(Some(ConsistencyReport))` - 前回が異常終了だったため修復を実行した
+    /// * `Ok(None)` - 前回は正常終了していた（初回起動を含む）
+    /// * `Err(ServiceError)` - エラー
+    pub fn begin_session(
+        conn: &mut SqliteConnection,
+    ) -> Result<Option<ConsistencyReport>, ServiceError> {
+        let crashed = SettingsService::get(conn, SESSION_OPEN_KEY)?
+            .and_then(|v| v.parse::<bool>().ok())
+            .unwrap_or(false);
+        let report = if crashed {
+            Some(Self::repair(conn)?)
+        } else {
+            None
+        };
+
+        SettingsService::set(conn, SESSION_OPEN_KEY, "true")?;
+        Ok(report)
+    }
+
+    /// アプリの正常終了時に呼び出す
+    pub fn end_session(conn: &mut SqliteConnection) -> Result<(), ServiceError> {
+        SettingsService::set(conn, SESSION_OPEN_KEY, "false")
+    }
+
+    /// 今日以降のキューに残っているCompleted/Archivedのタスク（内部用ヘルパー）
+    fn closed_queue_entries(
+        conn: &mut SqliteConnection,
+        today: &str,
+    ) -> Result<Vec<(QueueEntry, Task)>, ServiceError> {
+        Ok(task_queue::table
+            .inner_join(tasks::table)
+            .filter(task_queue::queue_date.ge(today))
+            .filter(tasks::status.eq_any(Self::closed_statuses()))
+            .order((task_queue::queue_date.asc(), task_queue::rank.asc()))
+            .load::<(QueueEntry, Task)>(conn)?)
+    }
+
+    /// 延期中のCompleted/Archivedのタスク（内部用ヘルパー）
+    fn closed_deferred_tasks(conn: &mut SqliteConnection) -> Result<Vec<Task>, ServiceError> {
+        Ok(tasks::table
+            .filter(tasks::id.eq_any(deferred_tasks::table.select(deferred_tasks::task_id)))
+            .filter(tasks::status.eq_any(Self::closed_statuses()))
+            .order(tasks::created_at.asc())
+            .load::<Task>(conn)?)
+    }
+
+    /// キューにも延期中のタスクにも無い、子タスクを持たないActiveのタスク（内部用ヘルパー）
+    fn active_not_queued(conn: &mut SqliteConnection) -> Result<Vec<Task>, ServiceError> {
+        let parent_ids = Self::parent_ids(conn)?;
+        let tasks = tasks::table
+            .filter(tasks::status.eq(TaskStatus::Active.as_str()))
+            .filter(tasks::id.ne_all(task_queue::table.select(task_queue::task_id)))
+            .filter(tasks::id.ne_all(deferred_tasks::table.select(deferred_tasks::task_id)))
+            .order(tasks::created_at.asc())
+            .load::<Task>(conn)?;
+
+        Ok(tasks
+            .into_iter()
+            .filter(|task| !parent_ids.contains(&task.id))
+            .collect())
+    }
+
+    /// rankが不正なキューエントリ（内部用ヘルパー）
+    fn invalid_rank_entries(conn: &mut SqliteConnection) -> Result<Vec<QueueEntry>, ServiceError> {
+        let entries = task_queue::table
+            .order((
+                task_queue::queue_id.asc(),
+                task_queue::queue_date.asc(),
+                task_queue::rank.asc(),
+            ))
+            .load::<QueueEntry>(conn)?;
+
+        Ok(entries
+            .into_iter()
+            .filter(|entry| !rank::is_valid(&entry.rank))
+            .collect())
+    }
+
+    /// ステータスが子タスクから計算したステータスと一致しない親タスク（内部用ヘルパー）
+    ///
+    /// Archivedの親タスクは対象外（アーカイブは子タスクの状態によらず維持する）。
+    fn parent_status_mismatches(
+        conn: &mut SqliteConnection,
+    ) -> Result<Vec<(Task, TaskStatus)>, ServiceError> {
+        let mut child_statuses: HashMap<String, Vec<TaskStatus>> = HashMap::new();
+        for (parent_id, status) in tasks::table
+            .filter(tasks::parent_id.is_not_null())
+            .select((tasks::parent_id, tasks::status))
+            .load::<(Option<String>, String)>(conn)?
+        {
+            let status = TaskStatus::from_str(&status)
+                .ok_or_else(|| ServiceError::InvalidTaskStatus(status.clone()))?;
+            if let Some(parent_id) = parent_id {
+                child_statuses.entry(parent_id).or_default().push(status);
+            }
+        }
+
+        let parents = tasks::table
+            .filter(tasks::id.eq_any(child_statuses.keys()))
+            .filter(tasks::status.ne(TaskStatus::Archived.as_str()))
+            .order(tasks::created_at.asc())
+            .load::<Task>(conn)?;
+
+        Ok(parents
+            .into_iter()
+            .filter_map(|parent| {
+                let expected = TaskService::calculate_parent_status(
+                    child_statuses.remove(&parent.id).unwrap_or_default(),
+                );
+                (parent.status != expected.as_str()).then_some((parent, expected))
+            })
+            .collect())
+    }
+
+    /// 子タスクを持つタスクのID（内部用ヘルパー）
+    fn parent_ids(conn: &mut SqliteConnection) -> Result<HashSet<String>, ServiceError> {
+        Ok(tasks::table
+            .filter(tasks::parent_id.is_not_null())
+            .select(tasks::parent_id)
+            .distinct()
+            .load::<Option<String>>(conn)?
+            .into_iter()
+            .flatten()
+            .collect())
+    }
+
+    fn closed_statuses() -> [&'static str; 2] {
+        [
+            TaskStatus::Completed.as_str(),
+            TaskStatus::Archived.as_str(),
+        ]
+    }
+
+    fn closed_queued_issue(task: &Task, entry: Option<&QueueEntry>) -> ConsistencyIssue {
+        let message = match entry {
+            Some(entry) => format!(
+                "{}のタスク「{}」が{}のキューに残っています",
+                task.status, task.title, entry.queue_date
+            ),
+            None => format!("{}のタスク「{}」が延期中です", task.status, task.title),
+        };
+        ConsistencyIssue {
+            kind: ConsistencyIssueKind::ClosedTaskQueued,
+            task_id: task.id.clone(),
+            message,
+        }
+    }
+
+    fn active_not_queued_issue(task: &Task) -> ConsistencyIssue {
+        ConsistencyIssue {
+            kind: ConsistencyIssueKind::ActiveNotQueued,
+            task_id: task.id.clone(),
+            message: format!("Activeのタスク「{}」がキューにありません", task.title),
+        }
+    }
+
+    fn invalid_rank_issue(entry: &QueueEntry) -> ConsistencyIssue {
+        ConsistencyIssue {
+            kind: ConsistencyIssueKind::InvalidQueueRank,
+            task_id: entry.task_id.clone(),
+            message: format!(
+                "{}のキューの並び順キーが不正です: {:?}",
+                entry.queue_date, entry.rank
+            ),
+        }
+    }
+
+    fn parent_status_issue(task: &Task, expected: &TaskStatus) -> ConsistencyIssue {
+        ConsistencyIssue {
+            kind: ConsistencyIssueKind::ParentStatusMismatch,
+            task_id: task.id.clone(),
+            message: format!(
+                "親タスク「{}」のステータスが{}です（子タスクからは{}）",
+                task.title,
+                task.status,
+                expected.as_str()
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::queue::DEFAULT_QUEUE_ID;
+    use crate::models::task::CreateTaskRequest;
+    use crate::schema::queue_history;
+    use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+
+    pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
+
+    fn setup_test_db() -> SqliteConnection {
+        let mut conn =
+            SqliteConnection::establish(":memory:").expect("Failed to create in-memory database");
+
+        conn.run_pending_migrations(MIGRATIONS)
+            .expect("Failed to run migrations");
+
+        conn
+    }
+
+    fn create_task(conn: &mut SqliteConnection, title: &str, parent_id: Option<String>) -> String {
+        let req = CreateTaskRequest {
+            title: title.to_string(),
+            description: None,
+            tags: vec![],
+            parent_id,
+            estimated_minutes: None,
+        };
+        TaskService::create_task(conn, req).unwrap().id
+    }
+
+    fn set_status(conn: &mut SqliteConnection, task_id: &str, status: TaskStatus) {
+        diesel::update(tasks::table.find(task_id))
+            .set(tasks::status.eq(status.as_str()))
+            .execute(conn)
+            .unwrap();
+    }
+
+    fn status_of(conn: &mut SqliteConnection, task_id: &str) -> String {
+        tasks::table
+            .find(task_id)
+            .select(tasks::status)
+            .first::<String>(conn)
+            .unwrap()
+    }
+
+    fn kinds(report: &ConsistencyReport) -> Vec<(ConsistencyIssueKind, String)> {
+        let mut kinds: Vec<_> = report
+            .issues
+            .iter()
+            .map(|issue| (issue.kind, issue.task_id.clone()))
+            .collect();
+        kinds.sort_by(|a, b| a.1.cmp(&b.1));
+        kinds
+    }
+
+    #[test]
+    fn test_check_and_repair_fix_violations() {
+        let mut conn = setup_test_db();
+        let parent = create_task(&mut conn, "親", None);
+        let child = create_task(&mut conn, "子", Some(parent.clone()));
+        let done = create_task(&mut conn, "完了済み", None);
+        let queued = create_task(&mut conn, "キュー", None);
+        for task_id in [&child, &done, &queued] {
+            QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, task_id.clone(), None, false)
+                .unwrap();
+        }
+        assert!(ConsistencyService::check(&mut conn)
+            .unwrap()
+            .issues
+            .is_empty());
+
+        // update_taskなどでステータスだけが変更された状態を作る
+        set_status(&mut conn, &done, TaskStatus::Completed);
+        diesel::delete(task_queue::table.find(&child))
+            .execute(&mut conn)
+            .unwrap();
+        diesel::update(task_queue::table.find(&queued))
+            .set(task_queue::rank.eq("i0"))
+            .execute(&mut conn)
+            .unwrap();
+
+        let report = ConsistencyService::check(&mut conn).unwrap();
+        assert!(!report.repaired);
+        let mut expected = vec![
+            (ConsistencyIssueKind::ClosedTaskQueued, done.clone()),
+            (ConsistencyIssueKind::ActiveNotQueued, child.clone()),
+            (ConsistencyIssueKind::InvalidQueueRank, queued.clone()),
+        ];
+        expected.sort_by(|a, b| a.1.cmp(&b.1));
+        assert_eq!(kinds(&report), expected);
+
+        // 修復すると子タスクがDraftに戻り、親タスクのステータスも計算し直される
+        let report = ConsistencyService::repair(&mut conn).unwrap();
+        assert!(report.repaired);
+        let mut expected_repaired = expected.clone();
+        expected_repaired.push((ConsistencyIssueKind::ParentStatusMismatch, parent.clone()));
+        expected_repaired.sort_by(|a, b| a.1.cmp(&b.1));
+        assert_eq!(kinds(&report), expected_repaired);
+
+        assert_eq!(status_of(&mut conn, &child), TaskStatus::Draft.as_str());
+        assert_eq!(status_of(&mut conn, &parent), TaskStatus::Draft.as_str());
+        let queue = QueueService::get_queue(&mut conn, DEFAULT_QUEUE_ID, None).unwrap();
+        assert_eq!(queue.len(), 1);
+        assert!(rank::is_valid(&queue[0].rank));
+        let outcomes: Vec<String> = queue_history::table
+            .filter(queue_history::task_id.eq(&done))
+            .select(queue_history::outcome)
+            .load(&mut conn)
+            .unwrap();
+        assert_eq!(outcomes, vec![QueueOutcome::Completed.as_str()]);
+
+        assert!(ConsistencyService::check(&mut conn)
+            .unwrap()
+            .issues
+            .is_empty());
+    }
+
+    #[test]
+    fn test_begin_session_repairs_only_after_abnormal_exit() {
+        let mut conn = setup_test_db();
+        let task_id = create_task(&mut conn, "タスク", None);
+        set_status(&mut conn, &task_id, TaskStatus::Active);
+
+        // 初回起動では修復しない
+        assert!(ConsistencyService::begin_session(&mut conn)
+            .unwrap()
+            .is_none());
+        ConsistencyService::end_session(&mut conn).unwrap();
+        assert!(ConsistencyService::begin_session(&mut conn)
+            .unwrap()
+            .is_none());
+
+        // end_sessionを経ずに再起動した場合は修復する
+        let report = ConsistencyService::begin_session(&mut conn)
+            .unwrap()
+            .unwrap();
+        assert_eq!(report.affected_task_ids(), vec![task_id.clone()]);
+        assert_eq!(status_of(&mut conn, &task_id), TaskStatus::Draft.as_str());
+    }
+}
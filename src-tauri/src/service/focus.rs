@@ -0,0 +1,445 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel::SqliteConnection;
+
+use crate::error::ServiceError;
+use crate::models::focus::{
+    FocusPhase, FocusSession, FocusSessionResponse, FocusSessionState, NewFocusSession,
+};
+use crate::schema::{focus_sessions, task_queue};
+use crate::service::queue::{date_key, QueueService};
+use crate::service::settings::SettingsService;
+
+/// FocusService: 今日のキュー先頭のタスクに対する集中セッション（ポモドーロ）
+///
+/// 同時に進行できるセッションは1つだけ。セッションは作業時間→休憩時間の順に進み、
+/// 集中時間は一時停止していた時間を除いた経過時間（作業時間が上限）として記録される。
+pub struct FocusService;
+
+impl FocusService {
+    /// 指定キューの今日の先頭のタスクで集中セッションを開始
+    ///
+    /// # Arguments
+    /// * `conn` - データベース接続
+    /// * `queue_id` - キューID
+    /// * `now` - 現在時刻
+    ///
+    /// # Returns
+    /// * `Ok(FocusSessionResponse)` - 開始されたセッション
+    /// * `Err(ServiceError::FocusSessionInProgress)` - 進行中のセッションがある
+    /// * `Err(ServiceError::InvalidInput)` - 今日のキューが空
+    pub fn start_session(
+        conn: &mut SqliteConnection,
+        queue_id: &str,
+        now: DateTime<Utc>,
+    ) -> Result<FocusSessionResponse, ServiceError> {
+        let session = conn.transaction::<_, ServiceError, _>(|conn| {
+            if let Some(open) = Self::find_open_session(conn)? {
+                return Err(ServiceError::FocusSessionInProgress(open.id));
+            }
+
+            let today = QueueService::today(conn)?;
+            let head = QueueService::get_queue(conn, queue_id, Some(today))?
+                .into_iter()
+                .next()
+                .ok_or_else(|| {
+                    ServiceError::InvalidInput("今日のキューにタスクがありません".to_string())
+                })?;
+            let settings = SettingsService::get_focus_settings(conn)?;
+
+            diesel::insert_into(focus_sessions::table)
+                .values(&NewFocusSession {
+                    task_id: head.task_id,
+                    task_title: head.task_title,
+                    queue_date: date_key(today),
+                    state: FocusSessionState::Running.as_str().to_string(),
+                    work_minutes: settings.work_minutes as i32,
+                    break_minutes: settings.break_minutes as i32,
+                    started_at: now.to_rfc3339(),
+                })
+                .execute(conn)?;
+
+            Self::find_open_session(conn)?.ok_or(ServiceError::FocusSessionNotFound)
+        })?;
+
+        Self::to_response(session, now)
+    }
+
+    /// 進行中のセッションを一時停止
+    ///
+    /// # Returns
+    /// * `Err(ServiceError::FocusSessionNotFound)` - 進行中のセッションが無い
+    /// * `Err(ServiceError::InvalidInput)` - すでに一時停止中
+    pub fn pause_session(
+        conn: &mut SqliteConnection,
+        now: DateTime<Utc>,
+    ) -> Result<FocusSessionResponse, ServiceError> {
+        let session = Self::find_open_session(conn)?.ok_or(ServiceError::FocusSessionNotFound)?;
+        if Self::parse_state(&session.state)? == FocusSessionState::Paused {
+            return Err(ServiceError::InvalidInput(
+                "セッションはすでに一時停止中です".to_string(),
+            ));
+        }
+
+        diesel::update(focus_sessions::table.find(session.id))
+            .set((
+                focus_sessions::state.eq(FocusSessionState::Paused.as_str()),
+                focus_sessions::paused_at.eq(Some(now.to_rfc3339())),
+            ))
+            .execute(conn)?;
+
+        Self::get_session(conn, session.id, now)
+    }
+
+    /// 一時停止中のセッションを再開
+    ///
+    /// # Returns
+    /// * `Err(ServiceError::FocusSessionNotFound)` - 進行中のセッションが無い
+    /// * `Err(ServiceError::InvalidInput)` - 一時停止中ではない
+    pub fn resume_session(
+        conn: &mut SqliteConnection,
+        now: DateTime<Utc>,
+    ) -> Result<FocusSessionResponse, ServiceError> {
+        let session = Self::find_open_session(conn)?.ok_or(ServiceError::FocusSessionNotFound)?;
+        if Self::parse_state(&session.state)? != FocusSessionState::Paused {
+            return Err(ServiceError::InvalidInput(
+                "セッションは一時停止中ではありません".to_string(),
+            ));
+        }
+
+        let paused_seconds = Self::total_paused_seconds(&session, now)?;
+        diesel::update(focus_sessions::table.find(session.id))
+            .set((
+                focus_sessions::state.eq(FocusSessionState::Running.as_str()),
+                focus_sessions::paused_at.eq(None::<String>),
+                focus_sessions::paused_seconds.eq(paused_seconds as i32),
+            ))
+            .execute(conn)?;
+
+        Self::get_session(conn, session.id, now)
+    }
+
+    /// 進行中のセッションを終了
+    ///
+    /// # Arguments
+    /// * `conn` - データベース接続
+    /// * `complete_task` - trueの場合、タスクを完了にする（remove_from_queue と同じ処理）
+    /// * `now` - 現在時刻
+    ///
+    /// # Returns
+    /// * `Ok(FocusSessionResponse)` - 終了したセッション（集中時間確定済み）
+    /// * `Err(ServiceError::FocusSessionNotFound)` - 進行中のセッションが無い
+    /// * `Err(ServiceError::QueueEntryNotFound)` - 完了指定時、タスクがキューに無い
+    pub fn finish_session(
+        conn: &mut SqliteConnection,
+        complete_task: bool,
+        now: DateTime<Utc>,
+    ) -> Result<FocusSessionResponse, ServiceError> {
+        let session = Self::find_open_session(conn)?.ok_or(ServiceError::FocusSessionNotFound)?;
+
+        let paused_seconds = Self::total_paused_seconds(&session, now)?;
+        let focused_seconds =
+            Self::active_seconds(&session, now)?.min(i64::from(session.work_minutes) * 60);
+
+        conn.transaction::<_, ServiceError, _>(|conn| {
+            diesel::update(focus_sessions::table.find(session.id))
+                .set((
+                    focus_sessions::state.eq(FocusSessionState::Finished.as_str()),
+                    focus_sessions::paused_at.eq(None::<String>),
+                    focus_sessions::paused_seconds.eq(paused_seconds as i32),
+                    focus_sessions::ended_at.eq(Some(now.to_rfc3339())),
+                    focus_sessions::focused_seconds.eq(focused_seconds as i32),
+                    focus_sessions::completed_task.eq(complete_task),
+                ))
+                .execute(conn)?;
+
+            if complete_task {
+                // セッション中に別のキューへ移動されていてもよいよう、現在のキューから外す
+                let queue_id = task_queue::table
+                    .find(&session.task_id)
+                    .select(task_queue::queue_id)
+                    .first::<String>(conn)
+                    .optional()?
+                    .ok_or_else(|| ServiceError::QueueEntryNotFound(session.task_id.clone()))?;
+                QueueService::remove_from_queue(
+                    conn,
+                    &queue_id,
+                    session.task_id.clone(),
+                    "completed".to_string(),
+                )?;
+            }
+            Ok(())
+        })?;
+
+        Self::get_session(conn, session.id, now)
+    }
+
+    /// 進行中（計測中・一時停止中）のセッションを取得
+    pub fn get_current_session(
+        conn: &mut SqliteConnection,
+        now: DateTime<Utc>,
+    ) -> Result<Option<FocusSessionResponse>, ServiceError> {
+        Self::find_open_session(conn)?
+            .map(|session| Self::to_response(session, now))
+            .transpose()
+    }
+
+    /// 終了していないセッションを取得
+    fn find_open_session(
+        conn: &mut SqliteConnection,
+    ) -> Result<Option<FocusSession>, ServiceError> {
+        let session = focus_sessions::table
+            .filter(focus_sessions::state.ne(FocusSessionState::Finished.as_str()))
+            .order(focus_sessions::id.desc())
+            .first::<FocusSession>(conn)
+            .optional()?;
+        Ok(session)
+    }
+
+    /// IDでセッションを取得してレスポンスに変換
+    fn get_session(
+        conn: &mut SqliteConnection,
+        session_id: i32,
+        now: DateTime<Utc>,
+    ) -> Result<FocusSessionResponse, ServiceError> {
+        let session = focus_sessions::table
+            .find(session_id)
+            .first::<FocusSession>(conn)?;
+        Self::to_response(session, now)
+    }
+
+    /// セッションをレスポンスに変換（フェーズと残り時間を算出）
+    fn to_response(
+        session: FocusSession,
+        now: DateTime<Utc>,
+    ) -> Result<FocusSessionResponse, ServiceError> {
+        let state = Self::parse_state(&session.state)?;
+        let active = Self::active_seconds(&session, now)?;
+        let work_seconds = i64::from(session.work_minutes) * 60;
+        let break_seconds = i64::from(session.break_minutes) * 60;
+
+        let (phase, remaining_seconds) = if active < work_seconds {
+            (FocusPhase::Work, work_seconds - active)
+        } else if active < work_seconds + break_seconds {
+            (FocusPhase::Break, work_seconds + break_seconds - active)
+        } else {
+            (FocusPhase::Over, 0)
+        };
+
+        let focused_seconds = if state == FocusSessionState::Finished {
+            i64::from(session.focused_seconds)
+        } else {
+            active.min(work_seconds)
+        };
+
+        Ok(FocusSessionResponse {
+            id: session.id,
+            task_id: session.task_id,
+            task_title: session.task_title,
+            queue_date: session.queue_date,
+            state,
+            phase,
+            work_minutes: session.work_minutes,
+            break_minutes: session.break_minutes,
+            started_at: session.started_at,
+            ended_at: session.ended_at,
+            focused_seconds,
+            remaining_seconds,
+            completed_task: session.completed_task,
+        })
+    }
+
+    /// 一時停止を除いた経過秒数
+    fn active_seconds(session: &FocusSession, now: DateTime<Utc>) -> Result<i64, ServiceError> {
+        let started_at = parse_timestamp(&session.started_at)?;
+        let end = match session.ended_at {
+            Some(ref ended_at) => parse_timestamp(ended_at)?,
+            None => now,
+        };
+        let elapsed = (end - started_at).num_seconds();
+        Ok((elapsed - Self::total_paused_seconds(session, now)?).max(0))
+    }
+
+    /// 一時停止していた合計秒数（一時停止中の場合は現在までの分を含む）
+    fn total_paused_seconds(
+        session: &FocusSession,
+        now: DateTime<Utc>,
+    ) -> Result<i64, ServiceError> {
+        let current = match session.paused_at {
+            Some(ref paused_at) => (now - parse_timestamp(paused_at)?).num_seconds().max(0),
+            None => 0,
+        };
+        Ok(i64::from(session.paused_seconds) + current)
+    }
+
+    /// 状態文字列を解析
+    fn parse_state(state: &str) -> Result<FocusSessionState, ServiceError> {
+        match state {
+            "running" => Ok(FocusSessionState::Running),
+            "paused" => Ok(FocusSessionState::Paused),
+            "finished" => Ok(FocusSessionState::Finished),
+            other => Err(ServiceError::InternalError(format!(
+                "Invalid focus session state: {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// RFC 3339 形式の時刻を解析
+fn parse_timestamp(value: &str) -> Result<DateTime<Utc>, ServiceError> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|e| ServiceError::InternalError(format!("Invalid timestamp {}: {}", value, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::queue::DEFAULT_QUEUE_ID;
+    use crate::models::queue_history::QueueOutcome;
+    use crate::models::settings::FocusSettings;
+    use crate::models::task::{CreateTaskRequest, TaskStatus};
+    use crate::schema::queue_history;
+    use crate::service::TaskService;
+    use chrono::Duration;
+    use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+
+    pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
+
+    fn setup_test_db() -> SqliteConnection {
+        let mut conn =
+            SqliteConnection::establish(":memory:").expect("Failed to create in-memory database");
+
+        conn.run_pending_migrations(MIGRATIONS)
+            .expect("Failed to run migrations");
+
+        conn
+    }
+
+    fn enqueue(conn: &mut SqliteConnection, title: &str) -> String {
+        let task = TaskService::create_task(
+            conn,
+            CreateTaskRequest {
+                title: title.to_string(),
+                description: None,
+                tags: vec![],
+                parent_id: None,
+                estimated_minutes: None,
+            },
+        )
+        .unwrap();
+        QueueService::add_to_queue(conn, DEFAULT_QUEUE_ID, task.id.clone(), None, false).unwrap();
+        task.id
+    }
+
+    #[test]
+    fn test_start_session_uses_queue_head() {
+        let mut conn = setup_test_db();
+        let now = Utc::now();
+
+        // キューが空なら開始できない
+        let result = FocusService::start_session(&mut conn, DEFAULT_QUEUE_ID, now);
+        assert!(matches!(result, Err(ServiceError::InvalidInput(_))));
+
+        let head = enqueue(&mut conn, "Head");
+        enqueue(&mut conn, "Second");
+
+        let session = FocusService::start_session(&mut conn, DEFAULT_QUEUE_ID, now).unwrap();
+        assert_eq!(session.task_id, head);
+        assert_eq!(session.state, FocusSessionState::Running);
+        assert_eq!(session.phase, FocusPhase::Work);
+        assert_eq!(session.remaining_seconds, 25 * 60);
+
+        // 進行中のセッションがあると開始できない
+        let result = FocusService::start_session(&mut conn, DEFAULT_QUEUE_ID, now);
+        assert!(matches!(
+            result,
+            Err(ServiceError::FocusSessionInProgress(id)) if id == session.id
+        ));
+    }
+
+    #[test]
+    fn test_pause_and_resume_exclude_paused_time() {
+        let mut conn = setup_test_db();
+        SettingsService::update_focus_settings(
+            &mut conn,
+            FocusSettings {
+                work_minutes: 20,
+                break_minutes: 5,
+            },
+        )
+        .unwrap();
+        enqueue(&mut conn, "Task");
+        let start = Utc::now();
+
+        FocusService::start_session(&mut conn, DEFAULT_QUEUE_ID, start).unwrap();
+        let paused = FocusService::pause_session(&mut conn, start + Duration::minutes(10)).unwrap();
+        assert_eq!(paused.state, FocusSessionState::Paused);
+
+        // 一時停止中は経過時間が進まない
+        let current = FocusService::get_current_session(&mut conn, start + Duration::minutes(30))
+            .unwrap()
+            .unwrap();
+        assert_eq!(current.focused_seconds, 10 * 60);
+
+        let result = FocusService::resume_session(&mut conn, start + Duration::minutes(30));
+        assert_eq!(result.unwrap().state, FocusSessionState::Running);
+
+        // 作業20分 + 休憩5分のうち、集中15分目（一時停止20分を除く）
+        let current = FocusService::get_current_session(&mut conn, start + Duration::minutes(35))
+            .unwrap()
+            .unwrap();
+        assert_eq!(current.phase, FocusPhase::Work);
+        assert_eq!(current.remaining_seconds, 5 * 60);
+
+        // 作業時間を過ぎると休憩、休憩も過ぎるとOver
+        let current = FocusService::get_current_session(&mut conn, start + Duration::minutes(42))
+            .unwrap()
+            .unwrap();
+        assert_eq!(current.phase, FocusPhase::Break);
+        let current = FocusService::get_current_session(&mut conn, start + Duration::minutes(50))
+            .unwrap()
+            .unwrap();
+        assert_eq!(current.phase, FocusPhase::Over);
+
+        // 集中時間は作業時間が上限
+        let finished =
+            FocusService::finish_session(&mut conn, false, start + Duration::minutes(50)).unwrap();
+        assert_eq!(finished.state, FocusSessionState::Finished);
+        assert_eq!(finished.focused_seconds, 20 * 60);
+        assert!(FocusService::get_current_session(&mut conn, start)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_finish_session_completes_task() {
+        let mut conn = setup_test_db();
+        let task_id = enqueue(&mut conn, "Task");
+        let start = Utc::now();
+
+        FocusService::start_session(&mut conn, DEFAULT_QUEUE_ID, start).unwrap();
+        let finished =
+            FocusService::finish_session(&mut conn, true, start + Duration::minutes(12)).unwrap();
+        assert!(finished.completed_task);
+        assert_eq!(finished.focused_seconds, 12 * 60);
+
+        // remove_from_queue(completed) と同じ結果になる
+        let task = TaskService::get_task(&mut conn, &task_id).unwrap();
+        assert_eq!(task.status, TaskStatus::Completed);
+        assert!(QueueService::get_queue(&mut conn, DEFAULT_QUEUE_ID, None)
+            .unwrap()
+            .is_empty());
+        let outcomes = queue_history::table
+            .filter(queue_history::task_id.eq(&task_id))
+            .select(queue_history::outcome)
+            .load::<String>(&mut conn)
+            .unwrap();
+        assert_eq!(outcomes, vec![QueueOutcome::Completed.as_str()]);
+
+        // 終了後は操作できない
+        let result = FocusService::pause_session(&mut conn, start);
+        assert!(matches!(result, Err(ServiceError::FocusSessionNotFound)));
+    }
+}
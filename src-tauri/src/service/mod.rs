@@ -1,7 +1,28 @@
+pub mod auto_tag;
+pub mod consistency;
+pub mod focus;
 pub mod queue;
+pub mod quick_switch;
+pub mod rank;
+pub mod rollover;
+pub mod schedule;
+pub mod settings;
+pub mod similarity;
+pub mod stats;
+pub mod suggestion;
 pub mod tag;
 pub mod task;
 
+pub use auto_tag::AutoTagService;
+pub use consistency::ConsistencyService;
+pub use focus::FocusService;
 pub use queue::QueueService;
+pub use quick_switch::{QuickSwitchIndex, QuickSwitchState};
+pub use rollover::RolloverService;
+pub use schedule::ScheduleService;
+pub use settings::SettingsService;
+pub use similarity::SimilarityService;
+pub use stats::StatsService;
+pub use suggestion::SuggestionService;
 pub use tag::TagService;
 pub use task::TaskService;
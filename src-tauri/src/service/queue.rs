@@ -1,52 +1,247 @@
-use chrono::Utc;
+use std::collections::{HashMap, HashSet};
+
+use chrono::{Duration, Local, NaiveDate, NaiveDateTime, Utc};
 use diesel::prelude::*;
+use diesel::sql_types::Text;
 use diesel::SqliteConnection;
 
 use crate::error::ServiceError;
-use crate::models::queue::{NewQueueEntry, QueueEntry, QueueEntryWithTask};
-use crate::models::task::TaskStatus;
-use crate::schema::{task_queue, tasks};
+use crate::models::focus::FocusSessionState;
+use crate::models::queue::{
+    DeferredTask, DeferredTaskWithTitle, EnqueueByFilterResult, EnqueueSkipReason, EnqueueSortKey,
+    NewQueueEntry, Queue, QueueEntry, QueueEntryWithTask, SkippedQueueTask, DEFAULT_QUEUE_ID,
+    QUEUE_DATE_FORMAT,
+};
+use crate::models::queue_history::{NewQueueHistoryEntry, QueueOutcome};
+use crate::models::settings::QueueLimits;
+use crate::models::task::{SearchTasksParams, Task, TaskStatus};
+use crate::schema::{
+    deferred_tasks, focus_sessions, queue_history, queues, tags, task_queue, task_tags, tasks,
+};
+use crate::service::rank::{self, MAX_RANK_LEN};
+use crate::service::settings::SettingsService;
 use crate::service::task::TaskService;
 
 /// QueueService: タスクキュー管理操作を提供
+///
+/// キューは名前付きキュー（「仕事」「家」など）ごと・日付ごとに管理される
+/// （1つのタスクはいずれか1つのキューの1日分にのみ所属できる）。
+/// タスクステータスの自動変更（追加時Active、削除時Draft/Completed等）は
+/// 今日のキューに対する操作でのみ行われ（どの名前付きキューでも同じ）、
+/// 先の日付の計画は状態に影響しない。
+///
+/// 並び順は連番ではなく並び順キー（rank、service::rank を参照）で保持するため、
+/// 追加・削除・移動で書き換えるのは対象エントリの行だけになる。
+/// APIの position はrank順に数えた0始まりの位置。
 pub struct QueueService;
 
 impl QueueService {
-    /// キュー全体を取得（タスク情報含む）
+    /// 今日の日付（キューの既定の対象日）
+    ///
+    /// 設定された1日の開始時刻（day_start_hour）より前は前日として扱う。
+    pub fn today(conn: &mut SqliteConnection) -> Result<NaiveDate, ServiceError> {
+        let settings = SettingsService::get_rollover_settings(conn)?;
+        Ok(Self::queue_day(
+            Local::now().naive_local(),
+            settings.day_start_hour,
+        ))
+    }
+
+    /// 指定時刻が属するキューの日付を計算
+    ///
+    /// # Arguments
+    /// * `now` - 現在時刻（ローカル）
+    /// * `day_start_hour` - 1日の開始時刻（0〜23時）
+    pub fn queue_day(now: NaiveDateTime, day_start_hour: u32) -> NaiveDate {
+        (now - Duration::hours(i64::from(day_start_hour))).date()
+    }
+
+    /// キュー対象日の文字列（YYYY-MM-DD）を解析
+    ///
+    /// # Returns
+    /// * `Ok(NaiveDate)` - 解析された日付
+    /// * `Err(ServiceError::InvalidInput)` - 形式が不正
+    pub fn parse_queue_date(date: &str) -> Result<NaiveDate, ServiceError> {
+        NaiveDate::parse_from_str(date, QUEUE_DATE_FORMAT).map_err(|_| {
+            ServiceError::InvalidInput(format!("日付の形式が不正です（YYYY-MM-DD）: {}", date))
+        })
+    }
+
+    /// 名前付きキューの一覧を取得（既定のキューが先頭、以降は作成順）
+    pub fn list_queues(conn: &mut SqliteConnection) -> Result<Vec<Queue>, ServiceError> {
+        let mut queues = queues::table
+            .order((queues::created_at.asc(), queues::id.asc()))
+            .load::<Queue>(conn)?;
+        queues.sort_by_key(|queue| queue.id != DEFAULT_QUEUE_ID);
+        Ok(queues)
+    }
+
+    /// 名前付きキューを作成
+    ///
+    /// # Returns
+    /// * `Ok(Queue)` - 作成されたキュー
+    /// * `Err(ServiceError::InvalidInput)` - 名前が空
+    /// * `Err(ServiceError::DuplicateQueueName)` - 同じ名前のキューがある
+    pub fn create_queue(conn: &mut SqliteConnection, name: String) -> Result<Queue, ServiceError> {
+        let queue = Queue::new(Self::validate_queue_name(&name)?);
+
+        match diesel::insert_into(queues::table)
+            .values(&queue)
+            .execute(conn)
+        {
+            Ok(_) => Ok(queue),
+            Err(diesel::result::Error::DatabaseError(
+                diesel::result::DatabaseErrorKind::UniqueViolation,
+                _,
+            )) => Err(ServiceError::DuplicateQueueName(queue.name)),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// 名前付きキューの名前を変更
+    ///
+    /// # Returns
+    /// * `Ok(Queue)` - 変更後のキュー
+    /// * `Err(ServiceError::QueueNotFound)` - キューが存在しない
+    /// * `Err(ServiceError::DuplicateQueueName)` - 同じ名前のキューがある
+    pub fn rename_queue(
+        conn: &mut SqliteConnection,
+        queue_id: &str,
+        name: String,
+    ) -> Result<Queue, ServiceError> {
+        Self::ensure_queue_exists(conn, queue_id)?;
+        let name = Self::validate_queue_name(&name)?;
+
+        match diesel::update(queues::table.find(queue_id))
+            .set((
+                queues::name.eq(&name),
+                queues::updated_at.eq(Utc::now().to_rfc3339()),
+            ))
+            .execute(conn)
+        {
+            Ok(_) => Ok(queues::table.find(queue_id).first::<Queue>(conn)?),
+            Err(diesel::result::Error::DatabaseError(
+                diesel::result::DatabaseErrorKind::UniqueViolation,
+                _,
+            )) => Err(ServiceError::DuplicateQueueName(name)),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// 名前付きキューを削除
+    ///
+    /// # Returns
+    /// * `Ok(())` - 削除成功
+    /// * `Err(ServiceError::QueueNotFound)` - キューが存在しない
+    /// * `Err(ServiceError::InvalidInput)` - 既定のキュー、またはタスクが残っているキュー
+    ///
+    /// # Business Logic
+    /// - タスクが残っているキューは削除できない（ステータスの自動変更を経ずに
+    ///   タスクがキューから消えないよう、先に移動・削除すること）
+    /// - このキューへ戻す予定の延期中のタスクは、既定のキューへ戻すように変更する
+    pub fn delete_queue(conn: &mut SqliteConnection, queue_id: &str) -> Result<(), ServiceError> {
+        if queue_id == DEFAULT_QUEUE_ID {
+            return Err(ServiceError::InvalidInput(
+                "既定のキューは削除できません".to_string(),
+            ));
+        }
+        Self::ensure_queue_exists(conn, queue_id)?;
+
+        let entry_count: i64 = task_queue::table
+            .filter(task_queue::queue_id.eq(queue_id))
+            .count()
+            .get_result(conn)?;
+        if entry_count > 0 {
+            return Err(ServiceError::InvalidInput(format!(
+                "キューにタスクが{}件残っているため削除できません",
+                entry_count
+            )));
+        }
+
+        conn.transaction::<_, ServiceError, _>(|conn| {
+            diesel::update(deferred_tasks::table.filter(deferred_tasks::queue_id.eq(queue_id)))
+                .set(deferred_tasks::queue_id.eq(DEFAULT_QUEUE_ID))
+                .execute(conn)?;
+            diesel::delete(queues::table.find(queue_id)).execute(conn)?;
+            Ok(())
+        })
+    }
+
+    /// 指定日のキューを取得（タスク情報含む）
     ///
     /// # Arguments
     /// * `conn` - データベース接続
+    /// * `queue_id` - キューID
+    /// * `queue_date` - 対象日（Noneの場合は今日）
     ///
     /// # Returns
-    /// * `Ok(Vec<QueueEntryWithTask>)` - キューエントリリスト（position順）
+    /// * `Ok(Vec<QueueEntryWithTask>)` - キューエントリリスト（rank順）
+    /// * `Err(ServiceError::QueueNotFound)` - キューが存在しない
     /// * `Err(ServiceError)` - エラー
     pub fn get_queue(
         conn: &mut SqliteConnection,
+        queue_id: &str,
+        queue_date: Option<NaiveDate>,
     ) -> Result<Vec<QueueEntryWithTask>, ServiceError> {
+        Self::ensure_queue_exists(conn, queue_id)?;
+        let queue_date = date_key(Self::resolve_date(conn, queue_date)?);
+
         let results = task_queue::table
             .inner_join(tasks::table.on(task_queue::task_id.eq(tasks::id)))
+            .filter(task_queue::queue_id.eq(queue_id))
+            .filter(task_queue::queue_date.eq(&queue_date))
             .select((
                 task_queue::task_id,
-                task_queue::position,
+                task_queue::rank,
                 task_queue::added_at,
+                task_queue::queue_date,
                 tasks::title,
                 tasks::status,
                 tasks::description,
+                tasks::estimated_minutes,
             ))
-            .order(task_queue::position.asc())
-            .load::<(String, i32, String, String, String, Option<String>)>(conn)?;
+            .order(task_queue::rank.asc())
+            .load::<(
+                String,
+                String,
+                String,
+                String,
+                String,
+                String,
+                Option<String>,
+                Option<i32>,
+            )>(conn)?;
 
         let queue_entries = results
             .into_iter()
+            .enumerate()
             .map(
-                |(task_id, position, added_at, task_title, task_status, task_description)| {
+                |(
+                    position,
+                    (
+                        task_id,
+                        rank,
+                        added_at,
+                        queue_date,
+                        task_title,
+                        task_status,
+                        task_description,
+                        estimated_minutes,
+                    ),
+                )| {
                     QueueEntryWithTask {
                         task_id,
-                        position,
+                        queue_id: queue_id.to_string(),
+                        position: position as i32,
+                        rank,
                         added_at,
+                        queue_date,
                         task_title,
                         task_status,
                         task_description,
+                        estimated_minutes,
+                        scheduled_start: None,
+                        scheduled_end: None,
                     }
                 },
             )
@@ -59,20 +254,35 @@ impl QueueService {
     ///
     /// # Arguments
     /// * `conn` - データベース接続
+    /// * `queue_id` - キューID
     /// * `task_id` - タスクID
+    /// * `queue_date` - 対象日（Noneの場合は今日）
+    /// * `override_limits` - trueの場合、キューの上限（WIP制限）を無視する
     ///
     /// # Returns
     /// * `Ok(QueueEntry)` - 追加されたキューエントリ
+    /// * `Err(ServiceError::QueueLimitExceeded)` - 追加すると対象日のキューが上限を超える
     /// * `Err(ServiceError)` - エラー
     ///
     /// # Business Logic
     /// - タスクが存在すること
-    /// - タスクがキューに既に存在しないこと
-    /// - **キューへ追加時、タスクのステータスを自動的にActiveに変更**
+    /// - タスクがいずれのキュー（いずれの日）にも存在しないこと
+    /// - 過去の日付のキューには追加できない
+    /// - 最大エントリ数・見積もり合計・タグごとの上限を超えないこと（override_limits指定時を除く）
+    /// - 延期中のタスクは延期を解除して追加する
+    /// - **今日のキューへ追加時、タスクのステータスを自動的にActiveに変更**
     pub fn add_to_queue(
         conn: &mut SqliteConnection,
+        queue_id: &str,
         task_id: String,
+        queue_date: Option<NaiveDate>,
+        override_limits: bool,
     ) -> Result<QueueEntry, ServiceError> {
+        Self::ensure_queue_exists(conn, queue_id)?;
+        let today = Self::today(conn)?;
+        let queue_date = queue_date.unwrap_or(today);
+        Self::validate_not_past(queue_date, today)?;
+
         // タスクが存在するか確認
         let task = tasks::table
             .find(&task_id)
@@ -96,30 +306,38 @@ impl QueueService {
             return Err(ServiceError::TaskHasChildren(task_id));
         }
 
+        // キューの上限（WIP制限）チェック
+        if !override_limits {
+            Self::enforce_limits(
+                conn,
+                queue_id,
+                &date_key(queue_date),
+                std::slice::from_ref(&task_id),
+            )?;
+        }
+
         // トランザクション内で処理
         conn.transaction::<QueueEntry, ServiceError, _>(|conn| {
-            // タスクのステータスをActiveに更新
-            diesel::update(tasks::table.find(&task_id))
-                .set(tasks::status.eq(TaskStatus::Active.as_str()))
-                .execute(conn)?;
-
-            // 【新規追加】親ステータス更新
-            TaskService::update_parent_status_if_needed(conn, &task_id)?;
-
-            // 現在の最大positionを取得
-            let max_position: Option<i32> = task_queue::table
-                .select(diesel::dsl::max(task_queue::position))
-                .first(conn)?;
+            if queue_date == today {
+                // タスクのステータスをActiveに更新
+                diesel::update(tasks::table.find(&task_id))
+                    .set(tasks::status.eq(TaskStatus::Active.as_str()))
+                    .execute(conn)?;
 
-            let new_position = max_position.unwrap_or(-1) + 1;
+                // 【新規追加】親ステータス更新
+                TaskService::update_parent_status_if_needed(conn, &task_id)?;
+            }
 
-            // 新規エントリ作成
-            let new_entry = NewQueueEntry::new(task_id.clone(), new_position);
+            // 対象日の末尾に挿入
+            let new_rank =
+                Self::new_ranks(conn, queue_id, &date_key(queue_date), None, 1, None)?.remove(0);
+            let new_entry =
+                NewQueueEntry::new(queue_id.to_string(), task_id.clone(), new_rank, queue_date);
 
-            // キューに挿入
             diesel::insert_into(task_queue::table)
                 .values(&new_entry)
                 .execute(conn)?;
+            Self::clear_deferrals(conn, std::slice::from_ref(&task_id))?;
 
             // 挿入されたエントリを取得
             let entry = task_queue::table.find(&task_id).first::<QueueEntry>(conn)?;
@@ -128,10 +346,266 @@ impl QueueService {
         })
     }
 
+    /// 親タスクの子タスクをまとめてキューの末尾に追加
+    ///
+    /// # Arguments
+    /// * `conn` - データベース接続
+    /// * `queue_id` - キューID
+    /// * `parent_id` - 親タスクID
+    /// * `queue_date` - 対象日（Noneの場合は今日）
+    /// * `override_limits` - trueの場合、キューの上限（WIP制限）を無視する
+    ///
+    /// # Returns
+    /// * `Ok(Vec<QueueEntry>)` - 追加されたキューエントリ（追加順）
+    /// * `Err(ServiceError::InvalidInput)` - 子タスクが無い
+    /// * `Err(ServiceError::QueueLimitExceeded)` - 追加すると対象日のキューが上限を超える
+    /// * `Err(ServiceError)` - エラー
+    ///
+    /// # Business Logic
+    /// - 親タスクはキューに追加できない（BR-015）ため、代わりに子タスクを展開して追加する
+    /// - 追加順は階層表示と同じ兄弟順（作成日時の新しい順）
+    /// - Completed・Archivedの子タスク、既にいずれかのキューにある子タスクはスキップ
+    /// - 追加する子タスク全体で上限（WIP制限）を検証する（override_limits指定時を除く）
+    /// - トランザクション内で実行し、**今日のキューへ追加時は子タスクをActiveにして、
+    ///   最後に親ステータスを1回だけ更新**
+    pub fn enqueue_subtree(
+        conn: &mut SqliteConnection,
+        queue_id: &str,
+        parent_id: String,
+        queue_date: Option<NaiveDate>,
+        override_limits: bool,
+    ) -> Result<Vec<QueueEntry>, ServiceError> {
+        Self::ensure_queue_exists(conn, queue_id)?;
+        let today = Self::today(conn)?;
+        let queue_date = queue_date.unwrap_or(today);
+        Self::validate_not_past(queue_date, today)?;
+        let queue_key = date_key(queue_date);
+
+        tasks::table
+            .find(&parent_id)
+            .select(tasks::id)
+            .first::<String>(conn)
+            .optional()?
+            .ok_or_else(|| ServiceError::TaskNotFound(parent_id.clone()))?;
+
+        if !TaskService::has_children(conn, &parent_id)? {
+            return Err(ServiceError::InvalidInput(format!(
+                "子タスクがありません: {}",
+                parent_id
+            )));
+        }
+
+        let queued: HashSet<String> = task_queue::table
+            .select(task_queue::task_id)
+            .load::<String>(conn)?
+            .into_iter()
+            .collect();
+        let child_ids: Vec<String> = tasks::table
+            .filter(tasks::parent_id.eq(&parent_id))
+            .filter(tasks::status.eq_any([TaskStatus::Draft.as_str(), TaskStatus::Active.as_str()]))
+            .order((tasks::created_at.desc(), tasks::id.desc()))
+            .select(tasks::id)
+            .load::<String>(conn)?
+            .into_iter()
+            .filter(|child_id| !queued.contains(child_id))
+            .collect();
+
+        if child_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // キューの上限（WIP制限）チェック（追加する子タスク全体で判定）
+        if !override_limits {
+            Self::enforce_limits(conn, queue_id, &queue_key, &child_ids)?;
+        }
+
+        conn.transaction::<_, ServiceError, _>(|conn| {
+            let new_ranks =
+                Self::new_ranks(conn, queue_id, &queue_key, None, child_ids.len(), None)?;
+            let new_entries: Vec<NewQueueEntry> = child_ids
+                .iter()
+                .zip(new_ranks)
+                .map(|(child_id, rank)| {
+                    NewQueueEntry::new(queue_id.to_string(), child_id.clone(), rank, queue_date)
+                })
+                .collect();
+            diesel::insert_into(task_queue::table)
+                .values(&new_entries)
+                .execute(conn)?;
+            Self::clear_deferrals(conn, &child_ids)?;
+
+            if queue_date == today {
+                diesel::update(tasks::table.filter(tasks::id.eq_any(&child_ids)))
+                    .set((
+                        tasks::status.eq(TaskStatus::Active.as_str()),
+                        tasks::updated_at.eq(Utc::now().to_rfc3339()),
+                    ))
+                    .execute(conn)?;
+
+                // 親ステータス更新（全ての子タスクの追加後に1回だけ）
+                TaskService::update_parent_status_if_needed(conn, &child_ids[0])?;
+            }
+
+            // rankは追加順に昇順で割り当てている
+            let entries = task_queue::table
+                .filter(task_queue::task_id.eq_any(&child_ids))
+                .order(task_queue::rank.asc())
+                .load::<QueueEntry>(conn)?;
+            Ok(entries)
+        })
+    }
+
+    /// 検索条件に一致するタスクをまとめてキューの末尾に追加
+    ///
+    /// # Arguments
+    /// * `conn` - データベース接続
+    /// * `queue_id` - キューID
+    /// * `filter` - 検索条件（search_tasksと同じ q・status・tags）
+    /// * `sort_by` - 追加順のキー
+    /// * `descending` - trueの場合は追加順を逆にする
+    /// * `queue_date` - 対象日（Noneの場合は今日）
+    /// * `override_limits` - trueの場合、キューの上限（WIP制限）を無視する
+    ///
+    /// # Returns
+    /// * `Ok(EnqueueByFilterResult)` - 追加したエントリとスキップしたタスク（理由付き）
+    /// * `Err(ServiceError::InvalidInput)` - 検索条件が1つも指定されていない
+    /// * `Err(ServiceError::QueueLimitExceeded)` - 追加すると対象日のキューが上限を超える
+    /// * `Err(ServiceError)` - エラー
+    ///
+    /// # Business Logic
+    /// - 既にキューにあるタスク、子タスクを持つ親タスク、Completed・Archivedのタスクはスキップ
+    ///   （enqueue_subtree と同じく、完了済みのタスクをActiveに戻さない）
+    /// - 追加するタスク全体で上限（WIP制限）を検証する（override_limits指定時を除く）
+    /// - 検索からキューへの追加までをトランザクション内で実行し、**今日のキューへ追加時はActiveにして、
+    ///   親ステータスを親タスクごとに1回更新**
+    pub fn enqueue_matching(
+        conn: &mut SqliteConnection,
+        queue_id: &str,
+        filter: &SearchTasksParams,
+        sort_by: EnqueueSortKey,
+        descending: bool,
+        queue_date: Option<NaiveDate>,
+        override_limits: bool,
+    ) -> Result<EnqueueByFilterResult, ServiceError> {
+        let has_condition = filter.q.as_ref().is_some_and(|q| !q.trim().is_empty())
+            || filter.status.is_some()
+            || filter.tags.as_ref().is_some_and(|tags| !tags.is_empty());
+        if !has_condition {
+            return Err(ServiceError::InvalidInput(
+                "検索条件（キーワード・ステータス・タグ）を指定してください".to_string(),
+            ));
+        }
+
+        Self::ensure_queue_exists(conn, queue_id)?;
+        let today = Self::today(conn)?;
+        let queue_date = queue_date.unwrap_or(today);
+        Self::validate_not_past(queue_date, today)?;
+        let queue_key = date_key(queue_date);
+
+        conn.transaction::<_, ServiceError, _>(|conn| {
+            let mut matched = TaskService::find_matching_tasks(conn, filter)?;
+            sort_for_enqueue(&mut matched, sort_by, descending);
+
+            let queued: HashSet<String> = task_queue::table
+                .select(task_queue::task_id)
+                .load::<String>(conn)?
+                .into_iter()
+                .collect();
+            let parents: HashSet<String> = tasks::table
+                .filter(tasks::parent_id.is_not_null())
+                .filter(tasks::status.ne(TaskStatus::Archived.as_str()))
+                .select(tasks::parent_id)
+                .load::<Option<String>>(conn)?
+                .into_iter()
+                .flatten()
+                .collect();
+
+            let mut to_add: Vec<Task> = Vec::new();
+            let mut skipped = Vec::new();
+            for task in matched {
+                let reason = if task.status == TaskStatus::Archived.as_str() {
+                    Some(EnqueueSkipReason::Archived)
+                } else if task.status == TaskStatus::Completed.as_str() {
+                    Some(EnqueueSkipReason::Completed)
+                } else if queued.contains(&task.id) {
+                    Some(EnqueueSkipReason::AlreadyQueued)
+                } else if parents.contains(&task.id) {
+                    Some(EnqueueSkipReason::HasChildren)
+                } else {
+                    None
+                };
+
+                match reason {
+                    Some(reason) => skipped.push(SkippedQueueTask {
+                        task_id: task.id,
+                        title: task.title,
+                        reason,
+                    }),
+                    None => to_add.push(task),
+                }
+            }
+
+            if to_add.is_empty() {
+                return Ok(EnqueueByFilterResult {
+                    added: Vec::new(),
+                    skipped,
+                });
+            }
+
+            let task_ids: Vec<String> = to_add.iter().map(|task| task.id.clone()).collect();
+
+            // キューの上限（WIP制限）チェック（追加するタスク全体で判定）
+            if !override_limits {
+                Self::enforce_limits(conn, queue_id, &queue_key, &task_ids)?;
+            }
+
+            let new_ranks =
+                Self::new_ranks(conn, queue_id, &queue_key, None, task_ids.len(), None)?;
+            let new_entries: Vec<NewQueueEntry> = task_ids
+                .iter()
+                .zip(new_ranks)
+                .map(|(task_id, rank)| {
+                    NewQueueEntry::new(queue_id.to_string(), task_id.clone(), rank, queue_date)
+                })
+                .collect();
+            diesel::insert_into(task_queue::table)
+                .values(&new_entries)
+                .execute(conn)?;
+            Self::clear_deferrals(conn, &task_ids)?;
+
+            if queue_date == today {
+                diesel::update(tasks::table.filter(tasks::id.eq_any(&task_ids)))
+                    .set((
+                        tasks::status.eq(TaskStatus::Active.as_str()),
+                        tasks::updated_at.eq(Utc::now().to_rfc3339()),
+                    ))
+                    .execute(conn)?;
+
+                // 親ステータス更新（親タスクごとに1回だけ）
+                let mut updated_parents = HashSet::new();
+                for task in &to_add {
+                    if let Some(parent_id) = &task.parent_id {
+                        if updated_parents.insert(parent_id) {
+                            TaskService::update_parent_status_if_needed(conn, &task.id)?;
+                        }
+                    }
+                }
+            }
+
+            // rankは追加順に昇順で割り当てている
+            let added = task_queue::table
+                .filter(task_queue::task_id.eq_any(&task_ids))
+                .order(task_queue::rank.asc())
+                .load::<QueueEntry>(conn)?;
+            Ok(EnqueueByFilterResult { added, skipped })
+        })
+    }
+
     /// タスクをキューから削除
     ///
     /// # Arguments
     /// * `conn` - データベース接続
+    /// * `queue_id` - キューID
     /// * `task_id` - タスクID
     ///
     /// # Returns
@@ -139,12 +613,12 @@ impl QueueService {
     /// * `Err(ServiceError)` - エラー
     ///
     /// # Business Logic
-    /// - **キューから削除時、タスクのステータスを自動更新**
-    ///   - 現在のステータスがDraft → Archivedに変更
-    ///   - 現在のステータスがCompleted → Completedのまま（変更なし）
-    ///   - それ以外（Active等） → Draftに変更
+    /// - 同じ日のキューの他のエントリは変更しない（rankは連番ではないため繰り上げ不要）
+    /// - **今日のキューから削除時のみ、タスクのステータスをtarget_statusに更新**し、
+    ///   結果（completed / returned_to_draft）をキュー履歴に記録する
     pub fn remove_from_queue(
         conn: &mut SqliteConnection,
+        queue_id: &str,
         task_id: String,
         target_status: String,
     ) -> Result<(), ServiceError> {
@@ -154,47 +628,151 @@ impl QueueService {
         }
 
         // エントリが存在するか確認
-        let entry = task_queue::table
-            .find(&task_id)
-            .first::<QueueEntry>(conn)
-            .optional()?;
+        let entry = Self::find_entry(conn, queue_id, &task_id)?;
 
-        if entry.is_none() {
-            return Err(ServiceError::QueueEntryNotFound(task_id.clone()));
-        }
-
-        let removed_position = entry.unwrap().position;
+        let is_today = entry.queue_date == date_key(Self::today(conn)?);
 
         // トランザクション内で処理
         conn.transaction::<(), ServiceError, _>(|conn| {
-            // エントリ削除
+            if is_today {
+                let outcome = if target_status == "completed" {
+                    QueueOutcome::Completed
+                } else {
+                    QueueOutcome::ReturnedToDraft
+                };
+                Self::record_history(conn, &entry, outcome)?;
+            }
+
             diesel::delete(task_queue::table.find(&task_id)).execute(conn)?;
 
-            // 削除されたエントリより後ろのpositionを1つずつ繰り上げ
-            diesel::update(task_queue::table.filter(task_queue::position.gt(removed_position)))
-                .set(task_queue::position.eq(task_queue::position - 1))
+            if is_today {
+                // 指定されたステータスに更新（updated_atも同時更新）
+                let now = Utc::now().to_rfc3339();
+                diesel::update(tasks::table.find(&task_id))
+                    .set((tasks::status.eq(&target_status), tasks::updated_at.eq(&now)))
+                    .execute(conn)?;
+
+                // 【新規追加】親ステータス更新
+                TaskService::update_parent_status_if_needed(conn, &task_id)?;
+            }
+
+            Ok(())
+        })
+    }
+
+    /// キューエントリを指定日まで延期（スヌーズ）
+    ///
+    /// # Arguments
+    /// * `conn` - データベース接続
+    /// * `queue_id` - キューID
+    /// * `task_id` - タスクID
+    /// * `until` - 再びキューに戻す日（明日以降）
+    ///
+    /// # Returns
+    /// * `Ok(DeferredTask)` - 延期情報
+    /// * `Err(ServiceError::QueueEntryNotFound)` - キューに存在しない
+    /// * `Err(ServiceError::FocusSessionInProgress)` - このタスクで集中セッションが進行中
+    /// * `Err(ServiceError::InvalidInput)` - 延期先が今日以前
+    ///
+    /// # Business Logic
+    /// - キューから外すが、remove_from_queue と異なり**ステータスは変更しない**
+    ///   （Draft → Archived や Active → Draft にならない）
+    /// - 延期中のタスクはプール（get_hierarchy）に表示されない
+    /// - until の日になるとロールオーバーで同じキューの今日の末尾へ戻される
+    /// - 今日のキューからの延期は、結果（deferred）をキュー履歴に記録する
+    pub fn defer_queue_entry(
+        conn: &mut SqliteConnection,
+        queue_id: &str,
+        task_id: String,
+        until: NaiveDate,
+    ) -> Result<DeferredTask, ServiceError> {
+        let today = Self::today(conn)?;
+        if until <= today {
+            return Err(ServiceError::InvalidInput(
+                "延期先は明日以降の日付を指定してください".to_string(),
+            ));
+        }
+
+        let entry = Self::find_entry(conn, queue_id, &task_id)?;
+
+        // 集中セッション中のタスクは延期できない（終了時にキューから外すため）
+        let open_session: Option<i32> = focus_sessions::table
+            .filter(focus_sessions::task_id.eq(&task_id))
+            .filter(focus_sessions::state.ne(FocusSessionState::Finished.as_str()))
+            .select(focus_sessions::id)
+            .first(conn)
+            .optional()?;
+        if let Some(session_id) = open_session {
+            return Err(ServiceError::FocusSessionInProgress(session_id));
+        }
+
+        let deferred = DeferredTask {
+            task_id: task_id.clone(),
+            queue_id: entry.queue_id.clone(),
+            until_date: date_key(until),
+            deferred_from: entry.queue_date.clone(),
+            deferred_at: Utc::now().to_rfc3339(),
+        };
+
+        conn.transaction::<_, ServiceError, _>(|conn| {
+            if entry.queue_date == date_key(today) {
+                Self::record_history(conn, &entry, QueueOutcome::Deferred)?;
+            }
+
+            diesel::delete(task_queue::table.find(&task_id)).execute(conn)?;
+            diesel::replace_into(deferred_tasks::table)
+                .values(&deferred)
                 .execute(conn)?;
 
-            // 指定されたステータスに更新（updated_atも同時更新）
-            let now = Utc::now().to_rfc3339();
+            // プールの差分更新（get_hierarchy_since）で検出できるよう更新日時のみ更新
             diesel::update(tasks::table.find(&task_id))
-                .set((
-                    tasks::status.eq(&target_status),
-                    tasks::updated_at.eq(&now),
-                ))
+                .set(tasks::updated_at.eq(&deferred.deferred_at))
                 .execute(conn)?;
-
-            // 【新規追加】親ステータス更新
             TaskService::update_parent_status_if_needed(conn, &task_id)?;
 
             Ok(())
-        })
+        })?;
+
+        Ok(deferred)
+    }
+
+    /// 指定キューへ戻す予定の延期中のタスク一覧を取得（戻す日の早い順）
+    pub fn get_deferred_tasks(
+        conn: &mut SqliteConnection,
+        queue_id: &str,
+    ) -> Result<Vec<DeferredTaskWithTitle>, ServiceError> {
+        Self::ensure_queue_exists(conn, queue_id)?;
+        let rows = deferred_tasks::table
+            .inner_join(tasks::table)
+            .filter(deferred_tasks::queue_id.eq(queue_id))
+            .order((
+                deferred_tasks::until_date.asc(),
+                deferred_tasks::deferred_at.asc(),
+            ))
+            .select((DeferredTask::as_select(), tasks::title, tasks::status))
+            .load::<(DeferredTask, String, String)>(conn)?;
+
+        Ok(rows
+            .into_iter()
+            .map(
+                |(deferred, task_title, task_status)| DeferredTaskWithTitle {
+                    task_id: deferred.task_id,
+                    queue_id: deferred.queue_id,
+                    task_title,
+                    task_status,
+                    until_date: deferred.until_date,
+                    deferred_from: deferred.deferred_from,
+                    deferred_at: deferred.deferred_at,
+                },
+            )
+            .collect())
     }
 
-    /// キュー内の全タスクを完了状態にする
+    /// 今日のキュー内の全タスクを完了状態にする
     ///
     /// # Arguments
     /// * `conn` - データベース接続
+    /// * `queue_id` - キューID
     ///
     /// # Returns
     /// * `Ok(usize)` - 完了したタスク数
@@ -205,13 +783,19 @@ impl QueueService {
     ///   - 全タスクのステータスを"completed"に変更
     ///   - 全タスクのupdated_atを現在時刻に更新
     ///   - 親ステータスを更新（子タスクの場合）
-    ///   - キュー全体を削除
+    ///   - 今日のキュー全体を削除（他の日・他のキューは変更しない）
+    ///   - 全タスクをcompletedとしてキュー履歴に記録
     ///   - トランザクション内で実行（all or nothing）
-    pub fn complete_all_queue(conn: &mut SqliteConnection) -> Result<usize, ServiceError> {
-        // キュー内の全タスクIDを取得
-        let task_ids: Vec<String> = task_queue::table
-            .select(task_queue::task_id)
-            .load::<String>(conn)?;
+    pub fn complete_all_queue(
+        conn: &mut SqliteConnection,
+        queue_id: &str,
+    ) -> Result<usize, ServiceError> {
+        Self::ensure_queue_exists(conn, queue_id)?;
+        let today = date_key(Self::today(conn)?);
+
+        // 今日のキュー内の全エントリを取得
+        let entries = Self::get_queue_entries(conn, queue_id, &today)?;
+        let task_ids: Vec<String> = entries.iter().map(|e| e.task_id.clone()).collect();
 
         let completed_count = task_ids.len();
 
@@ -219,6 +803,10 @@ impl QueueService {
         conn.transaction::<(), ServiceError, _>(|conn| {
             let now = Utc::now().to_rfc3339();
 
+            for entry in &entries {
+                Self::record_history(conn, entry, QueueOutcome::Completed)?;
+            }
+
             // 各タスクをcompletedステータスに更新
             for task_id in &task_ids {
                 diesel::update(tasks::table.find(task_id))
@@ -229,8 +817,13 @@ impl QueueService {
                     .execute(conn)?;
             }
 
-            // キュー全体を削除
-            diesel::delete(task_queue::table).execute(conn)?;
+            // 今日のキューを削除
+            diesel::delete(
+                task_queue::table
+                    .filter(task_queue::queue_id.eq(queue_id))
+                    .filter(task_queue::queue_date.eq(&today)),
+            )
+            .execute(conn)?;
 
             // 親ステータスを更新
             for task_id in &task_ids {
@@ -243,61 +836,84 @@ impl QueueService {
         Ok(completed_count)
     }
 
-    /// キュー全体をクリア
+    /// 指定日のキュー全体をクリア
     ///
     /// # Arguments
     /// * `conn` - データベース接続
+    /// * `queue_id` - キューID
+    /// * `queue_date` - 対象日（Noneの場合は今日）
     ///
     /// # Returns
     /// * `Ok(())` - クリア成功
     /// * `Err(ServiceError)` - エラー
     ///
     /// # Business Logic
-    /// - **全タスクのステータスを更新**
-    ///   - Draft → Archived
-    ///   - Completed → Completed（変更なし）
-    ///   - それ以外 → Draft
-    pub fn clear_queue(conn: &mut SqliteConnection) -> Result<(), ServiceError> {
-        // キュー内の全タスクIDを取得
-        let task_ids: Vec<String> = task_queue::table
-            .select(task_queue::task_id)
-            .load::<String>(conn)?;
+    /// - **今日のキューの場合のみ、全タスクのステータスを更新**し、結果をキュー履歴に記録
+    ///   - Draft → Archived（archived）
+    ///   - Completed → Completed（変更なし、completed）
+    ///   - それ以外 → Draft（returned_to_draft）
+    pub fn clear_queue(
+        conn: &mut SqliteConnection,
+        queue_id: &str,
+        queue_date: Option<NaiveDate>,
+    ) -> Result<(), ServiceError> {
+        Self::ensure_queue_exists(conn, queue_id)?;
+        let today = Self::today(conn)?;
+        let queue_date = queue_date.unwrap_or(today);
+        let is_today = queue_date == today;
+        let queue_date = date_key(queue_date);
+
+        // 対象日のキュー内の全エントリを取得
+        let entries = Self::get_queue_entries(conn, queue_id, &queue_date)?;
+        let task_ids: Vec<String> = entries.iter().map(|e| e.task_id.clone()).collect();
 
         // トランザクション内で処理
         conn.transaction::<(), ServiceError, _>(|conn| {
-            // 各タスクのステータスを更新
-            for task_id in &task_ids {
-                let current_task = tasks::table
-                    .find(task_id)
-                    .first::<crate::models::task::Task>(conn)?;
-
-                let new_status = match current_task.status.as_str() {
-                    "draft" => TaskStatus::Archived.as_str(),
-                    "completed" => TaskStatus::Completed.as_str(),
-                    _ => TaskStatus::Draft.as_str(),
-                };
-
-                diesel::update(tasks::table.find(task_id))
-                    .set(tasks::status.eq(new_status))
-                    .execute(conn)?;
+            if is_today {
+                // 各タスクのステータスを更新
+                for entry in &entries {
+                    let task_id = &entry.task_id;
+                    let current_task = tasks::table
+                        .find(task_id)
+                        .first::<crate::models::task::Task>(conn)?;
+
+                    let (new_status, outcome) = match current_task.status.as_str() {
+                        "draft" => (TaskStatus::Archived.as_str(), QueueOutcome::Archived),
+                        "completed" => (TaskStatus::Completed.as_str(), QueueOutcome::Completed),
+                        _ => (TaskStatus::Draft.as_str(), QueueOutcome::ReturnedToDraft),
+                    };
+                    Self::record_history(conn, entry, outcome)?;
+
+                    diesel::update(tasks::table.find(task_id))
+                        .set(tasks::status.eq(new_status))
+                        .execute(conn)?;
+                }
             }
 
-            // キュー全体を削除
-            diesel::delete(task_queue::table).execute(conn)?;
+            // 対象日のキューを削除
+            diesel::delete(
+                task_queue::table
+                    .filter(task_queue::queue_id.eq(queue_id))
+                    .filter(task_queue::queue_date.eq(&queue_date)),
+            )
+            .execute(conn)?;
 
-            // 【新規追加】親ステータスを更新（重複は update_parent_status_if_needed 内で処理される）
-            for task_id in &task_ids {
-                TaskService::update_parent_status_if_needed(conn, task_id)?;
+            if is_today {
+                // 【新規追加】親ステータスを更新（重複は update_parent_status_if_needed 内で処理される）
+                for task_id in &task_ids {
+                    TaskService::update_parent_status_if_needed(conn, task_id)?;
+                }
             }
 
             Ok(())
         })
     }
 
-    /// タスクのキュー内位置を更新
+    /// タスクのキュー内位置を更新（同じ日のキュー内での移動）
     ///
     /// # Arguments
     /// * `conn` - データベース接続
+    /// * `queue_id` - キューID
     /// * `task_id` - タスクID
     /// * `new_position` - 新しい位置（0始まり）
     ///
@@ -306,26 +922,22 @@ impl QueueService {
     /// * `Err(ServiceError)` - エラー
     ///
     /// # Note
-    /// 他のエントリの位置も自動的に調整されます
+    /// 書き換えるのは対象エントリのrankのみ（並び順キーが長くなりすぎた場合は同じ日のキューを再採番）
     /// ステータスは変更されません
     pub fn update_queue_position(
         conn: &mut SqliteConnection,
+        queue_id: &str,
         task_id: String,
         new_position: i32,
     ) -> Result<QueueEntry, ServiceError> {
         // エントリが存在するか確認
-        let entry = task_queue::table
-            .find(&task_id)
-            .first::<QueueEntry>(conn)
-            .optional()?;
-
-        let entry = entry.ok_or_else(|| ServiceError::QueueEntryNotFound(task_id.clone()))?;
+        let entry = Self::find_entry(conn, queue_id, &task_id)?;
 
-        let old_position = entry.position;
+        let entries = Self::get_queue_entries(conn, queue_id, &entry.queue_date)?;
+        let queue_size = entries.len();
 
         // 新しい位置が範囲内か確認
-        let queue_size: i64 = task_queue::table.count().get_result(conn)?;
-        if new_position < 0 || new_position >= queue_size as i32 {
+        if new_position < 0 || new_position as usize >= queue_size {
             return Err(ServiceError::InvalidInput(format!(
                 "Invalid position: {}. Queue size: {}",
                 new_position, queue_size
@@ -333,39 +945,24 @@ impl QueueService {
         }
 
         // 位置が変わらない場合は何もしない
-        if old_position == new_position {
+        if entries[new_position as usize].task_id == task_id {
             return Ok(entry);
         }
 
         // トランザクション内で位置を更新
         conn.transaction::<_, ServiceError, _>(|conn| {
-            if old_position < new_position {
-                // 下に移動: old_position < pos <= new_position の範囲を1つ上にシフト
-                diesel::update(
-                    task_queue::table.filter(
-                        task_queue::position
-                            .gt(old_position)
-                            .and(task_queue::position.le(new_position)),
-                    ),
-                )
-                .set(task_queue::position.eq(task_queue::position - 1))
-                .execute(conn)?;
-            } else {
-                // 上に移動: new_position <= pos < old_position の範囲を1つ下にシフト
-                diesel::update(
-                    task_queue::table.filter(
-                        task_queue::position
-                            .ge(new_position)
-                            .and(task_queue::position.lt(old_position)),
-                    ),
-                )
-                .set(task_queue::position.eq(task_queue::position + 1))
-                .execute(conn)?;
-            }
+            let new_rank = Self::new_ranks(
+                conn,
+                queue_id,
+                &entry.queue_date,
+                Some(new_position as usize),
+                1,
+                Some(&task_id),
+            )?
+            .remove(0);
 
-            // 対象タスクの位置を更新
             diesel::update(task_queue::table.find(&task_id))
-                .set(task_queue::position.eq(new_position))
+                .set(task_queue::rank.eq(new_rank))
                 .execute(conn)?;
 
             Ok(())
@@ -379,31 +976,167 @@ impl QueueService {
         Ok(updated_entry)
     }
 
-    /// キュー全体を一括で並び替え
+    /// キューエントリを別の日・別のキューへ移動
+    ///
+    /// # Arguments
+    /// * `conn` - データベース接続
+    /// * `queue_id` - 移動元のキューID
+    /// * `task_id` - タスクID
+    /// * `target_queue_id` - 移動先のキューID（移動元と同じでもよい）
+    /// * `target_date` - 移動先の日付
+    /// * `position` - 移動先での位置（Noneの場合は末尾）
+    /// * `override_limits` - trueの場合、移動先のキューの上限（WIP制限）を無視する
+    ///
+    /// # Returns
+    /// * `Ok(QueueEntry)` - 移動後のキューエントリ
+    /// * `Err(ServiceError::QueueLimitExceeded)` - 移動すると移動先のキューが上限を超える
+    /// * `Err(ServiceError)` - エラー
+    ///
+    /// # Business Logic
+    /// - 移動元の他のエントリは変更せず、移動先では前後のエントリの間のrankを割り当てる
+    /// - 過去の日付へは移動できない
+    /// - **今日のキューへ移動した場合はActiveに、今日のキューから外れた場合は
+    ///   Active → Draft に戻す**（追加・削除時の自動変更と同じ扱い。
+    ///   同じ日の別のキューへの移動ではステータスは変わらない）
+    pub fn move_queue_entry(
+        conn: &mut SqliteConnection,
+        queue_id: &str,
+        task_id: String,
+        target_queue_id: &str,
+        target_date: NaiveDate,
+        position: Option<i32>,
+        override_limits: bool,
+    ) -> Result<QueueEntry, ServiceError> {
+        let today = Self::today(conn)?;
+        Self::validate_not_past(target_date, today)?;
+
+        let entry = Self::find_entry(conn, queue_id, &task_id)?;
+        Self::ensure_queue_exists(conn, target_queue_id)?;
+
+        let today = date_key(today);
+        let target_key = date_key(target_date);
+
+        // 同じキューの同じ日への移動は位置変更として扱う
+        if entry.queue_id == target_queue_id && entry.queue_date == target_key {
+            return match position {
+                Some(position) => Self::update_queue_position(conn, queue_id, task_id, position),
+                None => {
+                    let last = Self::queue_size(conn, queue_id, &target_key)? as i32 - 1;
+                    Self::update_queue_position(conn, queue_id, task_id, last)
+                }
+            };
+        }
+
+        if !override_limits {
+            Self::enforce_limits(
+                conn,
+                target_queue_id,
+                &target_key,
+                std::slice::from_ref(&task_id),
+            )?;
+        }
+
+        let target_size = Self::queue_size(conn, target_queue_id, &target_key)? as i32;
+        let new_position = position.unwrap_or(target_size);
+        if new_position < 0 || new_position > target_size {
+            return Err(ServiceError::InvalidInput(format!(
+                "Invalid position: {}. Queue size: {}",
+                new_position, target_size
+            )));
+        }
+
+        conn.transaction::<_, ServiceError, _>(|conn| {
+            // 移動先の挿入位置の前後のエントリの間に入れる
+            let new_rank = Self::new_ranks(
+                conn,
+                target_queue_id,
+                &target_key,
+                Some(new_position as usize),
+                1,
+                None,
+            )?
+            .remove(0);
+
+            diesel::delete(task_queue::table.find(&entry.task_id)).execute(conn)?;
+            diesel::insert_into(task_queue::table)
+                .values((
+                    task_queue::task_id.eq(&entry.task_id),
+                    task_queue::queue_id.eq(target_queue_id),
+                    task_queue::rank.eq(new_rank),
+                    task_queue::added_at.eq(&entry.added_at),
+                    task_queue::queue_date.eq(&target_key),
+                ))
+                .execute(conn)?;
+
+            // 今日のキューへの出入りに応じてステータスを更新
+            let new_status = if target_key == today {
+                (entry.queue_date != today).then_some(TaskStatus::Active)
+            } else if entry.queue_date == today {
+                let current_status = tasks::table
+                    .find(&task_id)
+                    .select(tasks::status)
+                    .first::<String>(conn)?;
+                (current_status == TaskStatus::Active.as_str()).then_some(TaskStatus::Draft)
+            } else {
+                None
+            };
+
+            if let Some(new_status) = new_status {
+                diesel::update(tasks::table.find(&task_id))
+                    .set((
+                        tasks::status.eq(new_status.as_str()),
+                        tasks::updated_at.eq(Utc::now().to_rfc3339()),
+                    ))
+                    .execute(conn)?;
+                TaskService::update_parent_status_if_needed(conn, &task_id)?;
+            }
+
+            Ok(())
+        })?;
+
+        let moved_entry = task_queue::table.find(&task_id).first::<QueueEntry>(conn)?;
+
+        Ok(moved_entry)
+    }
+
+    /// 指定日のキュー全体を一括で並び替え
     ///
     /// # Arguments
     /// * `conn` - データベース接続
+    /// * `queue_id` - キューID
     /// * `task_ids` - 新しい順序でのタスクIDリスト
+    /// * `queue_date` - 対象日（Noneの場合は今日）
+    /// * `override_limits` - trueの場合、キューの上限（WIP制限）を無視する
     ///
     /// # Returns
     /// * `Ok(Vec<QueueEntry>)` - 並び替え後のキューエントリリスト
     /// * `Err(ServiceError)` - エラー
     ///
     /// # Validation
-    /// - 全タスクIDがキューに存在すること
-    /// - 現在のキューサイズとタスクIDリストのサイズが一致すること
+    /// - 全タスクIDが対象日のキューに存在すること
+    /// - 対象日のキューサイズとタスクIDリストのサイズが一致すること（重複不可）
+    /// - 対象日のキューが上限（WIP制限）を超えていないこと（上限を下げた後や
+    ///   持ち越しで超過したキューは、エントリを減らすかoverride_limitsを指定するまで並び替えできない）
     ///
     /// # Note
+    /// 現在の並びを最も多く保てるエントリ（rankの最長増加部分列）はそのままにし、
+    /// それ以外のエントリのrankだけを書き換えます（1件の移動なら1行のみ）。
     /// ステータスは変更されません
     pub fn reorder_queue(
         conn: &mut SqliteConnection,
+        queue_id: &str,
         task_ids: Vec<String>,
+        queue_date: Option<NaiveDate>,
+        override_limits: bool,
     ) -> Result<Vec<QueueEntry>, ServiceError> {
-        // 現在のキューサイズを取得
-        let current_queue_size: i64 = task_queue::table.count().get_result(conn)?;
+        Self::ensure_queue_exists(conn, queue_id)?;
+        let queue_date = date_key(Self::resolve_date(conn, queue_date)?);
+
+        // 対象日のキューサイズを取得
+        let current_queue_size = Self::queue_size(conn, queue_id, &queue_date)?;
 
         // タスクIDリストのサイズが一致するか確認
-        if task_ids.len() != current_queue_size as usize {
+        if task_ids.len() != current_queue_size {
             return Err(ServiceError::InvalidInput(format!(
                 "Task ID list size ({}) does not match queue size ({})",
                 task_ids.len(),
@@ -411,39 +1144,420 @@ impl QueueService {
             )));
         }
 
-        // 全タスクIDがキューに存在するか確認
+        // 全タスクIDが対象日のキューに存在するか確認
+        let current_ranks: HashMap<String, String> =
+            Self::get_queue_entries(conn, queue_id, &queue_date)?
+                .into_iter()
+                .map(|entry| (entry.task_id, entry.rank))
+                .collect();
+        let mut seen = HashSet::new();
+        let mut ranks = Vec::with_capacity(task_ids.len());
         for task_id in &task_ids {
-            let exists = task_queue::table
-                .find(task_id)
-                .first::<QueueEntry>(conn)
-                .optional()?;
+            let rank = current_ranks
+                .get(task_id)
+                .ok_or_else(|| ServiceError::QueueEntryNotFound(task_id.clone()))?;
+            if !seen.insert(task_id) {
+                return Err(ServiceError::InvalidInput(format!(
+                    "Duplicate task ID in queue order: {}",
+                    task_id
+                )));
+            }
+            ranks.push(rank.as_str());
+        }
+
+        if !override_limits {
+            Self::enforce_limits(conn, queue_id, &queue_date, &[])?;
+        }
+
+        let keep = longest_increasing(&ranks);
+        let moved: Vec<String> = task_ids
+            .iter()
+            .zip(&keep)
+            .filter(|(_, keep)| !**keep)
+            .map(|(task_id, _)| task_id.clone())
+            .collect();
+
+        if !moved.is_empty() {
+            // トランザクション内で、動かすエントリのみ前後の残すエントリの間のrankに更新
+            conn.transaction::<_, ServiceError, _>(|conn| {
+                Self::park_entries(conn, &moved)?;
+
+                let mut lower: Option<String> = None;
+                let mut index = 0;
+                let mut too_long = false;
+                while index < task_ids.len() {
+                    if keep[index] {
+                        lower = Some(ranks[index].to_string());
+                        index += 1;
+                        continue;
+                    }
+
+                    let run_end = (index..task_ids.len())
+                        .find(|&i| keep[i])
+                        .unwrap_or(task_ids.len());
+                    let upper = ranks.get(run_end).copied();
+                    let new_ranks = rank::keys_between(lower.as_deref(), upper, run_end - index);
+                    for (task_id, new_rank) in task_ids[index..run_end].iter().zip(&new_ranks) {
+                        too_long |= new_rank.len() > MAX_RANK_LEN;
+                        diesel::update(task_queue::table.find(task_id))
+                            .set(task_queue::rank.eq(new_rank))
+                            .execute(conn)?;
+                    }
+
+                    lower = new_ranks.last().cloned();
+                    index = run_end;
+                }
+
+                if too_long {
+                    Self::rebalance_queue(conn, queue_id, &queue_date)?;
+                }
+                Ok(())
+            })?;
+        }
+
+        // 並び替え後のキューを取得
+        Self::get_queue_entries(conn, queue_id, &queue_date)
+    }
+
+    /// キューの上限（WIP制限）を検証
+    ///
+    /// 対象のキューの対象日に `adding` のタスクを加えた状態が
+    /// 最大エントリ数・見積もり合計・タグごとの上限のいずれかを超える場合はエラー。
+    /// 上限は名前付きキューごとに適用する。
+    fn enforce_limits(
+        conn: &mut SqliteConnection,
+        queue_id: &str,
+        queue_date: &str,
+        adding: &[String],
+    ) -> Result<(), ServiceError> {
+        let limits = SettingsService::get_queue_limits(conn)?;
+        if limits.is_unlimited() {
+            return Ok(());
+        }
+
+        let mut task_ids: Vec<String> = task_queue::table
+            .filter(task_queue::queue_id.eq(queue_id))
+            .filter(task_queue::queue_date.eq(queue_date))
+            .select(task_queue::task_id)
+            .load(conn)?;
+        task_ids.extend(adding.iter().cloned());
+
+        let violations = Self::limit_violations(conn, &limits, &task_ids)?;
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(ServiceError::QueueLimitExceeded(violations.join("、")))
+        }
+    }
+
+    /// 指定タスク群を1日のキューとした場合に超える上限の一覧（超えなければ空）
+    pub(crate) fn limit_violations(
+        conn: &mut SqliteConnection,
+        limits: &QueueLimits,
+        task_ids: &[String],
+    ) -> Result<Vec<String>, ServiceError> {
+        let mut violations = Vec::new();
+
+        if let Some(max_entries) = limits.max_entries {
+            if task_ids.len() > max_entries as usize {
+                violations.push(format!("最大{}件", max_entries));
+            }
+        }
+
+        if let Some(max_total_minutes) = limits.max_total_minutes {
+            let total: i64 = tasks::table
+                .filter(tasks::id.eq_any(task_ids))
+                .select(tasks::estimated_minutes)
+                .load::<Option<i32>>(conn)?
+                .into_iter()
+                .flatten()
+                .map(i64::from)
+                .sum();
+            if total > i64::from(max_total_minutes) {
+                violations.push(format!(
+                    "見積もり合計{}分 / 上限{}分",
+                    total, max_total_minutes
+                ));
+            }
+        }
+
+        if !limits.tag_caps.is_empty() {
+            let tag_names: Vec<&String> = limits.tag_caps.keys().collect();
+            let mut counts: HashMap<String, u32> = HashMap::new();
+            for tag_name in task_tags::table
+                .inner_join(tags::table)
+                .filter(task_tags::task_id.eq_any(task_ids))
+                .filter(tags::name.eq_any(tag_names))
+                .select(tags::name)
+                .load::<String>(conn)?
+            {
+                *counts.entry(tag_name).or_insert(0) += 1;
+            }
+            for (tag_name, cap) in &limits.tag_caps {
+                if counts.get(tag_name).copied().unwrap_or(0) > *cap {
+                    violations.push(format!("タグ「{}」は最大{}件", tag_name, cap));
+                }
+            }
+        }
+
+        Ok(violations)
+    }
+
+    /// 指定キュー・指定日のキューエントリのみを取得（内部用ヘルパー）
+    fn get_queue_entries(
+        conn: &mut SqliteConnection,
+        queue_id: &str,
+        queue_date: &str,
+    ) -> Result<Vec<QueueEntry>, ServiceError> {
+        let entries = task_queue::table
+            .filter(task_queue::queue_id.eq(queue_id))
+            .filter(task_queue::queue_date.eq(queue_date))
+            .order(task_queue::rank.asc())
+            .load::<QueueEntry>(conn)?;
+        Ok(entries)
+    }
+
+    /// 対象日を解決（Noneの場合は今日）
+    fn resolve_date(
+        conn: &mut SqliteConnection,
+        queue_date: Option<NaiveDate>,
+    ) -> Result<NaiveDate, ServiceError> {
+        match queue_date {
+            Some(queue_date) => Ok(queue_date),
+            None => Self::today(conn),
+        }
+    }
+
+    /// 指定キュー・指定日のエントリ数を取得
+    pub(crate) fn queue_size(
+        conn: &mut SqliteConnection,
+        queue_id: &str,
+        queue_date: &str,
+    ) -> Result<usize, ServiceError> {
+        let count: i64 = task_queue::table
+            .filter(task_queue::queue_id.eq(queue_id))
+            .filter(task_queue::queue_date.eq(queue_date))
+            .count()
+            .get_result(conn)?;
+        Ok(count as usize)
+    }
 
-            if exists.is_none() {
-                return Err(ServiceError::QueueEntryNotFound(task_id.clone()));
+    /// 指定日のキューの `index` 番目（0始まり、Noneの場合は末尾）に挿入する `count` 個のrankを作成
+    ///
+    /// `moving` に指定したエントリは並びから除いて位置を数える（同じ日のキュー内での移動用）。
+    /// 作成したrankが長くなりすぎる場合は、先に同じ日のキューを再採番してから作り直す。
+    pub(crate) fn new_ranks(
+        conn: &mut SqliteConnection,
+        queue_id: &str,
+        queue_date: &str,
+        index: Option<usize>,
+        count: usize,
+        moving: Option<&str>,
+    ) -> Result<Vec<String>, ServiceError> {
+        let mut rebalanced = false;
+        loop {
+            let ranks: Vec<String> = Self::get_queue_entries(conn, queue_id, queue_date)?
+                .into_iter()
+                .filter(|entry| Some(entry.task_id.as_str()) != moving)
+                .map(|entry| entry.rank)
+                .collect();
+            let index = index.unwrap_or(ranks.len()).min(ranks.len());
+            let lower = index.checked_sub(1).map(|i| ranks[i].as_str());
+            let upper = ranks.get(index).map(String::as_str);
+
+            let new_ranks = rank::keys_between(lower, upper, count);
+            if rebalanced || new_ranks.iter().all(|rank| rank.len() <= MAX_RANK_LEN) {
+                return Ok(new_ranks);
             }
+
+            Self::rebalance_queue(conn, queue_id, queue_date)?;
+            rebalanced = true;
         }
+    }
 
-        // トランザクション内で一括更新
+    /// 指定日のキューのrankを均等な間隔で振り直す（並び順は変えない）
+    ///
+    /// 同じ隙間への挿入が続いてrankが長くなった場合に自動で実行される。
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - 振り直したエントリ数
+    /// * `Err(ServiceError)` - エラー
+    pub fn rebalance_queue(
+        conn: &mut SqliteConnection,
+        queue_id: &str,
+        queue_date: &str,
+    ) -> Result<usize, ServiceError> {
         conn.transaction::<_, ServiceError, _>(|conn| {
-            for (index, task_id) in task_ids.iter().enumerate() {
+            let task_ids: Vec<String> = Self::get_queue_entries(conn, queue_id, queue_date)?
+                .into_iter()
+                .map(|entry| entry.task_id)
+                .collect();
+
+            Self::park_entries(conn, &task_ids)?;
+            let new_ranks = rank::keys_between(None, None, task_ids.len());
+            for (task_id, new_rank) in task_ids.iter().zip(&new_ranks) {
                 diesel::update(task_queue::table.find(task_id))
-                    .set(task_queue::position.eq(index as i32))
+                    .set(task_queue::rank.eq(new_rank))
                     .execute(conn)?;
             }
+
+            Ok(task_ids.len())
+        })
+    }
+
+    /// rankを書き換えるエントリを一時的なrank（"~" + タスクID）に退避する（内部用ヘルパー）
+    ///
+    /// "~" は並び順キーの文字より後ろに並ぶため、書き換え途中に同じキュー・同じ日の
+    /// 一意制約（queue_id, queue_date, rank）に違反しない。
+    fn park_entries(conn: &mut SqliteConnection, task_ids: &[String]) -> Result<(), ServiceError> {
+        diesel::update(task_queue::table.filter(task_queue::task_id.eq_any(task_ids)))
+            .set(task_queue::rank.eq(diesel::dsl::sql::<Text>("'~' || task_id")))
+            .execute(conn)?;
+        Ok(())
+    }
+
+    /// 指定キューのエントリを取得（他のキューにある場合も見つからない扱い、内部用ヘルパー）
+    fn find_entry(
+        conn: &mut SqliteConnection,
+        queue_id: &str,
+        task_id: &str,
+    ) -> Result<QueueEntry, ServiceError> {
+        task_queue::table
+            .find(task_id)
+            .filter(task_queue::queue_id.eq(queue_id))
+            .first::<QueueEntry>(conn)
+            .optional()?
+            .ok_or_else(|| ServiceError::QueueEntryNotFound(task_id.to_string()))
+    }
+
+    /// キューが存在することを確認（内部用ヘルパー）
+    fn ensure_queue_exists(
+        conn: &mut SqliteConnection,
+        queue_id: &str,
+    ) -> Result<(), ServiceError> {
+        let exists = queues::table
+            .find(queue_id)
+            .count()
+            .get_result::<i64>(conn)?
+            > 0;
+        if exists {
             Ok(())
-        })?;
+        } else {
+            Err(ServiceError::QueueNotFound(queue_id.to_string()))
+        }
+    }
 
-        // 並び替え後のキューを取得
-        Self::get_queue_entries(conn)
+    /// 指定タスクの延期を解除（キューに追加した場合等、内部用ヘルパー）
+    fn clear_deferrals(
+        conn: &mut SqliteConnection,
+        task_ids: &[String],
+    ) -> Result<(), ServiceError> {
+        diesel::delete(deferred_tasks::table.filter(deferred_tasks::task_id.eq_any(task_ids)))
+            .execute(conn)?;
+        Ok(())
     }
 
-    /// キューエントリのみを取得（内部用ヘルパー）
-    fn get_queue_entries(conn: &mut SqliteConnection) -> Result<Vec<QueueEntry>, ServiceError> {
-        let entries = task_queue::table
-            .order(task_queue::position.asc())
-            .load::<QueueEntry>(conn)?;
-        Ok(entries)
+    /// キューエントリの結果をキュー履歴に記録（内部用ヘルパー）
+    pub(crate) fn record_history(
+        conn: &mut SqliteConnection,
+        entry: &QueueEntry,
+        outcome: QueueOutcome,
+    ) -> Result<(), ServiceError> {
+        let task_title = tasks::table
+            .find(&entry.task_id)
+            .select(tasks::title)
+            .first::<String>(conn)?;
+        let position: i64 = task_queue::table
+            .filter(task_queue::queue_id.eq(&entry.queue_id))
+            .filter(task_queue::queue_date.eq(&entry.queue_date))
+            .filter(task_queue::rank.lt(&entry.rank))
+            .count()
+            .get_result(conn)?;
+
+        diesel::insert_into(queue_history::table)
+            .values(&NewQueueHistoryEntry::new(
+                entry.queue_date.clone(),
+                entry.task_id.clone(),
+                task_title,
+                position as i32,
+                outcome,
+            ))
+            .execute(conn)?;
+
+        Ok(())
+    }
+
+    /// キュー名を検証（前後の空白を除いた名前を返す）
+    fn validate_queue_name(name: &str) -> Result<String, ServiceError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(ServiceError::InvalidInput("キュー名は必須です".to_string()));
+        }
+        Ok(name.to_string())
+    }
+
+    /// 過去の日付でないことを確認
+    fn validate_not_past(queue_date: NaiveDate, today: NaiveDate) -> Result<(), ServiceError> {
+        if queue_date < today {
+            return Err(ServiceError::InvalidInput(
+                "過去の日付のキューは変更できません".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// NaiveDateをキュー対象日の文字列キーに変換
+pub(crate) fn date_key(date: NaiveDate) -> String {
+    date.format(QUEUE_DATE_FORMAT).to_string()
+}
+
+/// 一括追加の並び順に並べ替え（同じキーの場合は作成日時の古い順）
+fn sort_for_enqueue(tasks: &mut [Task], sort_by: EnqueueSortKey, descending: bool) {
+    tasks.sort_by(|a, b| {
+        let ordering = match sort_by {
+            EnqueueSortKey::Created => a.created_at.cmp(&b.created_at),
+            EnqueueSortKey::Updated => a.updated_at.cmp(&b.updated_at),
+            EnqueueSortKey::Title => a.title.cmp(&b.title),
+            // 見積もり未設定は最後
+            EnqueueSortKey::Estimate => match (a.estimated_minutes, b.estimated_minutes) {
+                (Some(a), Some(b)) => a.cmp(&b),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Equal,
+            },
+        };
+        let ordering = if descending {
+            ordering.reverse()
+        } else {
+            ordering
+        };
+        ordering.then_with(|| a.created_at.cmp(&b.created_at))
+    });
+}
+
+/// 最長増加部分列に含まれる要素を示すフラグ（並び替えで動かさずに済むエントリの判定用）
+fn longest_increasing(ranks: &[&str]) -> Vec<bool> {
+    let n = ranks.len();
+    // lengths[i]: ranks[i] で終わる増加部分列の最長の長さ、previous[i]: その直前の要素
+    let mut lengths = vec![1; n];
+    let mut previous: Vec<Option<usize>> = vec![None; n];
+    for i in 0..n {
+        for j in 0..i {
+            if ranks[j] < ranks[i] && lengths[j] + 1 > lengths[i] {
+                lengths[i] = lengths[j] + 1;
+                previous[i] = Some(j);
+            }
+        }
+    }
+
+    let mut keep = vec![false; n];
+    let mut current = (0..n).max_by_key(|&i| (lengths[i], std::cmp::Reverse(i)));
+    while let Some(i) = current {
+        keep[i] = true;
+        current = previous[i];
     }
+    keep
 }
 
 #[cfg(test)]
@@ -475,16 +1589,18 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         let task = TaskService::create_task(&mut conn, req).unwrap();
 
         // キューに追加
-        let result = QueueService::add_to_queue(&mut conn, task.id.clone());
+        let result =
+            QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, task.id.clone(), None, false);
         assert!(result.is_ok());
 
         let entry = result.unwrap();
         assert_eq!(entry.task_id, task.id);
-        assert_eq!(entry.position, 0);
+        assert_eq!(entry.rank, rank::between(None, None));
 
         // タスクのステータスがActiveになっているか確認
         let updated_task = TaskService::get_task(&mut conn, &task.id).unwrap();
@@ -495,7 +1611,13 @@ mod tests {
     fn test_add_to_queue_task_not_found() {
         let mut conn = setup_test_db();
 
-        let result = QueueService::add_to_queue(&mut conn, "non-existent-id".to_string());
+        let result = QueueService::add_to_queue(
+            &mut conn,
+            DEFAULT_QUEUE_ID,
+            "non-existent-id".to_string(),
+            None,
+            false,
+        );
         assert!(result.is_err());
 
         if let Err(ServiceError::TaskNotFound(id)) = result {
@@ -515,12 +1637,15 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         let task = TaskService::create_task(&mut conn, req).unwrap();
-        QueueService::add_to_queue(&mut conn, task.id.clone()).unwrap();
+        QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, task.id.clone(), None, false)
+            .unwrap();
 
         // 同じタスクを再度追加しようとする
-        let result = QueueService::add_to_queue(&mut conn, task.id.clone());
+        let result =
+            QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, task.id.clone(), None, false);
         assert!(result.is_err());
 
         if let Err(ServiceError::DuplicateQueueEntry(_)) = result {
@@ -541,13 +1666,14 @@ mod tests {
                 description: None,
                 tags: vec![],
                 parent_id: None,
+                estimated_minutes: None,
             };
             let task = TaskService::create_task(&mut conn, req).unwrap();
-            QueueService::add_to_queue(&mut conn, task.id).unwrap();
+            QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, task.id, None, false).unwrap();
         }
 
         // キュー取得
-        let result = QueueService::get_queue(&mut conn);
+        let result = QueueService::get_queue(&mut conn, DEFAULT_QUEUE_ID, None);
         assert!(result.is_ok());
 
         let queue = result.unwrap();
@@ -567,12 +1693,19 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         let task = TaskService::create_task(&mut conn, req).unwrap();
-        QueueService::add_to_queue(&mut conn, task.id.clone()).unwrap();
+        QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, task.id.clone(), None, false)
+            .unwrap();
 
         // キューから削除（draftに戻す）
-        let result = QueueService::remove_from_queue(&mut conn, task.id.clone(), "draft".to_string());
+        let result = QueueService::remove_from_queue(
+            &mut conn,
+            DEFAULT_QUEUE_ID,
+            task.id.clone(),
+            "draft".to_string(),
+        );
         assert!(result.is_ok());
 
         // タスクのステータスがDraftに戻っているか確認
@@ -584,7 +1717,12 @@ mod tests {
     fn test_remove_from_queue_not_found() {
         let mut conn = setup_test_db();
 
-        let result = QueueService::remove_from_queue(&mut conn, "non-existent-id".to_string(), "draft".to_string());
+        let result = QueueService::remove_from_queue(
+            &mut conn,
+            DEFAULT_QUEUE_ID,
+            "non-existent-id".to_string(),
+            "draft".to_string(),
+        );
         assert!(result.is_err());
 
         if let Err(ServiceError::QueueEntryNotFound(_)) = result {
@@ -605,17 +1743,18 @@ mod tests {
                 description: None,
                 tags: vec![],
                 parent_id: None,
+                estimated_minutes: None,
             };
             let task = TaskService::create_task(&mut conn, req).unwrap();
-            QueueService::add_to_queue(&mut conn, task.id).unwrap();
+            QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, task.id, None, false).unwrap();
         }
 
         // キュー全体をクリア
-        let result = QueueService::clear_queue(&mut conn);
+        let result = QueueService::clear_queue(&mut conn, DEFAULT_QUEUE_ID, None);
         assert!(result.is_ok());
 
         // キューが空になっているか確認
-        let queue = QueueService::get_queue(&mut conn).unwrap();
+        let queue = QueueService::get_queue(&mut conn, DEFAULT_QUEUE_ID, None).unwrap();
         assert_eq!(queue.len(), 0);
     }
 
@@ -631,19 +1770,25 @@ mod tests {
                 description: None,
                 tags: vec![],
                 parent_id: None,
+                estimated_minutes: None,
             };
             let task = TaskService::create_task(&mut conn, req).unwrap();
-            QueueService::add_to_queue(&mut conn, task.id.clone()).unwrap();
+            QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, task.id.clone(), None, false)
+                .unwrap();
             task_ids.push(task.id);
         }
 
         // 最初のタスク（position=0）を最後（position=2）に移動
-        let result =
-            QueueService::update_queue_position(&mut conn, task_ids[0].clone(), 2);
+        let result = QueueService::update_queue_position(
+            &mut conn,
+            DEFAULT_QUEUE_ID,
+            task_ids[0].clone(),
+            2,
+        );
         assert!(result.is_ok());
 
         // キューを確認
-        let queue = QueueService::get_queue(&mut conn).unwrap();
+        let queue = QueueService::get_queue(&mut conn, DEFAULT_QUEUE_ID, None).unwrap();
         assert_eq!(queue[0].task_title, "Task 2");
         assert_eq!(queue[1].task_title, "Task 3");
         assert_eq!(queue[2].task_title, "Task 1");
@@ -661,19 +1806,26 @@ mod tests {
                 description: None,
                 tags: vec![],
                 parent_id: None,
+                estimated_minutes: None,
             };
             let task = TaskService::create_task(&mut conn, req).unwrap();
-            QueueService::add_to_queue(&mut conn, task.id.clone()).unwrap();
+            QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, task.id.clone(), None, false)
+                .unwrap();
             task_ids.push(task.id);
         }
 
         // 順序を逆にする
-        let new_order = vec![task_ids[2].clone(), task_ids[1].clone(), task_ids[0].clone()];
-        let result = QueueService::reorder_queue(&mut conn, new_order);
+        let new_order = vec![
+            task_ids[2].clone(),
+            task_ids[1].clone(),
+            task_ids[0].clone(),
+        ];
+        let result =
+            QueueService::reorder_queue(&mut conn, DEFAULT_QUEUE_ID, new_order, None, false);
         assert!(result.is_ok());
 
         // キューを確認
-        let queue = QueueService::get_queue(&mut conn).unwrap();
+        let queue = QueueService::get_queue(&mut conn, DEFAULT_QUEUE_ID, None).unwrap();
         assert_eq!(queue[0].task_title, "Task 3");
         assert_eq!(queue[1].task_title, "Task 2");
         assert_eq!(queue[2].task_title, "Task 1");
@@ -691,6 +1843,7 @@ mod tests {
                 description: None,
                 tags: vec![],
                 parent_id: None,
+                estimated_minutes: None,
             },
         )
         .unwrap();
@@ -703,12 +1856,14 @@ mod tests {
                 description: None,
                 tags: vec![],
                 parent_id: Some(parent.id.clone()),
+                estimated_minutes: None,
             },
         )
         .unwrap();
 
         // 親タスクをキューに追加しようとする（失敗するはず）
-        let result = QueueService::add_to_queue(&mut conn, parent.id.clone());
+        let result =
+            QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, parent.id.clone(), None, false);
         assert!(result.is_err());
 
         // エラーの種類を確認
@@ -732,6 +1887,7 @@ mod tests {
                 description: None,
                 tags: vec![],
                 parent_id: None,
+                estimated_minutes: None,
             },
         )
         .unwrap();
@@ -744,16 +1900,18 @@ mod tests {
                 description: None,
                 tags: vec![],
                 parent_id: Some(parent.id.clone()),
+                estimated_minutes: None,
             },
         )
         .unwrap();
 
         // 子タスクをキューに追加する（成功するはず）
-        let result = QueueService::add_to_queue(&mut conn, child.id.clone());
+        let result =
+            QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, child.id.clone(), None, false);
         assert!(result.is_ok());
 
         // キューを確認
-        let queue = QueueService::get_queue(&mut conn).unwrap();
+        let queue = QueueService::get_queue(&mut conn, DEFAULT_QUEUE_ID, None).unwrap();
         assert_eq!(queue.len(), 1);
         assert_eq!(queue[0].task_title, "子タスク");
     }
@@ -770,12 +1928,14 @@ mod tests {
                 description: None,
                 tags: vec![],
                 parent_id: None,
+                estimated_minutes: None,
             },
         )
         .unwrap();
 
         // キューに追加
-        QueueService::add_to_queue(&mut conn, task.id.clone()).unwrap();
+        QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, task.id.clone(), None, false)
+            .unwrap();
 
         // 元のupdated_atを記録
         let original_updated_at = task.updated_at.clone();
@@ -784,8 +1944,13 @@ mod tests {
         std::thread::sleep(std::time::Duration::from_millis(10));
 
         // キューから削除（completedに変更）
-        QueueService::remove_from_queue(&mut conn, task.id.clone(), "completed".to_string())
-            .unwrap();
+        QueueService::remove_from_queue(
+            &mut conn,
+            DEFAULT_QUEUE_ID,
+            task.id.clone(),
+            "completed".to_string(),
+        )
+        .unwrap();
 
         // タスクを再取得してupdated_atを確認
         let updated_task = TaskService::get_task(&mut conn, &task.id).unwrap();
@@ -812,6 +1977,7 @@ mod tests {
                 description: None,
                 tags: vec![],
                 parent_id: None,
+                estimated_minutes: None,
             },
         )
         .unwrap();
@@ -824,12 +1990,14 @@ mod tests {
                 description: None,
                 tags: vec![],
                 parent_id: Some(parent.id.clone()),
+                estimated_minutes: None,
             },
         )
         .unwrap();
 
         // 子タスクが存在するので親タスクをキューに追加できない
-        let result = QueueService::add_to_queue(&mut conn, parent.id.clone());
+        let result =
+            QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, parent.id.clone(), None, false);
         assert!(
             result.is_err(),
             "子タスクが存在する場合、親タスクはキューに追加できないべき"
@@ -843,7 +2011,8 @@ mod tests {
         TaskService::delete_task(&mut conn, &child.id).unwrap();
 
         // 子タスクが全てアーカイブされたので親タスクをキューに追加できる
-        let result = QueueService::add_to_queue(&mut conn, parent.id.clone());
+        let result =
+            QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, parent.id.clone(), None, false);
         assert!(
             result.is_ok(),
             "全ての子タスクがアーカイブされた場合、親タスクはキューに追加できるべき"
@@ -866,6 +2035,7 @@ mod tests {
                 description: None,
                 tags: vec![],
                 parent_id: None,
+                estimated_minutes: None,
             },
         )
         .unwrap();
@@ -878,6 +2048,7 @@ mod tests {
                 description: None,
                 tags: vec![],
                 parent_id: Some(parent.id.clone()),
+                estimated_minutes: None,
             },
         )
         .unwrap();
@@ -890,6 +2061,7 @@ mod tests {
                 description: None,
                 tags: vec![],
                 parent_id: Some(parent.id.clone()),
+                estimated_minutes: None,
             },
         )
         .unwrap();
@@ -898,7 +2070,8 @@ mod tests {
         TaskService::delete_task(&mut conn, &child1.id).unwrap();
 
         // まだ1つのDraft子タスクが残っているので親タスクをキューに追加できない
-        let result = QueueService::add_to_queue(&mut conn, parent.id.clone());
+        let result =
+            QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, parent.id.clone(), None, false);
         assert!(
             result.is_err(),
             "1つでもアクティブな子タスクが存在する場合、親タスクはキューに追加できないべき"
@@ -912,10 +2085,884 @@ mod tests {
         TaskService::delete_task(&mut conn, &child2.id).unwrap();
 
         // 全ての子タスクがアーカイブされたので親タスクをキューに追加できる
-        let result = QueueService::add_to_queue(&mut conn, parent.id.clone());
+        let result =
+            QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, parent.id.clone(), None, false);
         assert!(
             result.is_ok(),
             "全ての子タスクがアーカイブされた場合、親タスクはキューに追加できるべき"
         );
     }
+
+    fn create_draft_task(conn: &mut SqliteConnection, title: &str) -> String {
+        TaskService::create_task(
+            conn,
+            CreateTaskRequest {
+                title: title.to_string(),
+                description: None,
+                tags: vec![],
+                parent_id: None,
+                estimated_minutes: None,
+            },
+        )
+        .unwrap()
+        .id
+    }
+
+    fn tomorrow(conn: &mut SqliteConnection) -> NaiveDate {
+        QueueService::today(conn).unwrap().succ_opt().unwrap()
+    }
+
+    #[test]
+    fn test_add_to_future_queue_keeps_status() {
+        let mut conn = setup_test_db();
+        let tomorrow = tomorrow(&mut conn);
+        let task_id = create_draft_task(&mut conn, "明日のタスク");
+
+        let entry = QueueService::add_to_queue(
+            &mut conn,
+            DEFAULT_QUEUE_ID,
+            task_id.clone(),
+            Some(tomorrow),
+            false,
+        )
+        .unwrap();
+        assert_eq!(entry.rank, rank::between(None, None));
+        assert_eq!(entry.queue_date, tomorrow.format("%Y-%m-%d").to_string());
+
+        // 今日のキューには含まれず、ステータスもDraftのまま
+        assert!(QueueService::get_queue(&mut conn, DEFAULT_QUEUE_ID, None)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            QueueService::get_queue(&mut conn, DEFAULT_QUEUE_ID, Some(tomorrow))
+                .unwrap()
+                .len(),
+            1
+        );
+        let task = TaskService::get_task(&mut conn, &task_id).unwrap();
+        assert_eq!(task.status, TaskStatus::Draft);
+
+        // 先の日付のキューから削除してもステータスは変わらない
+        QueueService::remove_from_queue(
+            &mut conn,
+            DEFAULT_QUEUE_ID,
+            task_id.clone(),
+            "completed".to_string(),
+        )
+        .unwrap();
+        let task = TaskService::get_task(&mut conn, &task_id).unwrap();
+        assert_eq!(task.status, TaskStatus::Draft);
+    }
+
+    #[test]
+    fn test_add_to_past_queue_rejected() {
+        let mut conn = setup_test_db();
+        let task_id = create_draft_task(&mut conn, "Task");
+        let yesterday = QueueService::today(&mut conn).unwrap().pred_opt().unwrap();
+
+        let result = QueueService::add_to_queue(
+            &mut conn,
+            DEFAULT_QUEUE_ID,
+            task_id,
+            Some(yesterday),
+            false,
+        );
+        assert!(matches!(result, Err(ServiceError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_move_queue_entry_between_days_keeps_positions_dense() {
+        let mut conn = setup_test_db();
+        let tomorrow = tomorrow(&mut conn);
+        let today_ids: Vec<String> = (1..=3)
+            .map(|i| {
+                let id = create_draft_task(&mut conn, &format!("Today {}", i));
+                QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, id.clone(), None, false)
+                    .unwrap();
+                id
+            })
+            .collect();
+        let future_id = create_draft_task(&mut conn, "Tomorrow 1");
+        QueueService::add_to_queue(
+            &mut conn,
+            DEFAULT_QUEUE_ID,
+            future_id.clone(),
+            Some(tomorrow),
+            false,
+        )
+        .unwrap();
+
+        // 今日の2番目を明日の先頭へ移動
+        let moved = QueueService::move_queue_entry(
+            &mut conn,
+            DEFAULT_QUEUE_ID,
+            today_ids[1].clone(),
+            DEFAULT_QUEUE_ID,
+            tomorrow,
+            Some(0),
+            false,
+        )
+        .unwrap();
+        assert_eq!(moved.queue_date, date_key(tomorrow));
+
+        let today_queue = QueueService::get_queue(&mut conn, DEFAULT_QUEUE_ID, None).unwrap();
+        let positions: Vec<(String, i32)> = today_queue
+            .iter()
+            .map(|e| (e.task_title.clone(), e.position))
+            .collect();
+        assert_eq!(
+            positions,
+            vec![("Today 1".to_string(), 0), ("Today 3".to_string(), 1)]
+        );
+
+        let tomorrow_queue =
+            QueueService::get_queue(&mut conn, DEFAULT_QUEUE_ID, Some(tomorrow)).unwrap();
+        let positions: Vec<(String, i32)> = tomorrow_queue
+            .iter()
+            .map(|e| (e.task_title.clone(), e.position))
+            .collect();
+        assert_eq!(
+            positions,
+            vec![("Today 2".to_string(), 0), ("Tomorrow 1".to_string(), 1)]
+        );
+
+        // 今日のキューから外れたタスクはDraftに戻り、今日に入ったタスクはActiveになる
+        let task = TaskService::get_task(&mut conn, &today_ids[1]).unwrap();
+        assert_eq!(task.status, TaskStatus::Draft);
+
+        let today = QueueService::today(&mut conn).unwrap();
+        QueueService::move_queue_entry(
+            &mut conn,
+            DEFAULT_QUEUE_ID,
+            future_id.clone(),
+            DEFAULT_QUEUE_ID,
+            today,
+            None,
+            false,
+        )
+        .unwrap();
+        let task = TaskService::get_task(&mut conn, &future_id).unwrap();
+        assert_eq!(task.status, TaskStatus::Active);
+        assert_eq!(
+            QueueService::get_queue(&mut conn, DEFAULT_QUEUE_ID, None).unwrap()[2].task_id,
+            future_id
+        );
+    }
+
+    #[test]
+    fn test_clear_future_queue_only_affects_that_day() {
+        let mut conn = setup_test_db();
+        let tomorrow = tomorrow(&mut conn);
+        let today_id = create_draft_task(&mut conn, "Today");
+        QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, today_id.clone(), None, false)
+            .unwrap();
+        let future_id = create_draft_task(&mut conn, "Tomorrow");
+        QueueService::add_to_queue(
+            &mut conn,
+            DEFAULT_QUEUE_ID,
+            future_id.clone(),
+            Some(tomorrow),
+            false,
+        )
+        .unwrap();
+
+        QueueService::clear_queue(&mut conn, DEFAULT_QUEUE_ID, Some(tomorrow)).unwrap();
+
+        assert!(
+            QueueService::get_queue(&mut conn, DEFAULT_QUEUE_ID, Some(tomorrow))
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            QueueService::get_queue(&mut conn, DEFAULT_QUEUE_ID, None)
+                .unwrap()
+                .len(),
+            1
+        );
+        // 先の日付のクリアではDraft → Archived の変更は行われない
+        let task = TaskService::get_task(&mut conn, &future_id).unwrap();
+        assert_eq!(task.status, TaskStatus::Draft);
+    }
+
+    #[test]
+    fn test_parse_queue_date() {
+        assert_eq!(
+            QueueService::parse_queue_date("2026-01-05").unwrap(),
+            NaiveDate::from_ymd_opt(2026, 1, 5).unwrap()
+        );
+        assert!(matches!(
+            QueueService::parse_queue_date("2026/01/05"),
+            Err(ServiceError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_queue_limits_entries_and_estimate() {
+        let mut conn = setup_test_db();
+        SettingsService::update_queue_limits(
+            &mut conn,
+            QueueLimits {
+                max_entries: Some(2),
+                max_total_minutes: Some(60),
+                tag_caps: Default::default(),
+            },
+        )
+        .unwrap();
+
+        let long = TaskService::create_task(
+            &mut conn,
+            CreateTaskRequest {
+                title: "Long".to_string(),
+                description: None,
+                tags: vec![],
+                parent_id: None,
+                estimated_minutes: Some(45),
+            },
+        )
+        .unwrap()
+        .id;
+        QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, long, None, false).unwrap();
+
+        // 見積もり合計が上限を超える
+        let medium = TaskService::create_task(
+            &mut conn,
+            CreateTaskRequest {
+                title: "Medium".to_string(),
+                description: None,
+                tags: vec![],
+                parent_id: None,
+                estimated_minutes: Some(30),
+            },
+        )
+        .unwrap()
+        .id;
+        let result =
+            QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, medium.clone(), None, false);
+        assert!(matches!(result, Err(ServiceError::QueueLimitExceeded(_))));
+        // 上限超過時はステータスも変わらない
+        let task = TaskService::get_task(&mut conn, &medium).unwrap();
+        assert_eq!(task.status, TaskStatus::Draft);
+
+        // 見積もり無しのタスクは件数のみで判定
+        let short = create_draft_task(&mut conn, "Short");
+        QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, short, None, false).unwrap();
+        let extra = create_draft_task(&mut conn, "Extra");
+        let result =
+            QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, extra.clone(), None, false);
+        assert!(matches!(result, Err(ServiceError::QueueLimitExceeded(_))));
+
+        // override指定時は上限を無視する
+        QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, extra, None, true).unwrap();
+        assert_eq!(
+            QueueService::get_queue(&mut conn, DEFAULT_QUEUE_ID, None)
+                .unwrap()
+                .len(),
+            3
+        );
+
+        // 上限を超えたキューは override 無しでは並び替えできない
+        let mut order: Vec<String> = QueueService::get_queue(&mut conn, DEFAULT_QUEUE_ID, None)
+            .unwrap()
+            .into_iter()
+            .map(|e| e.task_id)
+            .collect();
+        order.reverse();
+        let result =
+            QueueService::reorder_queue(&mut conn, DEFAULT_QUEUE_ID, order.clone(), None, false);
+        assert!(matches!(result, Err(ServiceError::QueueLimitExceeded(_))));
+        QueueService::reorder_queue(&mut conn, DEFAULT_QUEUE_ID, order, None, true).unwrap();
+    }
+
+    #[test]
+    fn test_queue_limits_tag_cap_and_move() {
+        let mut conn = setup_test_db();
+        crate::service::TagService::create_tag(
+            &mut conn,
+            crate::models::tag::CreateTagRequest {
+                name: "meeting".to_string(),
+                color: None,
+            },
+        )
+        .unwrap();
+        SettingsService::update_queue_limits(
+            &mut conn,
+            QueueLimits {
+                max_entries: None,
+                max_total_minutes: None,
+                tag_caps: [("meeting".to_string(), 1)].into_iter().collect(),
+            },
+        )
+        .unwrap();
+
+        let create_meeting = |conn: &mut SqliteConnection, title: &str| {
+            TaskService::create_task(
+                conn,
+                CreateTaskRequest {
+                    title: title.to_string(),
+                    description: None,
+                    tags: vec!["meeting".to_string()],
+                    parent_id: None,
+                    estimated_minutes: None,
+                },
+            )
+            .unwrap()
+            .id
+        };
+        let first = create_meeting(&mut conn, "Meeting 1");
+        let second = create_meeting(&mut conn, "Meeting 2");
+        QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, first, None, false).unwrap();
+
+        // タグの付いていないタスクは制限されない
+        let plain = create_draft_task(&mut conn, "Plain");
+        QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, plain, None, false).unwrap();
+
+        let result =
+            QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, second.clone(), None, false);
+        match result {
+            Err(ServiceError::QueueLimitExceeded(detail)) => assert!(detail.contains("meeting")),
+            other => panic!("QueueLimitExceeded expected: {:?}", other),
+        }
+
+        // 明日のキューには入れられるが、今日へ移動すると上限を超える
+        let tomorrow = tomorrow(&mut conn);
+        QueueService::add_to_queue(
+            &mut conn,
+            DEFAULT_QUEUE_ID,
+            second.clone(),
+            Some(tomorrow),
+            false,
+        )
+        .unwrap();
+        let today = QueueService::today(&mut conn).unwrap();
+        let result = QueueService::move_queue_entry(
+            &mut conn,
+            DEFAULT_QUEUE_ID,
+            second.clone(),
+            DEFAULT_QUEUE_ID,
+            today,
+            None,
+            false,
+        );
+        assert!(matches!(result, Err(ServiceError::QueueLimitExceeded(_))));
+        QueueService::move_queue_entry(
+            &mut conn,
+            DEFAULT_QUEUE_ID,
+            second,
+            DEFAULT_QUEUE_ID,
+            today,
+            None,
+            true,
+        )
+        .unwrap();
+    }
+
+    fn ranks(conn: &mut SqliteConnection) -> HashMap<String, String> {
+        QueueService::get_queue(conn, DEFAULT_QUEUE_ID, None)
+            .unwrap()
+            .into_iter()
+            .map(|e| (e.task_id, e.rank))
+            .collect()
+    }
+
+    #[test]
+    fn test_moving_one_entry_updates_only_its_rank() {
+        let mut conn = setup_test_db();
+        let ids: Vec<String> = (0..5)
+            .map(|i| {
+                let id = create_draft_task(&mut conn, &format!("Task {}", i));
+                QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, id.clone(), None, false)
+                    .unwrap();
+                id
+            })
+            .collect();
+        let before = ranks(&mut conn);
+
+        // 位置の更新・削除・一括並び替え（1件だけ移動）で他のエントリのrankは変わらない
+        QueueService::update_queue_position(&mut conn, DEFAULT_QUEUE_ID, ids[4].clone(), 1)
+            .unwrap();
+        QueueService::remove_from_queue(
+            &mut conn,
+            DEFAULT_QUEUE_ID,
+            ids[2].clone(),
+            "draft".to_string(),
+        )
+        .unwrap();
+        let order = vec![
+            ids[1].clone(),
+            ids[0].clone(),
+            ids[4].clone(),
+            ids[3].clone(),
+        ];
+        QueueService::reorder_queue(&mut conn, DEFAULT_QUEUE_ID, order.clone(), None, false)
+            .unwrap();
+
+        let after = ranks(&mut conn);
+        let changed: Vec<&String> = after
+            .iter()
+            .filter(|(id, rank)| before.get(*id) != Some(rank))
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(changed.len(), 2);
+        assert!(changed.contains(&&ids[4]) && changed.contains(&&ids[1]));
+
+        let queue = QueueService::get_queue(&mut conn, DEFAULT_QUEUE_ID, None).unwrap();
+        let queued: Vec<String> = queue.iter().map(|e| e.task_id.clone()).collect();
+        assert_eq!(queued, order);
+        let positions: Vec<i32> = queue.iter().map(|e| e.position).collect();
+        assert_eq!(positions, vec![0, 1, 2, 3]);
+
+        // 重複したタスクIDは拒否
+        let duplicated = vec![
+            ids[1].clone(),
+            ids[1].clone(),
+            ids[4].clone(),
+            ids[3].clone(),
+        ];
+        let result =
+            QueueService::reorder_queue(&mut conn, DEFAULT_QUEUE_ID, duplicated, None, false);
+        assert!(matches!(result, Err(ServiceError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_rebalance_when_ranks_get_long() {
+        let mut conn = setup_test_db();
+        let first = create_draft_task(&mut conn, "First");
+        let second = create_draft_task(&mut conn, "Second");
+        QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, first.clone(), None, false)
+            .unwrap();
+        QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, second.clone(), None, false)
+            .unwrap();
+
+        // 同じ隙間（先頭と2番目の間）への移動を繰り返す
+        let mut titles = vec!["First".to_string(), "Second".to_string()];
+        for i in 0..150 {
+            let id = create_draft_task(&mut conn, &format!("Inserted {}", i));
+            QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, id.clone(), None, false)
+                .unwrap();
+            QueueService::update_queue_position(&mut conn, DEFAULT_QUEUE_ID, id, 1).unwrap();
+            titles.insert(1, format!("Inserted {}", i));
+        }
+
+        let queue = QueueService::get_queue(&mut conn, DEFAULT_QUEUE_ID, None).unwrap();
+        let queued: Vec<String> = queue.iter().map(|e| e.task_title.clone()).collect();
+        assert_eq!(queued, titles);
+        assert!(queue.iter().all(|e| e.rank.len() <= MAX_RANK_LEN));
+
+        // 再採番しても並び順は変わらない
+        let today = date_key(QueueService::today(&mut conn).unwrap());
+        assert_eq!(
+            QueueService::rebalance_queue(&mut conn, DEFAULT_QUEUE_ID, &today).unwrap(),
+            152
+        );
+        let queue = QueueService::get_queue(&mut conn, DEFAULT_QUEUE_ID, None).unwrap();
+        let queued: Vec<String> = queue.iter().map(|e| e.task_title.clone()).collect();
+        assert_eq!(queued, titles);
+        assert!(queue.iter().all(|e| e.rank.len() <= 3));
+    }
+
+    #[test]
+    fn test_enqueue_subtree() {
+        let mut conn = setup_test_db();
+        let parent = create_draft_task(&mut conn, "Project");
+        let create_child = |conn: &mut SqliteConnection, title: &str| {
+            TaskService::create_task(
+                conn,
+                CreateTaskRequest {
+                    title: title.to_string(),
+                    description: None,
+                    tags: vec![],
+                    parent_id: Some(parent.clone()),
+                    estimated_minutes: None,
+                },
+            )
+            .unwrap()
+            .id
+        };
+        let first = create_child(&mut conn, "Step 1");
+        let done = create_child(&mut conn, "Step 2");
+        let planned = create_child(&mut conn, "Step 3");
+        let last = create_child(&mut conn, "Step 4");
+        diesel::update(tasks::table.find(&done))
+            .set(tasks::status.eq(TaskStatus::Completed.as_str()))
+            .execute(&mut conn)
+            .unwrap();
+        let tomorrow = tomorrow(&mut conn);
+        QueueService::add_to_queue(
+            &mut conn,
+            DEFAULT_QUEUE_ID,
+            planned.clone(),
+            Some(tomorrow),
+            false,
+        )
+        .unwrap();
+        let existing = create_draft_task(&mut conn, "Existing");
+        QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, existing.clone(), None, false)
+            .unwrap();
+
+        let entries =
+            QueueService::enqueue_subtree(&mut conn, DEFAULT_QUEUE_ID, parent.clone(), None, false)
+                .unwrap();
+
+        // Completed・他の日のキューにある子タスクはスキップし、兄弟順で末尾に追加
+        let added: Vec<&str> = entries.iter().map(|e| e.task_id.as_str()).collect();
+        assert_eq!(added, vec![last.as_str(), first.as_str()]);
+        let queued: Vec<String> = QueueService::get_queue(&mut conn, DEFAULT_QUEUE_ID, None)
+            .unwrap()
+            .into_iter()
+            .map(|e| e.task_id)
+            .collect();
+        assert_eq!(queued, vec![existing, last.clone(), first.clone()]);
+
+        for task_id in [&first, &last, &parent] {
+            let task = TaskService::get_task(&mut conn, task_id).unwrap();
+            assert_eq!(task.status, TaskStatus::Active);
+        }
+
+        // 追加できる子タスクが残っていなければ何もしない
+        let entries =
+            QueueService::enqueue_subtree(&mut conn, DEFAULT_QUEUE_ID, parent.clone(), None, false)
+                .unwrap();
+        assert!(entries.is_empty());
+
+        // 子タスクの無いタスクは展開できない
+        let result = QueueService::enqueue_subtree(&mut conn, DEFAULT_QUEUE_ID, first, None, false);
+        assert!(matches!(result, Err(ServiceError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_enqueue_subtree_respects_limits() {
+        let mut conn = setup_test_db();
+        SettingsService::update_queue_limits(
+            &mut conn,
+            QueueLimits {
+                max_entries: Some(2),
+                max_total_minutes: None,
+                tag_caps: Default::default(),
+            },
+        )
+        .unwrap();
+        let parent = create_draft_task(&mut conn, "Project");
+        for title in ["A", "B", "C"] {
+            TaskService::create_task(
+                &mut conn,
+                CreateTaskRequest {
+                    title: title.to_string(),
+                    description: None,
+                    tags: vec![],
+                    parent_id: Some(parent.clone()),
+                    estimated_minutes: None,
+                },
+            )
+            .unwrap();
+        }
+
+        // 上限を超える場合は1件も追加しない
+        let result =
+            QueueService::enqueue_subtree(&mut conn, DEFAULT_QUEUE_ID, parent.clone(), None, false);
+        assert!(matches!(result, Err(ServiceError::QueueLimitExceeded(_))));
+        assert!(QueueService::get_queue(&mut conn, DEFAULT_QUEUE_ID, None)
+            .unwrap()
+            .is_empty());
+        let parent_task = TaskService::get_task(&mut conn, &parent).unwrap();
+        assert_eq!(parent_task.status, TaskStatus::Draft);
+
+        let entries =
+            QueueService::enqueue_subtree(&mut conn, DEFAULT_QUEUE_ID, parent, None, true).unwrap();
+        assert_eq!(entries.len(), 3);
+    }
+
+    #[test]
+    fn test_enqueue_matching_by_tag() {
+        let mut conn = setup_test_db();
+        crate::service::TagService::create_tag(
+            &mut conn,
+            crate::models::tag::CreateTagRequest {
+                name: "errand".to_string(),
+                color: None,
+            },
+        )
+        .unwrap();
+        let create = |conn: &mut SqliteConnection, title: &str, estimate: Option<i32>| {
+            TaskService::create_task(
+                conn,
+                CreateTaskRequest {
+                    title: title.to_string(),
+                    description: None,
+                    tags: vec!["errand".to_string()],
+                    parent_id: None,
+                    estimated_minutes: estimate,
+                },
+            )
+            .unwrap()
+            .id
+        };
+        let long = create(&mut conn, "Long", Some(60));
+        let short = create(&mut conn, "Short", Some(10));
+        let unestimated = create(&mut conn, "Unestimated", None);
+        let queued = create(&mut conn, "Queued", Some(5));
+        QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, queued.clone(), None, false)
+            .unwrap();
+        let parent = create(&mut conn, "Parent", None);
+        TaskService::create_task(
+            &mut conn,
+            CreateTaskRequest {
+                title: "Child".to_string(),
+                description: None,
+                tags: vec![],
+                parent_id: Some(parent.clone()),
+                estimated_minutes: None,
+            },
+        )
+        .unwrap();
+        create_draft_task(&mut conn, "Untagged");
+
+        let filter = SearchTasksParams {
+            tags: Some(vec!["errand".to_string()]),
+            ..Default::default()
+        };
+        let result = QueueService::enqueue_matching(
+            &mut conn,
+            DEFAULT_QUEUE_ID,
+            &filter,
+            EnqueueSortKey::Estimate,
+            false,
+            None,
+            false,
+        )
+        .unwrap();
+
+        // 見積もりの短い順（未設定は最後）に追加
+        let added: Vec<&str> = result.added.iter().map(|e| e.task_id.as_str()).collect();
+        assert_eq!(
+            added,
+            vec![short.as_str(), long.as_str(), unestimated.as_str()]
+        );
+        let skipped: Vec<(&str, EnqueueSkipReason)> = result
+            .skipped
+            .iter()
+            .map(|s| (s.task_id.as_str(), s.reason))
+            .collect();
+        assert_eq!(
+            skipped,
+            vec![
+                (queued.as_str(), EnqueueSkipReason::AlreadyQueued),
+                (parent.as_str(), EnqueueSkipReason::HasChildren),
+            ]
+        );
+        let task = TaskService::get_task(&mut conn, &short).unwrap();
+        assert_eq!(task.status, TaskStatus::Active);
+
+        // アーカイブ済みタスクはステータスで明示的に検索した場合もスキップ
+        let archived = create_draft_task(&mut conn, "Archived");
+        TaskService::delete_task(&mut conn, &archived).unwrap();
+        let filter = SearchTasksParams {
+            status: Some("archived".to_string()),
+            ..Default::default()
+        };
+        let result = QueueService::enqueue_matching(
+            &mut conn,
+            DEFAULT_QUEUE_ID,
+            &filter,
+            EnqueueSortKey::Created,
+            false,
+            None,
+            false,
+        )
+        .unwrap();
+        assert!(result.added.is_empty());
+        assert_eq!(result.skipped[0].reason, EnqueueSkipReason::Archived);
+
+        // 完了済みタスクもスキップし、Activeに戻さない
+        let completed = create_draft_task(&mut conn, "Completed");
+        diesel::update(tasks::table.find(&completed))
+            .set(tasks::status.eq(TaskStatus::Completed.as_str()))
+            .execute(&mut conn)
+            .unwrap();
+        let filter = SearchTasksParams {
+            status: Some("completed".to_string()),
+            ..Default::default()
+        };
+        let result = QueueService::enqueue_matching(
+            &mut conn,
+            DEFAULT_QUEUE_ID,
+            &filter,
+            EnqueueSortKey::Created,
+            false,
+            None,
+            false,
+        )
+        .unwrap();
+        assert!(result.added.is_empty());
+        assert_eq!(result.skipped[0].task_id, completed);
+        assert_eq!(result.skipped[0].reason, EnqueueSkipReason::Completed);
+        let task = TaskService::get_task(&mut conn, &completed).unwrap();
+        assert_eq!(task.status, TaskStatus::Completed);
+
+        // 検索条件の無い一括追加は拒否
+        let result = QueueService::enqueue_matching(
+            &mut conn,
+            DEFAULT_QUEUE_ID,
+            &SearchTasksParams::default(),
+            EnqueueSortKey::Created,
+            false,
+            None,
+            false,
+        );
+        assert!(matches!(result, Err(ServiceError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_defer_queue_entry_keeps_status_and_hides_from_pool() {
+        let mut conn = setup_test_db();
+        let task_id = create_draft_task(&mut conn, "後回し");
+        QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, task_id.clone(), None, false)
+            .unwrap();
+        let today = QueueService::today(&mut conn).unwrap();
+
+        // 今日以前への延期は不可
+        let result =
+            QueueService::defer_queue_entry(&mut conn, DEFAULT_QUEUE_ID, task_id.clone(), today);
+        assert!(matches!(result, Err(ServiceError::InvalidInput(_))));
+
+        let until = today + chrono::Duration::days(3);
+        let deferred =
+            QueueService::defer_queue_entry(&mut conn, DEFAULT_QUEUE_ID, task_id.clone(), until)
+                .unwrap();
+        assert_eq!(deferred.until_date, date_key(until));
+        assert_eq!(deferred.deferred_from, date_key(today));
+
+        // キューから外れるがステータスは変わらない
+        assert!(QueueService::get_queue(&mut conn, DEFAULT_QUEUE_ID, None)
+            .unwrap()
+            .is_empty());
+        let task = TaskService::get_task(&mut conn, &task_id).unwrap();
+        assert_eq!(task.status, TaskStatus::Active);
+        let outcomes: Vec<String> = queue_history::table
+            .filter(queue_history::task_id.eq(&task_id))
+            .select(queue_history::outcome)
+            .load(&mut conn)
+            .unwrap();
+        assert_eq!(outcomes, vec![QueueOutcome::Deferred.as_str()]);
+
+        // プールには表示されない
+        let hierarchy = TaskService::get_hierarchy(&mut conn).unwrap();
+        assert!(hierarchy.iter().all(|t| t.id != task_id));
+        let deferred_list = QueueService::get_deferred_tasks(&mut conn, DEFAULT_QUEUE_ID).unwrap();
+        assert_eq!(deferred_list.len(), 1);
+        assert_eq!(deferred_list[0].task_title, "後回し");
+
+        // キューに戻すと延期は解除される
+        QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, task_id.clone(), None, false)
+            .unwrap();
+        assert!(
+            QueueService::get_deferred_tasks(&mut conn, DEFAULT_QUEUE_ID)
+                .unwrap()
+                .is_empty()
+        );
+        assert!(QueueService::defer_queue_entry(
+            &mut conn,
+            DEFAULT_QUEUE_ID,
+            "missing".to_string(),
+            until
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_named_queues_keep_separate_order() {
+        let mut conn = setup_test_db();
+        let work = QueueService::create_queue(&mut conn, "仕事".to_string()).unwrap();
+        let home = QueueService::create_queue(&mut conn, " 家 ".to_string()).unwrap();
+        assert_eq!(home.name, "家");
+        assert!(matches!(
+            QueueService::create_queue(&mut conn, "仕事".to_string()),
+            Err(ServiceError::DuplicateQueueName(_))
+        ));
+        let names: Vec<String> = QueueService::list_queues(&mut conn)
+            .unwrap()
+            .into_iter()
+            .map(|q| q.id)
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                DEFAULT_QUEUE_ID.to_string(),
+                work.id.clone(),
+                home.id.clone()
+            ]
+        );
+
+        let a = create_draft_task(&mut conn, "A");
+        let b = create_draft_task(&mut conn, "B");
+        let c = create_draft_task(&mut conn, "C");
+        QueueService::add_to_queue(&mut conn, &work.id, a.clone(), None, false).unwrap();
+        QueueService::add_to_queue(&mut conn, &work.id, b.clone(), None, false).unwrap();
+        QueueService::add_to_queue(&mut conn, &home.id, c.clone(), None, false).unwrap();
+
+        // タスクは1つのキューにしか入れない
+        let result = QueueService::add_to_queue(&mut conn, &home.id, a.clone(), None, false);
+        assert!(matches!(result, Err(ServiceError::DuplicateQueueEntry(_))));
+        // 別のキューを指定した操作は対象外
+        let result =
+            QueueService::remove_from_queue(&mut conn, &home.id, a.clone(), "draft".to_string());
+        assert!(matches!(result, Err(ServiceError::QueueEntryNotFound(_))));
+
+        // 並べ替えは各キューの中だけに影響する
+        QueueService::update_queue_position(&mut conn, &work.id, b.clone(), 0).unwrap();
+        let work_ids: Vec<String> = QueueService::get_queue(&mut conn, &work.id, None)
+            .unwrap()
+            .into_iter()
+            .map(|e| e.task_id)
+            .collect();
+        assert_eq!(work_ids, vec![b.clone(), a.clone()]);
+        assert_eq!(
+            QueueService::get_queue(&mut conn, &home.id, None)
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(QueueService::get_queue(&mut conn, DEFAULT_QUEUE_ID, None)
+            .unwrap()
+            .is_empty());
+
+        // 同じ日のまま別のキューへ移してもステータスは変わらない
+        let today = QueueService::today(&mut conn).unwrap();
+        let moved = QueueService::move_queue_entry(
+            &mut conn,
+            &work.id,
+            a.clone(),
+            &home.id,
+            today,
+            Some(0),
+            false,
+        )
+        .unwrap();
+        assert_eq!(moved.queue_id, home.id);
+        assert_eq!(
+            TaskService::get_task(&mut conn, &a).unwrap().status,
+            TaskStatus::Active
+        );
+        assert_eq!(
+            QueueService::get_queue(&mut conn, &home.id, None).unwrap()[0].task_id,
+            a
+        );
+
+        // 既定のキューとタスクが残っているキューは削除できない
+        assert!(matches!(
+            QueueService::delete_queue(&mut conn, DEFAULT_QUEUE_ID),
+            Err(ServiceError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            QueueService::delete_queue(&mut conn, &home.id),
+            Err(ServiceError::InvalidInput(_))
+        ));
+        QueueService::remove_from_queue(&mut conn, &work.id, b, "draft".to_string()).unwrap();
+        let renamed = QueueService::rename_queue(&mut conn, &work.id, "職場".to_string()).unwrap();
+        assert_eq!(renamed.name, "職場");
+        QueueService::delete_queue(&mut conn, &work.id).unwrap();
+        assert!(matches!(
+            QueueService::get_queue(&mut conn, &work.id, None),
+            Err(ServiceError::QueueNotFound(_))
+        ));
+    }
 }
@@ -0,0 +1,620 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, MutexGuard};
+
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel::SqliteConnection;
+
+use crate::error::ServiceError;
+use crate::models::quick_switch::QuickSwitchResult;
+use crate::models::task::{Task, TaskStatus};
+use crate::schema::{tags, task_tags, tasks};
+
+/// 短縮IDの文字数
+const SHORT_ID_LEN: usize = 8;
+
+/// 検索結果のデフォルト件数
+const DEFAULT_LIMIT: usize = 20;
+
+/// 検索結果の最大件数
+const MAX_LIMIT: usize = 100;
+
+/// 新しさボーナスの最大値
+const RECENCY_BONUS_MAX: f64 = 30.0;
+
+/// 新しさボーナスの半減期（日）
+const RECENCY_HALF_LIFE_DAYS: f64 = 14.0;
+
+/// Tauriのmanaged stateとして保持するインデックス
+pub struct QuickSwitchState(pub Mutex<QuickSwitchIndex>);
+
+impl QuickSwitchState {
+    pub fn new(index: QuickSwitchIndex) -> Self {
+        Self(Mutex::new(index))
+    }
+
+    /// インデックスをロックして取得
+    pub fn lock(&self) -> Result<MutexGuard<'_, QuickSwitchIndex>, ServiceError> {
+        self.0.lock().map_err(|e| {
+            ServiceError::InternalError(format!("インデックスのロックに失敗しました: {}", e))
+        })
+    }
+}
+
+/// インデックスに保持するタスク情報（検索用に正規化済み）
+#[derive(Debug, Clone)]
+struct IndexEntry {
+    task_id: String,
+    short_id: String,
+    title: String,
+    title_chars: Vec<char>,
+    tags: Vec<String>,
+    tag_chars: Vec<Vec<char>>,
+    status: TaskStatus,
+    parent_id: Option<String>,
+    updated_at: i64,
+}
+
+impl IndexEntry {
+    fn new(task: Task, tags: Vec<String>) -> Self {
+        let status = task.status_enum();
+        let updated_at = DateTime::parse_from_rfc3339(&task.updated_at)
+            .map(|dt| dt.timestamp())
+            .unwrap_or(0);
+
+        Self {
+            short_id: task
+                .id
+                .chars()
+                .take(SHORT_ID_LEN)
+                .collect::<String>()
+                .to_lowercase(),
+            title_chars: normalize(&task.title),
+            tag_chars: tags.iter().map(|t| normalize(t)).collect(),
+            task_id: task.id,
+            title: task.title,
+            tags,
+            status,
+            parent_id: task.parent_id,
+            updated_at,
+        }
+    }
+}
+
+/// QuickSwitchIndex: Ctrl+K ジャンプ用のあいまい検索インデックス
+///
+/// タイトル・短縮ID・タグを対象に、部分列マッチ（タイポ許容）でスコアリングし、
+/// 一致度と新しさでランキングする。起動時に全件を構築し、以降は
+/// タスク・キュー・タグの変更コマンドから `refresh` 等で差分更新する。
+///
+/// # Notes
+/// - インデックスはTauriのmanaged stateでサービス層からは参照できないため、更新は意図的に
+///   コマンド層で行う（サービスは `&mut SqliteConnection` のみを受け取る方針を維持する）
+/// - タスクを変更するコマンドを追加する場合は、必ず `commands::quick_switch::refresh_index`
+///   （タグの変更は `rename_tag` / `remove_tag`）を呼ぶこと。呼び忘れてもエラーにはならず、
+///   次回起動時の再構築までインデックスが古いままになる
+#[derive(Debug, Default)]
+pub struct QuickSwitchIndex {
+    entries: HashMap<String, IndexEntry>,
+}
+
+impl QuickSwitchIndex {
+    /// DBの全タスクからインデックスを構築
+    pub fn build(conn: &mut SqliteConnection) -> Result<Self, ServiceError> {
+        let all_tasks = tasks::table.load::<Task>(conn)?;
+        let mut tag_names = Self::load_all_tag_names(conn)?;
+
+        let entries = all_tasks
+            .into_iter()
+            .map(|task| {
+                let task_tags = tag_names.remove(&task.id).unwrap_or_default();
+                (task.id.clone(), IndexEntry::new(task, task_tags))
+            })
+            .collect();
+
+        Ok(Self { entries })
+    }
+
+    /// インデックス内のタスク数
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// インデックスが空かどうか
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 指定タスク（とその親タスク）をDBから再読込して差分更新
+    ///
+    /// # Arguments
+    /// * `conn` - データベース接続
+    /// * `task_ids` - 変更されたタスクID
+    ///
+    /// # Notes
+    /// - 子タスクの変更で親ステータスが自動同期されるため（BR-013）、親も再読込する
+    /// - DBに存在しないタスクはインデックスから削除し、その子孫のタスクも削除する（CASCADE）
+    pub fn refresh(
+        &mut self,
+        conn: &mut SqliteConnection,
+        task_ids: &[String],
+    ) -> Result<(), ServiceError> {
+        if task_ids.is_empty() {
+            return Ok(());
+        }
+
+        let mut loaded = tasks::table
+            .filter(tasks::id.eq_any(task_ids))
+            .load::<Task>(conn)?;
+
+        // 物理削除されたタスクと、その子孫のタスクを削除
+        let found: HashSet<&str> = loaded.iter().map(|t| t.id.as_str()).collect();
+        let mut removed: HashSet<String> = task_ids
+            .iter()
+            .filter(|id| !found.contains(id.as_str()))
+            .cloned()
+            .collect();
+        let mut pending: Vec<String> = removed.iter().cloned().collect();
+        while !pending.is_empty() {
+            for id in &pending {
+                self.entries.remove(id);
+            }
+            pending = self
+                .entries
+                .values()
+                .filter(|entry| {
+                    entry
+                        .parent_id
+                        .as_ref()
+                        .is_some_and(|parent_id| removed.contains(parent_id))
+                })
+                .map(|entry| entry.task_id.clone())
+                .collect();
+            removed.extend(pending.iter().cloned());
+        }
+
+        // 親タスクも再読込
+        let parent_ids: Vec<String> = loaded
+            .iter()
+            .filter_map(|t| t.parent_id.clone())
+            .filter(|pid| !found.contains(pid.as_str()))
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        if !parent_ids.is_empty() {
+            loaded.extend(
+                tasks::table
+                    .filter(tasks::id.eq_any(&parent_ids))
+                    .load::<Task>(conn)?,
+            );
+        }
+
+        let loaded_ids: Vec<String> = loaded.iter().map(|t| t.id.clone()).collect();
+        let mut tag_names: HashMap<String, Vec<String>> = HashMap::new();
+        for (task_id, tag_name) in task_tags::table
+            .inner_join(tags::table)
+            .filter(task_tags::task_id.eq_any(&loaded_ids))
+            .select((task_tags::task_id, tags::name))
+            .load::<(String, String)>(conn)?
+        {
+            tag_names.entry(task_id).or_default().push(tag_name);
+        }
+
+        for task in loaded {
+            let task_tags = tag_names.remove(&task.id).unwrap_or_default();
+            self.entries
+                .insert(task.id.clone(), IndexEntry::new(task, task_tags));
+        }
+
+        Ok(())
+    }
+
+    /// タグ名変更をインデックスに反映
+    pub fn rename_tag(&mut self, old_name: &str, new_name: &str) {
+        for entry in self.entries.values_mut() {
+            if let Some(pos) = entry.tags.iter().position(|t| t == old_name) {
+                entry.tags[pos] = new_name.to_string();
+                entry.tag_chars[pos] = normalize(new_name);
+            }
+        }
+    }
+
+    /// タグ削除をインデックスに反映
+    pub fn remove_tag(&mut self, name: &str) {
+        for entry in self.entries.values_mut() {
+            if let Some(pos) = entry.tags.iter().position(|t| t == name) {
+                entry.tags.remove(pos);
+                entry.tag_chars.remove(pos);
+            }
+        }
+    }
+
+    /// あいまい検索
+    ///
+    /// # Arguments
+    /// * `query` - 検索文字列（空白区切りの各語がすべて一致する必要がある）
+    /// * `limit` - 最大件数（デフォルト: 20、最大: 100）
+    /// * `include_archived` - Archivedタスクを含めるか
+    ///
+    /// # Returns
+    /// * スコア降順の検索結果（空クエリの場合は更新日時の新しい順）
+    pub fn search(
+        &self,
+        query: &str,
+        limit: Option<usize>,
+        include_archived: bool,
+    ) -> Vec<QuickSwitchResult> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+        let now = Utc::now().timestamp();
+        let tokens: Vec<Vec<char>> = query.split_whitespace().map(normalize).collect();
+
+        let mut matches: Vec<(i32, Vec<usize>, &IndexEntry)> = self
+            .entries
+            .values()
+            .filter(|entry| include_archived || entry.status != TaskStatus::Archived)
+            .filter_map(|entry| {
+                let (match_score, highlights) = match_entry(&tokens, entry)?;
+                Some((
+                    match_score + recency_bonus(entry.updated_at, now),
+                    highlights,
+                    entry,
+                ))
+            })
+            .collect();
+
+        let compare = |a: &(i32, Vec<usize>, &IndexEntry), b: &(i32, Vec<usize>, &IndexEntry)| {
+            b.0.cmp(&a.0)
+                .then_with(|| b.2.updated_at.cmp(&a.2.updated_at))
+                .then_with(|| a.2.task_id.cmp(&b.2.task_id))
+        };
+        if matches.len() > limit {
+            matches.select_nth_unstable_by(limit, compare);
+            matches.truncate(limit);
+        }
+        matches.sort_by(compare);
+
+        matches
+            .into_iter()
+            .map(|(score, title_highlights, entry)| QuickSwitchResult {
+                task_id: entry.task_id.clone(),
+                short_id: entry.short_id.clone(),
+                title: entry.title.clone(),
+                status: entry.status.clone(),
+                tags: entry.tags.clone(),
+                score,
+                title_highlights,
+            })
+            .collect()
+    }
+
+    /// 全タスクのタグ名を取得（インデックス構築用）
+    fn load_all_tag_names(
+        conn: &mut SqliteConnection,
+    ) -> Result<HashMap<String, Vec<String>>, ServiceError> {
+        let mut tag_names: HashMap<String, Vec<String>> = HashMap::new();
+        for (task_id, tag_name) in task_tags::table
+            .inner_join(tags::table)
+            .select((task_tags::task_id, tags::name))
+            .load::<(String, String)>(conn)?
+        {
+            tag_names.entry(task_id).or_default().push(tag_name);
+        }
+        Ok(tag_names)
+    }
+}
+
+/// 検索用に正規化（小文字化した文字列）
+fn normalize(text: &str) -> Vec<char> {
+    text.to_lowercase().chars().collect()
+}
+
+/// 全トークンをタイトル・タグ・短縮IDに対して照合
+///
+/// # Returns
+/// * `Some((score, title_highlights))` - 全トークンが一致した場合
+/// * `None` - 一致しないトークンがある場合
+fn match_entry(tokens: &[Vec<char>], entry: &IndexEntry) -> Option<(i32, Vec<usize>)> {
+    let mut total = 0;
+    let mut highlights: Vec<usize> = Vec::new();
+
+    for token in tokens {
+        let title_match = fuzzy_score(token, &entry.title_chars);
+        let tag_score = entry
+            .tag_chars
+            .iter()
+            .filter_map(|tag| fuzzy_score(token, tag))
+            .map(|(score, _)| score * 4 / 5)
+            .max();
+        let id_score = short_id_score(token, &entry.short_id);
+
+        let title_score = title_match.as_ref().map(|(score, _)| *score);
+        let best = [title_score, tag_score, id_score]
+            .into_iter()
+            .flatten()
+            .max()?;
+
+        if let Some((score, positions)) = title_match {
+            if score == best {
+                highlights.extend(positions);
+            }
+        }
+        total += best;
+    }
+
+    highlights.sort_unstable();
+    highlights.dedup();
+    Some((total, highlights))
+}
+
+/// 短縮IDの前方一致スコア
+fn short_id_score(token: &[char], short_id: &str) -> Option<i32> {
+    if token.len() < 4 || token.len() > short_id.len() {
+        return None;
+    }
+    let is_prefix = short_id.chars().zip(token.iter()).all(|(a, b)| a == *b);
+    is_prefix.then(|| 100 + token.len() as i32 * 20)
+}
+
+/// 部分列マッチのスコアを計算（タイポ許容）
+///
+/// # Scoring
+/// - 連続した部分文字列として含まれる場合は最も高く評価（先頭一致はさらに加点）
+/// - それ以外は貪欲な部分列マッチで、連続一致・単語先頭一致に加点し、ギャップを減点
+/// - クエリ長に応じて一致しない文字を許容（4文字以上で1文字、8文字以上で2文字）
+///
+/// # Returns
+/// * `Some((score, positions))` - 一致した場合のスコアと一致位置
+/// * `None` - 一致しない場合
+fn fuzzy_score(query: &[char], target: &[char]) -> Option<(i32, Vec<usize>)> {
+    if query.is_empty() || target.is_empty() {
+        return None;
+    }
+
+    // 連続部分文字列として含まれる場合
+    if query.len() <= target.len() {
+        if let Some(start) = target.windows(query.len()).position(|w| w == query) {
+            let mut score = 40 * query.len() as i32 + 30;
+            if start == 0 {
+                score += 50;
+            } else if is_word_boundary(target[start - 1]) {
+                score += 25;
+            }
+            score -= length_penalty(target.len());
+            return Some((score, (start..start + query.len()).collect()));
+        }
+    }
+
+    // 貪欲な部分列マッチ（タイポ許容）
+    let max_typos = match query.len() {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    };
+    let mut typos = 0;
+    let mut score = 0;
+    let mut positions = Vec::with_capacity(query.len());
+    let mut cursor = 0;
+
+    for &qc in query {
+        let found = target
+            .get(cursor..)
+            .and_then(|rest| rest.iter().position(|&c| c == qc));
+        match found {
+            Some(offset) => {
+                let idx = cursor + offset;
+                score += 16;
+                if positions.last().is_some_and(|&prev| prev + 1 == idx) {
+                    score += 24;
+                } else if !positions.is_empty() {
+                    score -= offset.min(10) as i32;
+                }
+                if idx == 0 || is_word_boundary(target[idx - 1]) {
+                    score += 20;
+                }
+                positions.push(idx);
+                cursor = idx + 1;
+            }
+            None => {
+                typos += 1;
+                if typos > max_typos {
+                    return None;
+                }
+                score -= 20;
+            }
+        }
+    }
+
+    // 一致文字が少なすぎる場合は不一致とみなす
+    if positions.len() * 2 < query.len() || positions.is_empty() {
+        return None;
+    }
+
+    score -= positions[0].min(15) as i32;
+    score -= length_penalty(target.len());
+    Some((score, positions))
+}
+
+/// 単語の区切り文字か
+fn is_word_boundary(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '-' | '_' | '/' | '.' | '[' | ']' | '(' | ')' | ':' | '#')
+}
+
+/// 長い対象文字列ほどわずかに減点（同程度の一致なら短い方を優先）
+fn length_penalty(len: usize) -> i32 {
+    (len / 10).min(10) as i32
+}
+
+/// 更新日時に基づく新しさボーナス（半減期14日で減衰）
+fn recency_bonus(updated_at: i64, now: i64) -> i32 {
+    let age_days = (now - updated_at).max(0) as f64 / 86_400.0;
+    (RECENCY_BONUS_MAX * 0.5f64.powf(age_days / RECENCY_HALF_LIFE_DAYS)) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::tag::CreateTagRequest;
+    use crate::models::task::CreateTaskRequest;
+    use crate::service::{TagService, TaskService};
+    use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+
+    pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
+
+    fn setup_test_db() -> SqliteConnection {
+        let mut conn =
+            SqliteConnection::establish(":memory:").expect("Failed to create in-memory database");
+
+        conn.run_pending_migrations(MIGRATIONS)
+            .expect("Failed to run migrations");
+
+        conn
+    }
+
+    fn create_task(conn: &mut SqliteConnection, title: &str, tags: Vec<&str>) -> String {
+        TaskService::create_task(
+            conn,
+            CreateTaskRequest {
+                title: title.to_string(),
+                description: None,
+                tags: tags.into_iter().map(String::from).collect(),
+                parent_id: None,
+                estimated_minutes: None,
+            },
+        )
+        .unwrap()
+        .id
+    }
+
+    #[test]
+    fn test_fuzzy_score_substring_beats_subsequence() {
+        let target = normalize("Fix login bug");
+        let substring = fuzzy_score(&normalize("login"), &target).unwrap();
+        let subsequence = fuzzy_score(&normalize("lgn"), &target).unwrap();
+        assert!(substring.0 > subsequence.0);
+        assert_eq!(substring.1, vec![4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn test_fuzzy_score_tolerates_typo() {
+        let target = normalize("Quarterly report");
+        assert!(fuzzy_score(&normalize("reprot"), &target).is_some());
+        assert!(fuzzy_score(&normalize("xyz"), &target).is_none());
+    }
+
+    #[test]
+    fn test_search_matches_title_tag_and_short_id() {
+        let mut conn = setup_test_db();
+        TagService::create_tag(
+            &mut conn,
+            CreateTagRequest {
+                name: "meeting".to_string(),
+                color: None,
+            },
+        )
+        .unwrap();
+
+        let report_id = create_task(&mut conn, "週次レポート作成", vec![]);
+        let sync_id = create_task(&mut conn, "Team sync", vec!["meeting"]);
+
+        let index = QuickSwitchIndex::build(&mut conn).unwrap();
+        assert_eq!(index.len(), 2);
+
+        let results = index.search("レポート", None, false);
+        assert_eq!(results[0].task_id, report_id);
+        assert_eq!(results[0].title_highlights, vec![2, 3, 4, 5]);
+
+        let results = index.search("meeting", None, false);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].task_id, sync_id);
+
+        let results = index.search(&sync_id[..6], None, false);
+        assert_eq!(results[0].task_id, sync_id);
+    }
+
+    #[test]
+    fn test_search_requires_all_tokens() {
+        let mut conn = setup_test_db();
+        let target_id = create_task(&mut conn, "Fix login bug", vec![]);
+        create_task(&mut conn, "Fix signup page", vec![]);
+
+        let index = QuickSwitchIndex::build(&mut conn).unwrap();
+        let results = index.search("fix login", None, false);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].task_id, target_id);
+    }
+
+    #[test]
+    fn test_refresh_applies_changes_incrementally() {
+        let mut conn = setup_test_db();
+        let task_id = create_task(&mut conn, "Draft plan", vec![]);
+
+        let mut index = QuickSwitchIndex::build(&mut conn).unwrap();
+        assert_eq!(index.search("plan", None, false).len(), 1);
+
+        // アーカイブされたタスクはデフォルトで除外
+        TaskService::delete_task(&mut conn, &task_id).unwrap();
+        index.refresh(&mut conn, std::slice::from_ref(&task_id)).unwrap();
+        assert!(index.search("plan", None, false).is_empty());
+        assert_eq!(index.search("plan", None, true).len(), 1);
+
+        // 物理削除されたタスクはインデックスから削除
+        TaskService::delete_task_permanently(&mut conn, &task_id).unwrap();
+        index.refresh(&mut conn, &[task_id]).unwrap();
+        assert!(index.is_empty());
+    }
+
+    #[test]
+    fn test_refresh_removes_deleted_subtree() {
+        let mut conn = setup_test_db();
+        let root_id = create_task(&mut conn, "Root", vec![]);
+        let child_id = create_task(&mut conn, "Child", vec![]);
+        let grandchild_id = create_task(&mut conn, "Grandchild", vec![]);
+        create_task(&mut conn, "Unrelated", vec![]);
+
+        // サービスでは孫タスクを作れないため、親子関係を直接設定する
+        for (task_id, parent_id) in [(&child_id, &root_id), (&grandchild_id, &child_id)] {
+            diesel::update(tasks::table.find(task_id))
+                .set(tasks::parent_id.eq(parent_id))
+                .execute(&mut conn)
+                .unwrap();
+        }
+        let mut index = QuickSwitchIndex::build(&mut conn).unwrap();
+        assert_eq!(index.len(), 4);
+
+        // ルートの物理削除で子孫ごと消える（テストDBはCASCADEしないため、すべて削除する）
+        diesel::delete(tasks::table.filter(tasks::id.eq_any([
+            &root_id,
+            &child_id,
+            &grandchild_id,
+        ])))
+        .execute(&mut conn)
+        .unwrap();
+        index.refresh(&mut conn, &[root_id]).unwrap();
+        assert_eq!(index.len(), 1);
+        assert!(index.search("Grandchild", None, true).is_empty());
+    }
+
+    #[test]
+    fn test_rename_and_remove_tag() {
+        let mut conn = setup_test_db();
+        TagService::create_tag(
+            &mut conn,
+            CreateTagRequest {
+                name: "mtg".to_string(),
+                color: None,
+            },
+        )
+        .unwrap();
+        create_task(&mut conn, "Weekly", vec!["mtg"]);
+
+        let mut index = QuickSwitchIndex::build(&mut conn).unwrap();
+        index.rename_tag("mtg", "meeting");
+        assert_eq!(
+            index.search("meeting", None, false)[0].tags,
+            vec!["meeting"]
+        );
+
+        index.remove_tag("meeting");
+        assert!(index.search("meeting", None, false).is_empty());
+    }
+}
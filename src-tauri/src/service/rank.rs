@@ -0,0 +1,155 @@
+//! キューの並び順キー（LexoRank方式の分数順序キー）
+//!
+//! キーは基数36の数字（`0-9a-z`）からなる文字列で、小数点以下の桁として解釈する
+//! （"i" = 0.5、"0i" = 0.5/36 など）。文字列の辞書順と数値の大小が一致するよう、
+//! キーの末尾は `0` にしない。任意の2つのキーの間には常に新しいキーを作れるため、
+//! 1件の移動で書き換えるのはそのエントリの1行だけで済む。
+
+/// キーが長くなりすぎたとみなす長さ（これを超えたら再採番する）
+pub const MAX_RANK_LEN: usize = 24;
+
+/// 基数
+const BASE: u8 = 36;
+
+/// 文字を桁の値に変換
+fn digit_value(c: u8) -> u8 {
+    match c {
+        b'0'..=b'9' => c - b'0',
+        b'a'..=b'z' => c - b'a' + 10,
+        _ => 0,
+    }
+}
+
+/// 桁の値を文字に変換
+fn digit_char(value: u8) -> char {
+    match value {
+        0..=9 => (b'0' + value) as char,
+        _ => (b'a' + value - 10) as char,
+    }
+}
+
+/// 2つのキーの間に入るキーを作成
+///
+/// # Arguments
+/// * `lower` - 直前のキー（Noneの場合は先頭）
+/// * `upper` - 直後のキー（Noneの場合は末尾）
+///
+/// `lower < upper` であること。結果は `lower < 結果 < upper` を満たし、末尾は `0` にならない。
+pub fn between(lower: Option<&str>, upper: Option<&str>) -> String {
+    let lower = lower.unwrap_or("").as_bytes();
+    let mut upper = upper.map(str::as_bytes);
+    debug_assert!(upper.is_none_or(|upper| lower < upper));
+
+    let mut key = String::new();
+    for i in 0.. {
+        let low = lower.get(i).copied().map_or(0, digit_value);
+        // 上限が無い（またはここまでで下回った）場合は1.0を上限とみなす
+        let high = match upper {
+            Some(upper) => upper.get(i).copied().map_or(0, digit_value),
+            None => BASE,
+        };
+
+        if high > low + 1 {
+            key.push(digit_char((low + high) / 2));
+            break;
+        }
+
+        // 間に桁が無い: 下限の桁を採用して次の桁へ
+        key.push(digit_char(low));
+        if high == low + 1 {
+            upper = None;
+        }
+    }
+    key
+}
+
+/// 2つのキーの間に入る、昇順の `count` 個のキーを作成
+///
+/// 中央から二分して作るため、キーの長さは件数の対数程度に抑えられる。
+pub fn keys_between(lower: Option<&str>, upper: Option<&str>, count: usize) -> Vec<String> {
+    if count == 0 {
+        return Vec::new();
+    }
+
+    let middle_index = count / 2;
+    let middle = between(lower, upper);
+    let mut keys = keys_between(lower, Some(&middle), middle_index);
+    let after = keys_between(Some(&middle), upper, count - middle_index - 1);
+    keys.push(middle);
+    keys.extend(after);
+    keys
+}
+
+/// 並び順キーとして正しい形式か（空でない・`0-9a-z` のみ・末尾が `0` でない・長すぎない）
+pub fn is_valid(key: &str) -> bool {
+    !key.is_empty()
+        && key.len() <= MAX_RANK_LEN
+        && key
+            .bytes()
+            .all(|c| c.is_ascii_digit() || c.is_ascii_lowercase())
+        && !key.ends_with('0')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_ordered(keys: &[String]) {
+        for pair in keys.windows(2) {
+            assert!(pair[0] < pair[1], "{:?} < {:?}", pair[0], pair[1]);
+        }
+        for key in keys {
+            assert!(!key.ends_with('0'), "trailing zero: {:?}", key);
+        }
+    }
+
+    #[test]
+    fn test_between() {
+        assert_eq!(between(None, None), "i");
+        assert_eq!(between(Some("i"), None), "r");
+        assert_eq!(between(None, Some("i")), "9");
+        assert_eq!(between(Some("a"), Some("c")), "b");
+        // 隣り合う桁の間は1桁伸ばす
+        assert_eq!(between(Some("a"), Some("b")), "ai");
+        assert_eq!(between(Some("z"), None), "zi");
+        assert_eq!(between(None, Some("1")), "0i");
+        // 既存の移行キー（固定長の数字 + "i"）の間にも入る
+        assert_eq!(between(Some("00001i"), Some("00002i")), "00001r");
+    }
+
+    #[test]
+    fn test_repeated_insertions_stay_ordered() {
+        // 同じ位置（先頭・末尾・同じ隙間）への挿入を繰り返しても順序が保たれる
+        let mut keys = vec![between(None, None)];
+        for _ in 0..50 {
+            keys.insert(0, between(None, Some(&keys[0])));
+            keys.push(between(keys.last().map(String::as_str), None));
+            let key = between(Some(&keys[1]), Some(&keys[2]));
+            keys.insert(2, key);
+        }
+        assert_ordered(&keys);
+    }
+
+    #[test]
+    fn test_keys_between() {
+        let keys = keys_between(None, None, 1000);
+        assert_eq!(keys.len(), 1000);
+        assert_ordered(&keys);
+        assert!(keys.iter().all(|key| key.len() <= 3));
+
+        let keys = keys_between(Some("a"), Some("b"), 5);
+        assert_ordered(&keys);
+        assert!(keys.first().unwrap().as_str() > "a" && keys.last().unwrap().as_str() < "b");
+    }
+
+    #[test]
+    fn test_is_valid() {
+        assert!(is_valid("i"));
+        assert!(is_valid("00001i"));
+        assert!(!is_valid(""));
+        assert!(!is_valid("i0"));
+        assert!(!is_valid("I"));
+        assert!(!is_valid("~task"));
+        assert!(!is_valid(&"i".repeat(MAX_RANK_LEN + 1)));
+    }
+}
@@ -0,0 +1,640 @@
+use std::collections::HashMap;
+
+use chrono::{NaiveDateTime, Utc};
+use diesel::prelude::*;
+use diesel::SqliteConnection;
+
+use crate::error::ServiceError;
+use crate::models::queue::QueueEntry;
+use crate::models::queue_history::{
+    CarriedOverTask, NewQueueHistoryEntry, QueueOutcome, RolloverResult, TaskSlipCount,
+};
+use crate::models::task::{Task, TaskStatus};
+use crate::schema::{deferred_tasks, queue_history, task_queue, tasks};
+use crate::service::queue::{date_key, QueueService};
+use crate::service::settings::{SettingsService, LAST_ROLLOVER_DATE_KEY};
+use crate::service::task::TaskService;
+
+/// RolloverService: 日次キューのロールオーバー（日付変更処理）
+///
+/// UI（Tauriコマンド）に依存しないため、CLI（`tms-rollover`）からも同じ処理を実行できる。
+/// 現在時刻は引数で受け取り、テストでは任意の時刻を指定する。
+pub struct RolloverService;
+
+impl RolloverService {
+    /// ロールオーバーを実行
+    ///
+    /// # Arguments
+    /// * `conn` - データベース接続
+    /// * `now` - 現在時刻（ローカル）。day_start_hour設定と合わせて「今日」を決定する
+    ///
+    /// # Returns
+    /// * `Ok(RolloverResult)` - 実行結果
+    /// * `Err(ServiceError)` - エラー
+    ///
+    /// # Business Logic
+    /// - 今日より前の日付のキューをすべて履歴（queue_history）に記録する
+    ///   - Completed → completed、Archived → archived、それ以外 → carried_over
+    /// - 未完了のタスクを同じキューの今日へ持ち越す（設定により先頭または末尾、元の順序を維持）
+    /// - 延期期限（until_date）を迎えたタスクを延期元のキューの今日の末尾へ戻す
+    ///   （Draft/Activeで子タスクを持たないもの。それ以外は延期を解除してプールに戻す）
+    /// - 今日のキューのDraftタスク（事前に計画されたタスク・持ち越したタスク・戻したタスク）をActiveに変更
+    /// - 何度実行しても結果が変わらない（過去のキューが無ければ何もしない）
+    pub fn rollover(
+        conn: &mut SqliteConnection,
+        now: NaiveDateTime,
+    ) -> Result<RolloverResult, ServiceError> {
+        let settings = SettingsService::get_rollover_settings(conn)?;
+        let today = date_key(QueueService::queue_day(now, settings.day_start_hour));
+
+        conn.transaction::<_, ServiceError, _>(|conn| {
+            // 過去の日付のキュー（日付・rank順）
+            let past_entries = task_queue::table
+                .inner_join(tasks::table)
+                .filter(task_queue::queue_date.lt(&today))
+                .order((task_queue::queue_date.asc(), task_queue::rank.asc()))
+                .load::<(QueueEntry, Task)>(conn)?;
+
+            let mut rolled_over_dates: Vec<String> = Vec::new();
+            let mut history = Vec::with_capacity(past_entries.len());
+            let mut carried: Vec<(QueueEntry, Task)> = Vec::new();
+            let mut finished_count = 0;
+            // キューごとの位置（日付が変わったらリセット）
+            let mut positions: HashMap<String, i32> = HashMap::new();
+
+            for (entry, task) in past_entries {
+                if rolled_over_dates.last() != Some(&entry.queue_date) {
+                    rolled_over_dates.push(entry.queue_date.clone());
+                    positions.clear();
+                }
+                let position = positions.entry(entry.queue_id.clone()).or_insert(0);
+
+                let outcome = match task.status_enum() {
+                    TaskStatus::Completed => QueueOutcome::Completed,
+                    TaskStatus::Archived => QueueOutcome::Archived,
+                    _ => QueueOutcome::CarriedOver,
+                };
+                history.push(NewQueueHistoryEntry::new(
+                    entry.queue_date.clone(),
+                    task.id.clone(),
+                    task.title.clone(),
+                    *position,
+                    outcome,
+                ));
+                *position += 1;
+
+                if outcome == QueueOutcome::CarriedOver {
+                    carried.push((entry, task));
+                } else {
+                    finished_count += 1;
+                }
+            }
+
+            if !history.is_empty() {
+                diesel::insert_into(queue_history::table)
+                    .values(&history)
+                    .execute(conn)?;
+            }
+
+            diesel::delete(task_queue::table.filter(task_queue::queue_date.lt(&today)))
+                .execute(conn)?;
+
+            // 未完了タスクを同じキューの今日へ持ち越し（既存のエントリのrankは変更しない）
+            let insert_at = settings.carry_over_to_top.then_some(0);
+            let mut carried_by_queue: Vec<(&str, Vec<&QueueEntry>)> = Vec::new();
+            for (entry, _) in &carried {
+                match carried_by_queue
+                    .iter_mut()
+                    .find(|(queue_id, _)| *queue_id == entry.queue_id)
+                {
+                    Some((_, entries)) => entries.push(entry),
+                    None => carried_by_queue.push((&entry.queue_id, vec![entry])),
+                }
+            }
+
+            for (queue_id, entries) in carried_by_queue {
+                let new_ranks = QueueService::new_ranks(
+                    conn,
+                    queue_id,
+                    &today,
+                    insert_at,
+                    entries.len(),
+                    None,
+                )?;
+                for (entry, new_rank) in entries.into_iter().zip(new_ranks) {
+                    diesel::insert_into(task_queue::table)
+                        .values((
+                            task_queue::task_id.eq(&entry.task_id),
+                            task_queue::queue_id.eq(queue_id),
+                            task_queue::rank.eq(new_rank),
+                            task_queue::added_at.eq(&entry.added_at),
+                            task_queue::queue_date.eq(&today),
+                        ))
+                        .execute(conn)?;
+                }
+            }
+
+            let (restored_task_ids, released_task_ids) = Self::restore_deferred(conn, &today)?;
+
+            let activated_task_ids = Self::activate_today_queue(conn, &today)?;
+
+            // 持ち越し回数（今回を含む）
+            let carried_ids: Vec<&str> = carried.iter().map(|(e, _)| e.task_id.as_str()).collect();
+            let slip_counts: HashMap<String, i64> = queue_history::table
+                .filter(queue_history::outcome.eq(QueueOutcome::CarriedOver.as_str()))
+                .filter(queue_history::task_id.eq_any(&carried_ids))
+                .group_by(queue_history::task_id)
+                .select((queue_history::task_id, diesel::dsl::count_star()))
+                .load::<(String, i64)>(conn)?
+                .into_iter()
+                .collect();
+
+            let carried_over = carried
+                .into_iter()
+                .map(|(entry, task)| CarriedOverTask {
+                    slip_count: slip_counts.get(&task.id).copied().unwrap_or(0),
+                    task_id: task.id,
+                    task_title: task.title,
+                    from_date: entry.queue_date,
+                })
+                .collect();
+
+            SettingsService::set(conn, LAST_ROLLOVER_DATE_KEY, &today)?;
+
+            Ok(RolloverResult {
+                today,
+                rolled_over_dates,
+                carried_over,
+                finished_count,
+                activated_task_ids,
+                restored_task_ids,
+                released_task_ids,
+            })
+        })
+    }
+
+    /// 前回のロールオーバー以降に日付が変わっていればロールオーバーを実行
+    ///
+    /// # Returns
+    /// * `Ok(Some(RolloverResult))` - 実行した
+    /// * `Ok(None)` - 今日はすでに実行済み
+    pub fn rollover_if_due(
+        conn: &mut SqliteConnection,
+        now: NaiveDateTime,
+    ) -> Result<Option<RolloverResult>, ServiceError> {
+        let settings = SettingsService::get_rollover_settings(conn)?;
+        let today = date_key(QueueService::queue_day(now, settings.day_start_hour));
+
+        if SettingsService::get(conn, LAST_ROLLOVER_DATE_KEY)?.as_deref() == Some(today.as_str()) {
+            return Ok(None);
+        }
+
+        Self::rollover(conn, now).map(Some)
+    }
+
+    /// 持ち越されたことのあるタスクを持ち越し回数の多い順に取得
+    pub fn get_slipped_tasks(
+        conn: &mut SqliteConnection,
+    ) -> Result<Vec<TaskSlipCount>, ServiceError> {
+        let rows = queue_history::table
+            .filter(queue_history::outcome.eq(QueueOutcome::CarriedOver.as_str()))
+            .order((queue_history::queue_date.asc(), queue_history::id.asc()))
+            .select((
+                queue_history::task_id,
+                queue_history::task_title,
+                queue_history::queue_date,
+            ))
+            .load::<(String, String, String)>(conn)?;
+
+        let mut slips: HashMap<String, TaskSlipCount> = HashMap::new();
+        for (task_id, task_title, queue_date) in rows {
+            let slip = slips
+                .entry(task_id.clone())
+                .or_insert_with(|| TaskSlipCount {
+                    task_id,
+                    task_title: String::new(),
+                    slip_count: 0,
+                    last_slipped_date: String::new(),
+                });
+            slip.slip_count += 1;
+            slip.task_title = task_title;
+            slip.last_slipped_date = queue_date;
+        }
+
+        let mut slips: Vec<TaskSlipCount> = slips.into_values().collect();
+        slips.sort_by(|a, b| {
+            b.slip_count
+                .cmp(&a.slip_count)
+                .then_with(|| b.last_slipped_date.cmp(&a.last_slipped_date))
+                .then_with(|| a.task_id.cmp(&b.task_id))
+        });
+        Ok(slips)
+    }
+
+    /// 延期期限を迎えたタスクを延期元のキューの今日の末尾へ戻す
+    ///
+    /// # Returns
+    /// * `(戻したタスクID, キューへ戻せず延期だけ解除したタスクID)`
+    fn restore_deferred(
+        conn: &mut SqliteConnection,
+        today: &str,
+    ) -> Result<(Vec<String>, Vec<String>), ServiceError> {
+        let due = deferred_tasks::table
+            .inner_join(tasks::table)
+            .filter(deferred_tasks::until_date.le(today))
+            .order((
+                deferred_tasks::until_date.asc(),
+                deferred_tasks::deferred_at.asc(),
+            ))
+            .select((
+                deferred_tasks::task_id,
+                deferred_tasks::queue_id,
+                tasks::status,
+            ))
+            .load::<(String, String, String)>(conn)?;
+
+        if due.is_empty() {
+            return Ok((Vec::new(), Vec::new()));
+        }
+
+        let mut restored: Vec<(String, String)> = Vec::new();
+        let mut released = Vec::new();
+        for (task_id, queue_id, status) in due {
+            let queued = task_queue::table
+                .find(&task_id)
+                .count()
+                .get_result::<i64>(conn)?
+                > 0;
+            let open =
+                status == TaskStatus::Draft.as_str() || status == TaskStatus::Active.as_str();
+            let restorable = open && !queued && !TaskService::has_children(conn, &task_id)?;
+
+            if restorable {
+                restored.push((queue_id, task_id));
+            } else {
+                released.push(task_id);
+            }
+        }
+
+        // 1件ずつ末尾に追加するため、延期した順序が保たれる
+        let added_at = Utc::now().to_rfc3339();
+        for (queue_id, task_id) in &restored {
+            let new_rank = QueueService::new_ranks(conn, queue_id, today, None, 1, None)?.remove(0);
+            diesel::insert_into(task_queue::table)
+                .values((
+                    task_queue::task_id.eq(task_id),
+                    task_queue::queue_id.eq(queue_id),
+                    task_queue::rank.eq(new_rank),
+                    task_queue::added_at.eq(&added_at),
+                    task_queue::queue_date.eq(today),
+                ))
+                .execute(conn)?;
+        }
+        let restored: Vec<String> = restored.into_iter().map(|(_, task_id)| task_id).collect();
+
+        // プールの差分更新で再表示されるよう更新日時を更新
+        let all_ids: Vec<&String> = restored.iter().chain(released.iter()).collect();
+        diesel::update(tasks::table.filter(tasks::id.eq_any(&all_ids)))
+            .set(tasks::updated_at.eq(&added_at))
+            .execute(conn)?;
+        diesel::delete(deferred_tasks::table.filter(deferred_tasks::task_id.eq_any(&all_ids)))
+            .execute(conn)?;
+        for task_id in &all_ids {
+            TaskService::update_parent_status_if_needed(conn, task_id)?;
+        }
+
+        Ok((restored, released))
+    }
+
+    /// 今日のキューのDraftタスクをActiveに変更（今日のキューへの追加と同じ扱い）
+    fn activate_today_queue(
+        conn: &mut SqliteConnection,
+        today: &str,
+    ) -> Result<Vec<String>, ServiceError> {
+        let draft_ids: Vec<String> = task_queue::table
+            .inner_join(tasks::table)
+            .filter(task_queue::queue_date.eq(today))
+            .filter(tasks::status.eq(TaskStatus::Draft.as_str()))
+            .order(task_queue::rank.asc())
+            .select(task_queue::task_id)
+            .load::<String>(conn)?;
+
+        if draft_ids.is_empty() {
+            return Ok(draft_ids);
+        }
+
+        diesel::update(tasks::table.filter(tasks::id.eq_any(&draft_ids)))
+            .set((
+                tasks::status.eq(TaskStatus::Active.as_str()),
+                tasks::updated_at.eq(Utc::now().to_rfc3339()),
+            ))
+            .execute(conn)?;
+
+        for task_id in &draft_ids {
+            TaskService::update_parent_status_if_needed(conn, task_id)?;
+        }
+
+        Ok(draft_ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::queue::{DeferredTask, NewQueueEntry, DEFAULT_QUEUE_ID};
+    use crate::models::settings::RolloverSettings;
+    use crate::models::task::CreateTaskRequest;
+    use chrono::{Duration, Local, NaiveDate};
+    use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+
+    pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
+
+    fn setup_test_db() -> SqliteConnection {
+        let mut conn =
+            SqliteConnection::establish(":memory:").expect("Failed to create in-memory database");
+
+        conn.run_pending_migrations(MIGRATIONS)
+            .expect("Failed to run migrations");
+
+        conn
+    }
+
+    fn create_task(conn: &mut SqliteConnection, title: &str) -> String {
+        TaskService::create_task(
+            conn,
+            CreateTaskRequest {
+                title: title.to_string(),
+                description: None,
+                tags: vec![],
+                parent_id: None,
+                estimated_minutes: None,
+            },
+        )
+        .unwrap()
+        .id
+    }
+
+    /// 過去の日付のキューへ直接追加（add_to_queueは過去の日付を受け付けないため）
+    fn enqueue_on(conn: &mut SqliteConnection, task_id: &str, date: NaiveDate, position: i32) {
+        enqueue_in(conn, DEFAULT_QUEUE_ID, task_id, date, position);
+    }
+
+    fn enqueue_in(
+        conn: &mut SqliteConnection,
+        queue_id: &str,
+        task_id: &str,
+        date: NaiveDate,
+        position: i32,
+    ) {
+        diesel::insert_into(task_queue::table)
+            .values(&NewQueueEntry::new(
+                queue_id.to_string(),
+                task_id.to_string(),
+                format!("{:06}i", position),
+                date,
+            ))
+            .execute(conn)
+            .unwrap();
+    }
+
+    fn set_status(conn: &mut SqliteConnection, task_id: &str, status: TaskStatus) {
+        diesel::update(tasks::table.find(task_id))
+            .set(tasks::status.eq(status.as_str()))
+            .execute(conn)
+            .unwrap();
+    }
+
+    fn queue_titles(conn: &mut SqliteConnection, date: NaiveDate) -> Vec<String> {
+        QueueService::get_queue(conn, DEFAULT_QUEUE_ID, Some(date))
+            .unwrap()
+            .into_iter()
+            .map(|e| e.task_title)
+            .collect()
+    }
+
+    #[test]
+    fn test_queue_day_respects_day_start_hour() {
+        let early = NaiveDate::from_ymd_opt(2026, 1, 5)
+            .unwrap()
+            .and_hms_opt(3, 30, 0)
+            .unwrap();
+        assert_eq!(
+            QueueService::queue_day(early, 4),
+            NaiveDate::from_ymd_opt(2026, 1, 4).unwrap()
+        );
+        assert_eq!(
+            QueueService::queue_day(early, 0),
+            NaiveDate::from_ymd_opt(2026, 1, 5).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_rollover_carries_over_unfinished_and_records_history() {
+        let mut conn = setup_test_db();
+        let now = Local::now().naive_local();
+        let today = QueueService::queue_day(now, 0);
+        let yesterday = today - Duration::days(1);
+
+        let done = create_task(&mut conn, "Done");
+        let pending = create_task(&mut conn, "Pending");
+        let planned = create_task(&mut conn, "Planned today");
+        enqueue_on(&mut conn, &done, yesterday, 0);
+        enqueue_on(&mut conn, &pending, yesterday, 1);
+        enqueue_on(&mut conn, &planned, today, 0);
+        set_status(&mut conn, &done, TaskStatus::Completed);
+        set_status(&mut conn, &pending, TaskStatus::Active);
+
+        let result = RolloverService::rollover(&mut conn, now).unwrap();
+
+        assert_eq!(result.rolled_over_dates, vec![date_key(yesterday)]);
+        assert_eq!(result.finished_count, 1);
+        assert_eq!(result.carried_over.len(), 1);
+        assert_eq!(result.carried_over[0].task_id, pending);
+        assert_eq!(result.carried_over[0].slip_count, 1);
+        assert_eq!(result.activated_task_ids, vec![planned.clone()]);
+
+        // 持ち越しは末尾（デフォルト）、過去のキューは残らない
+        assert_eq!(
+            queue_titles(&mut conn, today),
+            vec!["Planned today", "Pending"]
+        );
+        assert!(queue_titles(&mut conn, yesterday).is_empty());
+
+        let outcomes: Vec<String> = queue_history::table
+            .order(queue_history::position.asc())
+            .select(queue_history::outcome)
+            .load(&mut conn)
+            .unwrap();
+        assert_eq!(outcomes, vec!["completed", "carried_over"]);
+
+        // 2回目は何もしない
+        let result = RolloverService::rollover(&mut conn, now).unwrap();
+        assert!(result.rolled_over_dates.is_empty());
+        assert!(result.carried_over.is_empty());
+    }
+
+    #[test]
+    fn test_rollover_carry_over_to_top_keeps_order() {
+        let mut conn = setup_test_db();
+        SettingsService::update_rollover_settings(
+            &mut conn,
+            RolloverSettings {
+                day_start_hour: 0,
+                carry_over_to_top: true,
+            },
+        )
+        .unwrap();
+        let now = Local::now().naive_local();
+        let today = QueueService::queue_day(now, 0);
+        let two_days_ago = today - Duration::days(2);
+        let yesterday = today - Duration::days(1);
+
+        let old = create_task(&mut conn, "Old");
+        let recent = create_task(&mut conn, "Recent");
+        let planned = create_task(&mut conn, "Planned");
+        enqueue_on(&mut conn, &old, two_days_ago, 0);
+        enqueue_on(&mut conn, &recent, yesterday, 0);
+        enqueue_on(&mut conn, &planned, today, 0);
+
+        let result = RolloverService::rollover(&mut conn, now).unwrap();
+
+        assert_eq!(result.rolled_over_dates.len(), 2);
+        assert_eq!(
+            queue_titles(&mut conn, today),
+            vec!["Old", "Recent", "Planned"]
+        );
+        let positions: Vec<i32> = QueueService::get_queue(&mut conn, DEFAULT_QUEUE_ID, Some(today))
+            .unwrap()
+            .iter()
+            .map(|e| e.position)
+            .collect();
+        assert_eq!(positions, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_slip_counts_accumulate_across_days() {
+        let mut conn = setup_test_db();
+        let now = Local::now().naive_local();
+        let today = QueueService::queue_day(now, 0);
+        let task_id = create_task(&mut conn, "Procrastinated");
+
+        // 2日前のキューから昨日へ、昨日から今日へ持ち越される
+        enqueue_on(&mut conn, &task_id, today - Duration::days(2), 0);
+        RolloverService::rollover(&mut conn, now - Duration::days(1)).unwrap();
+        let result = RolloverService::rollover(&mut conn, now).unwrap();
+
+        assert_eq!(result.carried_over[0].slip_count, 2);
+
+        let slipped = RolloverService::get_slipped_tasks(&mut conn).unwrap();
+        assert_eq!(slipped.len(), 1);
+        assert_eq!(slipped[0].task_id, task_id);
+        assert_eq!(slipped[0].slip_count, 2);
+        assert_eq!(
+            slipped[0].last_slipped_date,
+            date_key(today - Duration::days(1))
+        );
+    }
+
+    #[test]
+    fn test_rollover_if_due_runs_once_per_day() {
+        let mut conn = setup_test_db();
+        let now = Local::now().naive_local();
+
+        assert!(RolloverService::rollover_if_due(&mut conn, now)
+            .unwrap()
+            .is_some());
+        assert!(RolloverService::rollover_if_due(&mut conn, now)
+            .unwrap()
+            .is_none());
+        assert!(
+            RolloverService::rollover_if_due(&mut conn, now + Duration::days(1))
+                .unwrap()
+                .is_some()
+        );
+    }
+
+    /// 延期情報を直接登録（defer_queue_entryは今日以前の日付を受け付けないため）
+    fn defer_until(conn: &mut SqliteConnection, task_id: &str, until: NaiveDate) {
+        diesel::insert_into(deferred_tasks::table)
+            .values(&DeferredTask {
+                task_id: task_id.to_string(),
+                queue_id: DEFAULT_QUEUE_ID.to_string(),
+                until_date: date_key(until),
+                deferred_from: date_key(until - Duration::days(1)),
+                deferred_at: Utc::now().to_rfc3339(),
+            })
+            .execute(conn)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_rollover_restores_due_deferred_tasks() {
+        let mut conn = setup_test_db();
+        let now = Local::now().naive_local();
+        let today = QueueService::queue_day(now, 0);
+
+        let planned = create_task(&mut conn, "Planned today");
+        let due = create_task(&mut conn, "Due today");
+        let completed = create_task(&mut conn, "Completed meanwhile");
+        let later = create_task(&mut conn, "Not yet");
+        enqueue_on(&mut conn, &planned, today, 0);
+        defer_until(&mut conn, &due, today);
+        defer_until(&mut conn, &completed, today - Duration::days(1));
+        defer_until(&mut conn, &later, today + Duration::days(1));
+        set_status(&mut conn, &completed, TaskStatus::Completed);
+
+        let result = RolloverService::rollover(&mut conn, now).unwrap();
+
+        // 期限を迎えたタスクは末尾に戻り、Activeになる
+        assert_eq!(result.restored_task_ids, vec![due.clone()]);
+        assert_eq!(result.released_task_ids, vec![completed]);
+        assert_eq!(
+            queue_titles(&mut conn, today),
+            vec!["Planned today", "Due today"]
+        );
+        let task = TaskService::get_task(&mut conn, &due).unwrap();
+        assert_eq!(task.status, TaskStatus::Active);
+
+        // 期限前のタスクは延期されたまま
+        let remaining: Vec<String> = QueueService::get_deferred_tasks(&mut conn, DEFAULT_QUEUE_ID)
+            .unwrap()
+            .into_iter()
+            .map(|d| d.task_id)
+            .collect();
+        assert_eq!(remaining, vec![later]);
+    }
+
+    #[test]
+    fn test_rollover_keeps_entries_in_their_queue() {
+        let mut conn = setup_test_db();
+        let now = Local::now().naive_local();
+        let today = QueueService::queue_day(now, 0);
+        let yesterday = today - Duration::days(1);
+        let home = QueueService::create_queue(&mut conn, "Home".to_string()).unwrap();
+
+        let work_task = create_task(&mut conn, "Work task");
+        let home_task = create_task(&mut conn, "Home task");
+        let home_planned = create_task(&mut conn, "Home planned");
+        enqueue_on(&mut conn, &work_task, yesterday, 0);
+        enqueue_in(&mut conn, &home.id, &home_task, yesterday, 0);
+        enqueue_in(&mut conn, &home.id, &home_planned, today, 0);
+
+        RolloverService::rollover(&mut conn, now).unwrap();
+
+        assert_eq!(queue_titles(&mut conn, today), vec!["Work task"]);
+        let home_titles: Vec<String> = QueueService::get_queue(&mut conn, &home.id, Some(today))
+            .unwrap()
+            .into_iter()
+            .map(|e| e.task_title)
+            .collect();
+        assert_eq!(home_titles, vec!["Home planned", "Home task"]);
+
+        // 履歴の位置はキューごとに数える
+        let positions: Vec<i32> = queue_history::table
+            .order(queue_history::task_title.asc())
+            .select(queue_history::position)
+            .load(&mut conn)
+            .unwrap();
+        assert_eq!(positions, vec![0, 0]);
+    }
+}
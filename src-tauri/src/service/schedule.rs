@@ -0,0 +1,251 @@
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use diesel::SqliteConnection;
+
+use crate::error::ServiceError;
+use crate::models::queue::{QueueSchedule, SCHEDULE_TIME_FORMAT};
+use crate::models::settings::WorkdaySettings;
+use crate::service::queue::{date_key, QueueService};
+use crate::service::settings::SettingsService;
+
+/// ScheduleService: 見積もり時間に基づくキューの予定計算
+///
+/// キューの先頭から順に見積もり時間を積み上げ、各エントリの予定開始・終了時刻を求める。
+/// 見積もり未設定のエントリは開始時刻のみ設定し、後続の予定をずらさない。
+pub struct ScheduleService;
+
+impl ScheduleService {
+    /// 指定日のキューを予定付きで取得
+    ///
+    /// # Arguments
+    /// * `conn` - データベース接続
+    /// * `queue_id` - キューID
+    /// * `queue_date` - 対象日（Noneの場合は今日）
+    /// * `now` - 現在時刻（ローカル）
+    ///
+    /// # Business Logic
+    /// - 今日のキュー: 現在時刻（作業開始時刻が設定されていてそれより前なら作業開始時刻）から計算
+    /// - 先の日付のキュー: 作業開始時刻（未設定の場合は1日の開始時刻）から計算
+    /// - 過去の日付のキュー: 予定時刻は計算しない
+    /// - 見積もり合計が1日の作業時間を超える場合は overloaded = true
+    pub fn get_schedule(
+        conn: &mut SqliteConnection,
+        queue_id: &str,
+        queue_date: Option<NaiveDate>,
+        now: NaiveDateTime,
+    ) -> Result<QueueSchedule, ServiceError> {
+        let day_start_hour = SettingsService::get_rollover_settings(conn)?.day_start_hour;
+        let workday = SettingsService::get_workday_settings(conn)?;
+        let today = QueueService::queue_day(now, day_start_hour);
+        let queue_date = queue_date.unwrap_or(today);
+
+        let mut entries = QueueService::get_queue(conn, queue_id, Some(queue_date))?;
+
+        let mut cursor = Self::schedule_start(queue_date, today, now, &workday, day_start_hour)?;
+        let mut total_estimated_minutes: i64 = 0;
+        let mut unestimated_count = 0;
+
+        for entry in &mut entries {
+            let estimate = entry.estimated_minutes.map(i64::from);
+            match estimate {
+                Some(minutes) => total_estimated_minutes += minutes,
+                None => unestimated_count += 1,
+            }
+
+            if let Some(start) = cursor {
+                entry.scheduled_start = Some(start.format(SCHEDULE_TIME_FORMAT).to_string());
+                if let Some(minutes) = estimate {
+                    let end = start + Duration::minutes(minutes);
+                    entry.scheduled_end = Some(end.format(SCHEDULE_TIME_FORMAT).to_string());
+                    cursor = Some(end);
+                }
+            }
+        }
+
+        let projected_end = entries
+            .iter()
+            .rev()
+            .find_map(|entry| entry.scheduled_end.clone());
+        let overload_minutes =
+            (total_estimated_minutes - i64::from(workday.working_minutes)).max(0);
+
+        Ok(QueueSchedule {
+            queue_id: queue_id.to_string(),
+            queue_date: date_key(queue_date),
+            entries,
+            total_estimated_minutes,
+            unestimated_count,
+            working_minutes: workday.working_minutes,
+            overloaded: overload_minutes > 0,
+            overload_minutes,
+            projected_end,
+        })
+    }
+
+    /// 予定の開始時刻を計算（過去の日付はNone）
+    fn schedule_start(
+        queue_date: NaiveDate,
+        today: NaiveDate,
+        now: NaiveDateTime,
+        workday: &WorkdaySettings,
+        day_start_hour: u32,
+    ) -> Result<Option<NaiveDateTime>, ServiceError> {
+        if queue_date < today {
+            return Ok(None);
+        }
+
+        let workday_start = match workday.workday_start {
+            Some(ref start) => {
+                let time = SettingsService::parse_workday_start(start)?;
+                let mut start = queue_date.and_time(time);
+                // 1日の開始時刻より前の作業開始時刻は翌日の暦日として扱う
+                if time.hour() < day_start_hour {
+                    start += Duration::days(1);
+                }
+                Some(start)
+            }
+            None => None,
+        };
+
+        let start = if queue_date == today {
+            match workday_start {
+                Some(start) => start.max(now),
+                None => now,
+            }
+        } else {
+            workday_start.unwrap_or_else(|| {
+                queue_date.and_time(NaiveTime::MIN) + Duration::hours(i64::from(day_start_hour))
+            })
+        };
+
+        Ok(Some(start))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::queue::DEFAULT_QUEUE_ID;
+    use crate::models::task::CreateTaskRequest;
+    use crate::service::TaskService;
+    use diesel::Connection;
+    use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+
+    pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
+
+    fn setup_test_db() -> SqliteConnection {
+        let mut conn =
+            SqliteConnection::establish(":memory:").expect("Failed to create in-memory database");
+
+        conn.run_pending_migrations(MIGRATIONS)
+            .expect("Failed to run migrations");
+
+        conn
+    }
+
+    fn enqueue(
+        conn: &mut SqliteConnection,
+        title: &str,
+        estimated_minutes: Option<i32>,
+        queue_date: NaiveDate,
+    ) -> String {
+        let task = TaskService::create_task(
+            conn,
+            CreateTaskRequest {
+                title: title.to_string(),
+                description: None,
+                tags: vec![],
+                parent_id: None,
+                estimated_minutes,
+            },
+        )
+        .unwrap();
+        QueueService::add_to_queue(
+            conn,
+            DEFAULT_QUEUE_ID,
+            task.id.clone(),
+            Some(queue_date),
+            false,
+        )
+        .unwrap();
+        task.id
+    }
+
+    fn at(date: NaiveDate, hour: u32, minute: u32) -> NaiveDateTime {
+        date.and_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_schedule_from_now() {
+        let mut conn = setup_test_db();
+        let today = QueueService::today(&mut conn).unwrap();
+        enqueue(&mut conn, "A", Some(30), today);
+        enqueue(&mut conn, "B", None, today);
+        enqueue(&mut conn, "C", Some(45), today);
+
+        let schedule =
+            ScheduleService::get_schedule(&mut conn, DEFAULT_QUEUE_ID, None, at(today, 10, 0))
+                .unwrap();
+
+        let times: Vec<(Option<String>, Option<String>)> = schedule
+            .entries
+            .iter()
+            .map(|e| (e.scheduled_start.clone(), e.scheduled_end.clone()))
+            .collect();
+        let fmt = |h, m| Some(at(today, h, m).format(SCHEDULE_TIME_FORMAT).to_string());
+        assert_eq!(
+            times,
+            vec![
+                (fmt(10, 0), fmt(10, 30)),
+                (fmt(10, 30), None), // 見積もり未設定は後続をずらさない
+                (fmt(10, 30), fmt(11, 15)),
+            ]
+        );
+        assert_eq!(schedule.total_estimated_minutes, 75);
+        assert_eq!(schedule.unestimated_count, 1);
+        assert_eq!(schedule.projected_end, fmt(11, 15));
+        assert!(!schedule.overloaded);
+    }
+
+    #[test]
+    fn test_schedule_uses_workday_start_and_warns_on_overload() {
+        let mut conn = setup_test_db();
+        SettingsService::update_workday_settings(
+            &mut conn,
+            WorkdaySettings {
+                workday_start: Some("09:00".to_string()),
+                working_minutes: 60,
+            },
+        )
+        .unwrap();
+        let today = QueueService::today(&mut conn).unwrap();
+        let tomorrow = today + Duration::days(1);
+        enqueue(&mut conn, "Today", Some(30), today);
+        enqueue(&mut conn, "Tomorrow 1", Some(50), tomorrow);
+        enqueue(&mut conn, "Tomorrow 2", Some(40), tomorrow);
+
+        // 作業開始前なら作業開始時刻から
+        let early =
+            ScheduleService::get_schedule(&mut conn, DEFAULT_QUEUE_ID, None, at(today, 7, 0))
+                .unwrap();
+        assert_eq!(
+            early.entries[0].scheduled_start,
+            Some(at(today, 9, 0).format(SCHEDULE_TIME_FORMAT).to_string())
+        );
+
+        // 先の日付は作業開始時刻から、作業時間を超えた分を警告
+        let planned = ScheduleService::get_schedule(
+            &mut conn,
+            DEFAULT_QUEUE_ID,
+            Some(tomorrow),
+            at(today, 7, 0),
+        )
+        .unwrap();
+        assert_eq!(
+            planned.entries[0].scheduled_start,
+            Some(at(tomorrow, 9, 0).format(SCHEDULE_TIME_FORMAT).to_string())
+        );
+        assert_eq!(planned.total_estimated_minutes, 90);
+        assert!(planned.overloaded);
+        assert_eq!(planned.overload_minutes, 30);
+    }
+}
@@ -0,0 +1,445 @@
+use chrono::{NaiveTime, Utc};
+use diesel::prelude::*;
+use diesel::SqliteConnection;
+
+use crate::error::ServiceError;
+use crate::models::settings::{FocusSettings, QueueLimits, RolloverSettings, WorkdaySettings};
+use crate::schema::app_settings;
+
+/// 設定キー: 1日の開始時刻
+const DAY_START_HOUR_KEY: &str = "day_start_hour";
+
+/// 設定キー: 持ち越しタスクをキュー先頭に入れるか
+const CARRY_OVER_TO_TOP_KEY: &str = "carry_over_to_top";
+
+/// 設定キー: 作業開始時刻（HH:MM）
+const WORKDAY_START_KEY: &str = "workday_start";
+
+/// 設定キー: 1日の作業時間（分）
+const WORKING_MINUTES_KEY: &str = "working_minutes";
+
+/// 作業開始時刻の文字列形式
+const WORKDAY_START_FORMAT: &str = "%H:%M";
+
+/// 1日の作業時間の上限（分）
+const MAX_WORKING_MINUTES: u32 = 24 * 60;
+
+/// 設定キー: 集中セッションの作業時間（分）
+const FOCUS_WORK_MINUTES_KEY: &str = "focus_work_minutes";
+
+/// 設定キー: 集中セッションの休憩時間（分）
+const FOCUS_BREAK_MINUTES_KEY: &str = "focus_break_minutes";
+
+/// 集中セッションの作業時間の上限（分）
+const MAX_FOCUS_WORK_MINUTES: u32 = 240;
+
+/// 集中セッションの休憩時間の上限（分）
+const MAX_FOCUS_BREAK_MINUTES: u32 = 120;
+
+/// 設定キー: キューの最大エントリ数
+const QUEUE_MAX_ENTRIES_KEY: &str = "queue_max_entries";
+
+/// 設定キー: キューの見積もり合計の上限（分）
+const QUEUE_MAX_TOTAL_MINUTES_KEY: &str = "queue_max_total_minutes";
+
+/// 設定キー: タグごとのキュー上限（JSON: タグ名 → 上限）
+const QUEUE_TAG_CAPS_KEY: &str = "queue_tag_caps";
+
+/// 設定キー: 最後にロールオーバーを実行した日（YYYY-MM-DD）
+pub(crate) const LAST_ROLLOVER_DATE_KEY: &str = "last_rollover_date";
+
+/// 設定キー: アプリの起動中か（正常終了時にfalseへ戻す。起動時にtrueなら前回は異常終了）
+pub(crate) const SESSION_OPEN_KEY: &str = "session_open";
+
+/// SettingsService: アプリ設定（app_settingsテーブルのキー・値）の読み書き
+pub struct SettingsService;
+
+impl SettingsService {
+    /// 設定値を取得
+    ///
+    /// # Returns
+    /// * `Ok(Some(String))` - 設定値
+    /// * `Ok(None)` - 未設定
+    pub fn get(conn: &mut SqliteConnection, key: &str) -> Result<Option<String>, ServiceError> {
+        let value = app_settings::table
+            .find(key)
+            .select(app_settings::value)
+            .first::<String>(conn)
+            .optional()?;
+        Ok(value)
+    }
+
+    /// 設定値を保存（存在する場合は上書き）
+    pub fn set(conn: &mut SqliteConnection, key: &str, value: &str) -> Result<(), ServiceError> {
+        let now = Utc::now().to_rfc3339();
+        diesel::insert_into(app_settings::table)
+            .values((
+                app_settings::key.eq(key),
+                app_settings::value.eq(value),
+                app_settings::updated_at.eq(&now),
+            ))
+            .on_conflict(app_settings::key)
+            .do_update()
+            .set((
+                app_settings::value.eq(value),
+                app_settings::updated_at.eq(&now),
+            ))
+            .execute(conn)?;
+        Ok(())
+    }
+
+    /// ロールオーバー設定を取得（未設定・不正値の項目はデフォルト値）
+    pub fn get_rollover_settings(
+        conn: &mut SqliteConnection,
+    ) -> Result<RolloverSettings, ServiceError> {
+        let defaults = RolloverSettings::default();
+
+        let day_start_hour = Self::get(conn, DAY_START_HOUR_KEY)?
+            .and_then(|v| v.parse::<u32>().ok())
+            .filter(|hour| *hour < 24)
+            .unwrap_or(defaults.day_start_hour);
+
+        let carry_over_to_top = Self::get(conn, CARRY_OVER_TO_TOP_KEY)?
+            .and_then(|v| v.parse::<bool>().ok())
+            .unwrap_or(defaults.carry_over_to_top);
+
+        Ok(RolloverSettings {
+            day_start_hour,
+            carry_over_to_top,
+        })
+    }
+
+    /// ロールオーバー設定を更新
+    ///
+    /// # Returns
+    /// * `Ok(RolloverSettings)` - 更新後の設定
+    /// * `Err(ServiceError::InvalidInput)` - day_start_hourが0〜23の範囲外
+    pub fn update_rollover_settings(
+        conn: &mut SqliteConnection,
+        settings: RolloverSettings,
+    ) -> Result<RolloverSettings, ServiceError> {
+        if settings.day_start_hour > 23 {
+            return Err(ServiceError::InvalidInput(
+                "1日の開始時刻は0〜23の範囲で指定してください".to_string(),
+            ));
+        }
+
+        conn.transaction::<_, ServiceError, _>(|conn| {
+            Self::set(
+                conn,
+                DAY_START_HOUR_KEY,
+                &settings.day_start_hour.to_string(),
+            )?;
+            Self::set(
+                conn,
+                CARRY_OVER_TO_TOP_KEY,
+                &settings.carry_over_to_top.to_string(),
+            )?;
+            Ok(())
+        })?;
+
+        Ok(settings)
+    }
+
+    /// 作業時間設定を取得（未設定・不正値の項目はデフォルト値）
+    pub fn get_workday_settings(
+        conn: &mut SqliteConnection,
+    ) -> Result<WorkdaySettings, ServiceError> {
+        let defaults = WorkdaySettings::default();
+
+        let workday_start =
+            Self::get(conn, WORKDAY_START_KEY)?.filter(|v| Self::parse_workday_start(v).is_ok());
+
+        let working_minutes = Self::get(conn, WORKING_MINUTES_KEY)?
+            .and_then(|v| v.parse::<u32>().ok())
+            .filter(|minutes| (1..=MAX_WORKING_MINUTES).contains(minutes))
+            .unwrap_or(defaults.working_minutes);
+
+        Ok(WorkdaySettings {
+            workday_start,
+            working_minutes,
+        })
+    }
+
+    /// 作業時間設定を更新
+    ///
+    /// # Returns
+    /// * `Ok(WorkdaySettings)` - 更新後の設定
+    /// * `Err(ServiceError::InvalidInput)` - 作業開始時刻の形式が不正、または作業時間が1〜1440分の範囲外
+    pub fn update_workday_settings(
+        conn: &mut SqliteConnection,
+        settings: WorkdaySettings,
+    ) -> Result<WorkdaySettings, ServiceError> {
+        if let Some(ref start) = settings.workday_start {
+            Self::parse_workday_start(start)?;
+        }
+        if !(1..=MAX_WORKING_MINUTES).contains(&settings.working_minutes) {
+            return Err(ServiceError::InvalidInput(
+                "1日の作業時間は1〜1440分の範囲で指定してください".to_string(),
+            ));
+        }
+
+        conn.transaction::<_, ServiceError, _>(|conn| {
+            Self::set_or_clear(conn, WORKDAY_START_KEY, settings.workday_start.clone())?;
+            Self::set(
+                conn,
+                WORKING_MINUTES_KEY,
+                &settings.working_minutes.to_string(),
+            )?;
+            Ok(())
+        })?;
+
+        Ok(settings)
+    }
+
+    /// 集中セッション設定を取得（未設定・不正値の項目はデフォルト値）
+    pub fn get_focus_settings(conn: &mut SqliteConnection) -> Result<FocusSettings, ServiceError> {
+        let defaults = FocusSettings::default();
+
+        let work_minutes = Self::get(conn, FOCUS_WORK_MINUTES_KEY)?
+            .and_then(|v| v.parse::<u32>().ok())
+            .filter(|minutes| (1..=MAX_FOCUS_WORK_MINUTES).contains(minutes))
+            .unwrap_or(defaults.work_minutes);
+
+        let break_minutes = Self::get(conn, FOCUS_BREAK_MINUTES_KEY)?
+            .and_then(|v| v.parse::<u32>().ok())
+            .filter(|minutes| *minutes <= MAX_FOCUS_BREAK_MINUTES)
+            .unwrap_or(defaults.break_minutes);
+
+        Ok(FocusSettings {
+            work_minutes,
+            break_minutes,
+        })
+    }
+
+    /// 集中セッション設定を更新
+    ///
+    /// # Returns
+    /// * `Ok(FocusSettings)` - 更新後の設定
+    /// * `Err(ServiceError::InvalidInput)` - 作業時間が1〜240分、休憩時間が0〜120分の範囲外
+    pub fn update_focus_settings(
+        conn: &mut SqliteConnection,
+        settings: FocusSettings,
+    ) -> Result<FocusSettings, ServiceError> {
+        if !(1..=MAX_FOCUS_WORK_MINUTES).contains(&settings.work_minutes) {
+            return Err(ServiceError::InvalidInput(
+                "作業時間は1〜240分の範囲で指定してください".to_string(),
+            ));
+        }
+        if settings.break_minutes > MAX_FOCUS_BREAK_MINUTES {
+            return Err(ServiceError::InvalidInput(
+                "休憩時間は0〜120分の範囲で指定してください".to_string(),
+            ));
+        }
+
+        conn.transaction::<_, ServiceError, _>(|conn| {
+            Self::set(
+                conn,
+                FOCUS_WORK_MINUTES_KEY,
+                &settings.work_minutes.to_string(),
+            )?;
+            Self::set(
+                conn,
+                FOCUS_BREAK_MINUTES_KEY,
+                &settings.break_minutes.to_string(),
+            )?;
+            Ok(())
+        })?;
+
+        Ok(settings)
+    }
+
+    /// キューの上限（WIP制限）設定を取得（未設定・不正値の項目は無制限）
+    pub fn get_queue_limits(conn: &mut SqliteConnection) -> Result<QueueLimits, ServiceError> {
+        let max_entries = Self::get(conn, QUEUE_MAX_ENTRIES_KEY)?
+            .and_then(|v| v.parse::<u32>().ok())
+            .filter(|max| *max > 0);
+
+        let max_total_minutes = Self::get(conn, QUEUE_MAX_TOTAL_MINUTES_KEY)?
+            .and_then(|v| v.parse::<u32>().ok())
+            .filter(|max| *max > 0);
+
+        let tag_caps = Self::get(conn, QUEUE_TAG_CAPS_KEY)?
+            .and_then(|v| serde_json::from_str(&v).ok())
+            .unwrap_or_default();
+
+        Ok(QueueLimits {
+            max_entries,
+            max_total_minutes,
+            tag_caps,
+        })
+    }
+
+    /// キューの上限（WIP制限）設定を更新
+    ///
+    /// # Returns
+    /// * `Ok(QueueLimits)` - 更新後の設定
+    /// * `Err(ServiceError::InvalidInput)` - 上限に0が指定された、またはタグ名が空
+    pub fn update_queue_limits(
+        conn: &mut SqliteConnection,
+        limits: QueueLimits,
+    ) -> Result<QueueLimits, ServiceError> {
+        if limits.max_entries == Some(0) || limits.max_total_minutes == Some(0) {
+            return Err(ServiceError::InvalidInput(
+                "キューの上限は1以上で指定してください".to_string(),
+            ));
+        }
+        if limits.tag_caps.values().any(|cap| *cap == 0) {
+            return Err(ServiceError::InvalidInput(
+                "タグごとの上限は1以上で指定してください".to_string(),
+            ));
+        }
+        if limits.tag_caps.keys().any(|tag| tag.trim().is_empty()) {
+            return Err(ServiceError::InvalidInput("タグ名は必須です".to_string()));
+        }
+
+        let tag_caps = serde_json::to_string(&limits.tag_caps)
+            .map_err(|e| ServiceError::InternalError(e.to_string()))?;
+
+        conn.transaction::<_, ServiceError, _>(|conn| {
+            Self::set_or_clear(
+                conn,
+                QUEUE_MAX_ENTRIES_KEY,
+                limits.max_entries.map(|v| v.to_string()),
+            )?;
+            Self::set_or_clear(
+                conn,
+                QUEUE_MAX_TOTAL_MINUTES_KEY,
+                limits.max_total_minutes.map(|v| v.to_string()),
+            )?;
+            Self::set(conn, QUEUE_TAG_CAPS_KEY, &tag_caps)?;
+            Ok(())
+        })?;
+
+        Ok(limits)
+    }
+
+    /// 値がある場合は保存し、Noneの場合は設定を削除
+    fn set_or_clear(
+        conn: &mut SqliteConnection,
+        key: &str,
+        value: Option<String>,
+    ) -> Result<(), ServiceError> {
+        match value {
+            Some(value) => Self::set(conn, key, &value),
+            None => {
+                diesel::delete(app_settings::table.find(key)).execute(conn)?;
+                Ok(())
+            }
+        }
+    }
+
+    /// 作業開始時刻の文字列（HH:MM）を解析
+    ///
+    /// # Returns
+    /// * `Ok(NaiveTime)` - 解析された時刻
+    /// * `Err(ServiceError::InvalidInput)` - 形式が不正
+    pub fn parse_workday_start(value: &str) -> Result<NaiveTime, ServiceError> {
+        NaiveTime::parse_from_str(value, WORKDAY_START_FORMAT).map_err(|_| {
+            ServiceError::InvalidInput(format!("作業開始時刻の形式が不正です（HH:MM）: {}", value))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+
+    pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
+
+    fn setup_test_db() -> SqliteConnection {
+        let mut conn =
+            SqliteConnection::establish(":memory:").expect("Failed to create in-memory database");
+
+        conn.run_pending_migrations(MIGRATIONS)
+            .expect("Failed to run migrations");
+
+        conn
+    }
+
+    #[test]
+    fn test_rollover_settings_default_and_update() {
+        let mut conn = setup_test_db();
+
+        let settings = SettingsService::get_rollover_settings(&mut conn).unwrap();
+        assert_eq!(settings, RolloverSettings::default());
+
+        let updated = RolloverSettings {
+            day_start_hour: 4,
+            carry_over_to_top: true,
+        };
+        SettingsService::update_rollover_settings(&mut conn, updated).unwrap();
+        assert_eq!(
+            SettingsService::get_rollover_settings(&mut conn).unwrap(),
+            updated
+        );
+    }
+
+    #[test]
+    fn test_update_rollover_settings_invalid_hour() {
+        let mut conn = setup_test_db();
+
+        let result = SettingsService::update_rollover_settings(
+            &mut conn,
+            RolloverSettings {
+                day_start_hour: 24,
+                carry_over_to_top: false,
+            },
+        );
+        assert!(matches!(result, Err(ServiceError::InvalidInput(_))));
+    }
+    #[test]
+    fn test_workday_settings_update_and_clear_start() {
+        let mut conn = setup_test_db();
+
+        assert_eq!(
+            SettingsService::get_workday_settings(&mut conn).unwrap(),
+            WorkdaySettings::default()
+        );
+
+        let updated = WorkdaySettings {
+            workday_start: Some("09:30".to_string()),
+            working_minutes: 360,
+        };
+        SettingsService::update_workday_settings(&mut conn, updated.clone()).unwrap();
+        assert_eq!(
+            SettingsService::get_workday_settings(&mut conn).unwrap(),
+            updated
+        );
+
+        // 開始時刻を未設定に戻す
+        let cleared = WorkdaySettings {
+            workday_start: None,
+            working_minutes: 360,
+        };
+        SettingsService::update_workday_settings(&mut conn, cleared.clone()).unwrap();
+        assert_eq!(
+            SettingsService::get_workday_settings(&mut conn).unwrap(),
+            cleared
+        );
+    }
+
+    #[test]
+    fn test_update_workday_settings_invalid() {
+        let mut conn = setup_test_db();
+
+        let bad_start = SettingsService::update_workday_settings(
+            &mut conn,
+            WorkdaySettings {
+                workday_start: Some("25:00".to_string()),
+                working_minutes: 480,
+            },
+        );
+        assert!(matches!(bad_start, Err(ServiceError::InvalidInput(_))));
+
+        let bad_minutes = SettingsService::update_workday_settings(
+            &mut conn,
+            WorkdaySettings {
+                workday_start: None,
+                working_minutes: 0,
+            },
+        );
+        assert!(matches!(bad_minutes, Err(ServiceError::InvalidInput(_))));
+    }
+}
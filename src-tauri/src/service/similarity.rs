@@ -0,0 +1,374 @@
+use std::collections::HashMap;
+
+use diesel::prelude::*;
+use diesel::SqliteConnection;
+
+use crate::error::ServiceError;
+use crate::models::similarity::SimilarTask;
+use crate::models::task::{Task, TaskStatus};
+use crate::schema::tasks;
+
+/// 検索結果のデフォルト件数
+const DEFAULT_LIMIT: usize = 10;
+
+/// 検索結果の最大件数
+const MAX_LIMIT: usize = 50;
+
+/// 類似タスクとみなす最低スコア
+const SIMILAR_THRESHOLD: f64 = 0.2;
+
+/// 重複候補とみなす最低スコア
+const DUPLICATE_THRESHOLD: f64 = 0.5;
+
+/// 重複候補として返す最大件数
+const DUPLICATE_LIMIT: usize = 5;
+
+/// タイトル中の語の重み（説明文の語に対する倍率）
+const TITLE_WEIGHT: f64 = 2.0;
+
+/// 語ごとの出現頻度（重み付き）
+type TermCounts = HashMap<String, f64>;
+
+/// SimilarityService: 類似・重複タスクの検出
+///
+/// タイトルと説明文をトークン化し、TF-IDFベクトルのコサイン類似度で比較する。
+/// 英数字は単語単位、CJK文字（かな・漢字・ハングル）は文字bigramで分割するため、
+/// 分かち書きのない日本語でも類似度を計算できる。外部通信は行わない。
+pub struct SimilarityService;
+
+impl SimilarityService {
+    /// 類似タスクを検索
+    ///
+    /// # Arguments
+    /// * `conn` - データベース接続
+    /// * `task_id` - 基準とするタスクID（指定時はtextより優先）
+    /// * `text` - 基準とするテキスト
+    /// * `limit` - 最大件数（デフォルト: 10、最大: 50）
+    ///
+    /// # Returns
+    /// * `Ok(Vec<SimilarTask>)` - 類似度の高い順（基準タスク自身とArchivedタスクは除外）
+    /// * `Err(ServiceError::InvalidInput)` - task_id・textのどちらも指定されていない
+    /// * `Err(ServiceError::TaskNotFound)` - task_idのタスクが存在しない
+    pub fn find_similar_tasks(
+        conn: &mut SqliteConnection,
+        task_id: Option<&str>,
+        text: Option<&str>,
+        limit: Option<usize>,
+    ) -> Result<Vec<SimilarTask>, ServiceError> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+        if let Some(task_id) = task_id {
+            let task = tasks::table
+                .find(task_id)
+                .first::<Task>(conn)
+                .optional()?
+                .ok_or_else(|| ServiceError::TaskNotFound(task_id.to_string()))?;
+
+            let query = term_counts(&task.title, task.description.as_deref());
+            return Self::rank(conn, &query, Some(task_id), SIMILAR_THRESHOLD, limit);
+        }
+
+        match text.map(str::trim).filter(|t| !t.is_empty()) {
+            Some(text) => {
+                let query = term_counts(text, None);
+                Self::rank(conn, &query, None, SIMILAR_THRESHOLD, limit)
+            }
+            None => Err(ServiceError::InvalidInput(
+                "task_idまたはtextを指定してください".to_string(),
+            )),
+        }
+    }
+
+    /// 重複の可能性が高いタスクを検出（タスク作成時の警告用）
+    ///
+    /// # Arguments
+    /// * `conn` - データベース接続
+    /// * `title` - タイトル
+    /// * `description` - 説明文
+    /// * `exclude_task_id` - 比較対象から除外するタスクID（作成直後のタスク自身など）
+    pub fn check_duplicates(
+        conn: &mut SqliteConnection,
+        title: &str,
+        description: Option<&str>,
+        exclude_task_id: Option<&str>,
+    ) -> Result<Vec<SimilarTask>, ServiceError> {
+        let query = term_counts(title, description);
+        Self::rank(
+            conn,
+            &query,
+            exclude_task_id,
+            DUPLICATE_THRESHOLD,
+            DUPLICATE_LIMIT,
+        )
+    }
+
+    /// Archived以外の全タスクをコーパスとしてTF-IDFを計算し、類似度順に並べる
+    fn rank(
+        conn: &mut SqliteConnection,
+        query: &TermCounts,
+        exclude_task_id: Option<&str>,
+        threshold: f64,
+        limit: usize,
+    ) -> Result<Vec<SimilarTask>, ServiceError> {
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let documents: Vec<(Task, TermCounts)> = tasks::table
+            .filter(tasks::status.ne(TaskStatus::Archived.as_str()))
+            .load::<Task>(conn)?
+            .into_iter()
+            .filter(|task| Some(task.id.as_str()) != exclude_task_id)
+            .map(|task| {
+                let counts = term_counts(&task.title, task.description.as_deref());
+                (task, counts)
+            })
+            .collect();
+
+        // 文書頻度（平滑化IDF: ln((1 + N) / (1 + df)) + 1）
+        let mut document_frequency: HashMap<&str, usize> = HashMap::new();
+        for (_, counts) in &documents {
+            for term in counts.keys() {
+                *document_frequency.entry(term.as_str()).or_insert(0) += 1;
+            }
+        }
+        let corpus_size = documents.len() as f64;
+        let idf = |term: &str| {
+            let df = document_frequency.get(term).copied().unwrap_or(0) as f64;
+            ((1.0 + corpus_size) / (1.0 + df)).ln() + 1.0
+        };
+
+        let query_vector = tf_idf_vector(query, &idf);
+
+        let mut results: Vec<(SimilarTask, String)> = documents
+            .iter()
+            .filter_map(|(task, counts)| {
+                let score = cosine(&query_vector, &tf_idf_vector(counts, &idf));
+                (score >= threshold).then(|| {
+                    (
+                        SimilarTask {
+                            task_id: task.id.clone(),
+                            title: task.title.clone(),
+                            status: task.status_enum(),
+                            score: (score * 1000.0).round() / 1000.0,
+                        },
+                        task.updated_at.clone(),
+                    )
+                })
+            })
+            .collect();
+
+        results.sort_by(|a, b| b.0.score.total_cmp(&a.0.score).then_with(|| b.1.cmp(&a.1)));
+        results.truncate(limit);
+
+        Ok(results.into_iter().map(|(similar, _)| similar).collect())
+    }
+}
+
+/// タイトルと説明文から語の出現頻度を集計（タイトルの語は重み付け）
+fn term_counts(title: &str, description: Option<&str>) -> TermCounts {
+    let mut counts = TermCounts::new();
+    for term in tokenize(title) {
+        *counts.entry(term).or_insert(0.0) += TITLE_WEIGHT;
+    }
+    for term in description.map(tokenize).unwrap_or_default() {
+        *counts.entry(term).or_insert(0.0) += 1.0;
+    }
+    counts
+}
+
+/// 出現頻度をTF-IDFの単位ベクトルに変換
+fn tf_idf_vector<'a>(counts: &'a TermCounts, idf: &impl Fn(&str) -> f64) -> HashMap<&'a str, f64> {
+    let mut vector: HashMap<&str, f64> = counts
+        .iter()
+        .map(|(term, tf)| (term.as_str(), (1.0 + tf.ln()) * idf(term)))
+        .collect();
+
+    let norm = vector.values().map(|w| w * w).sum::<f64>().sqrt();
+    if norm > 0.0 {
+        vector.values_mut().for_each(|w| *w /= norm);
+    }
+    vector
+}
+
+/// 単位ベクトル同士のコサイン類似度
+fn cosine(a: &HashMap<&str, f64>, b: &HashMap<&str, f64>) -> f64 {
+    let (small, large) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    small
+        .iter()
+        .filter_map(|(term, w)| large.get(term).map(|v| w * v))
+        .sum()
+}
+
+/// テキストをトークン化
+///
+/// - 英数字の連続は小文字化して1語とする（1文字の語は除外）
+/// - CJK文字の連続は文字bigramに分割する（1文字のみの場合はその文字）
+/// - 全角英数字は半角に正規化する
+fn tokenize(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut word = String::new();
+    let mut cjk_run: Vec<char> = Vec::new();
+
+    for c in text.chars().map(to_halfwidth) {
+        if is_cjk(c) {
+            flush_word(&mut word, &mut tokens);
+            cjk_run.push(c);
+        } else if c.is_alphanumeric() {
+            flush_cjk_run(&mut cjk_run, &mut tokens);
+            word.extend(c.to_lowercase());
+        } else {
+            flush_word(&mut word, &mut tokens);
+            flush_cjk_run(&mut cjk_run, &mut tokens);
+        }
+    }
+    flush_word(&mut word, &mut tokens);
+    flush_cjk_run(&mut cjk_run, &mut tokens);
+
+    tokens
+}
+
+fn flush_word(word: &mut String, tokens: &mut Vec<String>) {
+    if word.chars().count() > 1 {
+        tokens.push(std::mem::take(word));
+    } else {
+        word.clear();
+    }
+}
+
+fn flush_cjk_run(run: &mut Vec<char>, tokens: &mut Vec<String>) {
+    match run.len() {
+        0 => {}
+        1 => tokens.push(run[0].to_string()),
+        _ => tokens.extend(run.windows(2).map(|pair| pair.iter().collect::<String>())),
+    }
+    run.clear();
+}
+
+/// ひらがな・カタカナ・CJK統合漢字・ハングルかどうか
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30FF}'
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{FF66}'..='\u{FF9F}'
+        | '\u{AC00}'..='\u{D7AF}')
+}
+
+/// 全角英数字・記号を半角に変換
+fn to_halfwidth(c: char) -> char {
+    match c {
+        '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+        '\u{3000}' => ' ',
+        _ => c,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::task::CreateTaskRequest;
+    use crate::service::TaskService;
+    use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+
+    pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
+
+    fn setup_test_db() -> SqliteConnection {
+        let mut conn =
+            SqliteConnection::establish(":memory:").expect("Failed to create in-memory database");
+
+        conn.run_pending_migrations(MIGRATIONS)
+            .expect("Failed to run migrations");
+
+        conn
+    }
+
+    fn create_task(conn: &mut SqliteConnection, title: &str, description: Option<&str>) -> String {
+        TaskService::create_task(
+            conn,
+            CreateTaskRequest {
+                title: title.to_string(),
+                description: description.map(String::from),
+                tags: vec![],
+                parent_id: None,
+                estimated_minutes: None,
+            },
+        )
+        .unwrap()
+        .id
+    }
+
+    #[test]
+    fn test_tokenize_mixed_text() {
+        let tokens = tokenize("ログイン画面のFix bug");
+        assert_eq!(
+            tokens,
+            vec!["ログ", "グイ", "イン", "ン画", "画面", "面の", "fix", "bug"]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_normalizes_fullwidth() {
+        assert_eq!(tokenize("ＡＰＩ　設計"), vec!["api", "設計"]);
+    }
+
+    #[test]
+    fn test_find_similar_by_text_japanese() {
+        let mut conn = setup_test_db();
+        let login_id = create_task(&mut conn, "ログイン画面のバグ修正", None);
+        create_task(&mut conn, "請求書を送る", None);
+
+        let results = SimilarityService::find_similar_tasks(
+            &mut conn,
+            None,
+            Some("ログイン画面が表示されない"),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].task_id, login_id);
+    }
+
+    #[test]
+    fn test_find_similar_by_task_id_excludes_self_and_archived() {
+        let mut conn = setup_test_db();
+        let base_id = create_task(&mut conn, "Write release notes", Some("for version 2"));
+        let similar_id = create_task(&mut conn, "Write release notes draft", None);
+        let archived_id = create_task(&mut conn, "Release notes", None);
+        TaskService::delete_task(&mut conn, &archived_id).unwrap();
+
+        let results =
+            SimilarityService::find_similar_tasks(&mut conn, Some(&base_id), None, None).unwrap();
+
+        let ids: Vec<&str> = results.iter().map(|r| r.task_id.as_str()).collect();
+        assert_eq!(ids, vec![similar_id.as_str()]);
+    }
+
+    #[test]
+    fn test_find_similar_requires_input() {
+        let mut conn = setup_test_db();
+        let result = SimilarityService::find_similar_tasks(&mut conn, None, Some("  "), None);
+        assert!(matches!(result, Err(ServiceError::InvalidInput(_))));
+
+        let result = SimilarityService::find_similar_tasks(&mut conn, Some("missing"), None, None);
+        assert!(matches!(result, Err(ServiceError::TaskNotFound(_))));
+    }
+
+    #[test]
+    fn test_check_duplicates_threshold() {
+        let mut conn = setup_test_db();
+        let existing_id = create_task(&mut conn, "週次レポートを作成", None);
+        create_task(&mut conn, "週次ミーティングの準備", None);
+
+        let duplicates =
+            SimilarityService::check_duplicates(&mut conn, "週次レポート作成", None, None).unwrap();
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(duplicates[0].task_id, existing_id);
+
+        let duplicates =
+            SimilarityService::check_duplicates(&mut conn, "買い物リスト", None, None).unwrap();
+        assert!(duplicates.is_empty());
+    }
+}
@@ -0,0 +1,603 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use chrono::{Datelike, Duration, NaiveDate};
+use diesel::prelude::*;
+use diesel::SqliteConnection;
+
+use crate::error::ServiceError;
+use crate::models::focus::{FocusSessionState, TaskFocusTotal};
+use crate::models::queue_history::{QueueHistoryEntry, QueueOutcome};
+use crate::models::stats::{CompletionStreak, DailyQueueStats, WeeklyTagTotal};
+use crate::schema::{focus_sessions, queue_history, tags, task_queue, task_tags};
+use crate::service::queue::{date_key, QueueService};
+
+/// 集計期間の最大日数
+const MAX_RANGE_DAYS: i64 = 366;
+
+/// StatsService: キュー履歴（queue_history）に基づく集計
+///
+/// 履歴は今日のキューからの削除・一括完了・クリアと、日次ロールオーバー時に記録される。
+/// 同じ日に同じタスクの記録が複数ある場合（Draftに戻してから再追加した等）は、
+/// 最後の記録をその日の結果として扱う。
+pub struct StatsService;
+
+impl StatsService {
+    /// 指定日のキュー履歴を取得（記録順）
+    pub fn get_queue_history(
+        conn: &mut SqliteConnection,
+        date: NaiveDate,
+    ) -> Result<Vec<QueueHistoryEntry>, ServiceError> {
+        let entries = queue_history::table
+            .filter(queue_history::queue_date.eq(date_key(date)))
+            .order(queue_history::id.asc())
+            .load::<QueueHistoryEntry>(conn)?;
+        Ok(entries)
+    }
+
+    /// 期間内の日ごとの集計を取得（記録の無い日も0件として含む）
+    ///
+    /// # Arguments
+    /// * `conn` - データベース接続
+    /// * `from` - 開始日（含む）
+    /// * `to` - 終了日（含む）
+    ///
+    /// # Returns
+    /// * `Ok(Vec<DailyQueueStats>)` - 日付順の集計
+    /// * `Err(ServiceError::InvalidInput)` - 期間が不正（from > to、または366日超）
+    pub fn get_daily_stats(
+        conn: &mut SqliteConnection,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<DailyQueueStats>, ServiceError> {
+        validate_range(from, to)?;
+        let (from_key, to_key) = (date_key(from), date_key(to));
+
+        let outcomes = Self::load_final_outcomes(conn, &from_key, &to_key)?;
+
+        // まだキューに残っているエントリ（今日・先の日付）
+        let mut queued: HashMap<String, HashSet<String>> = HashMap::new();
+        for (queue_date, task_id) in task_queue::table
+            .filter(task_queue::queue_date.between(&from_key, &to_key))
+            .select((task_queue::queue_date, task_queue::task_id))
+            .load::<(String, String)>(conn)?
+        {
+            queued.entry(queue_date).or_default().insert(task_id);
+        }
+
+        // 終了した集中セッションの集中時間（秒）
+        let mut focused: HashMap<String, i64> = HashMap::new();
+        for (queue_date, seconds) in Self::load_finished_focus(conn, &from_key, &to_key)?
+            .into_iter()
+            .map(|(queue_date, _, _, seconds)| (queue_date, seconds))
+        {
+            *focused.entry(queue_date).or_insert(0) += seconds;
+        }
+
+        let stats = from
+            .iter_days()
+            .take_while(|date| *date <= to)
+            .map(|date| {
+                let key = date_key(date);
+                let day_outcomes = outcomes.get(&key);
+                let day_queued = queued.get(&key);
+                let focused_seconds = focused.get(&key).copied().unwrap_or(0);
+
+                let count = |outcome: QueueOutcome| {
+                    day_outcomes
+                        .map(|m| m.values().filter(|o| **o == outcome).count())
+                        .unwrap_or(0)
+                };
+                let remaining = day_queued
+                    .map(|ids| {
+                        ids.iter()
+                            .filter(|id| day_outcomes.is_none_or(|m| !m.contains_key(*id)))
+                            .count()
+                    })
+                    .unwrap_or(0);
+                let planned = day_outcomes.map(|m| m.len()).unwrap_or(0) + remaining;
+                let completed = count(QueueOutcome::Completed);
+
+                DailyQueueStats {
+                    date: key,
+                    planned,
+                    completed,
+                    returned_to_draft: count(QueueOutcome::ReturnedToDraft),
+                    archived: count(QueueOutcome::Archived),
+                    carried_over: count(QueueOutcome::CarriedOver),
+                    deferred: count(QueueOutcome::Deferred),
+                    remaining,
+                    completion_rate: if planned == 0 {
+                        0.0
+                    } else {
+                        completed as f64 / planned as f64
+                    },
+                    focused_minutes: focused_seconds / 60,
+                }
+            })
+            .collect();
+
+        Ok(stats)
+    }
+
+    /// 連続完了日数を取得（「今日」は day_start_hour 設定に従う）
+    pub fn get_completion_streak(
+        conn: &mut SqliteConnection,
+    ) -> Result<CompletionStreak, ServiceError> {
+        let today = QueueService::today(conn)?;
+
+        let completed_dates: BTreeSet<NaiveDate> = queue_history::table
+            .filter(queue_history::outcome.eq(QueueOutcome::Completed.as_str()))
+            .select(queue_history::queue_date)
+            .distinct()
+            .load::<String>(conn)?
+            .iter()
+            .filter_map(|d| QueueService::parse_queue_date(d).ok())
+            .collect();
+
+        Ok(streak_of(&completed_dates, today))
+    }
+
+    /// 期間内の週ごと・タグごとの完了タスク数を取得
+    ///
+    /// タグは集計時点でタスクに付いているものを使用する（タグ無しのタスクは含まない）。
+    pub fn get_weekly_tag_totals(
+        conn: &mut SqliteConnection,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<WeeklyTagTotal>, ServiceError> {
+        validate_range(from, to)?;
+        let (from_key, to_key) = (date_key(from), date_key(to));
+
+        let outcomes = Self::load_final_outcomes(conn, &from_key, &to_key)?;
+
+        // 週ごとの完了タスクID
+        let mut completed_by_week: BTreeMap<NaiveDate, HashSet<String>> = BTreeMap::new();
+        for (date, day_outcomes) in &outcomes {
+            let Ok(date) = QueueService::parse_queue_date(date) else {
+                continue;
+            };
+            let week_start =
+                date - Duration::days(i64::from(date.weekday().num_days_from_monday()));
+            completed_by_week.entry(week_start).or_default().extend(
+                day_outcomes
+                    .iter()
+                    .filter(|(_, o)| **o == QueueOutcome::Completed)
+                    .map(|(id, _)| id.clone()),
+            );
+        }
+
+        let task_ids: Vec<String> = completed_by_week
+            .values()
+            .flatten()
+            .cloned()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let mut tags_by_task: HashMap<String, Vec<String>> = HashMap::new();
+        for (task_id, tag_name) in task_tags::table
+            .inner_join(tags::table)
+            .filter(task_tags::task_id.eq_any(&task_ids))
+            .select((task_tags::task_id, tags::name))
+            .load::<(String, String)>(conn)?
+        {
+            tags_by_task.entry(task_id).or_default().push(tag_name);
+        }
+
+        let mut totals = Vec::new();
+        for (week_start, task_ids) in completed_by_week {
+            let mut per_tag: HashMap<&str, usize> = HashMap::new();
+            for tag in task_ids
+                .iter()
+                .filter_map(|id| tags_by_task.get(id))
+                .flatten()
+            {
+                *per_tag.entry(tag.as_str()).or_insert(0) += 1;
+            }
+
+            let mut week_totals: Vec<WeeklyTagTotal> = per_tag
+                .into_iter()
+                .map(|(tag, completed)| WeeklyTagTotal {
+                    week_start: date_key(week_start),
+                    tag: tag.to_string(),
+                    completed,
+                })
+                .collect();
+            week_totals.sort_by(|a, b| {
+                b.completed
+                    .cmp(&a.completed)
+                    .then_with(|| a.tag.cmp(&b.tag))
+            });
+            totals.extend(week_totals);
+        }
+
+        Ok(totals)
+    }
+
+    /// 期間内のタスクごとの集中時間を取得（集中時間の多い順）
+    ///
+    /// 終了した集中セッションのみを対象とし、セッションのキュー対象日で期間を判定する。
+    pub fn get_focus_totals(
+        conn: &mut SqliteConnection,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<TaskFocusTotal>, ServiceError> {
+        validate_range(from, to)?;
+        let (from_key, to_key) = (date_key(from), date_key(to));
+
+        let mut totals: HashMap<String, (String, usize, i64)> = HashMap::new();
+        for (_, task_id, task_title, seconds) in
+            Self::load_finished_focus(conn, &from_key, &to_key)?
+        {
+            let total = totals.entry(task_id).or_insert((String::new(), 0, 0));
+            total.0 = task_title;
+            total.1 += 1;
+            total.2 += seconds;
+        }
+
+        let mut totals: Vec<TaskFocusTotal> = totals
+            .into_iter()
+            .map(
+                |(task_id, (task_title, session_count, seconds))| TaskFocusTotal {
+                    task_id,
+                    task_title,
+                    session_count,
+                    focused_minutes: seconds / 60,
+                },
+            )
+            .collect();
+        totals.sort_by(|a, b| {
+            b.focused_minutes
+                .cmp(&a.focused_minutes)
+                .then_with(|| a.task_title.cmp(&b.task_title))
+        });
+
+        Ok(totals)
+    }
+
+    /// 期間内に終了した集中セッション（対象日, タスクID, タイトル, 集中秒数）を記録順に取得
+    fn load_finished_focus(
+        conn: &mut SqliteConnection,
+        from_key: &str,
+        to_key: &str,
+    ) -> Result<Vec<(String, String, String, i64)>, ServiceError> {
+        let rows = focus_sessions::table
+            .filter(focus_sessions::queue_date.between(from_key, to_key))
+            .filter(focus_sessions::state.eq(FocusSessionState::Finished.as_str()))
+            .order(focus_sessions::id.asc())
+            .select((
+                focus_sessions::queue_date,
+                focus_sessions::task_id,
+                focus_sessions::task_title,
+                focus_sessions::focused_seconds,
+            ))
+            .load::<(String, String, String, i32)>(conn)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(queue_date, task_id, task_title, seconds)| {
+                (queue_date, task_id, task_title, i64::from(seconds))
+            })
+            .collect())
+    }
+
+    /// 期間内の日付ごと・タスクごとの最終結果を取得
+    fn load_final_outcomes(
+        conn: &mut SqliteConnection,
+        from_key: &str,
+        to_key: &str,
+    ) -> Result<HashMap<String, HashMap<String, QueueOutcome>>, ServiceError> {
+        let rows = queue_history::table
+            .filter(queue_history::queue_date.between(from_key, to_key))
+            .order(queue_history::id.asc())
+            .select((
+                queue_history::queue_date,
+                queue_history::task_id,
+                queue_history::outcome,
+            ))
+            .load::<(String, String, String)>(conn)?;
+
+        let mut outcomes: HashMap<String, HashMap<String, QueueOutcome>> = HashMap::new();
+        for (queue_date, task_id, outcome) in rows {
+            if let Some(outcome) = parse_outcome(&outcome) {
+                outcomes
+                    .entry(queue_date)
+                    .or_default()
+                    .insert(task_id, outcome);
+            }
+        }
+        Ok(outcomes)
+    }
+}
+
+/// 集計期間を検証
+fn validate_range(from: NaiveDate, to: NaiveDate) -> Result<(), ServiceError> {
+    if from > to {
+        return Err(ServiceError::InvalidInput(
+            "開始日は終了日以前の日付を指定してください".to_string(),
+        ));
+    }
+    if (to - from).num_days() >= MAX_RANGE_DAYS {
+        return Err(ServiceError::InvalidInput(format!(
+            "集計期間は{}日以内で指定してください",
+            MAX_RANGE_DAYS
+        )));
+    }
+    Ok(())
+}
+
+/// 履歴の結果文字列を解析
+fn parse_outcome(outcome: &str) -> Option<QueueOutcome> {
+    [
+        QueueOutcome::Completed,
+        QueueOutcome::Archived,
+        QueueOutcome::ReturnedToDraft,
+        QueueOutcome::CarriedOver,
+        QueueOutcome::Deferred,
+    ]
+    .into_iter()
+    .find(|o| o.as_str() == outcome)
+}
+
+/// 完了日の集合から連続日数を計算
+fn streak_of(completed_dates: &BTreeSet<NaiveDate>, today: NaiveDate) -> CompletionStreak {
+    let mut longest = 0;
+    let mut run = 0;
+    let mut previous: Option<NaiveDate> = None;
+    for date in completed_dates.iter().filter(|d| **d <= today) {
+        run = match previous {
+            Some(prev) if *date - prev == Duration::days(1) => run + 1,
+            _ => 1,
+        };
+        longest = longest.max(run);
+        previous = Some(*date);
+    }
+
+    // 今日まだ完了が無い場合は昨日までの連続日数を現在値とする
+    let mut current = 0;
+    let mut cursor = if completed_dates.contains(&today) {
+        today
+    } else {
+        today - Duration::days(1)
+    };
+    while completed_dates.contains(&cursor) {
+        current += 1;
+        cursor -= Duration::days(1);
+    }
+
+    CompletionStreak {
+        current,
+        longest,
+        last_completed_date: previous.map(date_key),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::queue::DEFAULT_QUEUE_ID;
+    use crate::models::queue_history::NewQueueHistoryEntry;
+    use crate::models::tag::CreateTagRequest;
+    use crate::models::task::CreateTaskRequest;
+    use crate::service::{FocusService, TagService, TaskService};
+    use chrono::Utc;
+    use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+
+    pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
+
+    fn setup_test_db() -> SqliteConnection {
+        let mut conn =
+            SqliteConnection::establish(":memory:").expect("Failed to create in-memory database");
+
+        conn.run_pending_migrations(MIGRATIONS)
+            .expect("Failed to run migrations");
+
+        conn
+    }
+
+    fn create_task(conn: &mut SqliteConnection, title: &str, tags: Vec<&str>) -> String {
+        TaskService::create_task(
+            conn,
+            CreateTaskRequest {
+                title: title.to_string(),
+                description: None,
+                tags: tags.into_iter().map(String::from).collect(),
+                parent_id: None,
+                estimated_minutes: None,
+            },
+        )
+        .unwrap()
+        .id
+    }
+
+    fn record(conn: &mut SqliteConnection, date: NaiveDate, task_id: &str, outcome: QueueOutcome) {
+        diesel::insert_into(queue_history::table)
+            .values(&NewQueueHistoryEntry::new(
+                date_key(date),
+                task_id.to_string(),
+                "title".to_string(),
+                0,
+                outcome,
+            ))
+            .execute(conn)
+            .unwrap();
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_queue_operations_record_history() {
+        let mut conn = setup_test_db();
+        let done = create_task(&mut conn, "Done", vec![]);
+        let back = create_task(&mut conn, "Back to draft", vec![]);
+        let rest = create_task(&mut conn, "Rest", vec![]);
+        for id in [&done, &back, &rest] {
+            QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, id.clone(), None, false)
+                .unwrap();
+        }
+
+        QueueService::remove_from_queue(
+            &mut conn,
+            DEFAULT_QUEUE_ID,
+            done.clone(),
+            "completed".to_string(),
+        )
+        .unwrap();
+        QueueService::remove_from_queue(
+            &mut conn,
+            DEFAULT_QUEUE_ID,
+            back.clone(),
+            "draft".to_string(),
+        )
+        .unwrap();
+        QueueService::complete_all_queue(&mut conn, DEFAULT_QUEUE_ID).unwrap();
+
+        let today = QueueService::today(&mut conn).unwrap();
+        let history = StatsService::get_queue_history(&mut conn, today).unwrap();
+        let recorded: Vec<(String, String)> = history
+            .into_iter()
+            .map(|e| (e.task_title, e.outcome))
+            .collect();
+        assert_eq!(
+            recorded,
+            vec![
+                ("Done".to_string(), "completed".to_string()),
+                ("Back to draft".to_string(), "returned_to_draft".to_string()),
+                ("Rest".to_string(), "completed".to_string()),
+            ]
+        );
+
+        let stats = StatsService::get_daily_stats(&mut conn, today, today).unwrap();
+        assert_eq!(stats[0].planned, 3);
+        assert_eq!(stats[0].completed, 2);
+        assert_eq!(stats[0].returned_to_draft, 1);
+        assert!((stats[0].completion_rate - 2.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_daily_stats_fill_empty_days_and_count_remaining() {
+        let mut conn = setup_test_db();
+        let a = create_task(&mut conn, "A", vec![]);
+        let b = create_task(&mut conn, "B", vec![]);
+        let today = QueueService::today(&mut conn).unwrap();
+        let two_days_ago = today - Duration::days(2);
+
+        record(&mut conn, two_days_ago, &a, QueueOutcome::CarriedOver);
+        // 同じ日に再記録された場合は最後の結果を採用
+        record(&mut conn, two_days_ago, &b, QueueOutcome::ReturnedToDraft);
+        record(&mut conn, two_days_ago, &b, QueueOutcome::Completed);
+        QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, a.clone(), None, false).unwrap();
+
+        let stats = StatsService::get_daily_stats(&mut conn, two_days_ago, today).unwrap();
+        assert_eq!(stats.len(), 3);
+        assert_eq!(stats[0].planned, 2);
+        assert_eq!(stats[0].completed, 1);
+        assert_eq!(stats[0].carried_over, 1);
+        assert_eq!(stats[0].returned_to_draft, 0);
+        assert_eq!(stats[1].planned, 0);
+        assert_eq!(stats[1].completion_rate, 0.0);
+        assert_eq!(stats[2].planned, 1);
+        assert_eq!(stats[2].remaining, 1);
+
+        let result = StatsService::get_daily_stats(&mut conn, today, two_days_ago);
+        assert!(matches!(result, Err(ServiceError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_streak_of() {
+        let dates: BTreeSet<NaiveDate> = [
+            date(2026, 1, 1),
+            date(2026, 1, 2),
+            date(2026, 1, 3),
+            date(2026, 1, 6),
+            date(2026, 1, 7),
+        ]
+        .into_iter()
+        .collect();
+
+        // 今日（1/8）はまだ完了が無い → 昨日までの2日間
+        let streak = streak_of(&dates, date(2026, 1, 8));
+        assert_eq!(streak.current, 2);
+        assert_eq!(streak.longest, 3);
+        assert_eq!(streak.last_completed_date.as_deref(), Some("2026-01-07"));
+
+        // 2日空くと途切れる
+        assert_eq!(streak_of(&dates, date(2026, 1, 9)).current, 0);
+    }
+
+    #[test]
+    fn test_weekly_tag_totals() {
+        let mut conn = setup_test_db();
+        for name in ["work", "home"] {
+            TagService::create_tag(
+                &mut conn,
+                CreateTagRequest {
+                    name: name.to_string(),
+                    color: None,
+                },
+            )
+            .unwrap();
+        }
+        let a = create_task(&mut conn, "A", vec!["work"]);
+        let b = create_task(&mut conn, "B", vec!["work", "home"]);
+        let c = create_task(&mut conn, "C", vec![]);
+
+        // 2026-01-05 は月曜日
+        record(&mut conn, date(2026, 1, 5), &a, QueueOutcome::Completed);
+        record(&mut conn, date(2026, 1, 7), &b, QueueOutcome::Completed);
+        record(&mut conn, date(2026, 1, 7), &c, QueueOutcome::Completed);
+        record(&mut conn, date(2026, 1, 12), &b, QueueOutcome::Completed);
+        record(&mut conn, date(2026, 1, 12), &a, QueueOutcome::CarriedOver);
+
+        let totals =
+            StatsService::get_weekly_tag_totals(&mut conn, date(2026, 1, 1), date(2026, 1, 31))
+                .unwrap();
+        let flattened: Vec<(String, String, usize)> = totals
+            .into_iter()
+            .map(|t| (t.week_start, t.tag, t.completed))
+            .collect();
+        assert_eq!(
+            flattened,
+            vec![
+                ("2026-01-05".to_string(), "work".to_string(), 2),
+                ("2026-01-05".to_string(), "home".to_string(), 1),
+                ("2026-01-12".to_string(), "home".to_string(), 1),
+                ("2026-01-12".to_string(), "work".to_string(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_focus_totals_and_daily_focused_minutes() {
+        let mut conn = setup_test_db();
+        let a = create_task(&mut conn, "A", vec![]);
+        let b = create_task(&mut conn, "B", vec![]);
+        for id in [&a, &b] {
+            QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, id.clone(), None, false)
+                .unwrap();
+        }
+        let today = QueueService::today(&mut conn).unwrap();
+        let start = Utc::now();
+
+        // A: 10分 + 15分（完了）、B: 5分
+        FocusService::start_session(&mut conn, DEFAULT_QUEUE_ID, start).unwrap();
+        FocusService::finish_session(&mut conn, false, start + Duration::minutes(10)).unwrap();
+        FocusService::start_session(&mut conn, DEFAULT_QUEUE_ID, start).unwrap();
+        FocusService::finish_session(&mut conn, true, start + Duration::minutes(15)).unwrap();
+        FocusService::start_session(&mut conn, DEFAULT_QUEUE_ID, start).unwrap();
+        FocusService::finish_session(&mut conn, false, start + Duration::minutes(5)).unwrap();
+        // 進行中のセッションは含まない
+        FocusService::start_session(&mut conn, DEFAULT_QUEUE_ID, start).unwrap();
+
+        let totals = StatsService::get_focus_totals(&mut conn, today, today).unwrap();
+        let flattened: Vec<(String, usize, i64)> = totals
+            .into_iter()
+            .map(|t| (t.task_id, t.session_count, t.focused_minutes))
+            .collect();
+        assert_eq!(flattened, vec![(a, 2, 25), (b, 1, 5)]);
+
+        let daily = StatsService::get_daily_stats(&mut conn, today, today).unwrap();
+        assert_eq!(daily[0].focused_minutes, 30);
+    }
+}
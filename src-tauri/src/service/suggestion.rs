@@ -0,0 +1,416 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel::SqliteConnection;
+
+use crate::error::ServiceError;
+use crate::models::focus::FocusSessionState;
+use crate::models::suggestion::{QueueSuggestion, SuggestionReason, SuggestionReasonKind};
+use crate::models::task::{Task, TaskStatus};
+use crate::schema::{deferred_tasks, focus_sessions, task_queue, tasks};
+use crate::service::queue::{date_key, QueueService};
+use crate::service::rollover::RolloverService;
+use crate::service::settings::SettingsService;
+
+/// 提案件数の上限
+const MAX_SUGGESTIONS: usize = 50;
+
+/// 「長期間更新なし」とみなす日数
+const STALE_DAYS: i64 = 7;
+
+/// 「すぐ終わる」とみなす見積もり時間（分）
+const QUICK_WIN_MINUTES: i32 = 15;
+
+/// スコアの重み: 持ち越し1回あたり
+const SLIPPED_WEIGHT: f64 = 3.0;
+
+/// スコアの重み: 集中セッションの実績あり
+const IN_PROGRESS_WEIGHT: f64 = 2.5;
+
+/// スコアの重み: 親タスクが進行中
+const PARENT_ACTIVE_WEIGHT: f64 = 2.0;
+
+/// スコアの重み: すぐ終わる
+const QUICK_WIN_WEIGHT: f64 = 1.0;
+
+/// SuggestionService: Draftプールから今日のキューへの追加候補を提案
+///
+/// タスクモデルには期限・優先度が無いため、持ち越し回数・集中時間の実績・
+/// 親タスクの進行状況・更新からの経過日数・見積もり時間をシグナルとして順位付けする。
+/// 候補は QueueService::add_to_queue で追加できるタスク（Draft、どのキューにも無い、
+/// アーカイブ以外の子タスクを持たない）に限られ、キューの上限（WIP制限）も考慮する。
+pub struct SuggestionService;
+
+impl SuggestionService {
+    /// 今日のキューへの追加候補を提案
+    ///
+    /// # Arguments
+    /// * `conn` - データベース接続
+    /// * `queue_id` - 追加先のキューID（上限（WIP制限）の判定に使用）
+    /// * `limit` - 提案件数（1〜50）
+    /// * `now` - 現在時刻
+    ///
+    /// # Returns
+    /// * `Ok(Vec<QueueSuggestion>)` - スコアの高い順（同点は作成日時の古い順）
+    /// * `Err(ServiceError::InvalidInput)` - limitが範囲外
+    pub fn suggest_queue(
+        conn: &mut SqliteConnection,
+        queue_id: &str,
+        limit: usize,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<QueueSuggestion>, ServiceError> {
+        if limit == 0 || limit > MAX_SUGGESTIONS {
+            return Err(ServiceError::InvalidInput(format!(
+                "提案件数は1〜{}件で指定してください",
+                MAX_SUGGESTIONS
+            )));
+        }
+
+        let candidates = Self::eligible_tasks(conn)?;
+        if candidates.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let slip_counts: HashMap<String, i64> = RolloverService::get_slipped_tasks(conn)?
+            .into_iter()
+            .map(|slip| (slip.task_id, slip.slip_count))
+            .collect();
+        let focused_seconds = Self::focused_seconds_by_task(conn)?;
+        let active_parents: HashMap<String, String> = tasks::table
+            .filter(tasks::status.eq(TaskStatus::Active.as_str()))
+            .select((tasks::id, tasks::title))
+            .load::<(String, String)>(conn)?
+            .into_iter()
+            .collect();
+
+        let mut ranked: Vec<(QueueSuggestion, String)> = candidates
+            .into_iter()
+            .map(|task| {
+                let mut reasons: Vec<(f64, SuggestionReason)> = Vec::new();
+
+                if let Some(count) = slip_counts.get(&task.id) {
+                    reasons.push((
+                        SLIPPED_WEIGHT * *count as f64,
+                        reason(
+                            SuggestionReasonKind::Slipped,
+                            format!("{}回持ち越し", count),
+                        ),
+                    ));
+                }
+                if let Some(seconds) = focused_seconds.get(&task.id) {
+                    reasons.push((
+                        IN_PROGRESS_WEIGHT,
+                        reason(
+                            SuggestionReasonKind::InProgress,
+                            format!("集中時間{}分（作業途中）", seconds / 60),
+                        ),
+                    ));
+                }
+                if let Some(parent_title) = task
+                    .parent_id
+                    .as_ref()
+                    .and_then(|parent_id| active_parents.get(parent_id))
+                {
+                    reasons.push((
+                        PARENT_ACTIVE_WEIGHT,
+                        reason(
+                            SuggestionReasonKind::ParentActive,
+                            format!("親タスク「{}」が進行中", parent_title),
+                        ),
+                    ));
+                }
+                let idle_days = days_since(&task.updated_at, now);
+                if idle_days >= STALE_DAYS {
+                    // 8週間で頭打ち
+                    reasons.push((
+                        1.0 + idle_days.min(8 * STALE_DAYS) as f64 / (2 * STALE_DAYS) as f64,
+                        reason(
+                            SuggestionReasonKind::Stale,
+                            format!("{}日間更新なし", idle_days),
+                        ),
+                    ));
+                }
+                if let Some(minutes) = task.estimated_minutes {
+                    if minutes <= QUICK_WIN_MINUTES {
+                        reasons.push((
+                            QUICK_WIN_WEIGHT,
+                            reason(
+                                SuggestionReasonKind::QuickWin,
+                                format!("{}分で終わる見積もり", minutes),
+                            ),
+                        ));
+                    }
+                }
+
+                // 作成からの経過日数はわずかな加点（同条件のタスクでは古いものを優先）
+                let age_days = days_since(&task.created_at, now);
+                let mut score = age_days.min(365) as f64 / 365.0;
+                if reasons.is_empty() {
+                    reasons.push((
+                        0.0,
+                        reason(
+                            SuggestionReasonKind::Waiting,
+                            format!("作成から{}日", age_days),
+                        ),
+                    ));
+                }
+
+                reasons.sort_by(|a, b| b.0.total_cmp(&a.0));
+                score += reasons.iter().map(|(weight, _)| weight).sum::<f64>();
+
+                (
+                    QueueSuggestion {
+                        task_id: task.id,
+                        title: task.title,
+                        estimated_minutes: task.estimated_minutes,
+                        score,
+                        reasons: reasons.into_iter().map(|(_, reason)| reason).collect(),
+                    },
+                    task.created_at,
+                )
+            })
+            .collect();
+
+        ranked.sort_by(|(a, a_created), (b, b_created)| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a_created.cmp(b_created))
+        });
+
+        Self::take_within_limits(conn, queue_id, ranked.into_iter().map(|(s, _)| s), limit)
+    }
+
+    /// キューに追加できるDraftタスクを取得（キュー内・延期中・子タスクを持つ親は除く）
+    fn eligible_tasks(conn: &mut SqliteConnection) -> Result<Vec<Task>, ServiceError> {
+        let queued: HashSet<String> = task_queue::table
+            .select(task_queue::task_id)
+            .load::<String>(conn)?
+            .into_iter()
+            .collect();
+
+        // アーカイブ以外の子タスクを持つ親（BR-015: キューに追加できない）
+        let parents: HashSet<String> = tasks::table
+            .filter(tasks::parent_id.is_not_null())
+            .filter(tasks::status.ne(TaskStatus::Archived.as_str()))
+            .select(tasks::parent_id)
+            .load::<Option<String>>(conn)?
+            .into_iter()
+            .flatten()
+            .collect();
+
+        Ok(tasks::table
+            .filter(tasks::status.eq(TaskStatus::Draft.as_str()))
+            .filter(tasks::id.ne_all(deferred_tasks::table.select(deferred_tasks::task_id)))
+            .load::<Task>(conn)?
+            .into_iter()
+            .filter(|task| !queued.contains(&task.id) && !parents.contains(&task.id))
+            .collect())
+    }
+
+    /// 終了した集中セッションの集中秒数（タスクごと、0秒のタスクは含まない）
+    fn focused_seconds_by_task(
+        conn: &mut SqliteConnection,
+    ) -> Result<HashMap<String, i64>, ServiceError> {
+        let mut totals: HashMap<String, i64> = HashMap::new();
+        for (task_id, seconds) in focus_sessions::table
+            .filter(focus_sessions::state.eq(FocusSessionState::Finished.as_str()))
+            .filter(focus_sessions::focused_seconds.gt(0))
+            .select((focus_sessions::task_id, focus_sessions::focused_seconds))
+            .load::<(String, i32)>(conn)?
+        {
+            *totals.entry(task_id).or_insert(0) += i64::from(seconds);
+        }
+        Ok(totals)
+    }
+
+    /// 今日のキューの上限（WIP制限）に収まる範囲で、順位の高い候補から取得
+    fn take_within_limits(
+        conn: &mut SqliteConnection,
+        queue_id: &str,
+        ranked: impl Iterator<Item = QueueSuggestion>,
+        limit: usize,
+    ) -> Result<Vec<QueueSuggestion>, ServiceError> {
+        let limits = SettingsService::get_queue_limits(conn)?;
+        if limits.is_unlimited() {
+            return Ok(ranked.take(limit).collect());
+        }
+
+        let today = date_key(QueueService::today(conn)?);
+        let mut planned: Vec<String> = task_queue::table
+            .filter(task_queue::queue_id.eq(queue_id))
+            .filter(task_queue::queue_date.eq(&today))
+            .select(task_queue::task_id)
+            .load(conn)?;
+
+        let mut suggestions = Vec::new();
+        for suggestion in ranked {
+            if suggestions.len() >= limit
+                || limits
+                    .max_entries
+                    .is_some_and(|max| planned.len() >= max as usize)
+            {
+                break;
+            }
+
+            planned.push(suggestion.task_id.clone());
+            if QueueService::limit_violations(conn, &limits, &planned)?.is_empty() {
+                suggestions.push(suggestion);
+            } else {
+                planned.pop();
+            }
+        }
+        Ok(suggestions)
+    }
+}
+
+/// 理由を作成
+fn reason(kind: SuggestionReasonKind, message: String) -> SuggestionReason {
+    SuggestionReason { kind, message }
+}
+
+/// RFC 3339 形式の時刻から現在までの経過日数（解析できない場合は0）
+fn days_since(timestamp: &str, now: DateTime<Utc>) -> i64 {
+    DateTime::parse_from_rfc3339(timestamp)
+        .map(|dt| (now - dt.with_timezone(&Utc)).num_days().max(0))
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::queue::DEFAULT_QUEUE_ID;
+    use crate::models::settings::QueueLimits;
+    use crate::models::task::CreateTaskRequest;
+    use crate::service::TaskService;
+    use chrono::Duration;
+    use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+
+    pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
+
+    fn setup_test_db() -> SqliteConnection {
+        let mut conn =
+            SqliteConnection::establish(":memory:").expect("Failed to create in-memory database");
+
+        conn.run_pending_migrations(MIGRATIONS)
+            .expect("Failed to run migrations");
+
+        conn
+    }
+
+    fn create_task(
+        conn: &mut SqliteConnection,
+        title: &str,
+        parent_id: Option<&str>,
+        estimated_minutes: Option<i32>,
+    ) -> String {
+        TaskService::create_task(
+            conn,
+            CreateTaskRequest {
+                title: title.to_string(),
+                description: None,
+                tags: vec![],
+                parent_id: parent_id.map(String::from),
+                estimated_minutes,
+            },
+        )
+        .unwrap()
+        .id
+    }
+
+    fn kinds(suggestion: &QueueSuggestion) -> Vec<SuggestionReasonKind> {
+        suggestion.reasons.iter().map(|r| r.kind).collect()
+    }
+
+    #[test]
+    fn test_suggest_queue_respects_eligibility() {
+        let mut conn = setup_test_db();
+        let parent = create_task(&mut conn, "Parent", None, None);
+        let child = create_task(&mut conn, "Child", Some(&parent), None);
+        let queued = create_task(&mut conn, "Queued", None, None);
+        QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, queued.clone(), None, false)
+            .unwrap();
+        let plain = create_task(&mut conn, "Plain", None, None);
+
+        let suggestions =
+            SuggestionService::suggest_queue(&mut conn, DEFAULT_QUEUE_ID, 10, Utc::now()).unwrap();
+        let ids: Vec<&str> = suggestions.iter().map(|s| s.task_id.as_str()).collect();
+
+        // 子タスクを持つ親・キュー内のタスクは候補にならない
+        assert!(!ids.contains(&parent.as_str()));
+        assert!(!ids.contains(&queued.as_str()));
+        assert!(ids.contains(&child.as_str()));
+        assert!(ids.contains(&plain.as_str()));
+        // 理由が無いタスクにも経過日数の説明が付く
+        let plain = suggestions.iter().find(|s| s.task_id == plain).unwrap();
+        assert_eq!(kinds(plain), vec![SuggestionReasonKind::Waiting]);
+    }
+
+    #[test]
+    fn test_suggest_queue_ranking_and_reasons() {
+        let mut conn = setup_test_db();
+        let plain = create_task(&mut conn, "Plain", None, None);
+        let quick = create_task(&mut conn, "Quick", None, Some(10));
+
+        // 兄弟タスクがキューにあり、親がActiveになっている子タスク
+        let parent = create_task(&mut conn, "Project", None, None);
+        let sibling = create_task(&mut conn, "Step 1", Some(&parent), None);
+        let next_step = create_task(&mut conn, "Step 2", Some(&parent), None);
+        QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, sibling, None, false).unwrap();
+
+        // 3週間後の時点で評価すると全タスクが「更新なし」になる
+        let now = Utc::now() + Duration::days(21);
+        let suggestions =
+            SuggestionService::suggest_queue(&mut conn, DEFAULT_QUEUE_ID, 10, now).unwrap();
+        let order: Vec<&str> = suggestions.iter().map(|s| s.task_id.as_str()).collect();
+        assert_eq!(
+            order,
+            vec![next_step.as_str(), quick.as_str(), plain.as_str()]
+        );
+
+        assert_eq!(
+            kinds(&suggestions[0]),
+            vec![
+                SuggestionReasonKind::Stale,
+                SuggestionReasonKind::ParentActive
+            ]
+        );
+        assert_eq!(
+            suggestions[0].reasons[1].message,
+            "親タスク「Project」が進行中"
+        );
+        assert_eq!(suggestions[2].reasons[0].message, "21日間更新なし");
+    }
+
+    #[test]
+    fn test_suggest_queue_within_limits() {
+        let mut conn = setup_test_db();
+        SettingsService::update_queue_limits(
+            &mut conn,
+            QueueLimits {
+                max_entries: Some(3),
+                max_total_minutes: Some(60),
+                tag_caps: Default::default(),
+            },
+        )
+        .unwrap();
+        let queued = create_task(&mut conn, "Queued", None, Some(30));
+        QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, queued, None, false).unwrap();
+        let too_long = create_task(&mut conn, "Too long", None, Some(45));
+        let fits = create_task(&mut conn, "Fits", None, Some(20));
+        let unestimated = create_task(&mut conn, "Unestimated", None, None);
+        create_task(&mut conn, "Over count", None, None);
+
+        let suggestions =
+            SuggestionService::suggest_queue(&mut conn, DEFAULT_QUEUE_ID, 10, Utc::now()).unwrap();
+        let ids: Vec<&str> = suggestions.iter().map(|s| s.task_id.as_str()).collect();
+
+        // 見積もり合計を超える候補は除外し、残り2件分だけ提案する
+        assert!(!ids.contains(&too_long.as_str()));
+        assert_eq!(ids.len(), 2);
+        assert!(ids.contains(&fits.as_str()) || ids.contains(&unestimated.as_str()));
+
+        let result = SuggestionService::suggest_queue(&mut conn, DEFAULT_QUEUE_ID, 0, Utc::now());
+        assert!(matches!(result, Err(ServiceError::InvalidInput(_))));
+    }
+}
@@ -1,108 +1,279 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use chrono::Utc;
 use diesel::prelude::*;
 
 use crate::error::ServiceError;
-use crate::models::tag::{CreateTagRequest, NewTag, Tag, UpdateTagRequest};
-use crate::schema::{tags, task_tags};
+use crate::models::tag::{
+    CreateTagGroupRequest, CreateTagRequest, NewTag, Tag, TagAlias, TagGroup, TagGroupWithTags,
+    TagTreeNode, UpdateTagGroupRequest, UpdateTagRequest, AUTO_TAG_COLORS, TAG_PATH_SEPARATOR,
+};
+use crate::schema::{auto_tag_rule_tags, tag_aliases, tag_groups, tags, task_tags};
 
 pub struct TagService;
 
 impl TagService {
-    /// 全タグを取得（usage_count含む）
+    /// 全タグを取得（usage_count・ステータスごとの件数含む）
     pub fn list_tags(conn: &mut SqliteConnection) -> Result<Vec<Tag>, ServiceError> {
-        // 全タグを取得
-        let all_tags = tags::table
+        Ok(tags::table
             .order(tags::created_at.desc())
-            .load::<Tag>(conn)?;
+            .load::<Tag>(conn)?)
+    }
+
+    /// 全タグを名前空間のツリーとして取得
+    ///
+    /// # Returns
+    /// * `Ok(Vec<TagTreeNode>)` - ルートのタグ（名前順、子タグも名前順）
+    /// * `Err(ServiceError)` - エラー
+    ///
+    /// # Business Logic
+    /// - total_usage_count は自身と子孫のタグが付いたタスク数（同じタスクは1回だけ数える）
+    pub fn list_tag_tree(conn: &mut SqliteConnection) -> Result<Vec<TagTreeNode>, ServiceError> {
+        let mut all_tags = Self::list_tags(conn)?;
+        all_tags.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let mut task_ids_by_tag: HashMap<String, Vec<String>> = HashMap::new();
+        for (tag_id, task_id) in task_tags::table
+            .select((task_tags::tag_id, task_tags::task_id))
+            .load::<(String, String)>(conn)?
+        {
+            task_ids_by_tag.entry(tag_id).or_default().push(task_id);
+        }
+
+        let mut aliases_by_tag: HashMap<String, Vec<String>> = HashMap::new();
+        for alias in tag_aliases::table
+            .order(tag_aliases::alias.asc())
+            .load::<TagAlias>(conn)?
+        {
+            aliases_by_tag
+                .entry(alias.tag_id)
+                .or_default()
+                .push(alias.alias);
+        }
+
+        let tag_ids: HashSet<String> = all_tags.iter().map(|tag| tag.id.clone()).collect();
+        let mut children: HashMap<Option<String>, Vec<Tag>> = HashMap::new();
+        for tag in all_tags {
+            // 親が見つからないタグはルートとして扱う
+            let parent_id = tag.parent_id.clone().filter(|id| tag_ids.contains(id));
+            children.entry(parent_id).or_default().push(tag);
+        }
 
-        // 各タグのusage_countを再計算して更新
-        let tags_with_count: Result<Vec<Tag>, ServiceError> = all_tags
+        let roots = children.remove(&None).unwrap_or_default();
+        Ok(roots
             .into_iter()
-            .map(|mut tag| {
-                // task_tagsテーブルから使用数をカウント
-                let count = task_tags::table
-                    .filter(task_tags::tag_id.eq(&tag.id))
-                    .count()
-                    .get_result::<i64>(conn)?;
-
-                tag.usage_count = count as i32;
-                Ok(tag)
+            .map(|tag| {
+                Self::build_tree_node(tag, &mut children, &task_ids_by_tag, &mut aliases_by_tag).0
             })
-            .collect();
-
-        tags_with_count
+            .collect())
     }
 
     /// タグをIDで取得
     pub fn get_tag(conn: &mut SqliteConnection, tag_id: &str) -> Result<Tag, ServiceError> {
-        let mut tag = tags::table
+        tags::table
             .find(tag_id)
             .first::<Tag>(conn)
             .optional()?
-            .ok_or_else(|| ServiceError::TagNotFound(tag_id.to_string()))?;
+            .ok_or_else(|| ServiceError::TagNotFound(tag_id.to_string()))
+    }
 
-        // usage_countを再計算
-        let count = task_tags::table
-            .filter(task_tags::tag_id.eq(tag_id))
-            .count()
-            .get_result::<i64>(conn)?;
+    /// タグIDに子孫のタグIDを加える（タグ絞り込みで名前空間の下位タグも対象にするため）
+    pub fn with_descendants(
+        conn: &mut SqliteConnection,
+        tag_ids: Vec<String>,
+    ) -> Result<Vec<String>, ServiceError> {
+        let mut seen: HashSet<String> = tag_ids.iter().cloned().collect();
+        let mut result = tag_ids.clone();
+        let mut frontier = tag_ids;
 
-        tag.usage_count = count as i32;
-        Ok(tag)
+        while !frontier.is_empty() {
+            let child_ids: Vec<String> = tags::table
+                .filter(tags::parent_id.eq_any(&frontier))
+                .select(tags::id)
+                .load::<String>(conn)?;
+            frontier = child_ids
+                .into_iter()
+                .filter(|id| seen.insert(id.clone()))
+                .collect();
+            result.extend(frontier.iter().cloned());
+        }
+
+        Ok(result)
     }
 
     /// 新規タグ作成
+    ///
+    /// # Business Logic
+    /// - "work/clientA/billing" のようなパスを指定すると、存在しない親のパス
+    ///   （"work"、"work/clientA"）のタグも作成して親子関係を設定する
+    /// - 既存の子のパスのタグ（親が未設定のもの）は作成したタグの子になる
     pub fn create_tag(
         conn: &mut SqliteConnection,
         req: CreateTagRequest,
     ) -> Result<Tag, ServiceError> {
         // バリデーション: 名前が空でないか
-        if req.name.trim().is_empty() {
-            return Err(ServiceError::InvalidInput(
-                "タグ名は必須です".to_string(),
-            ));
-        }
+        let name = Self::normalize_tag_name(&req.name)?;
+        Self::ensure_not_alias(conn, &name)?;
 
-        let new_tag = NewTag::from_request(req);
+        conn.transaction::<_, ServiceError, _>(|conn| {
+            let parent_id = match Self::parent_path(&name) {
+                Some(parent_path) => Some(Self::ensure_path(conn, parent_path)?),
+                None => None,
+            };
 
-        // タグを挿入（UNIQUE制約違反を検出）
-        match diesel::insert_into(tags::table)
-            .values(&new_tag)
-            .execute(conn)
-        {
-            Ok(_) => Self::get_tag(conn, &new_tag.id),
-            Err(diesel::result::Error::DatabaseError(
-                diesel::result::DatabaseErrorKind::UniqueViolation,
-                _,
-            )) => Err(ServiceError::DuplicateEntry(new_tag.name)),
-            Err(e) => Err(e.into()),
-        }
+            let mut new_tag = NewTag::from_request(CreateTagRequest { name, ..req });
+            new_tag.parent_id = parent_id;
+
+            // タグを挿入（UNIQUE制約違反を検出）
+            match diesel::insert_into(tags::table)
+                .values(&new_tag)
+                .execute(conn)
+            {
+                Ok(_) => {}
+                Err(diesel::result::Error::DatabaseError(
+                    diesel::result::DatabaseErrorKind::UniqueViolation,
+                    _,
+                )) => return Err(ServiceError::DuplicateEntry(new_tag.name)),
+                Err(e) => return Err(e.into()),
+            }
+
+            Self::adopt_orphans(conn, &new_tag.id, &new_tag.name)?;
+            Self::get_tag(conn, &new_tag.id)
+        })
     }
 
     /// タグ更新
+    ///
+    /// # Returns
+    /// * `Ok(Tag)` - 更新後のタグ（統合した場合は統合先のタグ）
+    /// * `Err(ServiceError::DuplicateEntry)` - 変更後の名前のタグが既に存在する（merge = false の場合）
+    ///
+    /// # Business Logic
+    /// - 名前（パス）を変更すると子孫のタグの名前も同じように変更する
+    ///   （"work" → "job" の場合、"work/clientA" は "job/clientA" になる）
+    /// - 親のパスが変わる場合は新しい親のタグに付け替える（存在しなければ作成する）
+    /// - 自身の子孫の下へは移動できない
+    /// - merge = true で変更後の名前のタグが既に存在する場合は、そのタグへ統合する（merge_tagsと同じ）
     pub fn update_tag(
         conn: &mut SqliteConnection,
         tag_id: &str,
         req: UpdateTagRequest,
     ) -> Result<Tag, ServiceError> {
         // タグが存在するか確認
-        let _existing_tag = Self::get_tag(conn, tag_id)?;
+        let existing_tag = Self::get_tag(conn, tag_id)?;
 
         // バリデーション: 名前が指定されている場合は空でないか
-        if let Some(ref name) = req.name {
-            if name.trim().is_empty() {
-                return Err(ServiceError::InvalidInput(
-                    "タグ名は必須です".to_string(),
-                ));
+        let mut update_req = req;
+        if let Some(ref name) = update_req.name {
+            update_req.name = Some(Self::normalize_tag_name(name)?);
+        }
+
+        let result_id = conn.transaction::<_, ServiceError, _>(|conn| {
+            if let Some(ref new_name) = update_req.name {
+                if *new_name != existing_tag.name {
+                    Self::ensure_not_alias(conn, new_name)?;
+                    let merge_target = if update_req.merge {
+                        tags::table
+                            .filter(tags::name.eq(new_name))
+                            .first::<Tag>(conn)
+                            .optional()?
+                    } else {
+                        None
+                    };
+
+                    if let Some(target) = merge_target {
+                        Self::merge_tags(conn, std::slice::from_ref(&existing_tag.id), &target.id)?;
+                        if let Some(ref color) = update_req.color {
+                            diesel::update(tags::table.find(&target.id))
+                                .set((
+                                    tags::color.eq(color),
+                                    tags::updated_at.eq(Utc::now().to_rfc3339()),
+                                ))
+                                .execute(conn)?;
+                        }
+                        return Ok(target.id);
+                    }
+
+                    Self::rename_subtree(conn, &existing_tag, new_name)?;
+                }
             }
+
+            let update_req = update_req.with_timestamp();
+            diesel::update(tags::table.find(tag_id))
+                .set(&update_req)
+                .execute(conn)?;
+
+            Ok(tag_id.to_string())
+        })?;
+
+        Self::get_tag(conn, &result_id)
+    }
+
+    /// タグを統合（統合元のタグを統合先のタグへまとめて削除する）
+    ///
+    /// # Arguments
+    /// * `source_ids` - 統合元のタグID
+    /// * `target_id` - 統合先のタグID
+    ///
+    /// # Returns
+    /// * `Ok(i32)` - 統合後の統合先のタグの使用数
+    /// * `Err(ServiceError::TagNotFound)` - タグが存在しない
+    /// * `Err(ServiceError::InvalidInput)` - 統合元が空、または統合先が統合元（の子孫）に含まれる
+    /// * `Err(ServiceError::ExclusiveTagConflict)` - 統合先が排他グループのタグで、統合元のタグが
+    ///   付いたタスクに同じグループの別のタグが付いている（何も変更しない）
+    ///
+    /// # Business Logic
+    /// - 統合元のタグが付いたタスクに統合先のタグを付ける（既に付いている場合はそのまま）
+    /// - 統合元の子タグは統合先の下へ移す（同じ名前の子タグがあればそのタグへ再帰的に統合）
+    /// - 1つのトランザクションで実行する
+    pub fn merge_tags(
+        conn: &mut SqliteConnection,
+        source_ids: &[String],
+        target_id: &str,
+    ) -> Result<i32, ServiceError> {
+        if source_ids.is_empty() {
+            return Err(ServiceError::InvalidInput(
+                "統合元のタグを指定してください".to_string(),
+            ));
+        }
+        Self::get_tag(conn, target_id)?;
+        for source_id in source_ids {
+            Self::get_tag(conn, source_id)?;
+        }
+        if Self::with_descendants(conn, source_ids.to_vec())?
+            .iter()
+            .any(|id| id == target_id)
+        {
+            return Err(ServiceError::InvalidInput(
+                "統合先のタグを統合元やその子孫にすることはできません".to_string(),
+            ));
         }
 
-        let update_req = req.with_timestamp();
+        conn.transaction::<_, ServiceError, _>(|conn| {
+            let target = tags::table.find(target_id).first::<Tag>(conn)?;
+            for source_id in source_ids {
+                // 先に統合した統合元の子孫として統合済みの場合は何もしない
+                if let Some(source) = tags::table.find(source_id).first::<Tag>(conn).optional()? {
+                    Self::merge_into(conn, &source, &target)?;
+                }
+            }
+            Ok(())
+        })?;
 
-        diesel::update(tags::table.find(tag_id))
-            .set(&update_req)
-            .execute(conn)?;
+        Ok(Self::get_tag(conn, target_id)?.usage_count)
+    }
 
-        Self::get_tag(conn, tag_id)
+    /// タグ（と子孫のタグ）が付いたタスクのID（インデックス更新用）
+    pub fn tagged_task_ids(
+        conn: &mut SqliteConnection,
+        tag_ids: Vec<String>,
+    ) -> Result<Vec<String>, ServiceError> {
+        let tag_ids = Self::with_descendants(conn, tag_ids)?;
+        Ok(task_tags::table
+            .filter(task_tags::tag_id.eq_any(tag_ids))
+            .select(task_tags::task_id)
+            .distinct()
+            .load::<String>(conn)?)
     }
 
     /// タグ削除
@@ -110,15 +281,740 @@ impl TagService {
     /// # Note
     /// - FOREIGN KEY CASCADE により、関連する task_tags レコードも自動削除される
     /// - 使用中のタグでも削除可能（DBレベルで関連付けが削除される）
+    /// - 子タグを持つタグ（名前空間）は削除できない（先に子タグを削除すること）
     pub fn delete_tag(conn: &mut SqliteConnection, tag_id: &str) -> Result<(), ServiceError> {
         // タグが存在するか確認
         let _tag = Self::get_tag(conn, tag_id)?;
 
+        let child_count: i64 = tags::table
+            .filter(tags::parent_id.eq(tag_id))
+            .count()
+            .get_result(conn)?;
+        if child_count > 0 {
+            return Err(ServiceError::InvalidInput(
+                "子タグがあるタグは削除できません".to_string(),
+            ));
+        }
+
         // タグを削除（CASCADE により task_tags も自動削除）
         diesel::delete(tags::table.find(tag_id)).execute(conn)?;
 
         Ok(())
     }
+
+    /// タググループの一覧を取得（名前順、所属するタグ付き）
+    pub fn list_tag_groups(
+        conn: &mut SqliteConnection,
+    ) -> Result<Vec<TagGroupWithTags>, ServiceError> {
+        let groups = tag_groups::table
+            .order(tag_groups::name.asc())
+            .load::<TagGroup>(conn)?;
+        let mut all_tags = Self::list_tags(conn)?;
+        all_tags.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(groups
+            .into_iter()
+            .map(|group| {
+                let tags = all_tags
+                    .iter()
+                    .filter(|tag| tag.group_id.as_deref() == Some(group.id.as_str()))
+                    .cloned()
+                    .collect();
+                TagGroupWithTags { group, tags }
+            })
+            .collect())
+    }
+
+    /// タググループをIDで取得
+    pub fn get_tag_group(
+        conn: &mut SqliteConnection,
+        group_id: &str,
+    ) -> Result<TagGroup, ServiceError> {
+        tag_groups::table
+            .find(group_id)
+            .first::<TagGroup>(conn)
+            .optional()?
+            .ok_or_else(|| ServiceError::TagGroupNotFound(group_id.to_string()))
+    }
+
+    /// タググループを作成
+    ///
+    /// # Returns
+    /// * `Ok(TagGroup)` - 作成されたグループ
+    /// * `Err(ServiceError::InvalidInput)` - 名前が空
+    /// * `Err(ServiceError::DuplicateTagGroupName)` - 同じ名前のグループがある
+    pub fn create_tag_group(
+        conn: &mut SqliteConnection,
+        req: CreateTagGroupRequest,
+    ) -> Result<TagGroup, ServiceError> {
+        let group = TagGroup::new(Self::validate_group_name(&req.name)?, req.exclusive);
+
+        match diesel::insert_into(tag_groups::table)
+            .values(&group)
+            .execute(conn)
+        {
+            Ok(_) => Ok(group),
+            Err(diesel::result::Error::DatabaseError(
+                diesel::result::DatabaseErrorKind::UniqueViolation,
+                _,
+            )) => Err(ServiceError::DuplicateTagGroupName(group.name)),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// タググループを更新
+    ///
+    /// # Returns
+    /// * `Ok(TagGroup)` - 更新後のグループ
+    /// * `Err(ServiceError::TagGroupNotFound)` - グループが存在しない
+    /// * `Err(ServiceError::DuplicateTagGroupName)` - 同じ名前のグループがある
+    /// * `Err(ServiceError::ExclusiveTagConflict)` - 排他にしようとしたが、グループのタグが
+    ///   複数付いたタスクがある
+    pub fn update_tag_group(
+        conn: &mut SqliteConnection,
+        group_id: &str,
+        req: UpdateTagGroupRequest,
+    ) -> Result<TagGroup, ServiceError> {
+        let group = Self::get_tag_group(conn, group_id)?;
+        let name = match req.name {
+            Some(ref name) => Self::validate_group_name(name)?,
+            None => group.name.clone(),
+        };
+        let exclusive = req.exclusive.unwrap_or(group.exclusive);
+        if exclusive && !group.exclusive {
+            Self::ensure_exclusive(conn, &group, None)?;
+        }
+
+        match diesel::update(tag_groups::table.find(group_id))
+            .set((
+                tag_groups::name.eq(&name),
+                tag_groups::exclusive.eq(exclusive),
+                tag_groups::updated_at.eq(Utc::now().to_rfc3339()),
+            ))
+            .execute(conn)
+        {
+            Ok(_) => Self::get_tag_group(conn, group_id),
+            Err(diesel::result::Error::DatabaseError(
+                diesel::result::DatabaseErrorKind::UniqueViolation,
+                _,
+            )) => Err(ServiceError::DuplicateTagGroupName(name)),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// タググループを削除（所属していたタグはどのグループにも属さなくなる）
+    pub fn delete_tag_group(
+        conn: &mut SqliteConnection,
+        group_id: &str,
+    ) -> Result<(), ServiceError> {
+        Self::get_tag_group(conn, group_id)?;
+
+        conn.transaction::<_, ServiceError, _>(|conn| {
+            diesel::update(tags::table.filter(tags::group_id.eq(group_id)))
+                .set(tags::group_id.eq(None::<String>))
+                .execute(conn)?;
+            diesel::delete(tag_groups::table.find(group_id)).execute(conn)?;
+            Ok(())
+        })
+    }
+
+    /// タグの所属グループを設定（Noneでグループから外す）
+    ///
+    /// # Returns
+    /// * `Ok(Tag)` - 更新後のタグ
+    /// * `Err(ServiceError::ExclusiveTagConflict)` - 排他グループに入れると、グループのタグが
+    ///   複数付いたタスクができる
+    pub fn set_tag_group(
+        conn: &mut SqliteConnection,
+        tag_id: &str,
+        group_id: Option<&str>,
+    ) -> Result<Tag, ServiceError> {
+        Self::get_tag(conn, tag_id)?;
+        if let Some(group_id) = group_id {
+            let group = Self::get_tag_group(conn, group_id)?;
+            if group.exclusive {
+                Self::ensure_exclusive(conn, &group, Some(tag_id))?;
+            }
+        }
+
+        diesel::update(tags::table.find(tag_id))
+            .set((
+                tags::group_id.eq(group_id),
+                tags::updated_at.eq(Utc::now().to_rfc3339()),
+            ))
+            .execute(conn)?;
+
+        Self::get_tag(conn, tag_id)
+    }
+
+    /// タスクに付けるタグが排他グループの制約を満たすか確認
+    ///
+    /// # Returns
+    /// * `Ok(())` - 制約を満たす
+    /// * `Err(ServiceError::ExclusiveTagConflict)` - 同じ排他グループのタグが複数含まれる
+    pub fn validate_exclusive_tags(
+        conn: &mut SqliteConnection,
+        tag_names: &[String],
+    ) -> Result<(), ServiceError> {
+        if tag_names.len() < 2 {
+            return Ok(());
+        }
+
+        let rows: Vec<(String, String)> = tags::table
+            .inner_join(tag_groups::table)
+            .filter(tags::name.eq_any(tag_names))
+            .filter(tag_groups::exclusive.eq(true))
+            .select((tag_groups::name, tags::name))
+            .order((tag_groups::name.asc(), tags::name.asc()))
+            .load(conn)?;
+
+        let mut names_by_group: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for (group_name, tag_name) in rows {
+            names_by_group.entry(group_name).or_default().push(tag_name);
+        }
+        match names_by_group
+            .into_iter()
+            .find(|(_, names)| names.len() > 1)
+        {
+            Some((group_name, names)) => Err(ServiceError::ExclusiveTagConflict(format!(
+                "{}: {}",
+                group_name,
+                names.join(", ")
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// タグ名の絞り込み条件を解決（search_tasks / search_task_ids で共用）
+    ///
+    /// # Returns
+    /// * `Ok(Some(Vec<Vec<String>>))` - タグIDの集合のリスト。各集合のいずれかのタグが付いた
+    ///   タスクに絞り込み、集合同士はAND条件とする
+    ///   - 同じグループのタグ（グループに属さないタグ同士も含む）は1つの集合にまとめる（OR条件）
+    ///   - 各集合には名前空間の子孫のタグも含める
+    /// * `Ok(None)` - 指定されたタグが1つも存在しない（結果は必ず空）
+    pub fn resolve_tag_filter(
+        conn: &mut SqliteConnection,
+        tag_names: &[String],
+    ) -> Result<Option<Vec<Vec<String>>>, ServiceError> {
+        let tag_names = Self::resolve_aliases(conn, tag_names)?;
+        let found: Vec<(String, Option<String>)> = tags::table
+            .filter(tags::name.eq_any(&tag_names))
+            .select((tags::id, tags::group_id))
+            .load(conn)?;
+        if found.is_empty() {
+            return Ok(None);
+        }
+
+        let mut ids_by_group: BTreeMap<Option<String>, Vec<String>> = BTreeMap::new();
+        for (tag_id, group_id) in found {
+            ids_by_group.entry(group_id).or_default().push(tag_id);
+        }
+
+        let mut tag_id_sets = Vec::with_capacity(ids_by_group.len());
+        for tag_ids in ids_by_group.into_values() {
+            tag_id_sets.push(Self::with_descendants(conn, tag_ids)?);
+        }
+        Ok(Some(tag_id_sets))
+    }
+
+    /// タグの別名の一覧を取得（名前順）
+    pub fn list_aliases(
+        conn: &mut SqliteConnection,
+        tag_id: &str,
+    ) -> Result<Vec<TagAlias>, ServiceError> {
+        Self::get_tag(conn, tag_id)?;
+        Ok(tag_aliases::table
+            .filter(tag_aliases::tag_id.eq(tag_id))
+            .order(tag_aliases::alias.asc())
+            .load::<TagAlias>(conn)?)
+    }
+
+    /// タグに別名を追加
+    ///
+    /// # Returns
+    /// * `Ok(TagAlias)` - 追加した別名
+    /// * `Err(ServiceError::TagNotFound)` - タグが存在しない
+    /// * `Err(ServiceError::DuplicateTagAlias)` - 同じ名前のタグまたは別名がすでにある
+    pub fn add_alias(
+        conn: &mut SqliteConnection,
+        tag_id: &str,
+        alias: &str,
+    ) -> Result<TagAlias, ServiceError> {
+        Self::get_tag(conn, tag_id)?;
+        let alias = Self::normalize_tag_name(alias)?;
+
+        let name_taken = tags::table
+            .filter(tags::name.eq(&alias))
+            .select(tags::id)
+            .first::<String>(conn)
+            .optional()?
+            .is_some();
+        if name_taken {
+            return Err(ServiceError::DuplicateTagAlias(alias));
+        }
+
+        let tag_alias = TagAlias {
+            alias,
+            tag_id: tag_id.to_string(),
+            created_at: Utc::now().to_rfc3339(),
+        };
+        match diesel::insert_into(tag_aliases::table)
+            .values(&tag_alias)
+            .execute(conn)
+        {
+            Ok(_) => Ok(tag_alias),
+            Err(diesel::result::Error::DatabaseError(
+                diesel::result::DatabaseErrorKind::UniqueViolation,
+                _,
+            )) => Err(ServiceError::DuplicateTagAlias(tag_alias.alias)),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// タグの別名を削除（add_alias と同じく前後の空白を除いて探す）
+    pub fn remove_alias(conn: &mut SqliteConnection, alias: &str) -> Result<(), ServiceError> {
+        let alias = Self::normalize_tag_name(alias)?;
+        let deleted = diesel::delete(tag_aliases::table.find(&alias)).execute(conn)?;
+        if deleted == 0 {
+            return Err(ServiceError::TagAliasNotFound(alias));
+        }
+        Ok(())
+    }
+
+    /// タグ名のリストの別名を正式なタグ名に読み替える
+    ///
+    /// - タグ名・別名のどちらでもない名前はそのまま残す（存在チェックは呼び出し側で行う）
+    /// - 読み替えた結果重複する名前は、最初の1つだけ残す
+    pub fn resolve_aliases(
+        conn: &mut SqliteConnection,
+        tag_names: &[String],
+    ) -> Result<Vec<String>, ServiceError> {
+        let canonical: HashMap<String, String> = tag_aliases::table
+            .inner_join(tags::table)
+            .filter(tag_aliases::alias.eq_any(tag_names))
+            .select((tag_aliases::alias, tags::name))
+            .load::<(String, String)>(conn)?
+            .into_iter()
+            .collect();
+
+        let mut seen = HashSet::new();
+        Ok(tag_names
+            .iter()
+            .map(|name| canonical.get(name).unwrap_or(name).clone())
+            .filter(|name| seen.insert(name.clone()))
+            .collect())
+    }
+
+    /// 存在しないタグを作成（タスク作成・更新時のタグの自動作成用）
+    ///
+    /// # Returns
+    /// * `Ok(Vec<String>)` - 新規作成したタグ名（作成順、存在しなかった親のパスを含む）
+    ///
+    /// # Business Logic
+    /// - 名前空間の親のパスから順に作成する
+    /// - 色は AUTO_TAG_COLORS から、既存のタグ数に応じて順に割り当てる
+    pub fn create_missing_tags(
+        conn: &mut SqliteConnection,
+        tag_names: &[String],
+    ) -> Result<Vec<String>, ServiceError> {
+        let mut created = Vec::new();
+        for name in tag_names {
+            let mut path = String::new();
+            for segment in name.split(TAG_PATH_SEPARATOR) {
+                if !path.is_empty() {
+                    path.push(TAG_PATH_SEPARATOR);
+                }
+                path.push_str(segment);
+
+                let exists = tags::table
+                    .filter(tags::name.eq(&path))
+                    .select(tags::id)
+                    .first::<String>(conn)
+                    .optional()?
+                    .is_some();
+                if exists {
+                    continue;
+                }
+
+                let tag_count = tags::table.count().get_result::<i64>(conn)?;
+                let color = AUTO_TAG_COLORS[tag_count as usize % AUTO_TAG_COLORS.len()];
+                Self::create_tag(
+                    conn,
+                    CreateTagRequest {
+                        name: path.clone(),
+                        color: Some(color.to_string()),
+                    },
+                )?;
+                created.push(path.clone());
+            }
+        }
+        Ok(created)
+    }
+
+    /// タグ名（パス）を正規化（各階層の前後の空白を除く）
+    ///
+    /// # Returns
+    /// * `Ok(String)` - 正規化した名前
+    /// * `Err(ServiceError::InvalidInput)` - 空の名前、または空の階層を含む
+    pub fn normalize_tag_name(name: &str) -> Result<String, ServiceError> {
+        if name.trim().is_empty() {
+            return Err(ServiceError::InvalidInput(
+                "タグ名は必須です".to_string(),
+            ));
+        }
+
+        let segments: Vec<&str> = name.split(TAG_PATH_SEPARATOR).map(str::trim).collect();
+        if segments.iter().any(|segment| segment.is_empty()) {
+            return Err(ServiceError::InvalidInput(format!(
+                "タグ名の階層に空の部分があります: {}",
+                name
+            )));
+        }
+
+        Ok(segments.join(&TAG_PATH_SEPARATOR.to_string()))
+    }
+
+    /// 名前がタグの別名として使われていないことを確認（内部用ヘルパー）
+    fn ensure_not_alias(conn: &mut SqliteConnection, name: &str) -> Result<(), ServiceError> {
+        let is_alias = tag_aliases::table
+            .find(name)
+            .select(tag_aliases::alias)
+            .first::<String>(conn)
+            .optional()?
+            .is_some();
+        if is_alias {
+            return Err(ServiceError::DuplicateTagAlias(name.to_string()));
+        }
+        Ok(())
+    }
+
+    /// タググループ名を検証（前後の空白を除いた名前を返す）
+    fn validate_group_name(name: &str) -> Result<String, ServiceError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(ServiceError::InvalidInput(
+                "タググループ名は必須です".to_string(),
+            ));
+        }
+        Ok(name.to_string())
+    }
+
+    /// グループのタグ（と追加するタグ）が複数付いたタスクが無いことを確認（内部用ヘルパー）
+    fn ensure_exclusive(
+        conn: &mut SqliteConnection,
+        group: &TagGroup,
+        adding_tag_id: Option<&str>,
+    ) -> Result<(), ServiceError> {
+        let mut tag_ids: Vec<String> = tags::table
+            .filter(tags::group_id.eq(&group.id))
+            .select(tags::id)
+            .load(conn)?;
+        tag_ids.extend(adding_tag_id.map(str::to_string));
+
+        let mut tag_counts: HashMap<String, usize> = HashMap::new();
+        for task_id in task_tags::table
+            .filter(task_tags::tag_id.eq_any(&tag_ids))
+            .select(task_tags::task_id)
+            .load::<String>(conn)?
+        {
+            *tag_counts.entry(task_id).or_default() += 1;
+        }
+
+        let conflicts = tag_counts.values().filter(|count| **count > 1).count();
+        if conflicts > 0 {
+            return Err(ServiceError::ExclusiveTagConflict(format!(
+                "{}: グループのタグが複数付いたタスクが{}件あります",
+                group.name, conflicts
+            )));
+        }
+        Ok(())
+    }
+
+    /// 親のパス（"work/clientA/billing" → "work/clientA"、ルートの場合はNone）
+    fn parent_path(name: &str) -> Option<&str> {
+        name.rsplit_once(TAG_PATH_SEPARATOR)
+            .map(|(parent, _)| parent)
+    }
+
+    /// パスのタグが存在しなければ親のパスから順に作成し、そのタグIDを返す（内部用ヘルパー）
+    ///
+    /// 作成するパスが別名として使われている場合は DuplicateTagAlias を返す
+    /// （別名と同じ名前のタグは、名前で指定しても別名の読み替えで届かなくなるため）
+    fn ensure_path(conn: &mut SqliteConnection, path: &str) -> Result<String, ServiceError> {
+        if let Some(tag_id) = tags::table
+            .filter(tags::name.eq(path))
+            .select(tags::id)
+            .first::<String>(conn)
+            .optional()?
+        {
+            return Ok(tag_id);
+        }
+        Self::ensure_not_alias(conn, path)?;
+
+        let parent_id = match Self::parent_path(path) {
+            Some(parent_path) => Some(Self::ensure_path(conn, parent_path)?),
+            None => None,
+        };
+        let mut new_tag = NewTag::from_request(CreateTagRequest {
+            name: path.to_string(),
+            color: None,
+        });
+        new_tag.parent_id = parent_id;
+        diesel::insert_into(tags::table)
+            .values(&new_tag)
+            .execute(conn)?;
+
+        Ok(new_tag.id)
+    }
+
+    /// 親が未設定の直下のパスのタグを子にする（内部用ヘルパー）
+    fn adopt_orphans(
+        conn: &mut SqliteConnection,
+        tag_id: &str,
+        name: &str,
+    ) -> Result<(), ServiceError> {
+        let orphans: Vec<(String, String)> = tags::table
+            .filter(tags::parent_id.is_null())
+            .filter(tags::id.ne(tag_id))
+            .select((tags::id, tags::name))
+            .load::<(String, String)>(conn)?;
+
+        for (orphan_id, orphan_name) in orphans {
+            if Self::parent_path(&orphan_name) == Some(name) {
+                diesel::update(tags::table.find(&orphan_id))
+                    .set(tags::parent_id.eq(tag_id))
+                    .execute(conn)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// タグを統合先へ統合して削除する（内部用ヘルパー）
+    fn merge_into(
+        conn: &mut SqliteConnection,
+        source: &Tag,
+        target: &Tag,
+    ) -> Result<(), ServiceError> {
+        // 子タグを統合先の下へ移す（同じ名前の子タグがあればそのタグへ統合）
+        let children = tags::table
+            .filter(tags::parent_id.eq(&source.id))
+            .load::<Tag>(conn)?;
+        for child in children {
+            let label = child
+                .name
+                .rsplit(TAG_PATH_SEPARATOR)
+                .next()
+                .unwrap_or(&child.name);
+            let new_name = format!("{}{}{}", target.name, TAG_PATH_SEPARATOR, label);
+            match tags::table
+                .filter(tags::name.eq(&new_name))
+                .first::<Tag>(conn)
+                .optional()?
+            {
+                Some(existing) => Self::merge_into(conn, &child, &existing)?,
+                None => {
+                    Self::rename_subtree(conn, &child, &new_name)?;
+                    diesel::update(tags::table.find(&child.id))
+                        .set((
+                            tags::name.eq(&new_name),
+                            tags::updated_at.eq(Utc::now().to_rfc3339()),
+                        ))
+                        .execute(conn)?;
+                }
+            }
+        }
+
+        // 統合元のタグが付いたタスクに統合先のタグを付ける（重複は無視）
+        let task_ids: Vec<String> = task_tags::table
+            .filter(task_tags::tag_id.eq(&source.id))
+            .select(task_tags::task_id)
+            .load::<String>(conn)?;
+        Self::ensure_exclusive_merge(conn, source, target, &task_ids)?;
+        let rows: Vec<_> = task_ids
+            .iter()
+            .map(|task_id| {
+                (
+                    task_tags::task_id.eq(task_id),
+                    task_tags::tag_id.eq(&target.id),
+                )
+            })
+            .collect();
+        diesel::insert_or_ignore_into(task_tags::table)
+            .values(&rows)
+            .execute(conn)?;
+
+        // 統合元のタグを付ける自動タグ付けルールは、統合先のタグを付けるようにする
+        let rule_ids: Vec<String> = auto_tag_rule_tags::table
+            .filter(auto_tag_rule_tags::tag_id.eq(&source.id))
+            .select(auto_tag_rule_tags::rule_id)
+            .load::<String>(conn)?;
+        let rows: Vec<_> = rule_ids
+            .iter()
+            .map(|rule_id| {
+                (
+                    auto_tag_rule_tags::rule_id.eq(rule_id),
+                    auto_tag_rule_tags::tag_id.eq(&target.id),
+                )
+            })
+            .collect();
+        diesel::insert_or_ignore_into(auto_tag_rule_tags::table)
+            .values(&rows)
+            .execute(conn)?;
+        diesel::delete(auto_tag_rule_tags::table.filter(auto_tag_rule_tags::tag_id.eq(&source.id)))
+            .execute(conn)?;
+
+        // 統合元の別名を統合先へ移す
+        diesel::update(tag_aliases::table.filter(tag_aliases::tag_id.eq(&source.id)))
+            .set(tag_aliases::tag_id.eq(&target.id))
+            .execute(conn)?;
+
+        // 統合元を削除（CASCADE により統合元の task_tags も削除される）
+        diesel::delete(tags::table.find(&source.id)).execute(conn)?;
+        Ok(())
+    }
+
+    /// 統合先が排他グループのタグの場合、統合元のタグが付いたタスクに
+    /// 同じグループの別のタグが付いていないことを確認（内部用ヘルパー）
+    fn ensure_exclusive_merge(
+        conn: &mut SqliteConnection,
+        source: &Tag,
+        target: &Tag,
+        task_ids: &[String],
+    ) -> Result<(), ServiceError> {
+        let Some(ref group_id) = target.group_id else {
+            return Ok(());
+        };
+        let group = tag_groups::table.find(group_id).first::<TagGroup>(conn)?;
+        if !group.exclusive {
+            return Ok(());
+        }
+
+        let mut names: Vec<String> = task_tags::table
+            .inner_join(tags::table)
+            .filter(task_tags::task_id.eq_any(task_ids))
+            .filter(tags::group_id.eq(group_id))
+            .filter(tags::id.ne(&source.id))
+            .filter(tags::id.ne(&target.id))
+            .select(tags::name)
+            .distinct()
+            .load(conn)?;
+        if names.is_empty() {
+            return Ok(());
+        }
+        names.push(target.name.clone());
+        names.sort();
+        Err(ServiceError::ExclusiveTagConflict(format!(
+            "{}: {}",
+            group.name,
+            names.join(", ")
+        )))
+    }
+
+    /// タグと子孫のタグの名前を変更し、親のタグを付け替える（内部用ヘルパー、自身の名前はupdate_tagで更新）
+    fn rename_subtree(
+        conn: &mut SqliteConnection,
+        tag: &Tag,
+        new_name: &str,
+    ) -> Result<(), ServiceError> {
+        let old_prefix = format!("{}{}", tag.name, TAG_PATH_SEPARATOR);
+        if new_name.starts_with(&old_prefix) {
+            return Err(ServiceError::InvalidInput(
+                "タグを自身の子孫の下へ移動することはできません".to_string(),
+            ));
+        }
+
+        let descendant_ids = Self::with_descendants(conn, vec![tag.id.clone()])?;
+        let descendants = tags::table
+            .filter(tags::id.eq_any(&descendant_ids))
+            .filter(tags::id.ne(&tag.id))
+            .load::<Tag>(conn)?;
+
+        // 変更後の名前が子孫以外のタグと重複しないか確認
+        let renames: Vec<(String, String)> =
+            std::iter::once((tag.id.clone(), new_name.to_string()))
+                .chain(descendants.iter().map(|descendant| {
+                    let suffix = descendant
+                        .name
+                        .strip_prefix(&tag.name)
+                        .unwrap_or(&descendant.name);
+                    (descendant.id.clone(), format!("{}{}", new_name, suffix))
+                }))
+                .collect();
+        for (_, name) in renames.iter().skip(1) {
+            Self::ensure_not_alias(conn, name)?;
+        }
+        let new_names: Vec<&String> = renames.iter().map(|(_, name)| name).collect();
+        if let Some(conflict) = tags::table
+            .filter(tags::name.eq_any(new_names))
+            .filter(tags::id.ne_all(&descendant_ids))
+            .select(tags::name)
+            .first::<String>(conn)
+            .optional()?
+        {
+            return Err(ServiceError::DuplicateEntry(conflict));
+        }
+
+        let now = Utc::now().to_rfc3339();
+        for (descendant_id, descendant_name) in renames.iter().skip(1) {
+            diesel::update(tags::table.find(descendant_id))
+                .set((tags::name.eq(descendant_name), tags::updated_at.eq(&now)))
+                .execute(conn)?;
+        }
+
+        let parent_id = match Self::parent_path(new_name) {
+            Some(parent_path) => Some(Self::ensure_path(conn, parent_path)?),
+            None => None,
+        };
+        diesel::update(tags::table.find(&tag.id))
+            .set(tags::parent_id.eq(parent_id))
+            .execute(conn)?;
+
+        Ok(())
+    }
+
+    /// ツリーのノードを作成し、子孫を含めたタスクIDの集合も返す（内部用ヘルパー）
+    fn build_tree_node(
+        tag: Tag,
+        children: &mut HashMap<Option<String>, Vec<Tag>>,
+        task_ids_by_tag: &HashMap<String, Vec<String>>,
+        aliases_by_tag: &mut HashMap<String, Vec<String>>,
+    ) -> (TagTreeNode, HashSet<String>) {
+        let mut task_ids: HashSet<String> = task_ids_by_tag
+            .get(&tag.id)
+            .into_iter()
+            .flatten()
+            .cloned()
+            .collect();
+
+        let child_nodes: Vec<TagTreeNode> = children
+            .remove(&Some(tag.id.clone()))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|child| {
+                let (node, child_task_ids) =
+                    Self::build_tree_node(child, children, task_ids_by_tag, aliases_by_tag);
+                task_ids.extend(child_task_ids);
+                node
+            })
+            .collect();
+
+        let label = tag
+            .name
+            .rsplit(TAG_PATH_SEPARATOR)
+            .next()
+            .unwrap_or(&tag.name)
+            .to_string();
+        let aliases = aliases_by_tag.remove(&tag.id).unwrap_or_default();
+        let node = TagTreeNode {
+            tag,
+            label,
+            total_usage_count: task_ids.len() as i32,
+            aliases,
+            children: child_nodes,
+        };
+        (node, task_ids)
+    }
 }
 
 #[cfg(test)]
@@ -196,6 +1092,7 @@ mod tests {
             name: Some("新しい名前".to_string()),
             color: Some("#0000FF".to_string()),
             updated_at: None,
+            merge: false,
         };
         let updated_tag = TagService::update_tag(&mut conn, &tag.id, update_req).unwrap();
 
@@ -210,6 +1107,7 @@ mod tests {
             name: Some("名前".to_string()),
             color: None,
             updated_at: None,
+            merge: false,
         };
 
         let result = TagService::update_tag(&mut conn, "non-existent-id", update_req);
@@ -260,6 +1158,7 @@ mod tests {
             description: None,
             parent_id: None,
             tags: vec![],
+            estimated_minutes: None,
         };
         let new_task = NewTask::from_request(task_req);
         diesel::insert_into(tasks::table)
@@ -304,4 +1203,558 @@ mod tests {
 
         assert_eq!(count_after, 0, "FOREIGN KEY CASCADE により task_tags レコードも削除されるはず");
     }
+
+    fn create(conn: &mut SqliteConnection, name: &str) -> Tag {
+        TagService::create_tag(
+            conn,
+            CreateTagRequest {
+                name: name.to_string(),
+                color: None,
+            },
+        )
+        .unwrap()
+    }
+
+    fn tag_names(conn: &mut SqliteConnection) -> Vec<String> {
+        let mut names: Vec<String> = TagService::list_tags(conn)
+            .unwrap()
+            .into_iter()
+            .map(|t| t.name)
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_create_tag_creates_namespace_ancestors() {
+        let mut conn = setup_test_db();
+        let billing = create(&mut conn, " work / clientA/billing ");
+        assert_eq!(billing.name, "work/clientA/billing");
+        assert_eq!(
+            tag_names(&mut conn),
+            vec!["work", "work/clientA", "work/clientA/billing"]
+        );
+
+        let client_a =
+            TagService::get_tag(&mut conn, billing.parent_id.as_deref().unwrap()).unwrap();
+        assert_eq!(client_a.name, "work/clientA");
+        let work = TagService::get_tag(&mut conn, client_a.parent_id.as_deref().unwrap()).unwrap();
+        assert!(work.parent_id.is_none());
+
+        // 既存の親のパスは再利用する
+        let client_b = create(&mut conn, "work/clientB");
+        assert_eq!(client_b.parent_id, Some(work.id.clone()));
+
+        for name in ["work//a", "/work", "work/"] {
+            let result = TagService::create_tag(
+                &mut conn,
+                CreateTagRequest {
+                    name: name.to_string(),
+                    color: None,
+                },
+            );
+            assert!(
+                matches!(result, Err(ServiceError::InvalidInput(_))),
+                "{}",
+                name
+            );
+        }
+
+        // 子タグを持つタグは削除できない
+        assert!(matches!(
+            TagService::delete_tag(&mut conn, &work.id),
+            Err(ServiceError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_list_tag_tree_rolls_up_usage_counts() {
+        use crate::models::task::CreateTaskRequest;
+        use crate::service::TaskService;
+
+        let mut conn = setup_test_db();
+        create(&mut conn, "work/clientA");
+        create(&mut conn, "work/clientB");
+        create(&mut conn, "home");
+
+        for tags in [
+            vec!["work/clientA"],
+            vec!["work/clientA", "work/clientB"],
+            vec!["work"],
+        ] {
+            TaskService::create_task(
+                &mut conn,
+                CreateTaskRequest {
+                    title: "タスク".to_string(),
+                    description: None,
+                    tags: tags.into_iter().map(String::from).collect(),
+                    parent_id: None,
+                    estimated_minutes: None,
+                },
+            )
+            .unwrap();
+        }
+
+        let tree = TagService::list_tag_tree(&mut conn).unwrap();
+        let roots: Vec<(&str, i32, i32)> = tree
+            .iter()
+            .map(|n| (n.label.as_str(), n.tag.usage_count, n.total_usage_count))
+            .collect();
+        assert_eq!(roots, vec![("home", 0, 0), ("work", 1, 3)]);
+
+        let children: Vec<(&str, i32)> = tree[1]
+            .children
+            .iter()
+            .map(|n| (n.label.as_str(), n.total_usage_count))
+            .collect();
+        assert_eq!(children, vec![("clientA", 2), ("clientB", 1)]);
+    }
+
+    #[test]
+    fn test_rename_namespace_cascades_to_descendants() {
+        let mut conn = setup_test_db();
+        let billing = create(&mut conn, "work/clientA/billing");
+        create(&mut conn, "job");
+        let work_id = billing.parent_id.clone().unwrap();
+        let work_id = TagService::get_tag(&mut conn, &work_id)
+            .unwrap()
+            .parent_id
+            .unwrap();
+
+        let rename = |conn: &mut SqliteConnection, tag_id: &str, name: &str| {
+            TagService::update_tag(
+                conn,
+                tag_id,
+                UpdateTagRequest {
+                    name: Some(name.to_string()),
+                    color: None,
+                    updated_at: None,
+                    merge: false,
+                },
+            )
+        };
+
+        // 変更後の名前が既存のタグと重複する場合は何も変更しない
+        assert!(matches!(
+            rename(&mut conn, &work_id, "job"),
+            Err(ServiceError::DuplicateEntry(_))
+        ));
+        // 自身の子孫の下へは移動できない
+        assert!(matches!(
+            rename(&mut conn, &work_id, "work/clientA/x"),
+            Err(ServiceError::InvalidInput(_))
+        ));
+
+        rename(&mut conn, &work_id, "office").unwrap();
+        assert_eq!(
+            tag_names(&mut conn),
+            vec!["job", "office", "office/clientA", "office/clientA/billing"]
+        );
+
+        // 別の名前空間へ移すと親も付け替わる（新しい親のパスは作成される）
+        let client_a =
+            TagService::get_tag(&mut conn, billing.parent_id.as_deref().unwrap()).unwrap();
+        let moved = rename(&mut conn, &client_a.id, "archive/2026/clientA").unwrap();
+        assert_eq!(
+            TagService::get_tag(&mut conn, moved.parent_id.as_deref().unwrap())
+                .unwrap()
+                .name,
+            "archive/2026"
+        );
+        assert_eq!(
+            TagService::get_tag(&mut conn, &billing.id).unwrap().name,
+            "archive/2026/clientA/billing"
+        );
+        assert_eq!(
+            TagService::with_descendants(&mut conn, vec![work_id.clone()]).unwrap(),
+            vec![work_id]
+        );
+    }
+
+    fn create_tagged_task(conn: &mut SqliteConnection, tags: &[&str]) -> String {
+        use crate::models::task::CreateTaskRequest;
+        use crate::service::TaskService;
+
+        TaskService::create_task(
+            conn,
+            CreateTaskRequest {
+                title: "タスク".to_string(),
+                description: None,
+                tags: tags.iter().map(|t| t.to_string()).collect(),
+                parent_id: None,
+                estimated_minutes: None,
+            },
+        )
+        .unwrap()
+        .id
+    }
+
+    #[test]
+    fn test_merge_tags_moves_task_tags_and_children() {
+        let mut conn = setup_test_db();
+        let bug = create(&mut conn, "bug");
+        let defect = create(&mut conn, "defect/ui");
+        let defect_id = defect.parent_id.clone().unwrap();
+        create(&mut conn, "bugs/ui");
+        let bugs_id = TagService::list_tags(&mut conn)
+            .unwrap()
+            .into_iter()
+            .find(|t| t.name == "bugs")
+            .unwrap()
+            .id;
+        create(&mut conn, "bugs/api");
+
+        // 両方のタグが付いたタスクは1件として数える
+        create_tagged_task(&mut conn, &["bug", "bugs"]);
+        create_tagged_task(&mut conn, &["bug"]);
+        create_tagged_task(&mut conn, &["defect"]);
+        let ui_task = create_tagged_task(&mut conn, &["defect/ui"]);
+
+        assert!(matches!(
+            TagService::merge_tags(&mut conn, &[], &bugs_id),
+            Err(ServiceError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            TagService::merge_tags(&mut conn, std::slice::from_ref(&bugs_id), &bugs_id),
+            Err(ServiceError::InvalidInput(_))
+        ));
+
+        let usage_count =
+            TagService::merge_tags(&mut conn, &[bug.id.clone(), defect_id], &bugs_id).unwrap();
+        assert_eq!(usage_count, 3);
+
+        // 同じ名前の子タグ（defect/ui → bugs/ui）は統合される
+        assert_eq!(tag_names(&mut conn), vec!["bugs", "bugs/api", "bugs/ui"]);
+        assert!(TagService::get_tag(&mut conn, &defect.id).is_err());
+        let ui_tags = crate::service::TaskService::get_task(&mut conn, &ui_task)
+            .unwrap()
+            .tags;
+        assert_eq!(ui_tags, vec!["bugs/ui".to_string()]);
+    }
+
+    #[test]
+    fn test_update_tag_rename_with_merge() {
+        let mut conn = setup_test_db();
+        let old = create(&mut conn, "old/child");
+        let old_id = old.parent_id.clone().unwrap();
+        let new = create(&mut conn, "new");
+        create_tagged_task(&mut conn, &["old"]);
+        create_tagged_task(&mut conn, &["old/child", "new"]);
+
+        let mut req = UpdateTagRequest {
+            name: Some("new".to_string()),
+            color: Some("#00FF00".to_string()),
+            updated_at: None,
+            merge: false,
+        };
+        let result = TagService::update_tag(&mut conn, &old_id, req);
+        assert!(matches!(result, Err(ServiceError::DuplicateEntry(_))));
+
+        req = UpdateTagRequest {
+            name: Some("new".to_string()),
+            color: Some("#00FF00".to_string()),
+            updated_at: None,
+            merge: true,
+        };
+        let merged = TagService::update_tag(&mut conn, &old_id, req).unwrap();
+        assert_eq!(merged.id, new.id);
+        assert_eq!(merged.usage_count, 2);
+        assert_eq!(merged.color, Some("#00FF00".to_string()));
+        assert_eq!(tag_names(&mut conn), vec!["new", "new/child"]);
+        assert!(TagService::get_tag(&mut conn, &old_id).is_err());
+    }
+
+    #[test]
+    fn test_usage_counts_follow_task_tags_and_status() {
+        use crate::models::task::UpdateTaskRequestInput;
+        use crate::service::TaskService;
+
+        let mut conn = setup_test_db();
+        let bug = create(&mut conn, "bug");
+        create(&mut conn, "ui");
+        let first = create_tagged_task(&mut conn, &["bug", "ui"]);
+        let second = create_tagged_task(&mut conn, &["bug"]);
+
+        let counts = |conn: &mut SqliteConnection| {
+            let tag = TagService::get_tag(conn, &bug.id).unwrap();
+            (
+                tag.usage_count,
+                tag.draft_count,
+                tag.active_count,
+                tag.completed_count,
+                tag.archived_count,
+            )
+        };
+        assert_eq!(counts(&mut conn), (2, 2, 0, 0, 0));
+
+        // タグを外すと減る
+        TaskService::update_task(
+            &mut conn,
+            &first,
+            UpdateTaskRequestInput {
+                title: None,
+                description: None,
+                status: None,
+                parent_id: None,
+                tags: Some(vec!["ui".to_string()]),
+            },
+        )
+        .unwrap();
+        assert_eq!(counts(&mut conn), (1, 1, 0, 0, 0));
+
+        // ステータスが変わると内訳が移る
+        for (status, expected) in [
+            ("completed", (1, 0, 0, 1, 0)),
+            ("archived", (1, 0, 0, 0, 1)),
+        ] {
+            diesel::update(crate::schema::tasks::table.find(&second))
+                .set(crate::schema::tasks::status.eq(status))
+                .execute(&mut conn)
+                .unwrap();
+            assert_eq!(counts(&mut conn), expected);
+        }
+
+        // 物理削除すると task_tags と一緒に消える
+        TaskService::delete_task_permanently(&mut conn, &second).unwrap();
+        assert_eq!(counts(&mut conn), (0, 0, 0, 0, 0));
+    }
+
+    #[test]
+    fn test_aliases_resolve_on_input_and_search() {
+        use crate::service::TaskService;
+
+        let mut conn = setup_test_db();
+        let meeting = create(&mut conn, "meeting");
+        let sync = create(&mut conn, "sync");
+        TagService::add_alias(&mut conn, &meeting.id, "mtg").unwrap();
+        TagService::add_alias(&mut conn, &meeting.id, " 会議 ").unwrap();
+        TagService::add_alias(&mut conn, &sync.id, "定例").unwrap();
+
+        // タグ名・別名と重複する名前は使えない
+        for result in [
+            TagService::add_alias(&mut conn, &sync.id, "mtg"),
+            TagService::add_alias(&mut conn, &sync.id, "meeting"),
+        ] {
+            assert!(matches!(result, Err(ServiceError::DuplicateTagAlias(_))));
+        }
+        let result = TagService::create_tag(
+            &mut conn,
+            CreateTagRequest {
+                name: "mtg".to_string(),
+                color: None,
+            },
+        );
+        assert!(matches!(result, Err(ServiceError::DuplicateTagAlias(_))));
+
+        // 自動作成される名前空間の親も別名と重複できない
+        let result = TagService::create_tag(
+            &mut conn,
+            CreateTagRequest {
+                name: "mtg/notes".to_string(),
+                color: None,
+            },
+        );
+        assert!(matches!(result, Err(ServiceError::DuplicateTagAlias(_))));
+        let result = TagService::create_missing_tags(&mut conn, &["mtg/notes".to_string()]);
+        assert!(matches!(result, Err(ServiceError::DuplicateTagAlias(_))));
+        assert_eq!(tag_names(&mut conn), vec!["meeting", "sync"]);
+
+        // 別名は正式なタグに読み替えられる（重複は1つにまとめる）
+        let task_id = create_tagged_task(&mut conn, &["mtg", "meeting", "会議"]);
+        let task = TaskService::get_task(&mut conn, &task_id).unwrap();
+        assert_eq!(task.tags, vec!["meeting".to_string()]);
+        let ids =
+            TaskService::search_task_ids(&mut conn, Some(vec!["会議".to_string()]), None).unwrap();
+        assert_eq!(ids, vec![task_id]);
+
+        // 統合すると統合元の別名は統合先へ移る
+        TagService::merge_tags(&mut conn, std::slice::from_ref(&sync.id), &meeting.id).unwrap();
+        let aliases: Vec<String> = TagService::list_aliases(&mut conn, &meeting.id)
+            .unwrap()
+            .into_iter()
+            .map(|a| a.alias)
+            .collect();
+        assert_eq!(aliases, vec!["mtg", "会議", "定例"]);
+
+        TagService::remove_alias(&mut conn, "mtg").unwrap();
+        TagService::remove_alias(&mut conn, " 定例 ").unwrap();
+        assert!(matches!(
+            TagService::remove_alias(&mut conn, "mtg"),
+            Err(ServiceError::TagAliasNotFound(_))
+        ));
+        let tree = TagService::list_tag_tree(&mut conn).unwrap();
+        assert_eq!(tree[0].aliases, vec!["会議"]);
+    }
+
+    fn create_group(conn: &mut SqliteConnection, name: &str, exclusive: bool) -> TagGroup {
+        TagService::create_tag_group(
+            conn,
+            CreateTagGroupRequest {
+                name: name.to_string(),
+                exclusive,
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_exclusive_tag_group_allows_one_tag_per_task() {
+        use crate::models::task::{CreateTaskRequest, UpdateTaskRequestInput};
+        use crate::service::TaskService;
+
+        let mut conn = setup_test_db();
+        let size = create_group(&mut conn, "サイズ", true);
+        let area = create_group(&mut conn, "領域", false);
+        for (name, group) in [("S", &size), ("M", &size), ("ui", &area), ("api", &area)] {
+            let tag = create(&mut conn, name);
+            TagService::set_tag_group(&mut conn, &tag.id, Some(&group.id)).unwrap();
+        }
+
+        let result = TaskService::create_task(
+            &mut conn,
+            CreateTaskRequest {
+                title: "タスク".to_string(),
+                description: None,
+                tags: vec!["S".to_string(), "M".to_string()],
+                parent_id: None,
+                estimated_minutes: None,
+            },
+        );
+        match result {
+            Err(ServiceError::ExclusiveTagConflict(detail)) => assert_eq!(detail, "サイズ: M, S"),
+            other => panic!("Expected ExclusiveTagConflict, got {:?}", other),
+        }
+
+        // 排他でないグループのタグは複数付けられる
+        let task_id = create_tagged_task(&mut conn, &["S", "ui", "api"]);
+        let result = TaskService::update_task(
+            &mut conn,
+            &task_id,
+            UpdateTaskRequestInput {
+                title: None,
+                description: None,
+                status: None,
+                parent_id: None,
+                tags: Some(vec!["S".to_string(), "M".to_string()]),
+            },
+        );
+        assert!(matches!(result, Err(ServiceError::ExclusiveTagConflict(_))));
+        let tags = TaskService::get_task(&mut conn, &task_id).unwrap().tags;
+        assert_eq!(tags.len(), 3);
+
+        let groups = TagService::list_tag_groups(&mut conn).unwrap();
+        assert_eq!(groups.len(), 2);
+        let size_tags: Vec<&str> = groups
+            .iter()
+            .find(|g| g.group.id == size.id)
+            .unwrap()
+            .tags
+            .iter()
+            .map(|t| t.name.as_str())
+            .collect();
+        assert_eq!(size_tags, vec!["M", "S"]);
+    }
+
+    #[test]
+    fn test_tag_group_rejects_existing_conflicts() {
+        let mut conn = setup_test_db();
+        let size = create_group(&mut conn, "サイズ", false);
+        let s = create(&mut conn, "S");
+        let m = create(&mut conn, "M");
+        let l = create(&mut conn, "L");
+        TagService::set_tag_group(&mut conn, &s.id, Some(&size.id)).unwrap();
+        TagService::set_tag_group(&mut conn, &m.id, Some(&size.id)).unwrap();
+        create_tagged_task(&mut conn, &["S", "M"]);
+        create_tagged_task(&mut conn, &["M", "L"]);
+
+        assert!(matches!(
+            create_group_result(&mut conn, "サイズ"),
+            Err(ServiceError::DuplicateTagGroupName(_))
+        ));
+
+        // 既に複数のタグが付いたタスクがあるので排他にできない
+        let exclusive = UpdateTagGroupRequest {
+            name: None,
+            exclusive: Some(true),
+        };
+        let result = TagService::update_tag_group(&mut conn, &size.id, exclusive);
+        assert!(matches!(result, Err(ServiceError::ExclusiveTagConflict(_))));
+
+        TagService::set_tag_group(&mut conn, &m.id, None).unwrap();
+        let exclusive = UpdateTagGroupRequest {
+            name: Some("大きさ".to_string()),
+            exclusive: Some(true),
+        };
+        let updated = TagService::update_tag_group(&mut conn, &size.id, exclusive).unwrap();
+        assert!(updated.exclusive);
+        assert_eq!(updated.name, "大きさ");
+
+        // Lを入れると「M, L」のタスクは問題ないが、Mを戻すと「S, M」のタスクが違反する
+        TagService::set_tag_group(&mut conn, &l.id, Some(&size.id)).unwrap();
+        let result = TagService::set_tag_group(&mut conn, &m.id, Some(&size.id));
+        assert!(matches!(result, Err(ServiceError::ExclusiveTagConflict(_))));
+
+        // グループを削除するとタグはグループから外れる
+        TagService::delete_tag_group(&mut conn, &size.id).unwrap();
+        assert!(TagService::get_tag(&mut conn, &s.id)
+            .unwrap()
+            .group_id
+            .is_none());
+        assert!(matches!(
+            TagService::get_tag_group(&mut conn, &size.id),
+            Err(ServiceError::TagGroupNotFound(_))
+        ));
+    }
+
+    fn create_group_result(
+        conn: &mut SqliteConnection,
+        name: &str,
+    ) -> Result<TagGroup, ServiceError> {
+        TagService::create_tag_group(
+            conn,
+            CreateTagGroupRequest {
+                name: name.to_string(),
+                exclusive: false,
+            },
+        )
+    }
+
+    #[test]
+    fn test_merge_tags_respects_exclusive_groups() {
+        let mut conn = setup_test_db();
+        let size = create_group(&mut conn, "サイズ", true);
+        let small = create(&mut conn, "small");
+        let m = create(&mut conn, "M");
+        let l = create(&mut conn, "L");
+        TagService::set_tag_group(&mut conn, &m.id, Some(&size.id)).unwrap();
+        TagService::set_tag_group(&mut conn, &l.id, Some(&size.id)).unwrap();
+        create_tagged_task(&mut conn, &["small"]);
+        let task_id = create_tagged_task(&mut conn, &["small", "L"]);
+
+        // 統合するとタスクにMとLの両方が付くので統合できない
+        let result = TagService::merge_tags(&mut conn, std::slice::from_ref(&small.id), &m.id);
+        match result {
+            Err(ServiceError::ExclusiveTagConflict(detail)) => assert_eq!(detail, "サイズ: L, M"),
+            other => panic!("Expected ExclusiveTagConflict, got {:?}", other),
+        }
+        let req = UpdateTagRequest {
+            name: Some("M".to_string()),
+            color: None,
+            updated_at: None,
+            merge: true,
+        };
+        let result = TagService::update_tag(&mut conn, &small.id, req);
+        assert!(matches!(result, Err(ServiceError::ExclusiveTagConflict(_))));
+        assert!(TagService::get_tag(&mut conn, &small.id).is_ok());
+
+        // 同じグループのタグ同士の統合は置き換えになるので統合できる
+        let mut tags = crate::service::TaskService::get_task(&mut conn, &task_id)
+            .unwrap()
+            .tags;
+        tags.sort();
+        assert_eq!(tags, vec!["L".to_string(), "small".to_string()]);
+        let usage_count =
+            TagService::merge_tags(&mut conn, std::slice::from_ref(&l.id), &m.id).unwrap();
+        assert_eq!(usage_count, 1);
+    }
 }
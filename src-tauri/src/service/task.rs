@@ -1,14 +1,28 @@
+use std::collections::{HashMap, HashSet};
+
 use chrono::Utc;
 use diesel::prelude::*;
+use diesel::sqlite::Sqlite;
 use diesel::SqliteConnection;
 
 use crate::error::ServiceError;
 use crate::models::task::{
-    CreateTaskRequest, ListTasksPaginatedParams, NewTask, PaginatedTaskResponse,
-    SearchTasksParams, Task, TaskHierarchyResponse, TaskResponse, TaskStatus, UpdateTaskRequest,
-    UpdateTaskRequestInput,
+    CreateTaskRequest, CursorDirection, ListTasksPaginatedParams, NewTask, PaginatedTaskResponse,
+    SearchTasksParams, Task, TaskCursor, TaskHierarchyDelta, TaskHierarchyResponse, TaskResponse,
+    TaskStatus, UpdateTaskRequest, UpdateTaskRequestInput,
 };
-use crate::schema::{task_tags, tags, tasks};
+use crate::schema::{deferred_tasks, tags, task_tags, tasks};
+use crate::service::auto_tag::AutoTagService;
+use crate::service::tag::TagService;
+
+/// 一括取得時のIN句チャンクサイズ（SQLiteのバインド変数上限対策）
+const BATCH_CHUNK_SIZE: usize = 500;
+
+/// 見積もり時間の上限（分）
+const MAX_ESTIMATED_MINUTES: i32 = 24 * 60;
+
+/// ページ取得結果（タスク, 次ページカーソル, 前ページカーソル）
+type TaskPage = (Vec<Task>, Option<String>, Option<String>);
 
 /// TaskService: タスクCRUD操作を提供
 pub struct TaskService;
@@ -31,78 +45,106 @@ impl TaskService {
         conn: &mut SqliteConnection,
         req: CreateTaskRequest,
     ) -> Result<TaskResponse, ServiceError> {
-        // バリデーション: タイトルが空でないこと
-        if req.title.trim().is_empty() {
-            return Err(ServiceError::InvalidInput(
-                "タイトルは必須です".to_string(),
-            ));
-        }
+        Self::create_task_with_options(conn, req, false)
+    }
 
-        // バリデーション: 親タスクが存在するか確認（指定されている場合）
-        if let Some(ref parent_id) = req.parent_id {
-            let parent_exists = tasks::table
-                .find(parent_id)
-                .select(tasks::id)
-                .first::<String>(conn)
-                .optional()?;
+    /// 新規タスクを作成（存在しないタグの自動作成を指定可能）
+    ///
+    /// # Arguments
+    /// * `auto_create_tags` - trueの場合、存在しないタグを作成してから関連付ける（色は自動で割り当てる）
+    ///
+    /// # Returns
+    /// * `Ok(TaskResponse)` - 作成されたタスク（新規作成したタグは `created_tags` に入る）
+    /// * `Err(ServiceError::TagNotFound)` - 存在しないタグが指定された（auto_create_tags = false の場合）
+    ///
+    /// # Notes
+    /// - タスクの作成とタグの作成・関連付けは1つのトランザクションで行う（エラー時は何も作成されない）
+    /// - 自動タグ付けルールに一致した場合、ルールのタグも付ける
+    pub fn create_task_with_options(
+        conn: &mut SqliteConnection,
+        req: CreateTaskRequest,
+        auto_create_tags: bool,
+    ) -> Result<TaskResponse, ServiceError> {
+        conn.transaction::<_, ServiceError, _>(|conn| {
+            // バリデーション: タイトルが空でないこと
+            if req.title.trim().is_empty() {
+                return Err(ServiceError::InvalidInput(
+                    "タイトルは必須です".to_string(),
+                ));
+            }
 
-            if parent_exists.is_none() {
-                return Err(ServiceError::ParentTaskNotFound(parent_id.clone()));
+            // バリデーション: 親タスクが存在するか確認（指定されている場合）
+            if let Some(ref parent_id) = req.parent_id {
+                let parent_exists = tasks::table
+                    .find(parent_id)
+                    .select(tasks::id)
+                    .first::<String>(conn)
+                    .optional()?;
+
+                if parent_exists.is_none() {
+                    return Err(ServiceError::ParentTaskNotFound(parent_id.clone()));
+                }
+
+                // 【新規追加】階層深度チェック（孫タスク作成禁止 - BR-016）
+                Self::validate_hierarchy_depth(conn, parent_id)?;
             }
 
-            // 【新規追加】階層深度チェック（孫タスク作成禁止 - BR-016）
-            Self::validate_hierarchy_depth(conn, parent_id)?;
-        }
+            // バリデーション: 見積もり時間（指定されている場合）
+            Self::validate_estimate(req.estimated_minutes)?;
 
-        // NewTask作成
-        let new_task = NewTask::from_request(req.clone());
-        let task_id = new_task.id.clone();
+            // タグ名を解決（別名の読み替え・タグの自動作成・排他グループのチェック）
+            let (tag_names, created_tags) =
+                Self::resolve_task_tags(conn, &req.tags, auto_create_tags)?;
 
-        // タスク挿入
-        diesel::insert_into(tasks::table)
-            .values(&new_task)
-            .execute(conn)?;
+            // NewTask作成
+            let new_task = NewTask::from_request(req.clone());
+            let task_id = new_task.id.clone();
 
-        // タグの関連付け（tags配列が空でない場合）
-        if !req.tags.is_empty() {
-            // task_tags中間テーブルに挿入
-            for tag_name in &req.tags {
-                // タグが存在するか確認（簡易実装: 外部キー制約に任せる）
-                // TODO: TagServiceと連携してタグのusage_countをインクリメント
-                use crate::schema::tags;
-
-                // タグIDを取得
-                let tag_id_result = tags::table
-                    .filter(tags::name.eq(tag_name))
-                    .select(tags::id)
-                    .first::<String>(conn)
-                    .optional()?;
+            // タスク挿入
+            diesel::insert_into(tasks::table)
+                .values(&new_task)
+                .execute(conn)?;
+
+            // タグの関連付け（tags配列が空でない場合）
+            if !tag_names.is_empty() {
+                // task_tags中間テーブルに挿入
+                for tag_name in &tag_names {
+                    // タグが存在するか確認（簡易実装: 外部キー制約に任せる）
+                    // usage_count は task_tags のトリガーで更新される
+                    use crate::schema::tags;
+
+                    // タグIDを取得
+                    let tag_id_result = tags::table
+                        .filter(tags::name.eq(tag_name))
+                        .select(tags::id)
+                        .first::<String>(conn)
+                        .optional()?;
 
-                if let Some(tag_id) = tag_id_result {
-                    // task_tags挿入
-                    diesel::insert_into(task_tags::table)
-                        .values((
-                            task_tags::task_id.eq(&task_id),
-                            task_tags::tag_id.eq(&tag_id),
-                        ))
-                        .execute(conn)?;
-                } else {
-                    return Err(ServiceError::TagNotFound(tag_name.clone()));
+                    if let Some(tag_id) = tag_id_result {
+                        // task_tags挿入
+                        diesel::insert_into(task_tags::table)
+                            .values((
+                                task_tags::task_id.eq(&task_id),
+                                task_tags::tag_id.eq(&tag_id),
+                            ))
+                            .execute(conn)?;
+                    } else {
+                        return Err(ServiceError::TagNotFound(tag_name.clone()));
+                    }
                 }
             }
-        }
 
-        // 作成されたタスクを取得
-        let created_task = tasks::table.find(&task_id).first::<Task>(conn)?;
+            // 自動タグ付けルールを適用
+            let mut tag_names = tag_names;
+            tag_names.extend(AutoTagService::apply_to_task(conn, &task_id)?);
 
-        // タグ名リストを取得
-        let tag_names = if req.tags.is_empty() {
-            Vec::new()
-        } else {
-            req.tags
-        };
+            // 作成されたタスクを取得
+            let created_task = tasks::table.find(&task_id).first::<Task>(conn)?;
 
-        Ok(created_task.with_tags(tag_names))
+            let mut response = created_task.with_tags(tag_names);
+            response.created_tags = created_tags;
+            Ok(response)
+        })
     }
 
     /// タスクを複製する（親タスクの場合は全ての子タスクも再帰的に複製）
@@ -150,6 +192,7 @@ impl TaskService {
             description: source_task.description.clone(),
             tags: source_task.tags.clone(),
             parent_id: None, // 新しい親タスクは親を持たない
+            estimated_minutes: source_task.estimated_minutes,
         };
 
         // Step 5: 親タスクを作成（create_taskを再利用）
@@ -164,6 +207,7 @@ impl TaskService {
                     description: child.description.clone(),
                     tags: child.tags.clone(),
                     parent_id: Some(new_parent.id.clone()), // 新しい親タスクにリンク
+                    estimated_minutes: child.estimated_minutes,
                 };
 
                 // 子タスクを作成（create_taskを再利用）
@@ -216,26 +260,24 @@ impl TaskService {
         // タスク取得
         let tasks = query.order(tasks::created_at.desc()).load::<Task>(conn)?;
 
-        // 各タスクにタグと子タスクIDを追加
-        tasks
-            .into_iter()
-            .map(|task| Self::enrich_task_response(conn, task))
-            .collect()
+        // 各タスクにタグ・子タスクID・親タイトルを一括付与
+        Self::enrich_task_responses(conn, tasks)
     }
 
     /// タスク一覧を取得（ページネーション対応）
     ///
     /// # Arguments
     /// * `conn` - データベース接続
-    /// * `params` - ページネーションパラメータ（status, limit, offset）
+    /// * `params` - ページネーションパラメータ（status, limit, offset, cursor）
     ///
     /// # Returns
-    /// * `PaginatedTaskResponse` - タスクリストと総件数
+    /// * `PaginatedTaskResponse` - タスクリスト・総件数・前後ページのカーソル
     ///
     /// # Examples
     /// - デフォルト（limit=20, offset=0）で Draft + Active を取得
     /// - CompletedPage/ArchivedPageでページネーション表示
     /// - REQ-0024: ページネーション機能実装
+    /// - cursor指定時はoffsetを無視し、カーソル位置から取得
     pub fn list_tasks_paginated(
         conn: &mut SqliteConnection,
         params: ListTasksPaginatedParams,
@@ -248,77 +290,39 @@ impl TaskService {
         let status_filter = if let Some(statuses) = params.status {
             if statuses.is_empty() {
                 // 空配列の場合、空の結果を返す
-                return Ok(PaginatedTaskResponse {
-                    tasks: Vec::new(),
-                    total: 0,
-                });
+                return Ok(PaginatedTaskResponse::empty());
             }
             Some(statuses)
         } else {
             None
         };
 
-        // 総件数取得用クエリ
-        let mut count_query = tasks::table.into_boxed();
-        if let Some(ref statuses) = status_filter {
-            count_query = count_query.filter(tasks::status.eq_any(statuses));
-        } else {
-            // デフォルト: Draft + Active（後方互換性維持）
-            count_query =
-                count_query.filter(tasks::status.eq("draft").or(tasks::status.eq("active")));
-        }
-        let total = count_query.count().get_result::<i64>(conn)?;
-
-        // タスク取得用クエリ
-        let mut data_query = tasks::table.into_boxed();
-        if let Some(statuses) = status_filter {
-            data_query = data_query.filter(tasks::status.eq_any(statuses));
-        } else {
-            // デフォルト: Draft + Active（後方互換性維持）
-            data_query = data_query.filter(tasks::status.eq("draft").or(tasks::status.eq("active")));
-        }
-
-        // タスク取得（limit/offset適用）
-        let tasks = data_query
-            .order(tasks::created_at.desc())
-            .limit(limit)
-            .offset(offset)
-            .load::<Task>(conn)?;
+        // フィルタ適用済みクエリ（総件数・データ取得で共用）
+        let build_query = || {
+            let query = tasks::table.into_boxed();
+            if let Some(ref statuses) = status_filter {
+                query.filter(tasks::status.eq_any(statuses.clone()))
+            } else {
+                // デフォルト: Draft + Active（後方互換性維持）
+                query.filter(tasks::status.eq("draft").or(tasks::status.eq("active")))
+            }
+        };
 
-        // 親タイトルのバッチ取得（パフォーマンス最適化）
-        use std::collections::HashMap;
-        let parent_ids: Vec<String> = tasks
-            .iter()
-            .filter_map(|t| t.parent_id.as_ref())
-            .cloned()
-            .collect();
+        // 総件数取得
+        let total = build_query().count().get_result::<i64>(conn)?;
 
-        let parent_titles: HashMap<String, String> = if !parent_ids.is_empty() {
-            tasks::table
-                .filter(tasks::id.eq_any(parent_ids))
-                .select((tasks::id, tasks::title))
-                .load::<(String, String)>(conn)?
-                .into_iter()
-                .collect()
-        } else {
-            HashMap::new()
-        };
+        // タスク取得（offset または cursor 適用）
+        let (tasks, next_cursor, prev_cursor) =
+            Self::load_task_page(conn, &build_query, limit, offset, params.cursor.as_deref())?;
 
-        // 各タスクにタグ・子タスクID・親タイトルを追加
-        let enriched_tasks: Result<Vec<TaskResponse>, ServiceError> = tasks
-            .into_iter()
-            .map(|task| {
-                let parent_title = task
-                    .parent_id
-                    .as_ref()
-                    .and_then(|pid| parent_titles.get(pid).cloned());
-                Self::enrich_task_response_with_parent_title(conn, task, parent_title)
-            })
-            .collect();
+        // 各タスクにタグ・子タスクID・親タイトルを一括付与
+        let enriched_tasks = Self::enrich_task_responses(conn, tasks)?;
 
         Ok(PaginatedTaskResponse {
-            tasks: enriched_tasks?,
+            tasks: enriched_tasks,
             total,
+            next_cursor,
+            prev_cursor,
         })
     }
 
@@ -328,93 +332,191 @@ impl TaskService {
     /// * Draft または Active なルートタスク（parentId なし）の配列
     /// * 各タスクは children フィールドに子タスクを含む
     /// * 子タスクは Draft、Active、Completed を含む（Archived は除外）
+    /// * 延期中（defer_queue_entry）のタスクは含まない
     ///
     /// # Note
     /// BR-016により階層は最大2層（親-子のみ、孫タスク禁止）
+    /// 対象タスクとタグを一括取得し、メモリ上でツリーを組み立てる（クエリ数は一定）
     pub fn get_hierarchy(
         conn: &mut SqliteConnection,
     ) -> Result<Vec<TaskHierarchyResponse>, ServiceError> {
-        // Step 1: Draft + Active なルートタスク（親なし）を取得
-        let root_tasks = tasks::table
+        Self::build_hierarchy(conn, None)
+    }
+
+    /// 指定時刻以降に変更されたサブツリーのみを取得（差分更新用）
+    ///
+    /// # Arguments
+    /// * `conn` - データベース接続
+    /// * `since` - 前回取得時の `synced_at`（RFC3339形式）
+    ///
+    /// # Returns
+    /// * `Ok(TaskHierarchyDelta)` - 変更されたサブツリー、プールから外れたルートタスクID、
+    ///   子に移動した可能性のあるタスクID
+    /// * `Err(ServiceError)` - エラー
+    ///
+    /// # Notes
+    /// - ルートまたはいずれかの子の updated_at が since より新しいサブツリーを返す
+    /// - 子タスクの変更時は親の updated_at も更新されるため（BR-013）、親単位で検出できる
+    /// - since はUTCに変換し、updated_at と同じ形式にそろえてから比較する
+    /// - 他のタスクの子に移動したルートを検出できるよう、since 以降に変更された子タスクの
+    ///   IDを moved_under_parent_ids で返す（移動の履歴は残らないため、内容だけ変更された
+    ///   子タスクも含む。クライアントは同じIDのルートがあれば取り除き、無ければ無視する）
+    /// - 物理削除されたタスクは検出できないため、定期的に get_hierarchy で全件取得すること
+    pub fn get_hierarchy_since(
+        conn: &mut SqliteConnection,
+        since: &str,
+    ) -> Result<TaskHierarchyDelta, ServiceError> {
+        let since = match chrono::DateTime::parse_from_rfc3339(since) {
+            Ok(since) => since.with_timezone(&Utc).to_rfc3339(),
+            Err(_) => {
+                return Err(ServiceError::InvalidInput(format!(
+                    "不正なタイムスタンプです: {}",
+                    since
+                )))
+            }
+        };
+        let since = since.as_str();
+
+        // 取得開始時刻を次回の since として返す
+        let synced_at = Utc::now().to_rfc3339();
+
+        let changed = Self::build_hierarchy(conn, Some(since))?;
+
+        // 完了・アーカイブ・延期等でプールから外れたルートタスク
+        let removed_root_ids = tasks::table
             .filter(tasks::parent_id.is_null())
-            .filter(tasks::status.eq("draft").or(tasks::status.eq("active")))
+            .filter(tasks::updated_at.gt(since))
+            .filter(
+                tasks::status
+                    .ne("draft")
+                    .and(tasks::status.ne("active"))
+                    .or(tasks::id.eq_any(deferred_tasks::table.select(deferred_tasks::task_id))),
+            )
+            .order(tasks::id.asc())
+            .select(tasks::id)
+            .load::<String>(conn)?;
+
+        // 他のタスクの子に移動した可能性のあるタスク（since 以降に変更された子タスク）
+        let moved_under_parent_ids = tasks::table
+            .filter(tasks::parent_id.is_not_null())
+            .filter(tasks::updated_at.gt(since))
+            .order(tasks::id.asc())
+            .select(tasks::id)
+            .load::<String>(conn)?;
+
+        Ok(TaskHierarchyDelta {
+            changed,
+            removed_root_ids,
+            moved_under_parent_ids,
+            synced_at,
+        })
+    }
+
+    /// 階層ツリーを一括構築（get_hierarchy / get_hierarchy_since 共用）
+    ///
+    /// # Arguments
+    /// * `conn` - データベース接続
+    /// * `since` - 指定時、この時刻以降に変更されたサブツリーのみを対象とする
+    ///
+    /// # Behavior
+    /// 1. 対象タスク（親: Draft/Active、子: Draft/Active/Completed、延期中を除く）を1クエリで取得
+    /// 2. 子タスクを親IDごとにグルーピング
+    /// 3. since 指定時は変更のあるサブツリーに絞り込む
+    /// 4. 残ったタスクのタグを一括取得してツリーを組み立てる
+    fn build_hierarchy(
+        conn: &mut SqliteConnection,
+        since: Option<&str>,
+    ) -> Result<Vec<TaskHierarchyResponse>, ServiceError> {
+        // Step 1: 対象タスクを一括取得（search_task_idsのデフォルト条件と同じ）
+        let candidates = tasks::table
+            .filter(
+                (tasks::parent_id
+                    .is_null()
+                    .and(tasks::status.eq("draft").or(tasks::status.eq("active"))))
+                .or(tasks::parent_id.is_not_null().and(
+                    tasks::status
+                        .eq("draft")
+                        .or(tasks::status.eq("active"))
+                        .or(tasks::status.eq("completed")),
+                )),
+            )
+            .filter(tasks::id.ne_all(deferred_tasks::table.select(deferred_tasks::task_id)))
             .order(tasks::created_at.desc())
             .load::<Task>(conn)?;
 
-        // Step 2: 各ルートタスクに対して子タスクを取得し、階層構造を構築
-        let hierarchy: Result<Vec<TaskHierarchyResponse>, ServiceError> = root_tasks
+        // Step 2: ルートと子に振り分け（created_at DESC の順序を維持）
+        let mut roots: Vec<Task> = Vec::new();
+        let mut children_by_parent: HashMap<String, Vec<Task>> = HashMap::new();
+        for task in candidates {
+            match task.parent_id.clone() {
+                Some(parent_id) => children_by_parent.entry(parent_id).or_default().push(task),
+                None => roots.push(task),
+            }
+        }
+
+        // Step 3: 差分モードでは変更のあるサブツリーのみ残す
+        if let Some(since) = since {
+            roots.retain(|root| {
+                root.updated_at.as_str() > since
+                    || children_by_parent.get(&root.id).is_some_and(|children| {
+                        children.iter().any(|c| c.updated_at.as_str() > since)
+                    })
+            });
+        }
+
+        // Step 4: 残ったタスクのタグを一括取得
+        let mut node_ids: Vec<String> = Vec::new();
+        for root in &roots {
+            node_ids.push(root.id.clone());
+            if let Some(children) = children_by_parent.get(&root.id) {
+                node_ids.extend(children.iter().map(|c| c.id.clone()));
+            }
+        }
+        let mut tag_names = Self::load_tag_names(conn, &node_ids)?;
+
+        // Step 5: ツリーを組み立てる
+        roots
             .into_iter()
-            .map(|parent_task| {
-                // 親タスクのタグを取得
-                let parent_tags = task_tags::table
-                    .inner_join(tags::table)
-                    .filter(task_tags::task_id.eq(&parent_task.id))
-                    .select(tags::name)
-                    .load::<String>(conn)?;
-
-                // 子タスクを取得（Draft, Active, Completed のみ）
-                let child_tasks = tasks::table
-                    .filter(tasks::parent_id.eq(&parent_task.id))
-                    .filter(
-                        tasks::status
-                            .eq("draft")
-                            .or(tasks::status.eq("active"))
-                            .or(tasks::status.eq("completed")),
-                    )
-                    .order(tasks::created_at.desc())
-                    .load::<Task>(conn)?;
-
-                // 子タスクを TaskHierarchyResponse に変換
-                let children: Result<Vec<TaskHierarchyResponse>, ServiceError> = child_tasks
+            .map(|root| {
+                let children = children_by_parent
+                    .remove(&root.id)
+                    .unwrap_or_default()
                     .into_iter()
-                    .map(|child_task| {
-                        // 子タスクのタグを取得
-                        let child_tags = task_tags::table
-                            .inner_join(tags::table)
-                            .filter(task_tags::task_id.eq(&child_task.id))
-                            .select(tags::name)
-                            .load::<String>(conn)?;
-
-                        Ok(TaskHierarchyResponse {
-                            id: child_task.id,
-                            title: child_task.title,
-                            description: child_task.description,
-                            status: TaskStatus::from_str(&child_task.status)
-                                .ok_or_else(|| {
-                                    ServiceError::InvalidTaskStatus(format!(
-                                        "Invalid status: {}",
-                                        child_task.status
-                                    ))
-                                })?,
-                            tags: child_tags,
-                            created_at: child_task.created_at,
-                            updated_at: child_task.updated_at,
-                            parent_id: child_task.parent_id,
-                            children: Vec::new(), // 孫タスク禁止（BR-016）
-                        })
+                    .map(|child| {
+                        let child_tags = tag_names.remove(&child.id).unwrap_or_default();
+                        // 孫タスク禁止（BR-016）
+                        Self::to_hierarchy_node(child, child_tags, Vec::new())
                     })
-                    .collect();
-
-                Ok(TaskHierarchyResponse {
-                    id: parent_task.id,
-                    title: parent_task.title,
-                    description: parent_task.description,
-                    status: TaskStatus::from_str(&parent_task.status).ok_or_else(|| {
-                        ServiceError::InvalidTaskStatus(format!(
-                            "Invalid status: {}",
-                            parent_task.status
-                        ))
-                    })?,
-                    tags: parent_tags,
-                    created_at: parent_task.created_at,
-                    updated_at: parent_task.updated_at,
-                    parent_id: parent_task.parent_id,
-                    children: children?,
-                })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                let root_tags = tag_names.remove(&root.id).unwrap_or_default();
+                Self::to_hierarchy_node(root, root_tags, children)
             })
-            .collect();
+            .collect()
+    }
 
-        hierarchy
+    /// TaskをTaskHierarchyResponseに変換
+    fn to_hierarchy_node(
+        task: Task,
+        tags: Vec<String>,
+        children: Vec<TaskHierarchyResponse>,
+    ) -> Result<TaskHierarchyResponse, ServiceError> {
+        let status = TaskStatus::from_str(&task.status).ok_or_else(|| {
+            ServiceError::InvalidTaskStatus(format!("Invalid status: {}", task.status))
+        })?;
+
+        Ok(TaskHierarchyResponse {
+            id: task.id,
+            title: task.title,
+            description: task.description,
+            status,
+            tags,
+            parent_id: task.parent_id,
+            created_at: task.created_at,
+            updated_at: task.updated_at,
+            estimated_minutes: task.estimated_minutes,
+            children,
+        })
     }
 
     /// タスク検索（フィルタ・キーワード対応、ページネーション対応）
@@ -430,9 +532,11 @@ impl TaskService {
     /// # Search Logic
     /// - q: タイトル・説明文のLIKE検索（部分一致）
     /// - status: ステータスフィルタ（未指定時はarchived以外）
-    /// - tags: タグ名のOR条件フィルタ
+    /// - tags: タグ名フィルタ（同じグループのタグ同士・グループに属さないタグ同士はOR条件、
+    ///   グループ同士はAND条件。名前空間のタグは子孫のタグも含む）
     /// - limit: 1ページあたりの件数（デフォルト: 100）
     /// - offset: スキップする件数（デフォルト: 0）
+    /// - cursor: ページングカーソル（指定時はoffsetより優先）
     /// - 全パラメータは任意かつ組み合わせ可能
     pub fn search_tasks(
         conn: &mut SqliteConnection,
@@ -442,118 +546,59 @@ impl TaskService {
         let limit = params.limit.unwrap_or(100);
         let offset = params.offset.unwrap_or(0);
 
-        // === COUNT QUERY ===
-        let mut count_query = tasks::table.into_boxed();
-
-        // キーワード検索フィルタ適用
-        if let Some(ref keyword) = params.q {
-            if !keyword.trim().is_empty() {
-                let pattern = format!("%{}%", keyword);
-                count_query = count_query.filter(
-                    tasks::title
-                        .like(pattern.clone())
-                        .or(tasks::description.like(pattern)),
-                );
-            }
-        }
-
-        // ステータスフィルタ適用
-        if let Some(ref status) = params.status {
-            count_query = count_query.filter(tasks::status.eq(status));
-        } else {
-            count_query = count_query.filter(tasks::status.ne("archived"));
-        }
-
-        // タグフィルタ適用（既存ロジック再利用）
-        let tag_ids_for_count = if let Some(ref tag_names) = params.tags {
-            if !tag_names.is_empty() {
-                let tag_ids: Vec<String> = tags::table
-                    .filter(tags::name.eq_any(tag_names))
-                    .select(tags::id)
-                    .load::<String>(conn)?;
-
-                if tag_ids.is_empty() {
-                    // タグが見つからない場合は空の結果
-                    return Ok(PaginatedTaskResponse {
-                        tasks: Vec::new(),
-                        total: 0,
-                    });
-                }
-
-                count_query = count_query.filter(
-                    tasks::id.eq_any(
-                        task_tags::table
-                            .filter(task_tags::tag_id.eq_any(tag_ids.clone()))
-                            .select(task_tags::task_id),
-                    ),
-                );
-
-                Some(tag_ids)
-            } else {
-                None
-            }
-        } else {
-            None
+        // 検索条件を解決（存在しないタグのみ指定された場合は空の結果）
+        let Some(filter) = SearchFilter::resolve(conn, &params)? else {
+            return Ok(PaginatedTaskResponse::empty());
         };
+        let build_query = || filter.query();
 
         // 総件数取得
-        let total = count_query.count().get_result::<i64>(conn)?;
-
-        // === DATA QUERY ===
-        let mut data_query = tasks::table.into_boxed();
-
-        // 同じフィルタを再適用
-        if let Some(ref keyword) = params.q {
-            if !keyword.trim().is_empty() {
-                let pattern = format!("%{}%", keyword);
-                data_query = data_query.filter(
-                    tasks::title
-                        .like(pattern.clone())
-                        .or(tasks::description.like(pattern)),
-                );
-            }
-        }
-
-        if let Some(ref status) = params.status {
-            data_query = data_query.filter(tasks::status.eq(status));
-        } else {
-            data_query = data_query.filter(tasks::status.ne("archived"));
-        }
-
-        if let Some(tag_ids) = tag_ids_for_count {
-            data_query = data_query.filter(
-                tasks::id.eq_any(
-                    task_tags::table
-                        .filter(task_tags::tag_id.eq_any(tag_ids))
-                        .select(task_tags::task_id),
-                ),
-            );
-        }
+        let total = build_query().count().get_result::<i64>(conn)?;
 
-        // LIMIT/OFFSET適用してタスク取得
-        let found_tasks = data_query
-            .order(tasks::created_at.desc())
-            .limit(limit)
-            .offset(offset)
-            .load::<Task>(conn)?;
+        // タスク取得（offset または cursor 適用）
+        let (found_tasks, next_cursor, prev_cursor) =
+            Self::load_task_page(conn, &build_query, limit, offset, params.cursor.as_deref())?;
 
-        // タグと子タスクIDを追加（既存のenrich_task_responseを使用）
-        let enriched_tasks: Result<Vec<TaskResponse>, ServiceError> = found_tasks
-            .into_iter()
-            .map(|task| Self::enrich_task_response(conn, task))
-            .collect();
+        // 各タスクにタグ・子タスクID・親タイトルを一括付与
+        let enriched_tasks = Self::enrich_task_responses(conn, found_tasks)?;
 
         Ok(PaginatedTaskResponse {
-            tasks: enriched_tasks?,
+            tasks: enriched_tasks,
             total,
+            next_cursor,
+            prev_cursor,
         })
     }
 
+    /// 検索条件に一致する全タスクを取得（ページングなし）
+    ///
+    /// # Arguments
+    /// * `conn` - データベース接続
+    /// * `params` - 検索パラメータ（q・status・tagsのみ使用し、limit・offset・cursorは無視）
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Task>)` - 一致したタスク（search_tasksと同じ条件、作成日時の新しい順）
+    /// * `Err(ServiceError)` - データベースエラー
+    pub fn find_matching_tasks(
+        conn: &mut SqliteConnection,
+        params: &SearchTasksParams,
+    ) -> Result<Vec<Task>, ServiceError> {
+        let Some(filter) = SearchFilter::resolve(conn, params)? else {
+            return Ok(Vec::new());
+        };
+
+        Ok(filter
+            .query()
+            .order((tasks::created_at.desc(), tasks::id.desc()))
+            .load::<Task>(conn)?)
+    }
+
     /// タスクIDのみを検索（軽量版）
     ///
     /// # Arguments
     /// * `conn` - データベース接続
-    /// * `tag_names` - タグ名リスト（OR条件）
+    /// * `tag_names` - タグ名リスト（同じグループ内はOR条件、グループ同士はAND条件。
+    ///   グループに属さないタグは1つのグループとして扱う。名前空間のタグは子孫のタグも含む）
     /// * `status_filter` - ステータスフィルタ（オプション、デフォルト: draft + active + completed）
     ///
     /// # Returns
@@ -592,29 +637,29 @@ impl TaskService {
                         ),
                     ),
             );
+            // 延期中のタスクはプールに表示しない
+            query = query
+                .filter(tasks::id.ne_all(deferred_tasks::table.select(deferred_tasks::task_id)));
         }
 
-        // タグフィルタ（OR条件）
+        // タグフィルタ（同じグループ内はOR条件、グループ間はAND条件）
         if let Some(tags) = tag_names {
             if !tags.is_empty() {
-                // タグ名からタグIDを取得
-                let tag_ids: Vec<String> = tags::table
-                    .filter(tags::name.eq_any(&tags))
-                    .select(tags::id)
-                    .load::<String>(conn)?;
-
-                if !tag_ids.is_empty() {
-                    // task_tagsでフィルタ
-                    query = query.filter(
-                        tasks::id.eq_any(
-                            task_tags::table
-                                .filter(task_tags::tag_id.eq_any(tag_ids))
-                                .select(task_tags::task_id),
-                        ),
-                    );
-                } else {
+                match TagService::resolve_tag_filter(conn, &tags)? {
+                    Some(tag_id_sets) => {
+                        // task_tagsでフィルタ
+                        for tag_ids in tag_id_sets {
+                            query = query.filter(
+                                tasks::id.eq_any(
+                                    task_tags::table
+                                        .filter(task_tags::tag_id.eq_any(tag_ids))
+                                        .select(task_tags::task_id),
+                                ),
+                            );
+                        }
+                    }
                     // タグが見つからない場合は空の結果を返す
-                    return Ok(Vec::new());
+                    None => return Ok(Vec::new()),
                 }
             }
         }
@@ -645,22 +690,10 @@ impl TaskService {
             .optional()?
             .ok_or_else(|| ServiceError::TaskNotFound(task_id.to_string()))?;
 
-        // タスクに関連付けられたタグ名を取得
-        let tag_names = task_tags::table
-            .inner_join(tags::table)
-            .filter(task_tags::task_id.eq(task_id))
-            .select(tags::name)
-            .load::<String>(conn)?;
-
-        // 子タスクのIDリストを取得
-        let children_ids = tasks::table
-            .filter(tasks::parent_id.eq(task_id))
-            .select(tasks::id)
-            .load::<String>(conn)?;
-
-        let mut response = task.with_tags(tag_names);
-        response.children_ids = children_ids; // 子タスクIDを設定
-        Ok(response)
+        // タグ・子タスクID・親タイトルを付与（一覧系APIと同じローダーを使用）
+        Self::enrich_task_responses(conn, vec![task])?
+            .pop()
+            .ok_or_else(|| ServiceError::TaskNotFound(task_id.to_string()))
     }
 
     /// タスクを更新
@@ -683,103 +716,222 @@ impl TaskService {
         task_id: &str,
         req_input: UpdateTaskRequestInput,
     ) -> Result<TaskResponse, ServiceError> {
-        // タスクが存在するか確認
-        let existing_task = tasks::table
-            .find(task_id)
-            .first::<Task>(conn)
-            .optional()?
-            .ok_or_else(|| ServiceError::TaskNotFound(task_id.to_string()))?;
+        Self::update_task_with_options(conn, task_id, req_input, false)
+    }
 
-        // Draft状態チェック: Draft以外のタスクは編集不可（REQ-0016）
-        if existing_task.status != "draft" {
-            return Err(ServiceError::TaskNotDraft(task_id.to_string()));
-        }
+    /// タスクを更新（存在しないタグの自動作成を指定可能）
+    ///
+    /// # Arguments
+    /// * `auto_create_tags` - trueの場合、存在しないタグを作成してから関連付ける（色は自動で割り当てる）
+    ///
+    /// # Returns
+    /// * `Ok(TaskResponse)` - 更新されたタスク（新規作成したタグは `created_tags` に入る）
+    /// * `Err(ServiceError::TagNotFound)` - 存在しないタグが指定された（auto_create_tags = false の場合）
+    ///
+    /// # Notes
+    /// - タスクの更新とタグの作成・関連付けは1つのトランザクションで行う（エラー時は何も変更されない）
+    /// - タイトル・説明・親タスクを変更した場合、自動タグ付けルールを適用し直す
+    pub fn update_task_with_options(
+        conn: &mut SqliteConnection,
+        task_id: &str,
+        req_input: UpdateTaskRequestInput,
+        auto_create_tags: bool,
+    ) -> Result<TaskResponse, ServiceError> {
+        conn.transaction::<_, ServiceError, _>(|conn| {
+            // タスクが存在するか確認
+            let existing_task = tasks::table
+                .find(task_id)
+                .first::<Task>(conn)
+                .optional()?
+                .ok_or_else(|| ServiceError::TaskNotFound(task_id.to_string()))?;
 
-        // バリデーション: タイトルが空でないこと
-        if let Some(ref title) = req_input.title {
-            if title.trim().is_empty() {
-                return Err(ServiceError::InvalidInput(
-                    "タイトルは必須です".to_string(),
-                ));
+            // Draft状態チェック: Draft以外のタスクは編集不可（REQ-0016）
+            if existing_task.status != "draft" {
+                return Err(ServiceError::TaskNotDraft(task_id.to_string()));
             }
-        }
-
-        // バリデーション: 親タスクが存在するか確認（指定されている場合）
-        if let Some(ref parent_id) = req_input.parent_id {
-            let parent_exists = tasks::table
-                .find(parent_id)
-                .select(tasks::id)
-                .first::<String>(conn)
-                .optional()?;
 
-            if parent_exists.is_none() {
-                return Err(ServiceError::ParentTaskNotFound(parent_id.clone()));
+            // バリデーション: タイトルが空でないこと
+            if let Some(ref title) = req_input.title {
+                if title.trim().is_empty() {
+                    return Err(ServiceError::InvalidInput("タイトルは必須です".to_string()));
+                }
             }
 
-            // 【新規追加】階層深度チェック（孫タスク作成禁止 - BR-016）
-            Self::validate_hierarchy_depth(conn, parent_id)?;
+            // バリデーション: 親タスクが存在するか確認（指定されている場合）
+            if let Some(ref parent_id) = req_input.parent_id {
+                let parent_exists = tasks::table
+                    .find(parent_id)
+                    .select(tasks::id)
+                    .first::<String>(conn)
+                    .optional()?;
+
+                if parent_exists.is_none() {
+                    return Err(ServiceError::ParentTaskNotFound(parent_id.clone()));
+                }
+
+                // 【新規追加】階層深度チェック（孫タスク作成禁止 - BR-016）
+                Self::validate_hierarchy_depth(conn, parent_id)?;
 
-            // 循環参照チェック: 親タスクを変更する場合
-            if Self::would_create_cycle(conn, task_id, parent_id)? {
-                return Err(ServiceError::CircularDependency(task_id.to_string()));
+                // 循環参照チェック: 親タスクを変更する場合
+                if Self::would_create_cycle(conn, task_id, parent_id)? {
+                    return Err(ServiceError::CircularDependency(task_id.to_string()));
+                }
             }
-        }
 
-        // UpdateTaskRequestInputからUpdateTaskRequestへ変換
-        let mut req = UpdateTaskRequest {
-            title: req_input.title,
-            description: req_input.description,
-            status: req_input.status,
-            parent_id: req_input.parent_id,
-            updated_at: None,
-        };
+            // タグ名を解決（タグを変更する場合、別名の読み替え・タグの自動作成・排他グループのチェック）
+            let (new_tags, created_tags) = match req_input.tags {
+                Some(ref tags) => {
+                    let (tag_names, created_tags) =
+                        Self::resolve_task_tags(conn, tags, auto_create_tags)?;
+                    (Some(tag_names), created_tags)
+                }
+                None => (None, Vec::new()),
+            };
 
-        // updated_atタイムスタンプを設定
-        req = req.with_timestamp();
+            // UpdateTaskRequestInputからUpdateTaskRequestへ変換
+            let mut req = UpdateTaskRequest {
+                title: req_input.title,
+                description: req_input.description,
+                status: req_input.status,
+                parent_id: req_input.parent_id,
+                updated_at: None,
+            };
 
-        // タスク更新
-        diesel::update(tasks::table.find(task_id))
-            .set(&req)
-            .execute(conn)?;
+            // ルールで判定する項目（タイトル・説明・親タスク）が変わるか
+            let rule_targets_changed =
+                req.title.is_some() || req.description.is_some() || req.parent_id.is_some();
 
-        // タグの更新（tags配列が指定されている場合）
-        if let Some(ref new_tags) = req_input.tags {
-            // 既存のタグ関連付けをすべて削除
-            diesel::delete(task_tags::table.filter(task_tags::task_id.eq(task_id)))
-                .execute(conn)?;
+            // updated_atタイムスタンプを設定
+            req = req.with_timestamp();
 
-            // 新しいタグを関連付け
-            if !new_tags.is_empty() {
-                for tag_name in new_tags {
-                    // タグIDを取得
-                    let tag_id_result = tags::table
-                        .filter(tags::name.eq(tag_name))
-                        .select(tags::id)
-                        .first::<String>(conn)
-                        .optional()?;
+            // タスク更新
+            diesel::update(tasks::table.find(task_id))
+                .set(&req)
+                .execute(conn)?;
 
-                    if let Some(tag_id) = tag_id_result {
-                        // task_tags挿入
-                        diesel::insert_into(task_tags::table)
-                            .values((
-                                task_tags::task_id.eq(task_id),
-                                task_tags::tag_id.eq(&tag_id),
-                            ))
-                            .execute(conn)?;
-                    } else {
-                        return Err(ServiceError::TagNotFound(tag_name.clone()));
+            // タグの更新（tags配列が指定されている場合）
+            if let Some(ref new_tags) = new_tags {
+                // 既存のタグ関連付けをすべて削除
+                diesel::delete(task_tags::table.filter(task_tags::task_id.eq(task_id)))
+                    .execute(conn)?;
+
+                // 新しいタグを関連付け
+                if !new_tags.is_empty() {
+                    for tag_name in new_tags {
+                        // タグIDを取得
+                        let tag_id_result = tags::table
+                            .filter(tags::name.eq(tag_name))
+                            .select(tags::id)
+                            .first::<String>(conn)
+                            .optional()?;
+
+                        if let Some(tag_id) = tag_id_result {
+                            // task_tags挿入
+                            diesel::insert_into(task_tags::table)
+                                .values((
+                                    task_tags::task_id.eq(task_id),
+                                    task_tags::tag_id.eq(&tag_id),
+                                ))
+                                .execute(conn)?;
+                        } else {
+                            return Err(ServiceError::TagNotFound(tag_name.clone()));
+                        }
                     }
                 }
             }
-        }
 
-        // 【新規追加】親ステータス更新（BR-013: 子タスク変更時の親ステータス自動同期）
-        Self::update_parent_status_if_needed(conn, task_id)?;
+            // 自動タグ付けルールを適用（タグだけを変更した場合は、手動で外したタグを付け直さない）
+            if rule_targets_changed {
+                AutoTagService::apply_to_task(conn, task_id)?;
+            }
+
+            // 【新規追加】親ステータス更新（BR-013: 子タスク変更時の親ステータス自動同期）
+            Self::update_parent_status_if_needed(conn, task_id)?;
+
+            // 更新されたタスクを取得して返却
+            let mut response = Self::get_task(conn, task_id)?;
+            response.created_tags = created_tags;
+            Ok(response)
+        })
+    }
+
+    /// タスクの見積もり時間を設定
+    ///
+    /// キューの予定を調整できるよう、Draft以外（キュー内のActive等）のタスクにも設定できる。
+    ///
+    /// # Arguments
+    /// * `conn` - データベース接続
+    /// * `task_id` - タスクID
+    /// * `estimated_minutes` - 見積もり時間（分、Noneで未設定に戻す）
+    ///
+    /// # Returns
+    /// * `Ok(TaskResponse)` - 更新されたタスク
+    /// * `Err(ServiceError::InvalidInput)` - 見積もり時間が1〜1440分の範囲外
+    pub fn set_task_estimate(
+        conn: &mut SqliteConnection,
+        task_id: &str,
+        estimated_minutes: Option<i32>,
+    ) -> Result<TaskResponse, ServiceError> {
+        Self::validate_estimate(estimated_minutes)?;
+
+        let updated = diesel::update(tasks::table.find(task_id))
+            .set((
+                tasks::estimated_minutes.eq(estimated_minutes),
+                tasks::updated_at.eq(Utc::now().to_rfc3339()),
+            ))
+            .execute(conn)?;
+        if updated == 0 {
+            return Err(ServiceError::TaskNotFound(task_id.to_string()));
+        }
 
-        // 更新されたタスクを取得して返却
         Self::get_task(conn, task_id)
     }
 
+    /// タスクに付けるタグ名を解決（create_task / update_task で共用）
+    ///
+    /// # Returns
+    /// * `Ok((tag_names, created_tags))` - 正式なタグ名（別名を読み替え済み）と、新規作成したタグ名
+    /// * `Err(ServiceError::ExclusiveTagConflict)` - 同じ排他グループのタグが複数含まれる
+    ///
+    /// # Business Logic
+    /// - auto_create_tags = true の場合、タグ名を正規化し、存在しないタグを作成する
+    fn resolve_task_tags(
+        conn: &mut SqliteConnection,
+        tag_names: &[String],
+        auto_create_tags: bool,
+    ) -> Result<(Vec<String>, Vec<String>), ServiceError> {
+        let tag_names = if auto_create_tags {
+            tag_names
+                .iter()
+                .map(|name| TagService::normalize_tag_name(name))
+                .collect::<Result<Vec<String>, ServiceError>>()?
+        } else {
+            tag_names.to_vec()
+        };
+        let tag_names = TagService::resolve_aliases(conn, &tag_names)?;
+
+        let created_tags = if auto_create_tags {
+            TagService::create_missing_tags(conn, &tag_names)?
+        } else {
+            Vec::new()
+        };
+
+        TagService::validate_exclusive_tags(conn, &tag_names)?;
+        Ok((tag_names, created_tags))
+    }
+
+    /// 見積もり時間のバリデーション（1〜1440分）
+    fn validate_estimate(estimated_minutes: Option<i32>) -> Result<(), ServiceError> {
+        match estimated_minutes {
+            Some(minutes) if !(1..=MAX_ESTIMATED_MINUTES).contains(&minutes) => {
+                Err(ServiceError::InvalidInput(
+                    "見積もり時間は1〜1440分の範囲で指定してください".to_string(),
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// タスクを削除（論理削除: archivedステータスに変更）
     ///
     /// # Arguments
@@ -929,65 +1081,269 @@ impl TaskService {
         Self::get_task(conn, task_id)
     }
 
-    /// タスクレスポンスをエンリッチ（タグ・子タスクID・親タイトル付与）
+    /// タスクレスポンスを一括エンリッチ（タグ・子タスクID・親タイトル付与）
     ///
     /// # Arguments
     /// * `conn` - データベース接続
-    /// * `task` - エンリッチ対象のタスク
-    /// * `parent_title` - 親タスクのタイトル（バッチ取得済みの場合に渡す）
+    /// * `tasks` - エンリッチ対象のタスク（並び順は維持される）
     ///
     /// # Returns
-    /// * `Ok(TaskResponse)` - エンリッチされたTaskResponse
+    /// * `Ok(Vec<TaskResponse>)` - エンリッチされたTaskResponse
     /// * `Err(ServiceError)` - データベースエラー
     ///
     /// # Notes
-    /// - list_tasks_paginatedではバッチ取得したparent_titleを渡す
-    /// - その他のAPIでは個別にDB取得
-    fn enrich_task_response_with_parent_title(
+    /// - タグ・子タスクID・親タイトルをそれぞれ一括取得するため、
+    ///   タスク件数に関わらずクエリ数は一定（IN句はBATCH_CHUNK_SIZE件ごとに分割）
+    /// - list_tasks, list_tasks_paginated, search_tasks, get_task で共用
+    fn enrich_task_responses(
         conn: &mut SqliteConnection,
-        task: Task,
-        parent_title: Option<String>,
-    ) -> Result<TaskResponse, ServiceError> {
-        // タグ名を取得
-        let tag_names = task_tags::table
-            .inner_join(tags::table)
-            .filter(task_tags::task_id.eq(&task.id))
-            .select(tags::name)
-            .load::<String>(conn)?;
+        tasks: Vec<Task>,
+    ) -> Result<Vec<TaskResponse>, ServiceError> {
+        if tasks.is_empty() {
+            return Ok(Vec::new());
+        }
 
-        // 子タスクのIDリストを取得
-        let children_ids = tasks::table
-            .filter(tasks::parent_id.eq(&task.id))
-            .select(tasks::id)
-            .load::<String>(conn)?;
+        let task_ids: Vec<String> = tasks.iter().map(|t| t.id.clone()).collect();
+        let parent_ids: Vec<String> = tasks
+            .iter()
+            .filter_map(|t| t.parent_id.clone())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
 
-        let mut response = task.with_tags(tag_names);
-        response.children_ids = children_ids;
-        response.parent_title = parent_title;
-        Ok(response)
+        // タグ名を一括取得
+        let mut tag_names = Self::load_tag_names(conn, &task_ids)?;
+
+        // 子タスクのIDリストを一括取得
+        let mut children_ids: HashMap<String, Vec<String>> = HashMap::new();
+        for chunk in task_ids.chunks(BATCH_CHUNK_SIZE) {
+            let rows = tasks::table
+                .filter(tasks::parent_id.eq_any(chunk))
+                .select((tasks::parent_id, tasks::id))
+                .load::<(Option<String>, String)>(conn)?;
+
+            for (parent_id, child_id) in rows {
+                if let Some(parent_id) = parent_id {
+                    children_ids.entry(parent_id).or_default().push(child_id);
+                }
+            }
+        }
+
+        // 親タスクのタイトルを一括取得
+        let mut parent_titles: HashMap<String, String> = HashMap::new();
+        for chunk in parent_ids.chunks(BATCH_CHUNK_SIZE) {
+            let rows = tasks::table
+                .filter(tasks::id.eq_any(chunk))
+                .select((tasks::id, tasks::title))
+                .load::<(String, String)>(conn)?;
+            parent_titles.extend(rows);
+        }
+
+        Ok(tasks
+            .into_iter()
+            .map(|task| {
+                let tags = tag_names.remove(&task.id).unwrap_or_default();
+                let children = children_ids.remove(&task.id).unwrap_or_default();
+                let parent_title = task
+                    .parent_id
+                    .as_ref()
+                    .and_then(|pid| parent_titles.get(pid).cloned());
+
+                let mut response = task.with_tags(tags);
+                response.children_ids = children;
+                response.parent_title = parent_title;
+                response
+            })
+            .collect())
+    }
+
+    /// 複数タスクのタグ名を一括取得
+    ///
+    /// # Returns
+    /// * `Ok(HashMap<task_id, Vec<tag_name>>)` - タグを持たないタスクはキーなし
+    fn load_tag_names(
+        conn: &mut SqliteConnection,
+        task_ids: &[String],
+    ) -> Result<HashMap<String, Vec<String>>, ServiceError> {
+        let mut tag_names: HashMap<String, Vec<String>> = HashMap::new();
+        for chunk in task_ids.chunks(BATCH_CHUNK_SIZE) {
+            let rows = task_tags::table
+                .inner_join(tags::table)
+                .filter(task_tags::task_id.eq_any(chunk))
+                .select((task_tags::task_id, tags::name))
+                .load::<(String, String)>(conn)?;
+
+            for (task_id, tag_name) in rows {
+                tag_names.entry(task_id).or_default().push(tag_name);
+            }
+        }
+        Ok(tag_names)
     }
 
-    /// タスクレスポンスをエンリッチ（互換性維持用ラッパー）
+    /// 1ページ分のタスクを取得（offset / カーソル両対応）
+    ///
+    /// # Arguments
+    /// * `conn` - データベース接続
+    /// * `build_query` - フィルタ適用済みのクエリを生成するクロージャ
+    /// * `limit` - 取得件数
+    /// * `offset` - オフセット（カーソル未指定時のみ使用）
+    /// * `cursor` - ページングカーソル（指定時はoffsetより優先）
+    ///
+    /// # Returns
+    /// * `Ok((tasks, next_cursor, prev_cursor))` - ページ内タスクと前後ページのカーソル
+    /// * `Err(ServiceError::InvalidInput)` - カーソルが不正な場合
     ///
     /// # Notes
-    /// - list_tasks, search_tasksなど既存APIで使用
-    /// - parent_titleは個別にDB取得
-    fn enrich_task_response(
+    /// - 並び順は (created_at DESC, id DESC) で固定
+    /// - カーソルモードでは境界の (created_at, id) を基準に取得するため、
+    ///   ページング中にタスクが追加・アーカイブされてもページがずれない
+    fn load_task_page<F>(
         conn: &mut SqliteConnection,
-        task: Task,
-    ) -> Result<TaskResponse, ServiceError> {
-        // 親タスクのタイトルを個別に取得
-        let parent_title = if let Some(ref parent_id) = task.parent_id {
-            tasks::table
-                .filter(tasks::id.eq(parent_id))
-                .select(tasks::title)
-                .first::<String>(conn)
-                .optional()?
-        } else {
-            None
+        build_query: &F,
+        limit: i64,
+        offset: i64,
+        cursor: Option<&str>,
+    ) -> Result<TaskPage, ServiceError>
+    where
+        F: Fn() -> tasks::BoxedQuery<'static, Sqlite>,
+    {
+        let limit = limit.max(0);
+
+        let Some(raw_cursor) = cursor else {
+            // offsetモード: limit+1件取得して次ページの有無を判定
+            let mut page = build_query()
+                .order((tasks::created_at.desc(), tasks::id.desc()))
+                .limit(limit + 1)
+                .offset(offset)
+                .load::<Task>(conn)?;
+
+            let has_next = page.len() as i64 > limit;
+            page.truncate(limit as usize);
+
+            let next_cursor = page
+                .last()
+                .filter(|_| has_next)
+                .map(|t| TaskCursor::new(t, CursorDirection::After).encode());
+            let prev_cursor = page
+                .first()
+                .filter(|_| offset > 0)
+                .map(|t| TaskCursor::new(t, CursorDirection::Before).encode());
+
+            return Ok((page, next_cursor, prev_cursor));
         };
 
-        Self::enrich_task_response_with_parent_title(conn, task, parent_title)
+        let cursor = TaskCursor::decode(raw_cursor)
+            .ok_or_else(|| ServiceError::InvalidInput("不正なカーソルです".to_string()))?;
+
+        match cursor.direction {
+            CursorDirection::After => {
+                // 境界より後ろを limit+1 件取得
+                let mut page =
+                    Self::filter_after_cursor(build_query(), &cursor.created_at, &cursor.id)
+                        .order((tasks::created_at.desc(), tasks::id.desc()))
+                        .limit(limit + 1)
+                        .load::<Task>(conn)?;
+
+                let has_next = page.len() as i64 > limit;
+                page.truncate(limit as usize);
+
+                // 先頭要素（空ページの場合はカーソル位置）より前に行があるか確認
+                let (anchor_created_at, anchor_id) = page
+                    .first()
+                    .map(|t| (t.created_at.clone(), t.id.clone()))
+                    .unwrap_or((cursor.created_at, cursor.id));
+                let has_prev =
+                    Self::filter_before_cursor(build_query(), &anchor_created_at, &anchor_id)
+                        .select(tasks::id)
+                        .first::<String>(conn)
+                        .optional()?
+                        .is_some();
+
+                let next_cursor = page
+                    .last()
+                    .filter(|_| has_next)
+                    .map(|t| TaskCursor::new(t, CursorDirection::After).encode());
+                let prev_cursor = has_prev.then(|| {
+                    TaskCursor {
+                        created_at: anchor_created_at,
+                        id: anchor_id,
+                        direction: CursorDirection::Before,
+                    }
+                    .encode()
+                });
+
+                Ok((page, next_cursor, prev_cursor))
+            }
+            CursorDirection::Before => {
+                // 境界より前を逆順で limit+1 件取得し、表示順に戻す
+                let mut page =
+                    Self::filter_before_cursor(build_query(), &cursor.created_at, &cursor.id)
+                        .order((tasks::created_at.asc(), tasks::id.asc()))
+                        .limit(limit + 1)
+                        .load::<Task>(conn)?;
+
+                let has_prev = page.len() as i64 > limit;
+                page.truncate(limit as usize);
+                page.reverse();
+
+                // 末尾要素（空ページの場合はカーソル位置）より後ろに行があるか確認
+                let (anchor_created_at, anchor_id) = page
+                    .last()
+                    .map(|t| (t.created_at.clone(), t.id.clone()))
+                    .unwrap_or((cursor.created_at, cursor.id));
+                let has_next =
+                    Self::filter_after_cursor(build_query(), &anchor_created_at, &anchor_id)
+                        .select(tasks::id)
+                        .first::<String>(conn)
+                        .optional()?
+                        .is_some();
+
+                let next_cursor = has_next.then(|| {
+                    TaskCursor {
+                        created_at: anchor_created_at,
+                        id: anchor_id,
+                        direction: CursorDirection::After,
+                    }
+                    .encode()
+                });
+                let prev_cursor = page
+                    .first()
+                    .filter(|_| has_prev)
+                    .map(|t| TaskCursor::new(t, CursorDirection::Before).encode());
+
+                Ok((page, next_cursor, prev_cursor))
+            }
+        }
+    }
+
+    /// 並び順 (created_at DESC, id DESC) で境界より後ろの行に絞り込む
+    fn filter_after_cursor(
+        query: tasks::BoxedQuery<'static, Sqlite>,
+        created_at: &str,
+        id: &str,
+    ) -> tasks::BoxedQuery<'static, Sqlite> {
+        query.filter(
+            tasks::created_at
+                .lt(created_at.to_string())
+                .or(tasks::created_at
+                    .eq(created_at.to_string())
+                    .and(tasks::id.lt(id.to_string()))),
+        )
+    }
+
+    /// 並び順 (created_at DESC, id DESC) で境界より前の行に絞り込む
+    fn filter_before_cursor(
+        query: tasks::BoxedQuery<'static, Sqlite>,
+        created_at: &str,
+        id: &str,
+    ) -> tasks::BoxedQuery<'static, Sqlite> {
+        query.filter(
+            tasks::created_at
+                .gt(created_at.to_string())
+                .or(tasks::created_at
+                    .eq(created_at.to_string())
+                    .and(tasks::id.gt(id.to_string()))),
+        )
     }
 
     /// 指定されたタスクが子タスクを持つかどうかを確認
@@ -1117,7 +1473,7 @@ impl TaskService {
     /// - 全子が Completed → 親も Completed
     /// - 全子が Draft → 親も Draft
     /// - 混在状態（Draft + Completed など）→ Active とみなす
-    fn calculate_parent_status(child_statuses: Vec<TaskStatus>) -> TaskStatus {
+    pub(crate) fn calculate_parent_status(child_statuses: Vec<TaskStatus>) -> TaskStatus {
         // Archivedの子タスクを除外（論理削除として扱う）
         let active_children: Vec<_> = child_statuses
             .into_iter()
@@ -1216,6 +1572,84 @@ impl TaskService {
     }
 }
 
+/// 解決済みの検索条件（search_tasks / find_matching_tasks で共用）
+struct SearchFilter {
+    /// キーワード検索パターン（LIKE用、空白のみの場合はNone）
+    keyword_pattern: Option<String>,
+    status: Option<String>,
+    /// タグ名から解決したタグIDの集合（子孫のタグを含む、集合同士はAND条件）
+    tag_id_sets: Option<Vec<Vec<String>>>,
+}
+
+impl SearchFilter {
+    /// 検索パラメータを解決
+    ///
+    /// # Returns
+    /// * `Ok(Some(SearchFilter))` - 解決済みの検索条件
+    /// * `Ok(None)` - 指定されたタグが1つも存在しない（結果は必ず空）
+    fn resolve(
+        conn: &mut SqliteConnection,
+        params: &SearchTasksParams,
+    ) -> Result<Option<Self>, ServiceError> {
+        let keyword_pattern = params
+            .q
+            .as_ref()
+            .filter(|keyword| !keyword.trim().is_empty())
+            .map(|keyword| format!("%{}%", keyword));
+
+        // タグ名からタグIDを解決（グループごとにまとめ、名前空間の子孫のタグも含める）
+        let tag_id_sets = match params.tags {
+            Some(ref tag_names) if !tag_names.is_empty() => {
+                match TagService::resolve_tag_filter(conn, tag_names)? {
+                    Some(tag_id_sets) => Some(tag_id_sets),
+                    None => return Ok(None),
+                }
+            }
+            _ => None,
+        };
+
+        Ok(Some(Self {
+            keyword_pattern,
+            status: params.status.clone(),
+            tag_id_sets,
+        }))
+    }
+
+    /// 検索条件を適用したクエリを作成（総件数・データ取得で共用）
+    fn query(&self) -> tasks::BoxedQuery<'static, Sqlite> {
+        let mut query = tasks::table.into_boxed();
+
+        // キーワード検索フィルタ適用
+        if let Some(ref pattern) = self.keyword_pattern {
+            query = query.filter(
+                tasks::title
+                    .like(pattern.clone())
+                    .or(tasks::description.like(pattern.clone())),
+            );
+        }
+
+        // ステータスフィルタ適用
+        if let Some(ref status) = self.status {
+            query = query.filter(tasks::status.eq(status.clone()));
+        } else {
+            query = query.filter(tasks::status.ne("archived"));
+        }
+
+        // タグフィルタ適用（集合内はOR条件、集合同士はAND条件）
+        for tag_ids in self.tag_id_sets.iter().flatten() {
+            query = query.filter(
+                tasks::id.eq_any(
+                    task_tags::table
+                        .filter(task_tags::tag_id.eq_any(tag_ids.clone()))
+                        .select(task_tags::task_id),
+                ),
+            );
+        }
+
+        query
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1243,6 +1677,7 @@ mod tests {
             description: Some("Test description".to_string()),
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
 
         let result = TaskService::create_task(&mut conn, req);
@@ -1264,6 +1699,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
 
         let result = TaskService::create_task(&mut conn, req);
@@ -1285,6 +1721,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: Some("non-existent-id".to_string()),
+            estimated_minutes: None,
         };
 
         let result = TaskService::create_task(&mut conn, req);
@@ -1307,6 +1744,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         let parent = TaskService::create_task(&mut conn, parent_req).unwrap();
 
@@ -1316,6 +1754,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: Some(parent.id.clone()),
+            estimated_minutes: None,
         };
         let result = TaskService::create_task(&mut conn, child_req);
         assert!(result.is_ok());
@@ -1324,6 +1763,52 @@ mod tests {
         assert_eq!(child.parent_id, Some(parent.id));
     }
 
+    #[test]
+    fn test_create_task_auto_create_tags() {
+        use crate::models::tag::AUTO_TAG_COLORS;
+
+        let mut conn = setup_test_db();
+        let req = CreateTaskRequest {
+            title: "Tagged Task".to_string(),
+            description: None,
+            tags: vec!["urgent".to_string(), "work / clientA".to_string()],
+            parent_id: None,
+            estimated_minutes: None,
+        };
+
+        // 指定しない場合はエラーになり、タスクも作成されない
+        let result = TaskService::create_task(&mut conn, req.clone());
+        assert!(matches!(result, Err(ServiceError::TagNotFound(_))));
+        let task_count: i64 = tasks::table.count().get_result(&mut conn).unwrap();
+        assert_eq!(task_count, 0);
+
+        let task = TaskService::create_task_with_options(&mut conn, req, true).unwrap();
+        assert_eq!(task.tags, vec!["urgent", "work/clientA"]);
+        assert_eq!(task.created_tags, vec!["urgent", "work", "work/clientA"]);
+        let colors: Vec<Option<String>> = TagService::list_tags(&mut conn)
+            .unwrap()
+            .into_iter()
+            .map(|tag| tag.color)
+            .collect();
+        assert_eq!(colors.len(), 3);
+        assert!(colors
+            .iter()
+            .all(|color| AUTO_TAG_COLORS.contains(&color.as_deref().unwrap())));
+
+        // 更新時は既存のタグは作成しない
+        let update_req = UpdateTaskRequestInput {
+            title: None,
+            description: None,
+            status: None,
+            parent_id: None,
+            tags: Some(vec!["urgent".to_string(), "later".to_string()]),
+        };
+        let updated =
+            TaskService::update_task_with_options(&mut conn, &task.id, update_req, true).unwrap();
+        assert_eq!(updated.tags.len(), 2);
+        assert_eq!(updated.created_tags, vec!["later"]);
+    }
+
     #[test]
     fn test_grandchild_creation_rejected() {
         let mut conn = setup_test_db();
@@ -1334,6 +1819,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         let parent = TaskService::create_task(&mut conn, parent_req).unwrap();
 
@@ -1343,6 +1829,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: Some(parent.id.clone()),
+            estimated_minutes: None,
         };
         let child = TaskService::create_task(&mut conn, child_req).unwrap();
 
@@ -1352,6 +1839,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: Some(child.id.clone()),
+            estimated_minutes: None,
         };
         let result = TaskService::create_task(&mut conn, grandchild_req);
         assert!(result.is_err());
@@ -1373,6 +1861,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         let parent = TaskService::create_task(&mut conn, parent_req).unwrap();
 
@@ -1382,6 +1871,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: Some(parent.id.clone()),
+            estimated_minutes: None,
         };
         let child = TaskService::create_task(&mut conn, child_req).unwrap();
 
@@ -1391,6 +1881,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         let task = TaskService::create_task(&mut conn, task_req).unwrap();
 
@@ -1422,6 +1913,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         let parent = TaskService::create_task(&mut conn, parent_req).unwrap();
 
@@ -1431,6 +1923,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: Some(parent.id.clone()),
+            estimated_minutes: None,
         };
         let result = TaskService::create_task(&mut conn, child_req);
         assert!(result.is_ok());
@@ -1450,6 +1943,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         let parent = TaskService::create_task(&mut conn, parent_req).unwrap();
 
@@ -1459,6 +1953,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: Some(parent.id.clone()),
+            estimated_minutes: None,
         };
         TaskService::create_task(&mut conn, child_req).unwrap();
 
@@ -1478,6 +1973,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         let parent = TaskService::create_task(&mut conn, parent_req).unwrap();
 
@@ -1540,6 +2036,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         let parent = TaskService::create_task(&mut conn, parent_req).unwrap();
         assert_eq!(parent.status, TaskStatus::Draft);
@@ -1550,6 +2047,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: Some(parent.id.clone()),
+            estimated_minutes: None,
         };
         let child1 = TaskService::create_task(&mut conn, child1_req).unwrap();
 
@@ -1559,6 +2057,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: Some(parent.id.clone()),
+            estimated_minutes: None,
         };
         let _child2 = TaskService::create_task(&mut conn, child2_req).unwrap();
 
@@ -1593,6 +2092,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         let task = TaskService::create_task(&mut conn, task_req).unwrap();
 
@@ -1611,6 +2111,7 @@ mod tests {
             description: Some("Description".to_string()),
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         let created = TaskService::create_task(&mut conn, req).unwrap();
 
@@ -1647,6 +2148,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         let created = TaskService::create_task(&mut conn, req).unwrap();
 
@@ -1697,6 +2199,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         let created = TaskService::create_task(&mut conn, req).unwrap();
 
@@ -1728,6 +2231,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         let created = TaskService::create_task(&mut conn, req).unwrap();
 
@@ -1764,6 +2268,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         let parent = TaskService::create_task(&mut conn, parent_req).unwrap();
 
@@ -1773,6 +2278,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: Some(parent.id.clone()),
+            estimated_minutes: None,
         };
         TaskService::create_task(&mut conn, child_req).unwrap();
 
@@ -1797,6 +2303,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         let created = TaskService::create_task(&mut conn, req).unwrap();
 
@@ -1828,6 +2335,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         let task_a = TaskService::create_task(&mut conn, req_a).unwrap();
 
@@ -1837,6 +2345,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: Some(task_a.id.clone()),
+            estimated_minutes: None,
         };
         let task_b = TaskService::create_task(&mut conn, req_b).unwrap();
 
@@ -1873,12 +2382,14 @@ mod tests {
             description: Some("Learn Rust language".to_string()),
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         let req2 = CreateTaskRequest {
             title: "Python coding".to_string(),
             description: Some("Build web app".to_string()),
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         TaskService::create_task(&mut conn, req1).unwrap();
         TaskService::create_task(&mut conn, req2).unwrap();
@@ -1890,6 +2401,7 @@ mod tests {
             tags: None,
             limit: None,
             offset: None,
+            cursor: None,
         };
         let result = TaskService::search_tasks(&mut conn, params).unwrap();
         assert_eq!(result.tasks.len(), 1);
@@ -1902,6 +2414,7 @@ mod tests {
             tags: None,
             limit: None,
             offset: None,
+            cursor: None,
         };
         let result = TaskService::search_tasks(&mut conn, params).unwrap();
         assert_eq!(result.tasks.len(), 1);
@@ -1918,6 +2431,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         let _task1 = TaskService::create_task(&mut conn, req1).unwrap();
 
@@ -1927,6 +2441,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         let task2 = TaskService::create_task(&mut conn, req2).unwrap();
         let update_req = UpdateTaskRequestInput {
@@ -1945,6 +2460,7 @@ mod tests {
             tags: None,
             limit: None,
             offset: None,
+            cursor: None,
         };
         let result = TaskService::search_tasks(&mut conn, params).unwrap();
         assert_eq!(result.tasks.len(), 1);
@@ -1957,6 +2473,7 @@ mod tests {
             tags: None,
             limit: None,
             offset: None,
+            cursor: None,
         };
         let result = TaskService::search_tasks(&mut conn, params).unwrap();
         assert_eq!(result.tasks.len(), 1);
@@ -1988,12 +2505,14 @@ mod tests {
             description: None,
             tags: vec!["work".to_string()],
             parent_id: None,
+            estimated_minutes: None,
         };
         let req2 = CreateTaskRequest {
             title: "Personal Task".to_string(),
             description: None,
             tags: vec!["personal".to_string()],
             parent_id: None,
+            estimated_minutes: None,
         };
         TaskService::create_task(&mut conn, req1).unwrap();
         TaskService::create_task(&mut conn, req2).unwrap();
@@ -2005,6 +2524,7 @@ mod tests {
             tags: Some(vec!["work".to_string()]),
             limit: None,
             offset: None,
+            cursor: None,
         };
         let result = TaskService::search_tasks(&mut conn, params).unwrap();
         assert_eq!(result.tasks.len(), 1);
@@ -2017,9 +2537,90 @@ mod tests {
             tags: Some(vec!["work".to_string(), "personal".to_string()]),
             limit: None,
             offset: None,
+            cursor: None,
+        };
+        let result = TaskService::search_tasks(&mut conn, params).unwrap();
+        assert_eq!(result.tasks.len(), 2);
+    }
+
+    #[test]
+    fn test_search_tasks_by_tags_across_groups() {
+        use crate::models::tag::{CreateTagGroupRequest, CreateTagRequest};
+        use crate::service::TagService;
+
+        let mut conn = setup_test_db();
+
+        // サイズ: S / M、領域: ui、グループなし: work / personal
+        for (group_name, tag_names) in [("サイズ", vec!["S", "M"]), ("領域", vec!["ui"])] {
+            let group = TagService::create_tag_group(
+                &mut conn,
+                CreateTagGroupRequest {
+                    name: group_name.to_string(),
+                    exclusive: false,
+                },
+            )
+            .unwrap();
+            for name in tag_names {
+                let tag = TagService::create_tag(
+                    &mut conn,
+                    CreateTagRequest {
+                        name: name.to_string(),
+                        color: None,
+                    },
+                )
+                .unwrap();
+                TagService::set_tag_group(&mut conn, &tag.id, Some(&group.id)).unwrap();
+            }
+        }
+
+        for name in ["work", "personal"] {
+            let req = CreateTagRequest {
+                name: name.to_string(),
+                color: None,
+            };
+            TagService::create_tag(&mut conn, req).unwrap();
+        }
+
+        let mut create = |tags: &[&str]| {
+            TaskService::create_task(
+                &mut conn,
+                CreateTaskRequest {
+                    title: tags.join(" "),
+                    description: None,
+                    tags: tags.iter().map(|t| t.to_string()).collect(),
+                    parent_id: None,
+                    estimated_minutes: None,
+                },
+            )
+            .unwrap()
+            .id
         };
-        let result = TaskService::search_tasks(&mut conn, params).unwrap();
-        assert_eq!(result.tasks.len(), 2);
+        let a = create(&["work", "S"]);
+        let b = create(&["personal", "ui"]);
+        let c = create(&["work", "M", "ui"]);
+        create(&["S"]);
+
+        let mut search = |tags: &[&str]| {
+            let tags = tags.iter().map(|t| t.to_string()).collect();
+            let mut ids = TaskService::search_task_ids(&mut conn, Some(tags), None).unwrap();
+            ids.sort();
+            ids
+        };
+        let sorted = |mut ids: Vec<String>| {
+            ids.sort();
+            ids
+        };
+
+        // グループに属さないタグ同士はOR条件
+        assert_eq!(
+            search(&["work", "personal"]),
+            sorted(vec![a.clone(), b.clone(), c.clone()])
+        );
+        // グループの異なるタグ同士はAND条件
+        assert_eq!(search(&["work", "S"]), vec![a.clone()]);
+        // 同じグループ内はOR条件、グループ同士はAND条件
+        assert_eq!(search(&["S", "M", "ui"]), vec![c.clone()]);
+        assert_eq!(search(&["work", "personal", "S", "M"]), sorted(vec![a, c]));
     }
 
     #[test]
@@ -2042,12 +2643,14 @@ mod tests {
             description: Some("Fix bug".to_string()),
             tags: vec!["urgent".to_string()],
             parent_id: None,
+            estimated_minutes: None,
         };
         let req2 = CreateTaskRequest {
             title: "Urgent Python Task".to_string(),
             description: Some("Add feature".to_string()),
             tags: vec!["urgent".to_string()],
             parent_id: None,
+            estimated_minutes: None,
         };
         TaskService::create_task(&mut conn, req1).unwrap();
         TaskService::create_task(&mut conn, req2).unwrap();
@@ -2059,6 +2662,7 @@ mod tests {
             tags: Some(vec!["urgent".to_string()]),
             limit: None,
             offset: None,
+            cursor: None,
         };
         let result = TaskService::search_tasks(&mut conn, params).unwrap();
         assert_eq!(result.tasks.len(), 1);
@@ -2075,6 +2679,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         TaskService::create_task(&mut conn, req).unwrap();
 
@@ -2085,6 +2690,7 @@ mod tests {
             tags: None,
             limit: None,
             offset: None,
+            cursor: None,
         };
         let result = TaskService::search_tasks(&mut conn, params).unwrap();
         assert_eq!(result.tasks.len(), 0);
@@ -2097,6 +2703,7 @@ mod tests {
             tags: Some(vec!["nonexistent".to_string()]),
             limit: None,
             offset: None,
+            cursor: None,
         };
         let result = TaskService::search_tasks(&mut conn, params).unwrap();
         assert_eq!(result.tasks.len(), 0);
@@ -2113,6 +2720,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         TaskService::create_task(&mut conn, req1).unwrap();
 
@@ -2122,6 +2730,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         let task2 = TaskService::create_task(&mut conn, req2).unwrap();
         let update_req = UpdateTaskRequestInput {
@@ -2140,6 +2749,7 @@ mod tests {
             tags: None,
             limit: None,
             offset: None,
+            cursor: None,
         };
         let result = TaskService::search_tasks(&mut conn, params).unwrap();
         assert_eq!(result.tasks.len(), 1); // archived以外
@@ -2158,6 +2768,7 @@ mod tests {
                 description: None,
                 parent_id: None,
                 tags: vec![],
+                estimated_minutes: None,
             };
             TaskService::create_task(&mut conn, req).unwrap();
         }
@@ -2169,6 +2780,7 @@ mod tests {
             tags: None,
             limit: None,
             offset: None,
+            cursor: None,
         };
 
         let result = TaskService::search_tasks(&mut conn, params).unwrap();
@@ -2188,6 +2800,7 @@ mod tests {
                 description: None,
                 parent_id: None,
                 tags: vec![],
+                estimated_minutes: None,
             };
             TaskService::create_task(&mut conn, req).unwrap();
         }
@@ -2199,6 +2812,7 @@ mod tests {
             tags: None,
             limit: Some(10),
             offset: Some(5),
+            cursor: None,
         };
 
         let result = TaskService::search_tasks(&mut conn, params).unwrap();
@@ -2218,6 +2832,7 @@ mod tests {
                 description: None,
                 parent_id: None,
                 tags: vec![],
+                estimated_minutes: None,
             };
             TaskService::create_task(&mut conn, req).unwrap();
         }
@@ -2229,6 +2844,7 @@ mod tests {
             tags: None,
             limit: Some(10),
             offset: Some(0),
+            cursor: None,
         };
         let result = TaskService::search_tasks(&mut conn, params).unwrap();
         assert_eq!(result.tasks.len(), 10, "1ページ目は10件");
@@ -2241,6 +2857,7 @@ mod tests {
             tags: None,
             limit: Some(10),
             offset: Some(10),
+            cursor: None,
         };
         let result = TaskService::search_tasks(&mut conn, params).unwrap();
         assert_eq!(result.tasks.len(), 10, "2ページ目は10件");
@@ -2253,6 +2870,7 @@ mod tests {
             tags: None,
             limit: Some(10),
             offset: Some(20),
+            cursor: None,
         };
         let result = TaskService::search_tasks(&mut conn, params).unwrap();
         assert_eq!(result.tasks.len(), 5, "3ページ目は残り5件");
@@ -2280,6 +2898,7 @@ mod tests {
                 description: None,
                 parent_id: None,
                 tags: vec!["important".to_string()],
+                estimated_minutes: None,
             };
             TaskService::create_task(&mut conn, req).unwrap();
         }
@@ -2290,6 +2909,7 @@ mod tests {
                 description: None,
                 parent_id: None,
                 tags: vec![],
+                estimated_minutes: None,
             };
             TaskService::create_task(&mut conn, req).unwrap();
         }
@@ -2301,6 +2921,7 @@ mod tests {
             tags: None,
             limit: Some(100),
             offset: None,
+            cursor: None,
         };
         let all_tasks = TaskService::search_tasks(&mut conn, all_tasks_params)
             .unwrap()
@@ -2326,6 +2947,7 @@ mod tests {
             tags: Some(vec!["important".to_string()]),
             limit: Some(5),
             offset: None,
+            cursor: None,
         };
 
         let result = TaskService::search_tasks(&mut conn, params).unwrap();
@@ -2350,6 +2972,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         let parent = TaskService::create_task(&mut conn, parent_req).unwrap();
 
@@ -2359,6 +2982,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: Some(parent.id.clone()),
+            estimated_minutes: None,
         };
         let child1 = TaskService::create_task(&mut conn, child1_req).unwrap();
         let update_req1 = UpdateTaskRequestInput {
@@ -2376,6 +3000,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: Some(parent.id.clone()),
+            estimated_minutes: None,
         };
         let child2 = TaskService::create_task(&mut conn, child2_req).unwrap();
         let update_req2 = UpdateTaskRequestInput {
@@ -2417,6 +3042,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         let parent = TaskService::create_task(&mut conn, parent_req).unwrap();
         let update_req = UpdateTaskRequestInput {
@@ -2434,6 +3060,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: Some(parent.id.clone()),
+            estimated_minutes: None,
         };
         TaskService::create_task(&mut conn, child_req).unwrap();
 
@@ -2454,6 +3081,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         let parent = TaskService::create_task(&mut conn, parent_req).unwrap();
         let update_parent_req = UpdateTaskRequestInput {
@@ -2471,6 +3099,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: Some(parent.id.clone()),
+            estimated_minutes: None,
         };
         TaskService::create_task(&mut conn, child1_req).unwrap();
 
@@ -2480,6 +3109,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: Some(parent.id.clone()),
+            estimated_minutes: None,
         };
         let child2 = TaskService::create_task(&mut conn, child2_req).unwrap();
         let update_req2 = UpdateTaskRequestInput {
@@ -2504,6 +3134,158 @@ mod tests {
         assert_eq!(hierarchy[0].children[0].status, TaskStatus::Draft);
     }
 
+    #[test]
+    fn test_get_hierarchy_since_returns_changed_subtrees() {
+        let mut conn = setup_test_db();
+
+        let parent_a = TaskService::create_task(
+            &mut conn,
+            CreateTaskRequest {
+                title: "Parent A".to_string(),
+                description: None,
+                tags: vec![],
+                parent_id: None,
+                estimated_minutes: None,
+            },
+        )
+        .unwrap();
+        let child_a = TaskService::create_task(
+            &mut conn,
+            CreateTaskRequest {
+                title: "Child A".to_string(),
+                description: None,
+                tags: vec![],
+                parent_id: Some(parent_a.id.clone()),
+                estimated_minutes: None,
+            },
+        )
+        .unwrap();
+        let parent_b = TaskService::create_task(
+            &mut conn,
+            CreateTaskRequest {
+                title: "Parent B".to_string(),
+                description: None,
+                tags: vec![],
+                parent_id: None,
+                estimated_minutes: None,
+            },
+        )
+        .unwrap();
+        let root_c = TaskService::create_task(
+            &mut conn,
+            CreateTaskRequest {
+                title: "Root C".to_string(),
+                description: None,
+                tags: vec![],
+                parent_id: None,
+                estimated_minutes: None,
+            },
+        )
+        .unwrap();
+
+        // 初回: 全サブツリーが変更扱い
+        let initial =
+            TaskService::get_hierarchy_since(&mut conn, "1970-01-01T00:00:00+00:00").unwrap();
+        assert_eq!(initial.changed.len(), 3);
+        assert!(initial.removed_root_ids.is_empty());
+        assert_eq!(initial.moved_under_parent_ids, vec![child_a.id.clone()]);
+
+        // 子タスクの変更とルートタスクのアーカイブ
+        TaskService::update_task(
+            &mut conn,
+            &child_a.id,
+            UpdateTaskRequestInput {
+                title: Some("Child A (edited)".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        TaskService::delete_task(&mut conn, &root_c.id).unwrap();
+
+        let delta = TaskService::get_hierarchy_since(&mut conn, &initial.synced_at).unwrap();
+        assert_eq!(delta.changed.len(), 1, "Parent Aのサブツリーのみ変更");
+        assert_eq!(delta.changed[0].id, parent_a.id);
+        assert_eq!(delta.changed[0].children[0].title, "Child A (edited)");
+        assert!(delta.changed.iter().all(|t| t.id != parent_b.id));
+        assert_eq!(delta.removed_root_ids, vec![root_c.id.clone()]);
+        // 内容だけ変更された子タスクも、子に移動した可能性があるものとして返す
+        assert_eq!(delta.moved_under_parent_ids, vec![child_a.id.clone()]);
+
+        // 変更がなければ空
+        let empty = TaskService::get_hierarchy_since(&mut conn, &delta.synced_at).unwrap();
+        assert!(empty.changed.is_empty());
+        assert!(empty.removed_root_ids.is_empty());
+        assert!(empty.moved_under_parent_ids.is_empty());
+    }
+
+    #[test]
+    fn test_get_hierarchy_since_normalizes_timestamp_and_detects_moved_roots() {
+        let mut conn = setup_test_db();
+
+        let parent = TaskService::create_task(
+            &mut conn,
+            CreateTaskRequest {
+                title: "Parent".to_string(),
+                description: None,
+                tags: vec![],
+                parent_id: None,
+                estimated_minutes: None,
+            },
+        )
+        .unwrap();
+        let moved = TaskService::create_task(
+            &mut conn,
+            CreateTaskRequest {
+                title: "Moved".to_string(),
+                description: None,
+                tags: vec![],
+                parent_id: None,
+                estimated_minutes: None,
+            },
+        )
+        .unwrap();
+
+        // 別のタイムゾーン・小数秒なしの since もUTCの時刻として比較する
+        let synced_at = chrono::DateTime::parse_from_rfc3339(&moved.created_at).unwrap();
+        let in_jst = synced_at
+            .with_timezone(&chrono::FixedOffset::east_opt(9 * 3600).unwrap())
+            .to_rfc3339_opts(chrono::SecondsFormat::Secs, false);
+
+        // ルートを他のタスクの子に移動
+        TaskService::update_task(
+            &mut conn,
+            &moved.id,
+            UpdateTaskRequestInput {
+                parent_id: Some(parent.id.clone()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let delta = TaskService::get_hierarchy_since(&mut conn, &in_jst).unwrap();
+        assert_eq!(delta.changed.len(), 1);
+        assert_eq!(delta.changed[0].id, parent.id);
+        assert_eq!(delta.changed[0].children[0].id, moved.id);
+        assert!(delta.removed_root_ids.is_empty());
+        assert_eq!(delta.moved_under_parent_ids, vec![moved.id.clone()]);
+
+        // 変更より後の時刻（Z表記）なら何も返さない
+        let later = (Utc::now() + chrono::Duration::seconds(1))
+            .to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+        let empty = TaskService::get_hierarchy_since(&mut conn, &later).unwrap();
+        assert!(empty.changed.is_empty());
+        assert!(empty.removed_root_ids.is_empty());
+        assert!(empty.moved_under_parent_ids.is_empty());
+    }
+
+    #[test]
+    fn test_get_hierarchy_since_invalid_timestamp() {
+        let mut conn = setup_test_db();
+
+        let result = TaskService::get_hierarchy_since(&mut conn, "yesterday");
+        assert!(matches!(result, Err(ServiceError::InvalidInput(_))));
+    }
+
     #[test]
     fn test_update_task_rejects_non_draft() {
         let mut conn = setup_test_db();
@@ -2514,6 +3296,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         let task = TaskService::create_task(&mut conn, create_req).unwrap();
 
@@ -2549,6 +3332,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         let task = TaskService::create_task(&mut conn, create_req).unwrap();
 
@@ -2576,6 +3360,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         let task = TaskService::create_task(&mut conn, create_req).unwrap();
         assert_eq!(task.status, TaskStatus::Draft);
@@ -2606,6 +3391,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         let task = TaskService::create_task(&mut conn, create_req).unwrap();
 
@@ -2638,6 +3424,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         let task = TaskService::create_task(&mut conn, create_req).unwrap();
 
@@ -2684,6 +3471,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         let parent = TaskService::create_task(&mut conn, parent_req).unwrap();
 
@@ -2693,6 +3481,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: Some(parent.id.clone()),
+            estimated_minutes: None,
         };
         let child1 = TaskService::create_task(&mut conn, child1_req).unwrap();
 
@@ -2702,6 +3491,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: Some(parent.id.clone()),
+            estimated_minutes: None,
         };
         let child2 = TaskService::create_task(&mut conn, child2_req).unwrap();
 
@@ -2769,6 +3559,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         let task = TaskService::create_task(&mut conn, create_req).unwrap();
 
@@ -2802,6 +3593,7 @@ mod tests {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         let draft_task = TaskService::create_task(&mut conn, create_req).unwrap();
 
@@ -2866,6 +3658,7 @@ mod tests {
                 description: None,
                 tags: vec![],
                 parent_id: None,
+                estimated_minutes: None,
             },
         )
         .unwrap();
@@ -2878,6 +3671,7 @@ mod tests {
                     description: None,
                     tags: vec![],
                     parent_id: None,
+                    estimated_minutes: None,
                 },
             )
             .unwrap();
@@ -2896,6 +3690,7 @@ mod tests {
                     description: None,
                     tags: vec![],
                     parent_id: None,
+                    estimated_minutes: None,
                 },
             )
             .unwrap();
@@ -2914,6 +3709,7 @@ mod tests {
                     description: None,
                     tags: vec![],
                     parent_id: None,
+                    estimated_minutes: None,
                 },
             )
             .unwrap();
@@ -2948,6 +3744,7 @@ mod tests {
                     description: None,
                     tags: vec![],
                     parent_id: None,
+                    estimated_minutes: None,
                 },
             )
             .unwrap();
@@ -2966,6 +3763,7 @@ mod tests {
                 description: None,
                 tags: vec![],
                 parent_id: None,
+                estimated_minutes: None,
             },
         )
         .unwrap();
@@ -2992,6 +3790,7 @@ mod tests {
                     description: None,
                     tags: vec![],
                     parent_id: None,
+                    estimated_minutes: None,
                 },
             )
             .unwrap();
@@ -3010,6 +3809,7 @@ mod tests {
                 description: None,
                 tags: vec![],
                 parent_id: None,
+                estimated_minutes: None,
             },
         )
         .unwrap();
@@ -3035,6 +3835,7 @@ mod tests {
                 description: None,
                 tags: vec![],
                 parent_id: None,
+                estimated_minutes: None,
             },
         )
         .unwrap();
@@ -3047,6 +3848,7 @@ mod tests {
                     description: None,
                     tags: vec![],
                     parent_id: None,
+                    estimated_minutes: None,
                 },
             )
             .unwrap();
@@ -3065,6 +3867,7 @@ mod tests {
                     description: None,
                     tags: vec![],
                     parent_id: None,
+                    estimated_minutes: None,
                 },
             )
             .unwrap();
@@ -3100,6 +3903,7 @@ mod tests {
                 description: None,
                 tags: vec![],
                 parent_id: None,
+                estimated_minutes: None,
             },
         )
         .unwrap();
@@ -3112,6 +3916,83 @@ mod tests {
         assert!(result.is_empty());
     }
 
+    #[test]
+    fn test_list_tasks_batch_enrichment() {
+        use crate::models::tag::CreateTagRequest;
+        use crate::service::TagService;
+
+        let mut conn = setup_test_db();
+
+        for name in ["work", "urgent"] {
+            TagService::create_tag(
+                &mut conn,
+                CreateTagRequest {
+                    name: name.to_string(),
+                    color: None,
+                },
+            )
+            .unwrap();
+        }
+
+        let parent = TaskService::create_task(
+            &mut conn,
+            CreateTaskRequest {
+                title: "Parent".to_string(),
+                description: None,
+                tags: vec!["work".to_string()],
+                parent_id: None,
+                estimated_minutes: None,
+            },
+        )
+        .unwrap();
+        let child1 = TaskService::create_task(
+            &mut conn,
+            CreateTaskRequest {
+                title: "Child 1".to_string(),
+                description: None,
+                tags: vec!["work".to_string(), "urgent".to_string()],
+                parent_id: Some(parent.id.clone()),
+                estimated_minutes: None,
+            },
+        )
+        .unwrap();
+        let child2 = TaskService::create_task(
+            &mut conn,
+            CreateTaskRequest {
+                title: "Child 2".to_string(),
+                description: None,
+                tags: vec![],
+                parent_id: Some(parent.id.clone()),
+                estimated_minutes: None,
+            },
+        )
+        .unwrap();
+
+        let tasks = TaskService::list_tasks(&mut conn, None).unwrap();
+        assert_eq!(tasks.len(), 3);
+
+        let listed_parent = tasks.iter().find(|t| t.id == parent.id).unwrap();
+        assert_eq!(listed_parent.tags, vec!["work".to_string()]);
+        assert_eq!(listed_parent.children_ids.len(), 2);
+        assert!(listed_parent.children_ids.contains(&child1.id));
+        assert!(listed_parent.children_ids.contains(&child2.id));
+        assert_eq!(listed_parent.parent_title, None);
+
+        let listed_child1 = tasks.iter().find(|t| t.id == child1.id).unwrap();
+        assert_eq!(listed_child1.tags.len(), 2);
+        assert_eq!(listed_child1.parent_title, Some("Parent".to_string()));
+        assert!(listed_child1.children_ids.is_empty());
+
+        let listed_child2 = tasks.iter().find(|t| t.id == child2.id).unwrap();
+        assert!(listed_child2.tags.is_empty());
+        assert_eq!(listed_child2.parent_title, Some("Parent".to_string()));
+
+        // get_taskも同じローダーで親タイトルを付与する
+        let fetched = TaskService::get_task(&mut conn, &child1.id).unwrap();
+        assert_eq!(fetched.parent_title, Some("Parent".to_string()));
+        assert_eq!(fetched.tags.len(), 2);
+    }
+
     // ===== ページネーション機能のテスト (REQ-0024) =====
 
     #[test]
@@ -3127,6 +4008,7 @@ mod tests {
                     description: None,
                     tags: vec![],
                     parent_id: None,
+                    estimated_minutes: None,
                 },
             )
             .unwrap();
@@ -3154,6 +4036,7 @@ mod tests {
                     description: None,
                     tags: vec![],
                     parent_id: None,
+                    estimated_minutes: None,
                 },
             )
             .unwrap();
@@ -3166,6 +4049,7 @@ mod tests {
                 status: None,
                 limit: Some(10),
                 offset: Some(0),
+                cursor: None,
             },
         )
         .unwrap();
@@ -3177,6 +4061,7 @@ mod tests {
                 status: None,
                 limit: Some(10),
                 offset: Some(10),
+                cursor: None,
             },
         )
         .unwrap();
@@ -3211,6 +4096,7 @@ mod tests {
                     description: None,
                     tags: vec![],
                     parent_id: None,
+                    estimated_minutes: None,
                 },
             )
             .unwrap();
@@ -3224,6 +4110,7 @@ mod tests {
                     description: None,
                     tags: vec![],
                     parent_id: None,
+                    estimated_minutes: None,
                 },
             )
             .unwrap();
@@ -3242,6 +4129,7 @@ mod tests {
                     description: None,
                     tags: vec![],
                     parent_id: None,
+                    estimated_minutes: None,
                 },
             )
             .unwrap();
@@ -3267,6 +4155,7 @@ mod tests {
                 status: Some(vec!["completed".to_string()]),
                 limit: None,
                 offset: None,
+                cursor: None,
             },
         )
         .unwrap();
@@ -3287,6 +4176,7 @@ mod tests {
                     description: None,
                     tags: vec![],
                     parent_id: None,
+                    estimated_minutes: None,
                 },
             )
             .unwrap();
@@ -3300,6 +4190,7 @@ mod tests {
                     description: None,
                     tags: vec![],
                     parent_id: None,
+                    estimated_minutes: None,
                 },
             )
             .unwrap();
@@ -3317,6 +4208,7 @@ mod tests {
                 status: Some(vec!["completed".to_string()]),
                 limit: Some(10),
                 offset: Some(0),
+                cursor: None,
             },
         )
         .unwrap();
@@ -3338,6 +4230,7 @@ mod tests {
                 status: Some(vec![]),
                 limit: None,
                 offset: None,
+                cursor: None,
             },
         )
         .unwrap();
@@ -3358,6 +4251,7 @@ mod tests {
                 description: None,
                 tags: vec![],
                 parent_id: None,
+                estimated_minutes: None,
             },
         )
         .unwrap();
@@ -3370,6 +4264,7 @@ mod tests {
                 description: None,
                 tags: vec![],
                 parent_id: Some(parent.id.clone()),
+                estimated_minutes: None,
             },
         )
         .unwrap();
@@ -3387,6 +4282,7 @@ mod tests {
                 status: Some(vec!["completed".to_string()]),
                 limit: Some(10),
                 offset: Some(0),
+                cursor: None,
             },
         )
         .unwrap();
@@ -3411,6 +4307,7 @@ mod tests {
                 description: None,
                 tags: vec![],
                 parent_id: None,
+                estimated_minutes: None,
             },
         )
         .unwrap();
@@ -3428,6 +4325,7 @@ mod tests {
                 status: Some(vec!["completed".to_string()]),
                 limit: Some(10),
                 offset: Some(0),
+                cursor: None,
             },
         )
         .unwrap();
@@ -3452,6 +4350,7 @@ mod tests {
                 description: None,
                 tags: vec![],
                 parent_id: None,
+                estimated_minutes: None,
             },
         )
         .unwrap();
@@ -3463,6 +4362,7 @@ mod tests {
                 description: None,
                 tags: vec![],
                 parent_id: None,
+                estimated_minutes: None,
             },
         )
         .unwrap();
@@ -3475,6 +4375,7 @@ mod tests {
                 description: None,
                 tags: vec![],
                 parent_id: Some(parent1.id.clone()),
+                estimated_minutes: None,
             },
         )
         .unwrap();
@@ -3486,6 +4387,7 @@ mod tests {
                 description: None,
                 tags: vec![],
                 parent_id: Some(parent2.id.clone()),
+                estimated_minutes: None,
             },
         )
         .unwrap();
@@ -3508,6 +4410,7 @@ mod tests {
                 status: Some(vec!["completed".to_string()]),
                 limit: Some(10),
                 offset: Some(0),
+                cursor: None,
             },
         )
         .unwrap();
@@ -3526,6 +4429,170 @@ mod tests {
         }
     }
 
+    // ===== カーソルページネーションのテスト =====
+
+    #[test]
+    fn test_list_tasks_paginated_cursor_stable_across_inserts() {
+        let mut conn = setup_test_db();
+
+        for i in 1..=5 {
+            TaskService::create_task(
+                &mut conn,
+                CreateTaskRequest {
+                    title: format!("Task {}", i),
+                    description: None,
+                    tags: vec![],
+                    parent_id: None,
+                    estimated_minutes: None,
+                },
+            )
+            .unwrap();
+        }
+
+        // 1ページ目（新しい順: Task 5, Task 4）
+        let page1 = TaskService::list_tasks_paginated(
+            &mut conn,
+            ListTasksPaginatedParams {
+                status: None,
+                limit: Some(2),
+                offset: None,
+                cursor: None,
+            },
+        )
+        .unwrap();
+        let titles: Vec<&str> = page1.tasks.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, vec!["Task 5", "Task 4"]);
+        assert!(page1.next_cursor.is_some());
+        assert!(page1.prev_cursor.is_none());
+
+        // ページング中に新しいタスクが追加される
+        TaskService::create_task(
+            &mut conn,
+            CreateTaskRequest {
+                title: "Task 6".to_string(),
+                description: None,
+                tags: vec![],
+                parent_id: None,
+                estimated_minutes: None,
+            },
+        )
+        .unwrap();
+
+        // 2ページ目はカーソル位置から取得されるためずれない
+        let page2 = TaskService::list_tasks_paginated(
+            &mut conn,
+            ListTasksPaginatedParams {
+                status: None,
+                limit: Some(2),
+                offset: None,
+                cursor: page1.next_cursor.clone(),
+            },
+        )
+        .unwrap();
+        let titles: Vec<&str> = page2.tasks.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, vec!["Task 3", "Task 2"]);
+        assert_eq!(page2.total, 6);
+        assert!(page2.next_cursor.is_some());
+        assert!(page2.prev_cursor.is_some());
+
+        // 最終ページ
+        let page3 = TaskService::list_tasks_paginated(
+            &mut conn,
+            ListTasksPaginatedParams {
+                status: None,
+                limit: Some(2),
+                offset: None,
+                cursor: page2.next_cursor.clone(),
+            },
+        )
+        .unwrap();
+        let titles: Vec<&str> = page3.tasks.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, vec!["Task 1"]);
+        assert!(page3.next_cursor.is_none());
+
+        // 2ページ目から前ページへ戻る
+        let back = TaskService::list_tasks_paginated(
+            &mut conn,
+            ListTasksPaginatedParams {
+                status: None,
+                limit: Some(2),
+                offset: None,
+                cursor: page2.prev_cursor.clone(),
+            },
+        )
+        .unwrap();
+        let titles: Vec<&str> = back.tasks.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, vec!["Task 5", "Task 4"]);
+        assert!(back.prev_cursor.is_some(), "追加されたTask 6が前に存在する");
+    }
+
+    #[test]
+    fn test_search_tasks_with_cursor() {
+        let mut conn = setup_test_db();
+
+        for i in 1..=4 {
+            TaskService::create_task(
+                &mut conn,
+                CreateTaskRequest {
+                    title: format!("Search {}", i),
+                    description: None,
+                    tags: vec![],
+                    parent_id: None,
+                    estimated_minutes: None,
+                },
+            )
+            .unwrap();
+        }
+
+        let page1 = TaskService::search_tasks(
+            &mut conn,
+            SearchTasksParams {
+                q: Some("Search".to_string()),
+                status: None,
+                tags: None,
+                limit: Some(3),
+                offset: None,
+                cursor: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(page1.tasks.len(), 3);
+
+        let page2 = TaskService::search_tasks(
+            &mut conn,
+            SearchTasksParams {
+                q: Some("Search".to_string()),
+                status: None,
+                tags: None,
+                limit: Some(3),
+                offset: None,
+                cursor: page1.next_cursor,
+            },
+        )
+        .unwrap();
+        assert_eq!(page2.tasks.len(), 1);
+        assert_eq!(page2.tasks[0].title, "Search 1");
+        assert!(page2.next_cursor.is_none());
+        assert_eq!(page2.total, 4);
+    }
+
+    #[test]
+    fn test_list_tasks_paginated_invalid_cursor() {
+        let mut conn = setup_test_db();
+
+        let result = TaskService::list_tasks_paginated(
+            &mut conn,
+            ListTasksPaginatedParams {
+                status: None,
+                limit: Some(10),
+                offset: None,
+                cursor: Some("not-a-cursor".to_string()),
+            },
+        );
+
+        assert!(matches!(result, Err(ServiceError::InvalidInput(_))));
+    }
+
     #[test]
     fn test_parent_updated_at_changes_when_child_status_changes() {
         let mut conn = setup_test_db();
@@ -3538,6 +4605,7 @@ mod tests {
                 description: None,
                 tags: vec![],
                 parent_id: None,
+                estimated_minutes: None,
             },
         )
         .unwrap();
@@ -3556,6 +4624,7 @@ mod tests {
                 description: None,
                 tags: vec![],
                 parent_id: Some(parent.id.clone()),
+                estimated_minutes: None,
             },
         )
         .unwrap();
@@ -3603,6 +4672,7 @@ mod tests {
                 description: None,
                 tags: vec![],
                 parent_id: None,
+                estimated_minutes: None,
             },
         )
         .unwrap();
@@ -3615,6 +4685,7 @@ mod tests {
                 description: None,
                 tags: vec![],
                 parent_id: Some(parent.id.clone()),
+                estimated_minutes: None,
             },
         )
         .unwrap();
@@ -3627,6 +4698,7 @@ mod tests {
                 description: None,
                 tags: vec![],
                 parent_id: Some(parent.id.clone()),
+                estimated_minutes: None,
             },
         )
         .unwrap();
@@ -3668,6 +4740,7 @@ mod tests {
                 description: None,
                 tags: vec![],
                 parent_id: None,
+                estimated_minutes: None,
             },
         )
         .unwrap();
@@ -3680,6 +4753,7 @@ mod tests {
                 description: None,
                 tags: vec![],
                 parent_id: Some(parent.id.clone()),
+                estimated_minutes: None,
             },
         )
         .unwrap();
@@ -3713,4 +4787,48 @@ mod tests {
             "親タスクがアーカイブされているべき"
         );
     }
+
+    #[test]
+    fn test_task_estimate() {
+        let conn = &mut setup_test_db();
+
+        // 作成時に見積もり時間を指定
+        let task = TaskService::create_task(
+            conn,
+            CreateTaskRequest {
+                title: "Estimated Task".to_string(),
+                description: None,
+                tags: vec![],
+                parent_id: None,
+                estimated_minutes: Some(25),
+            },
+        )
+        .unwrap();
+        assert_eq!(task.estimated_minutes, Some(25));
+
+        // 範囲外の見積もりはエラー
+        let result = TaskService::set_task_estimate(conn, &task.id, Some(0));
+        assert!(matches!(result, Err(ServiceError::InvalidInput(_))));
+
+        // Draft以外のタスクにも設定できる
+        crate::service::QueueService::add_to_queue(
+            conn,
+            crate::models::queue::DEFAULT_QUEUE_ID,
+            task.id.clone(),
+            None,
+            false,
+        )
+        .unwrap();
+        let updated = TaskService::set_task_estimate(conn, &task.id, Some(90)).unwrap();
+        assert_eq!(updated.status, TaskStatus::Active);
+        assert_eq!(updated.estimated_minutes, Some(90));
+
+        // Noneで未設定に戻す
+        let cleared = TaskService::set_task_estimate(conn, &task.id, None).unwrap();
+        assert_eq!(cleared.estimated_minutes, None);
+
+        // 存在しないタスク
+        let result = TaskService::set_task_estimate(conn, "missing", Some(10));
+        assert!(matches!(result, Err(ServiceError::TaskNotFound(_))));
+    }
 }
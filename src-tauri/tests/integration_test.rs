@@ -2,7 +2,8 @@ use diesel::prelude::*;
 use diesel::r2d2::{ConnectionManager, Pool};
 use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
 
-use tms_v2_lib::models::tag::{CreateTagRequest, UpdateTagRequest};
+use tms_v2_lib::models::queue::DEFAULT_QUEUE_ID;
+use tms_v2_lib::models::tag::{CreateTagGroupRequest, CreateTagRequest, UpdateTagRequest};
 use tms_v2_lib::models::task::{CreateTaskRequest, SearchTasksParams, TaskStatus, UpdateTaskRequestInput};
 use tms_v2_lib::schema::tasks;
 use tms_v2_lib::service::{QueueService, TagService, TaskService};
@@ -40,6 +41,7 @@ fn test_create_task_success() {
         description: Some("説明文".to_string()),
         tags: vec![],
         parent_id: None,
+        estimated_minutes: None,
     };
 
     let result = TaskService::create_task(&mut conn, req);
@@ -59,6 +61,7 @@ fn test_create_task_invalid_input() {
         description: None,
         tags: vec![],
         parent_id: None,
+        estimated_minutes: None,
     };
 
     let result = TaskService::create_task(&mut conn, req);
@@ -76,6 +79,7 @@ fn test_get_task_success() {
         description: None,
         tags: vec![],
         parent_id: None,
+        estimated_minutes: None,
     };
     let created = TaskService::create_task(&mut conn, req).unwrap();
 
@@ -107,6 +111,7 @@ fn test_update_task_success() {
         description: None,
         tags: vec![],
         parent_id: None,
+        estimated_minutes: None,
     };
     let created = TaskService::create_task(&mut conn, req).unwrap();
 
@@ -135,6 +140,7 @@ fn test_delete_task_success() {
         description: None,
         tags: vec![],
         parent_id: None,
+        estimated_minutes: None,
     };
     let created = TaskService::create_task(&mut conn, req).unwrap();
 
@@ -159,6 +165,7 @@ fn test_list_tasks() {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         TaskService::create_task(&mut conn, req).unwrap();
     }
@@ -181,6 +188,7 @@ fn test_search_tasks_with_filters() {
         description: Some("キーワード含む".to_string()),
         tags: vec![],
         parent_id: None,
+        estimated_minutes: None,
     };
     TaskService::create_task(&mut conn, req1).unwrap();
 
@@ -189,6 +197,7 @@ fn test_search_tasks_with_filters() {
         description: Some("別の説明".to_string()),
         tags: vec![],
         parent_id: None,
+        estimated_minutes: None,
     };
     TaskService::create_task(&mut conn, req2).unwrap();
 
@@ -199,6 +208,7 @@ fn test_search_tasks_with_filters() {
         tags: None,
         limit: None,
         offset: None,
+        cursor: None,
     };
     let result = TaskService::search_tasks(&mut conn, params);
     assert!(result.is_ok());
@@ -222,11 +232,13 @@ fn test_add_to_queue_success() {
         description: None,
         tags: vec![],
         parent_id: None,
+        estimated_minutes: None,
     };
     let task = TaskService::create_task(&mut conn, req).unwrap();
 
     // キューに追加（自動的にActiveに変更される）
-    let result = QueueService::add_to_queue(&mut conn, task.id.clone());
+    let result =
+        QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, task.id.clone(), None, false);
     assert!(result.is_ok());
 }
 
@@ -241,14 +253,16 @@ fn test_add_to_queue_duplicate() {
         description: None,
         tags: vec![],
         parent_id: None,
+        estimated_minutes: None,
     };
     let task = TaskService::create_task(&mut conn, req).unwrap();
 
     // 1回目の追加
-    QueueService::add_to_queue(&mut conn, task.id.clone()).unwrap();
+    QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, task.id.clone(), None, false).unwrap();
 
     // 2回目の追加（重複エラー）
-    let result = QueueService::add_to_queue(&mut conn, task.id.clone());
+    let result =
+        QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, task.id.clone(), None, false);
     assert!(result.is_err());
 }
 
@@ -264,13 +278,14 @@ fn test_get_queue() {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         let task = TaskService::create_task(&mut conn, req).unwrap();
-        QueueService::add_to_queue(&mut conn, task.id).unwrap();
+        QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, task.id, None, false).unwrap();
     }
 
     // キューを取得
-    let result = QueueService::get_queue(&mut conn);
+    let result = QueueService::get_queue(&mut conn, DEFAULT_QUEUE_ID, None);
     assert!(result.is_ok());
     let queue = result.unwrap();
     assert_eq!(queue.len(), 2);
@@ -287,14 +302,16 @@ fn test_remove_from_queue() {
         description: None,
         tags: vec![],
         parent_id: None,
+        estimated_minutes: None,
     };
     let task = TaskService::create_task(&mut conn, req).unwrap();
     let task_id = task.id.clone();
 
-    QueueService::add_to_queue(&mut conn, task_id.clone()).unwrap();
+    QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, task_id.clone(), None, false).unwrap();
 
     // キューから削除（draftに戻す）
-    let result = QueueService::remove_from_queue(&mut conn, task_id, "draft".to_string());
+    let result =
+        QueueService::remove_from_queue(&mut conn, DEFAULT_QUEUE_ID, task_id, "draft".to_string());
     assert!(result.is_ok());
 }
 
@@ -310,17 +327,18 @@ fn test_clear_queue() {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         let task = TaskService::create_task(&mut conn, req).unwrap();
-        QueueService::add_to_queue(&mut conn, task.id).unwrap();
+        QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, task.id, None, false).unwrap();
     }
 
     // キューをクリア
-    let result = QueueService::clear_queue(&mut conn);
+    let result = QueueService::clear_queue(&mut conn, DEFAULT_QUEUE_ID, None);
     assert!(result.is_ok());
 
     // 空になったことを確認
-    let queue = QueueService::get_queue(&mut conn).unwrap();
+    let queue = QueueService::get_queue(&mut conn, DEFAULT_QUEUE_ID, None).unwrap();
     assert_eq!(queue.len(), 0);
 }
 
@@ -335,14 +353,15 @@ fn test_update_queue_position() {
         description: None,
         tags: vec![],
         parent_id: None,
+        estimated_minutes: None,
     };
     let task = TaskService::create_task(&mut conn, req).unwrap();
     let task_id = task.id.clone();
 
-    QueueService::add_to_queue(&mut conn, task_id.clone()).unwrap();
+    QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, task_id.clone(), None, false).unwrap();
 
     // 位置を更新
-    let result = QueueService::update_queue_position(&mut conn, task_id, 0);
+    let result = QueueService::update_queue_position(&mut conn, DEFAULT_QUEUE_ID, task_id, 0);
     assert!(result.is_ok());
 }
 
@@ -359,20 +378,23 @@ fn test_reorder_queue() {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         };
         let task = TaskService::create_task(&mut conn, req).unwrap();
         let task_id = task.id.clone();
-        QueueService::add_to_queue(&mut conn, task_id.clone()).unwrap();
+        QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, task_id.clone(), None, false)
+            .unwrap();
         task_ids.push(task_id);
     }
 
     // 並び替え（逆順）
     task_ids.reverse();
-    let result = QueueService::reorder_queue(&mut conn, task_ids.clone());
+    let result =
+        QueueService::reorder_queue(&mut conn, DEFAULT_QUEUE_ID, task_ids.clone(), None, false);
     assert!(result.is_ok());
 
     // 順序を確認
-    let queue = QueueService::get_queue(&mut conn).unwrap();
+    let queue = QueueService::get_queue(&mut conn, DEFAULT_QUEUE_ID, None).unwrap();
     assert_eq!(queue[0].task_id, task_ids[0]);
     assert_eq!(queue[1].task_id, task_ids[1]);
     assert_eq!(queue[2].task_id, task_ids[2]);
@@ -389,17 +411,18 @@ fn test_reorder_queue_invalid_size() {
         description: None,
         tags: vec![],
         parent_id: None,
+        estimated_minutes: None,
     };
     let task = TaskService::create_task(&mut conn, req).unwrap();
-    QueueService::add_to_queue(&mut conn, task.id).unwrap();
+    QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, task.id, None, false).unwrap();
 
     // サイズが一致しない並び替え要求
-    let result = QueueService::reorder_queue(&mut conn, vec![]); // 空の配列
+    let result = QueueService::reorder_queue(&mut conn, DEFAULT_QUEUE_ID, vec![], None, false); // 空の配列
     assert!(result.is_err());
 }
 
 // ========================================
-// Tag API Tests (6 tests)
+// Tag API Tests (8 tests)
 // ========================================
 
 #[test]
@@ -471,6 +494,7 @@ fn test_update_tag() {
         name: Some("更新後".to_string()),
         color: None,
         updated_at: None,
+        merge: false,
     };
     let result = TagService::update_tag(&mut conn, &created.id, update_req);
     assert!(result.is_ok());
@@ -513,6 +537,7 @@ fn test_delete_tag_in_use() {
         description: None,
         tags: vec![tag.name.clone()],
         parent_id: None,
+        estimated_minutes: None,
     };
     TaskService::create_task(&mut conn, task_req).unwrap();
 
@@ -521,6 +546,170 @@ fn test_delete_tag_in_use() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_search_by_tag_namespace_includes_descendants() {
+    let pool = setup_test_pool();
+    let mut conn = pool.get().expect("Failed to get connection");
+
+    // 親のパス（work、work/clientA）も自動で作成される
+    for name in ["work/clientA/billing", "work/clientB", "home"] {
+        TagService::create_tag(
+            &mut conn,
+            CreateTagRequest {
+                name: name.to_string(),
+                color: None,
+            },
+        )
+        .unwrap();
+    }
+
+    let mut create = |title: &str, tag: &str| {
+        TaskService::create_task(
+            &mut conn,
+            CreateTaskRequest {
+                title: title.to_string(),
+                description: None,
+                tags: vec![tag.to_string()],
+                parent_id: None,
+                estimated_minutes: None,
+            },
+        )
+        .unwrap()
+        .id
+    };
+    let billing = create("請求書", "work/clientA/billing");
+    let client_b = create("打ち合わせ", "work/clientB");
+    let work = create("週報", "work");
+    let home = create("掃除", "home");
+
+    let params = |tag: &str| SearchTasksParams {
+        q: None,
+        status: None,
+        tags: Some(vec![tag.to_string()]),
+        limit: None,
+        offset: None,
+        cursor: None,
+    };
+    let mut found: Vec<String> = TaskService::search_tasks(&mut conn, params("work"))
+        .unwrap()
+        .tasks
+        .into_iter()
+        .map(|t| t.id)
+        .collect();
+    found.sort();
+    let mut expected = vec![billing.clone(), client_b, work];
+    expected.sort();
+    assert_eq!(found, expected);
+
+    let ids = TaskService::search_task_ids(&mut conn, Some(vec!["work/clientA".to_string()]), None)
+        .unwrap();
+    assert_eq!(ids, vec![billing]);
+    let ids =
+        TaskService::search_task_ids(&mut conn, Some(vec!["home".to_string()]), None).unwrap();
+    assert_eq!(ids, vec![home]);
+}
+
+#[test]
+fn test_search_by_tag_groups_ands_across_groups() {
+    let pool = setup_test_pool();
+    let mut conn = pool.get().expect("Failed to get connection");
+
+    // サイズ: S / M、領域: ui / api、グループなし: 急ぎ
+    for (group_name, tag_names) in [("サイズ", ["S", "M"]), ("領域", ["ui", "api"])] {
+        let group = TagService::create_tag_group(
+            &mut conn,
+            CreateTagGroupRequest {
+                name: group_name.to_string(),
+                exclusive: true,
+            },
+        )
+        .unwrap();
+        for name in tag_names {
+            let tag = TagService::create_tag(
+                &mut conn,
+                CreateTagRequest {
+                    name: name.to_string(),
+                    color: None,
+                },
+            )
+            .unwrap();
+            TagService::set_tag_group(&mut conn, &tag.id, Some(&group.id)).unwrap();
+        }
+    }
+    TagService::create_tag(
+        &mut conn,
+        CreateTagRequest {
+            name: "急ぎ".to_string(),
+            color: None,
+        },
+    )
+    .unwrap();
+
+    let mut create = |tags: &[&str]| {
+        TaskService::create_task(
+            &mut conn,
+            CreateTaskRequest {
+                title: tags.join(" "),
+                description: None,
+                tags: tags.iter().map(|t| t.to_string()).collect(),
+                parent_id: None,
+                estimated_minutes: None,
+            },
+        )
+        .unwrap()
+        .id
+    };
+    let small_ui = create(&["S", "ui"]);
+    let medium_ui = create(&["M", "ui", "急ぎ"]);
+    let small_api = create(&["S", "api"]);
+    let urgent = create(&["急ぎ"]);
+
+    let search = |conn: &mut SqliteConnection, tags: &[&str]| {
+        let mut ids = TaskService::search_task_ids(
+            conn,
+            Some(tags.iter().map(|t| t.to_string()).collect()),
+            None,
+        )
+        .unwrap();
+        ids.sort();
+        ids
+    };
+    let sorted = |mut ids: Vec<String>| {
+        ids.sort();
+        ids
+    };
+
+    // 同じグループ内はOR条件
+    assert_eq!(
+        search(&mut conn, &["S", "M"]),
+        sorted(vec![small_ui.clone(), medium_ui.clone(), small_api.clone()])
+    );
+    // グループ間はAND条件
+    assert_eq!(
+        search(&mut conn, &["S", "M", "ui"]),
+        sorted(vec![small_ui.clone(), medium_ui.clone()])
+    );
+    assert_eq!(search(&mut conn, &["S", "api"]), vec![small_api]);
+    // グループに属さないタグは1つのグループとして扱う
+    assert_eq!(search(&mut conn, &["急ぎ", "ui"]), vec![medium_ui.clone()]);
+    assert_eq!(
+        search(&mut conn, &["急ぎ"]),
+        sorted(vec![medium_ui.clone(), urgent])
+    );
+
+    let params = SearchTasksParams {
+        q: None,
+        status: None,
+        tags: Some(vec!["M".to_string(), "急ぎ".to_string()]),
+        limit: None,
+        offset: None,
+        cursor: None,
+    };
+    let result = TaskService::search_tasks(&mut conn, params).unwrap();
+    assert_eq!(result.total, 1);
+    assert_eq!(result.tasks[0].id, medium_ui);
+}
+
 // ========================================
 // Integration Scenario Tests (3 tests)
 // ========================================
@@ -543,14 +732,15 @@ fn test_complete_workflow() {
         description: Some("完全なワークフローテスト".to_string()),
         tags: vec![tag.name.clone()],
         parent_id: None,
+        estimated_minutes: None,
     };
     let task = TaskService::create_task(&mut conn, task_req).unwrap();
 
     // 3. キューに追加（自動的にActiveに変更される）
-    QueueService::add_to_queue(&mut conn, task.id.clone()).unwrap();
+    QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, task.id.clone(), None, false).unwrap();
 
     // 4. キューを取得して確認
-    let queue = QueueService::get_queue(&mut conn).unwrap();
+    let queue = QueueService::get_queue(&mut conn, DEFAULT_QUEUE_ID, None).unwrap();
     assert_eq!(queue.len(), 1);
     assert_eq!(queue[0].task_id, task.id);
 
@@ -576,6 +766,7 @@ fn test_parent_child_task_workflow() {
         description: None,
         tags: vec![],
         parent_id: None,
+        estimated_minutes: None,
     };
     let parent = TaskService::create_task(&mut conn, parent_req).unwrap();
 
@@ -585,6 +776,7 @@ fn test_parent_child_task_workflow() {
         description: None,
         tags: vec![],
         parent_id: Some(parent.id.clone()),
+        estimated_minutes: None,
     };
     let child = TaskService::create_task(&mut conn, child_req).unwrap();
     assert_eq!(child.parent_id, Some(parent.id.clone()));
@@ -604,6 +796,7 @@ fn test_parent_child_task_workflow() {
         description: None,
         tags: vec![],
         parent_id: Some(child.id.clone()),
+        estimated_minutes: None,
     };
     let grandchild_result = TaskService::create_task(&mut conn, grandchild_req);
     assert!(grandchild_result.is_err()); // 孫タスク作成は禁止されている
@@ -641,6 +834,7 @@ fn test_tag_filter_workflow() {
             description: None,
             tags: vec![tag1.name.clone()],
             parent_id: None,
+            estimated_minutes: None,
         },
     )
     .unwrap();
@@ -652,6 +846,7 @@ fn test_tag_filter_workflow() {
             description: None,
             tags: vec![tag2.name.clone()],
             parent_id: None,
+            estimated_minutes: None,
         },
     )
     .unwrap();
@@ -663,6 +858,7 @@ fn test_tag_filter_workflow() {
         tags: Some(vec![tag1.name]),
         limit: None,
         offset: None,
+        cursor: None,
     };
     let result = TaskService::search_tasks(&mut conn, params).unwrap();
     assert_eq!(result.tasks.len(), 1);
@@ -684,6 +880,7 @@ fn test_parent_status_sync_on_child_update() {
         description: None,
         tags: vec![],
         parent_id: None,
+        estimated_minutes: None,
     };
     let parent = TaskService::create_task(&mut conn, parent_req).unwrap();
 
@@ -693,6 +890,7 @@ fn test_parent_status_sync_on_child_update() {
         description: None,
         tags: vec![],
         parent_id: Some(parent.id.clone()),
+        estimated_minutes: None,
     };
     let child1 = TaskService::create_task(&mut conn, child1_req).unwrap();
 
@@ -702,6 +900,7 @@ fn test_parent_status_sync_on_child_update() {
         description: None,
         tags: vec![],
         parent_id: Some(parent.id.clone()),
+        estimated_minutes: None,
     };
     TaskService::create_task(&mut conn, child2_req).unwrap();
 
@@ -735,6 +934,7 @@ fn test_parent_status_sync_on_child_delete() {
         description: None,
         tags: vec![],
         parent_id: None,
+        estimated_minutes: None,
     };
     let parent = TaskService::create_task(&mut conn, parent_req).unwrap();
 
@@ -744,6 +944,7 @@ fn test_parent_status_sync_on_child_delete() {
         description: None,
         tags: vec![],
         parent_id: Some(parent.id.clone()),
+        estimated_minutes: None,
     };
     let child1 = TaskService::create_task(&mut conn, child1_req).unwrap();
 
@@ -784,6 +985,7 @@ fn test_queue_registration_restriction() {
         description: None,
         tags: vec![],
         parent_id: None,
+        estimated_minutes: None,
     };
     let parent = TaskService::create_task(&mut conn, parent_req).unwrap();
 
@@ -793,19 +995,22 @@ fn test_queue_registration_restriction() {
         description: None,
         tags: vec![],
         parent_id: Some(parent.id.clone()),
+        estimated_minutes: None,
     };
     let child = TaskService::create_task(&mut conn, child_req).unwrap();
 
     // 親タスクをキューに追加しようとする（失敗するはず：BR-015）
-    let parent_result = QueueService::add_to_queue(&mut conn, parent.id.clone());
+    let parent_result =
+        QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, parent.id.clone(), None, false);
     assert!(parent_result.is_err());
 
     // 子タスクをキューに追加する（成功するはず）
-    let child_result = QueueService::add_to_queue(&mut conn, child.id.clone());
+    let child_result =
+        QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, child.id.clone(), None, false);
     assert!(child_result.is_ok());
 
     // キューを確認（子タスクのみ）
-    let queue = QueueService::get_queue(&mut conn).unwrap();
+    let queue = QueueService::get_queue(&mut conn, DEFAULT_QUEUE_ID, None).unwrap();
     assert_eq!(queue.len(), 1);
     assert_eq!(queue[0].task_title, "子タスク");
 }
@@ -825,6 +1030,7 @@ fn test_queue_add_updates_parent_status() {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         },
     )
     .unwrap();
@@ -837,6 +1043,7 @@ fn test_queue_add_updates_parent_status() {
             description: None,
             tags: vec![],
             parent_id: Some(parent.id.clone()),
+            estimated_minutes: None,
         },
     )
     .unwrap();
@@ -846,7 +1053,7 @@ fn test_queue_add_updates_parent_status() {
     assert_eq!(parent_before.status, TaskStatus::Draft);
 
     // 子タスクをキューに追加
-    QueueService::add_to_queue(&mut conn, child.id.clone()).unwrap();
+    QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, child.id.clone(), None, false).unwrap();
 
     // 子タスクが Active になることを確認
     let child_after = TaskService::get_task(&mut conn, &child.id).unwrap();
@@ -870,6 +1077,7 @@ fn test_queue_remove_updates_parent_status() {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         },
     )
     .unwrap();
@@ -882,6 +1090,7 @@ fn test_queue_remove_updates_parent_status() {
             description: None,
             tags: vec![],
             parent_id: Some(parent.id.clone()),
+            estimated_minutes: None,
         },
     )
     .unwrap();
@@ -894,27 +1103,42 @@ fn test_queue_remove_updates_parent_status() {
             description: None,
             tags: vec![],
             parent_id: Some(parent.id.clone()),
+            estimated_minutes: None,
         },
     )
     .unwrap();
 
     // 両方の子タスクをキューに追加
-    QueueService::add_to_queue(&mut conn, child1.id.clone()).unwrap();
-    QueueService::add_to_queue(&mut conn, child2.id.clone()).unwrap();
+    QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, child1.id.clone(), None, false)
+        .unwrap();
+    QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, child2.id.clone(), None, false)
+        .unwrap();
 
     // 親タスクは Active になっているはず
     let parent_mid = TaskService::get_task(&mut conn, &parent.id).unwrap();
     assert_eq!(parent_mid.status, TaskStatus::Active);
 
     // 子タスク1をキューから削除（draft に戻す）
-    QueueService::remove_from_queue(&mut conn, child1.id.clone(), "draft".to_string()).unwrap();
+    QueueService::remove_from_queue(
+        &mut conn,
+        DEFAULT_QUEUE_ID,
+        child1.id.clone(),
+        "draft".to_string(),
+    )
+    .unwrap();
 
     // 親タスクはまだ Active のまま（子タスク2が Active のため）
     let parent_mid2 = TaskService::get_task(&mut conn, &parent.id).unwrap();
     assert_eq!(parent_mid2.status, TaskStatus::Active);
 
     // 子タスク2もキューから削除（draft に戻す）
-    QueueService::remove_from_queue(&mut conn, child2.id.clone(), "draft".to_string()).unwrap();
+    QueueService::remove_from_queue(
+        &mut conn,
+        DEFAULT_QUEUE_ID,
+        child2.id.clone(),
+        "draft".to_string(),
+    )
+    .unwrap();
 
     // 親タスクも Draft に戻ることを確認
     let parent_after = TaskService::get_task(&mut conn, &parent.id).unwrap();
@@ -934,6 +1158,7 @@ fn test_queue_clear_updates_parent_status() {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         },
     )
     .unwrap();
@@ -946,19 +1171,20 @@ fn test_queue_clear_updates_parent_status() {
             description: None,
             tags: vec![],
             parent_id: Some(parent.id.clone()),
+            estimated_minutes: None,
         },
     )
     .unwrap();
 
     // 子タスクをキューに追加
-    QueueService::add_to_queue(&mut conn, child.id.clone()).unwrap();
+    QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, child.id.clone(), None, false).unwrap();
 
     // 親タスクは Active になっているはず
     let parent_mid = TaskService::get_task(&mut conn, &parent.id).unwrap();
     assert_eq!(parent_mid.status, TaskStatus::Active);
 
     // キュー全体をクリア
-    QueueService::clear_queue(&mut conn).unwrap();
+    QueueService::clear_queue(&mut conn, DEFAULT_QUEUE_ID, None).unwrap();
 
     // 子タスクが Draft に戻ることを確認
     let child_after = TaskService::get_task(&mut conn, &child.id).unwrap();
@@ -982,6 +1208,7 @@ fn test_hierarchy_completed_children_workflow() {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         },
     )
     .unwrap();
@@ -994,6 +1221,7 @@ fn test_hierarchy_completed_children_workflow() {
             description: None,
             tags: vec![],
             parent_id: Some(parent.id.clone()),
+            estimated_minutes: None,
         },
     )
     .unwrap();
@@ -1006,12 +1234,14 @@ fn test_hierarchy_completed_children_workflow() {
             description: None,
             tags: vec![],
             parent_id: Some(parent.id.clone()),
+            estimated_minutes: None,
         },
     )
     .unwrap();
 
     // 子タスク1をキューに追加 → Active になる
-    QueueService::add_to_queue(&mut conn, child1.id.clone()).unwrap();
+    QueueService::add_to_queue(&mut conn, DEFAULT_QUEUE_ID, child1.id.clone(), None, false)
+        .unwrap();
 
     // 子タスク1を完了させる（Completed） - ActiveステータスのためDieselで直接更新（REQ-0016をバイパス）
     diesel::update(tasks::table.find(&child1.id))
@@ -1072,6 +1302,7 @@ fn test_update_task_rejects_non_draft() {
         description: None,
         tags: vec![],
         parent_id: None,
+        estimated_minutes: None,
     };
     let task = TaskService::create_task(&mut conn, req).unwrap();
 
@@ -1132,6 +1363,7 @@ fn test_delete_task_rejects_non_draft() {
         description: None,
         tags: vec![],
         parent_id: None,
+        estimated_minutes: None,
     };
     let task = TaskService::create_task(&mut conn, req).unwrap();
 
@@ -1181,6 +1413,7 @@ fn test_delete_task_permanently() {
         description: None,
         tags: vec![],
         parent_id: None,
+        estimated_minutes: None,
     };
     let task = TaskService::create_task(&mut conn, req).unwrap();
 
@@ -1214,6 +1447,7 @@ fn test_restore_task() {
         description: Some("復元されるタスク".to_string()),
         tags: vec![],
         parent_id: None,
+        estimated_minutes: None,
     };
     let task = TaskService::create_task(&mut conn, req).unwrap();
 
@@ -1250,6 +1484,7 @@ fn test_list_tasks_with_status_filter() {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         },
     )
     .unwrap();
@@ -1261,6 +1496,7 @@ fn test_list_tasks_with_status_filter() {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         },
     )
     .unwrap();
@@ -1272,6 +1508,7 @@ fn test_list_tasks_with_status_filter() {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         },
     )
     .unwrap();
@@ -1283,6 +1520,7 @@ fn test_list_tasks_with_status_filter() {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         },
     )
     .unwrap();
@@ -1365,6 +1603,7 @@ fn test_list_tasks_paginated_paging_behavior() {
                 description: None,
                 tags: vec![],
                 parent_id: None,
+                estimated_minutes: None,
             },
         )
         .unwrap();
@@ -1377,6 +1616,7 @@ fn test_list_tasks_paginated_paging_behavior() {
             status: None,
             limit: Some(20),
             offset: Some(0),
+            cursor: None,
         },
     )
     .unwrap();
@@ -1391,6 +1631,7 @@ fn test_list_tasks_paginated_paging_behavior() {
             status: None,
             limit: Some(20),
             offset: Some(20),
+            cursor: None,
         },
     )
     .unwrap();
@@ -1405,6 +1646,7 @@ fn test_list_tasks_paginated_paging_behavior() {
             status: None,
             limit: Some(20),
             offset: Some(40),
+            cursor: None,
         },
     )
     .unwrap();
@@ -1448,6 +1690,7 @@ fn test_list_tasks_paginated_total_count_with_filters() {
                 description: None,
                 tags: vec![],
                 parent_id: None,
+                estimated_minutes: None,
             },
         )
         .unwrap();
@@ -1461,6 +1704,7 @@ fn test_list_tasks_paginated_total_count_with_filters() {
                 description: None,
                 tags: vec![],
                 parent_id: None,
+                estimated_minutes: None,
             },
         )
         .unwrap();
@@ -1481,6 +1725,7 @@ fn test_list_tasks_paginated_total_count_with_filters() {
                 description: None,
                 tags: vec![],
                 parent_id: None,
+                estimated_minutes: None,
             },
         )
         .unwrap();
@@ -1501,6 +1746,7 @@ fn test_list_tasks_paginated_total_count_with_filters() {
                 description: None,
                 tags: vec![],
                 parent_id: None,
+                estimated_minutes: None,
             },
         )
         .unwrap();
@@ -1521,6 +1767,7 @@ fn test_list_tasks_paginated_total_count_with_filters() {
             status: Some(vec!["completed".to_string()]),
             limit: None,
             offset: None,
+            cursor: None,
         },
     )
     .unwrap();
@@ -1534,6 +1781,7 @@ fn test_list_tasks_paginated_total_count_with_filters() {
             status: Some(vec!["archived".to_string()]),
             limit: None,
             offset: None,
+            cursor: None,
         },
     )
     .unwrap();
@@ -1551,6 +1799,7 @@ fn test_list_tasks_paginated_total_count_with_filters() {
             ]),
             limit: Some(100),
             offset: None,
+            cursor: None,
         },
     )
     .unwrap();
@@ -1578,6 +1827,7 @@ fn test_list_tasks_paginated_includes_parent_title_for_child() {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         },
     )
     .unwrap();
@@ -1590,6 +1840,7 @@ fn test_list_tasks_paginated_includes_parent_title_for_child() {
             description: None,
             tags: vec![],
             parent_id: Some(parent.id.clone()),
+            estimated_minutes: None,
         },
     )
     .unwrap();
@@ -1607,6 +1858,7 @@ fn test_list_tasks_paginated_includes_parent_title_for_child() {
             status: Some(vec!["completed".to_string()]),
             limit: Some(20),
             offset: Some(0),
+            cursor: None,
         },
     )
     .unwrap();
@@ -1639,6 +1891,7 @@ fn test_list_tasks_paginated_no_parent_title_for_root() {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         },
     )
     .unwrap();
@@ -1656,6 +1909,7 @@ fn test_list_tasks_paginated_no_parent_title_for_root() {
             status: Some(vec!["archived".to_string()]),
             limit: Some(20),
             offset: Some(0),
+            cursor: None,
         },
     )
     .unwrap();
@@ -1688,6 +1942,7 @@ fn test_list_tasks_paginated_batch_parent_title_fetch() {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         },
     )
     .unwrap();
@@ -1700,6 +1955,7 @@ fn test_list_tasks_paginated_batch_parent_title_fetch() {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         },
     )
     .unwrap();
@@ -1712,6 +1968,7 @@ fn test_list_tasks_paginated_batch_parent_title_fetch() {
             description: None,
             tags: vec![],
             parent_id: Some(parent1.id.clone()),
+            estimated_minutes: None,
         },
     )
     .unwrap();
@@ -1724,6 +1981,7 @@ fn test_list_tasks_paginated_batch_parent_title_fetch() {
             description: None,
             tags: vec![],
             parent_id: Some(parent2.id.clone()),
+            estimated_minutes: None,
         },
     )
     .unwrap();
@@ -1736,6 +1994,7 @@ fn test_list_tasks_paginated_batch_parent_title_fetch() {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         },
     )
     .unwrap();
@@ -1755,6 +2014,7 @@ fn test_list_tasks_paginated_batch_parent_title_fetch() {
             status: Some(vec!["completed".to_string()]),
             limit: Some(20),
             offset: Some(0),
+            cursor: None,
         },
     )
     .unwrap();
@@ -1795,6 +2055,7 @@ fn test_list_tasks_paginated_archived_with_parent_title() {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         },
     )
     .unwrap();
@@ -1807,6 +2068,7 @@ fn test_list_tasks_paginated_archived_with_parent_title() {
             description: None,
             tags: vec![],
             parent_id: Some(parent.id.clone()),
+            estimated_minutes: None,
         },
     )
     .unwrap();
@@ -1824,6 +2086,7 @@ fn test_list_tasks_paginated_archived_with_parent_title() {
             status: Some(vec!["archived".to_string()]),
             limit: Some(20),
             offset: Some(0),
+            cursor: None,
         },
     )
     .unwrap();
@@ -1859,6 +2122,7 @@ fn test_parent_updated_at_is_updated_on_child_status_change() {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         },
     )
     .unwrap();
@@ -1877,6 +2141,7 @@ fn test_parent_updated_at_is_updated_on_child_status_change() {
             description: None,
             tags: vec![],
             parent_id: Some(parent.id.clone()),
+            estimated_minutes: None,
         },
     )
     .unwrap();
@@ -1926,6 +2191,7 @@ fn test_parent_updated_at_multiple_child_changes() {
             description: None,
             tags: vec![],
             parent_id: None,
+            estimated_minutes: None,
         },
     )
     .unwrap();
@@ -1938,6 +2204,7 @@ fn test_parent_updated_at_multiple_child_changes() {
             description: None,
             tags: vec![],
             parent_id: Some(parent.id.clone()),
+            estimated_minutes: None,
         },
     )
     .unwrap();
@@ -1950,6 +2217,7 @@ fn test_parent_updated_at_multiple_child_changes() {
             description: None,
             tags: vec![],
             parent_id: Some(parent.id.clone()),
+            estimated_minutes: None,
         },
     )
     .unwrap();
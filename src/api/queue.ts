@@ -3,20 +3,82 @@ import { withErrorHandling } from "../lib/errorHandler";
 import type {
   AddToQueueRequest,
   CompleteAllQueueResponse,
+  DeferQueueEntryRequest,
+  DeferredTask,
+  DeferredTaskWithTitle,
+  EnqueueByFilterRequest,
+  EnqueueByFilterResult,
+  EnqueueSubtreeRequest,
+  CreateQueueRequest,
+  Queue,
   QueueEntry,
   QueueEntryWithTask,
+  QueueSchedule,
+  QueueSuggestion,
   RemoveFromQueueRequest,
+  RenameQueueRequest,
   ReorderQueueRequest,
   UpdateQueueRequest,
 } from "../types/queue";
 
 export const queueApi = {
+  /**
+   * 名前付きキューの一覧を取得（既定のキューが先頭）
+   */
+  async getQueues(): Promise<Queue[]> {
+    return await withErrorHandling(
+      () => invokeWithTimeout<Queue[]>("get_queues"),
+      "キュー一覧の取得に失敗しました"
+    );
+  },
+
+  /**
+   * 名前付きキューを作成
+   */
+  async createQueue(name: string): Promise<Queue> {
+    const req: CreateQueueRequest = { name };
+    return await withErrorHandling(
+      () => invokeWithTimeout<Queue>("create_queue", { req }),
+      "キューの作成に失敗しました"
+    );
+  },
+
+  /**
+   * 名前付きキューの名前を変更
+   */
+  async renameQueue(queueId: string, name: string): Promise<Queue> {
+    const req: RenameQueueRequest = { queueId, name };
+    return await withErrorHandling(
+      () => invokeWithTimeout<Queue>("rename_queue", { req }),
+      "キューの名前変更に失敗しました"
+    );
+  },
+
+  /**
+   * 名前付きキューを削除（既定のキューとタスクが残っているキューは削除できない）
+   */
+  async deleteQueue(queueId: string): Promise<void> {
+    return await withErrorHandling(
+      () => invokeWithTimeout<void>("delete_queue", { queueId }),
+      "キューの削除に失敗しました"
+    );
+  },
+
   /**
    * キュー全体を取得（タスク情報含む）
    */
-  async getQueue(): Promise<QueueEntryWithTask[]> {
+  async getQueue(queueId?: string): Promise<QueueEntryWithTask[]> {
+    const schedule = await queueApi.getSchedule(undefined, queueId);
+    return schedule.entries;
+  },
+
+  /**
+   * キューを予定付きで取得（予定開始・終了時刻、見積もり合計、作業時間超過）
+   */
+  async getSchedule(date?: string, queueId?: string): Promise<QueueSchedule> {
     return await withErrorHandling(
-      () => invokeWithTimeout<QueueEntryWithTask[]>("get_task_queue"),
+      () =>
+        invokeWithTimeout<QueueSchedule>("get_task_queue", { date, queueId }),
       "キューの取得に失敗しました"
     );
   },
@@ -24,48 +86,103 @@ export const queueApi = {
   /**
    * タスクをキューに追加（ステータスがActiveになる）
    */
-  async addToQueue(taskId: string): Promise<QueueEntry> {
-    const req: AddToQueueRequest = { taskId };
+  async addToQueue(taskId: string, queueId?: string): Promise<QueueEntry> {
+    const req: AddToQueueRequest = { taskId, queueId };
     return await withErrorHandling(
       () => invokeWithTimeout<QueueEntry>("add_task_to_queue", { req }),
       "タスクのキュー追加に失敗しました"
     );
   },
 
+  /**
+   * 親タスクの子タスクをまとめてキューに追加（Completedの子タスクはスキップ）
+   */
+  async enqueueSubtree(
+    parentId: string,
+    queueId?: string
+  ): Promise<QueueEntry[]> {
+    const req: EnqueueSubtreeRequest = { parentId, queueId };
+    return await withErrorHandling(
+      () => invokeWithTimeout<QueueEntry[]>("enqueue_subtree", { req }),
+      "子タスクのキュー追加に失敗しました"
+    );
+  },
+
+  /**
+   * 検索条件に一致するタスクをまとめてキューに追加（スキップしたタスクと理由も返す）
+   */
+  async enqueueByFilter(
+    req: EnqueueByFilterRequest
+  ): Promise<EnqueueByFilterResult> {
+    return await withErrorHandling(
+      () => invokeWithTimeout<EnqueueByFilterResult>("enqueue_by_filter", { req }),
+      "タスクの一括キュー追加に失敗しました"
+    );
+  },
+
   /**
    * タスクをキューから削除（ステータスを指定）
    */
   async removeFromQueue(
     taskId: string,
-    targetStatus: "draft" | "completed"
+    targetStatus: "draft" | "completed",
+    queueId?: string
   ): Promise<void> {
-    const req: RemoveFromQueueRequest = { taskId, targetStatus };
+    const req: RemoveFromQueueRequest = { taskId, targetStatus, queueId };
     return await withErrorHandling(
       () => invokeWithTimeout<void>("remove_task_from_queue", { req }),
       "タスクのキュー削除に失敗しました"
     );
   },
 
+  /**
+   * タスクを指定日まで延期（ステータスは変わらず、その日にキューへ戻る）
+   */
+  async deferEntry(
+    taskId: string,
+    until: string,
+    queueId?: string
+  ): Promise<DeferredTask> {
+    const req: DeferQueueEntryRequest = { taskId, until, queueId };
+    return await withErrorHandling(
+      () => invokeWithTimeout<DeferredTask>("defer_queue_entry", { req }),
+      "タスクの延期に失敗しました"
+    );
+  },
+
+  /**
+   * 延期中のタスク一覧を取得
+   */
+  async getDeferredTasks(queueId?: string): Promise<DeferredTaskWithTitle[]> {
+    return await withErrorHandling(
+      () =>
+        invokeWithTimeout<DeferredTaskWithTitle[]>("get_deferred_tasks", {
+          queueId,
+        }),
+      "延期中のタスクの取得に失敗しました"
+    );
+  },
+
   /**
    * タスクをdraftに戻す（タスクプールに戻る）
    */
-  async returnToDraft(taskId: string): Promise<void> {
-    return this.removeFromQueue(taskId, "draft");
+  async returnToDraft(taskId: string, queueId?: string): Promise<void> {
+    return this.removeFromQueue(taskId, "draft", queueId);
   },
 
   /**
    * タスクを完了としてマーク
    */
-  async markAsCompleted(taskId: string): Promise<void> {
-    return this.removeFromQueue(taskId, "completed");
+  async markAsCompleted(taskId: string, queueId?: string): Promise<void> {
+    return this.removeFromQueue(taskId, "completed", queueId);
   },
 
   /**
    * キュー全体をクリア
    */
-  async clearQueue(): Promise<void> {
+  async clearQueue(queueId?: string): Promise<void> {
     return await withErrorHandling(
-      () => invokeWithTimeout<void>("clear_task_queue"),
+      () => invokeWithTimeout<void>("clear_task_queue", { queueId }),
       "キューのクリアに失敗しました"
     );
   },
@@ -73,9 +190,12 @@ export const queueApi = {
   /**
    * キュー内の全タスクを完了状態にする
    */
-  async completeAll(): Promise<CompleteAllQueueResponse> {
+  async completeAll(queueId?: string): Promise<CompleteAllQueueResponse> {
     return await withErrorHandling(
-      () => invokeWithTimeout<CompleteAllQueueResponse>("complete_all_queue"),
+      () =>
+        invokeWithTimeout<CompleteAllQueueResponse>("complete_all_queue", {
+          queueId,
+        }),
       "全タスクの完了に失敗しました"
     );
   },
@@ -85,9 +205,10 @@ export const queueApi = {
    */
   async updateQueuePosition(
     taskId: string,
-    newPosition: number
+    newPosition: number,
+    queueId?: string
   ): Promise<QueueEntry> {
-    const req: UpdateQueueRequest = { taskId, newPosition };
+    const req: UpdateQueueRequest = { taskId, newPosition, queueId };
     return await withErrorHandling(
       () => invokeWithTimeout<QueueEntry>("update_queue_position", { req }),
       "キュー位置の更新に失敗しました"
@@ -97,11 +218,31 @@ export const queueApi = {
   /**
    * キュー全体を一括で並び替え
    */
-  async reorderQueue(taskIds: string[]): Promise<QueueEntry[]> {
-    const req: ReorderQueueRequest = { taskIds };
+  async reorderQueue(
+    taskIds: string[],
+    queueId?: string
+  ): Promise<QueueEntry[]> {
+    const req: ReorderQueueRequest = { taskIds, queueId };
     return await withErrorHandling(
       () => invokeWithTimeout<QueueEntry[]>("reorder_task_queue", { req }),
       "キューの並び替えに失敗しました"
     );
   },
+
+  /**
+   * 今日のキューへの追加候補を取得（スコア順、理由付き）
+   */
+  async suggestQueue(
+    limit?: number,
+    queueId?: string
+  ): Promise<QueueSuggestion[]> {
+    return await withErrorHandling(
+      () =>
+        invokeWithTimeout<QueueSuggestion[]>("suggest_queue", {
+          limit,
+          queueId,
+        }),
+      "キューの提案の取得に失敗しました"
+    );
+  },
 };
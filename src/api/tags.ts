@@ -1,17 +1,53 @@
 import { invokeWithTimeout } from "../lib/invoke";
 import { withErrorHandling } from "../lib/errorHandler";
-import type { Tag, CreateTagRequest, UpdateTagRequest } from "../types/tag";
+import type {
+  Tag,
+  TagTreeNode,
+  TagAlias,
+  CreateTagRequest,
+  UpdateTagRequest,
+  TagGroup,
+  TagGroupWithTags,
+  CreateTagGroupRequest,
+  UpdateTagGroupRequest,
+  AutoTagRule,
+  CreateAutoTagRuleRequest,
+  UpdateAutoTagRuleRequest,
+} from "../types/tag";
+
+/**
+ * タグツリーを平坦なリストに変換（親 → 子の順）
+ */
+function flattenTagTree(nodes: TagTreeNode[]): Tag[] {
+  return nodes.flatMap((node) => {
+    const {
+      label: _label,
+      totalUsageCount: _total,
+      aliases: _aliases,
+      children,
+      ...tag
+    } = node;
+    return [tag, ...flattenTagTree(children)];
+  });
+}
 
 /**
  * タグAPI
  */
 export const tagsApi = {
   /**
-   * 全タグを取得
+   * 全タグを取得（名前空間のツリーを平坦にしたもの）
    */
   async list(): Promise<Tag[]> {
+    return flattenTagTree(await tagsApi.listTree());
+  },
+
+  /**
+   * 全タグを名前空間のツリーとして取得（子孫を含めた使用数付き）
+   */
+  async listTree(): Promise<TagTreeNode[]> {
     return await withErrorHandling(
-      () => invokeWithTimeout<Tag[]>("list_tags"),
+      () => invokeWithTimeout<TagTreeNode[]>("list_tags"),
       "タグの取得に失敗しました"
     );
   },
@@ -36,6 +72,36 @@ export const tagsApi = {
     );
   },
 
+  /**
+   * タグを統合（統合元のタグを統合先へまとめ、統合先の使用数を返す）
+   */
+  async merge(sourceIds: string[], targetId: string): Promise<number> {
+    return await withErrorHandling(
+      () => invokeWithTimeout<number>("merge_tags", { sourceIds, targetId }),
+      "タグの統合に失敗しました"
+    );
+  },
+
+  /**
+   * タグに別名を追加
+   */
+  async addAlias(tagId: string, alias: string): Promise<TagAlias> {
+    return await withErrorHandling(
+      () => invokeWithTimeout<TagAlias>("add_tag_alias", { tagId, alias }),
+      "タグの別名の追加に失敗しました"
+    );
+  },
+
+  /**
+   * タグの別名を削除
+   */
+  async removeAlias(alias: string): Promise<void> {
+    return await withErrorHandling(
+      () => invokeWithTimeout<void>("remove_tag_alias", { alias }),
+      "タグの別名の削除に失敗しました"
+    );
+  },
+
   /**
    * タグを削除
    */
@@ -45,4 +111,117 @@ export const tagsApi = {
       "タグの削除に失敗しました"
     );
   },
+
+  /**
+   * 全タググループを取得（所属するタグ付き）
+   */
+  async listGroups(): Promise<TagGroupWithTags[]> {
+    return await withErrorHandling(
+      () => invokeWithTimeout<TagGroupWithTags[]>("list_tag_groups"),
+      "タググループの取得に失敗しました"
+    );
+  },
+
+  /**
+   * タググループを作成
+   */
+  async createGroup(request: CreateTagGroupRequest): Promise<TagGroup> {
+    return await withErrorHandling(
+      () => invokeWithTimeout<TagGroup>("create_tag_group", { request }),
+      "タググループの作成に失敗しました"
+    );
+  },
+
+  /**
+   * タググループを更新
+   */
+  async updateGroup(
+    groupId: string,
+    request: UpdateTagGroupRequest
+  ): Promise<TagGroup> {
+    return await withErrorHandling(
+      () =>
+        invokeWithTimeout<TagGroup>("update_tag_group", { groupId, request }),
+      "タググループの更新に失敗しました"
+    );
+  },
+
+  /**
+   * タググループを削除（所属していたタグはグループから外れる）
+   */
+  async deleteGroup(groupId: string): Promise<void> {
+    return await withErrorHandling(
+      () => invokeWithTimeout<void>("delete_tag_group", { groupId }),
+      "タググループの削除に失敗しました"
+    );
+  },
+
+  /**
+   * タグの所属グループを設定（groupId を省略するとグループから外す）
+   */
+  async setGroup(tagId: string, groupId?: string): Promise<Tag> {
+    return await withErrorHandling(
+      () => invokeWithTimeout<Tag>("set_tag_group", { tagId, groupId }),
+      "タグのグループ設定に失敗しました"
+    );
+  },
+
+  /**
+   * 自動タグ付けルールを取得（作成順）
+   */
+  async listAutoTagRules(): Promise<AutoTagRule[]> {
+    return await withErrorHandling(
+      () => invokeWithTimeout<AutoTagRule[]>("list_auto_tag_rules"),
+      "自動タグ付けルールの取得に失敗しました"
+    );
+  },
+
+  /**
+   * 自動タグ付けルールを作成
+   */
+  async createAutoTagRule(
+    request: CreateAutoTagRuleRequest
+  ): Promise<AutoTagRule> {
+    return await withErrorHandling(
+      () => invokeWithTimeout<AutoTagRule>("create_auto_tag_rule", { request }),
+      "自動タグ付けルールの作成に失敗しました"
+    );
+  },
+
+  /**
+   * 自動タグ付けルールを更新
+   */
+  async updateAutoTagRule(
+    ruleId: string,
+    request: UpdateAutoTagRuleRequest
+  ): Promise<AutoTagRule> {
+    return await withErrorHandling(
+      () =>
+        invokeWithTimeout<AutoTagRule>("update_auto_tag_rule", {
+          ruleId,
+          request,
+        }),
+      "自動タグ付けルールの更新に失敗しました"
+    );
+  },
+
+  /**
+   * 自動タグ付けルールを削除（付けたタグはタスクに残る）
+   */
+  async deleteAutoTagRule(ruleId: string): Promise<void> {
+    return await withErrorHandling(
+      () => invokeWithTimeout<void>("delete_auto_tag_rule", { ruleId }),
+      "自動タグ付けルールの削除に失敗しました"
+    );
+  },
+
+  /**
+   * 有効なルールをすべてのタスクに適用し直す（タグを付けたタスク数を返す）
+   */
+  async applyAutoTagRules(): Promise<number> {
+    return await withErrorHandling(
+      () => invokeWithTimeout<number>("apply_auto_tag_rules"),
+      "自動タグ付けルールの適用に失敗しました"
+    );
+  },
 };
@@ -7,6 +7,7 @@ import type {
   DuplicateTaskRequest,
   UpdateTaskRequest,
   PaginatedTaskResponse,
+  ConsistencyReport,
 } from "../types/task";
 
 /**
@@ -14,11 +15,15 @@ import type {
  */
 export const tasksApi = {
   /**
-   * タスクを作成
+   * タスクを作成（autoCreateTags で存在しないタグを自動作成）
    */
-  async create(request: CreateTaskRequest): Promise<Task> {
+  async create(
+    request: CreateTaskRequest,
+    autoCreateTags?: boolean
+  ): Promise<Task> {
     return await withErrorHandling(
-      () => invokeWithTimeout<Task>("create_task", { req: request }),
+      () =>
+        invokeWithTimeout<Task>("create_task", { req: request, autoCreateTags }),
       "タスクの作成に失敗しました"
     );
   },
@@ -43,11 +48,20 @@ export const tasksApi = {
   },
 
   /**
-   * タスクを更新
+   * タスクを更新（autoCreateTags で存在しないタグを自動作成）
    */
-  async update(taskId: string, request: UpdateTaskRequest): Promise<Task> {
+  async update(
+    taskId: string,
+    request: UpdateTaskRequest,
+    autoCreateTags?: boolean
+  ): Promise<Task> {
     return await withErrorHandling(
-      () => invokeWithTimeout<Task>("update_task", { taskId, req: request }),
+      () =>
+        invokeWithTimeout<Task>("update_task", {
+          taskId,
+          req: request,
+          autoCreateTags,
+        }),
       "タスクの更新に失敗しました"
     );
   },
@@ -138,7 +152,7 @@ export const tasksApi = {
    * タスクを検索（キーワード、ステータス、タグでフィルタリング）
    * @param q - キーワード（タイトル、説明で検索）
    * @param status - ステータスフィルタ
-   * @param tags - タグフィルタ（OR条件）
+   * @param tags - タグフィルタ（同じグループ内はOR条件、グループ同士はAND条件）
    * @returns マッチしたタスクリスト
    */
   async search(
@@ -160,7 +174,7 @@ export const tasksApi = {
    * タスク検索（ページネーション対応、Backend search）
    * @param q - 検索キーワード（タイトル・説明文）
    * @param status - ステータスフィルタ
-   * @param tags - タグフィルタ（OR条件）
+   * @param tags - タグフィルタ（同じグループ内はOR条件、グループ同士はAND条件）
    * @param limit - 1ページあたりの件数（デフォルト: 100）
    * @param offset - オフセット（デフォルト: 0）
    * @returns ページネーション対応の検索結果
@@ -187,7 +201,7 @@ export const tasksApi = {
   /**
    * タスクIDのみを検索（軽量版）
    * タグフィルター用に最適化
-   * @param tags - タグフィルタ（OR条件）
+   * @param tags - タグフィルタ（同じグループ内はOR条件、グループ同士はAND条件）
    * @param status - ステータスフィルタ（デフォルト: draft + active + completed）
    * @returns マッチしたタスクIDリスト
    */
@@ -200,4 +214,17 @@ export const tasksApi = {
         })
     );
   },
+  /**
+   * タスクとキューの整合性をチェック
+   * @param repair - trueの場合は不整合を修復する
+   */
+  async checkConsistency(repair?: boolean): Promise<ConsistencyReport> {
+    return await withErrorHandling(
+      () =>
+        invokeWithTimeout<ConsistencyReport>("check_consistency", {
+          repair: repair ?? null,
+        }),
+      "整合性チェックに失敗しました"
+    );
+  },
 };
@@ -1,36 +1,279 @@
+export interface Queue {
+  id: string; // 既定のキューは "default"
+  name: string;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface CreateQueueRequest {
+  name: string;
+}
+
+export interface RenameQueueRequest {
+  queueId: string;
+  name: string;
+}
+
 export interface QueueEntry {
   taskId: string;
-  position: number;
+  queueId: string;
+  rank: string; // 同じ日のキュー内の並び順キー（辞書順）
   addedAt: string;
+  queueDate: string; // YYYY-MM-DD
 }
 
 export interface QueueEntryWithTask {
   taskId: string;
-  position: number;
+  queueId: string;
+  position: number; // キュー内の位置（0始まり）
+  rank: string;
   addedAt: string;
+  queueDate: string; // YYYY-MM-DD
   taskTitle: string;
   taskStatus: string;
   taskDescription?: string;
+  estimatedMinutes?: number; // 見積もり時間（分）
+  scheduledStart?: string; // 予定開始時刻（ローカル、YYYY-MM-DDTHH:MM:SS）
+  scheduledEnd?: string; // 予定終了時刻（見積もり未設定の場合は無し）
+}
+
+export interface QueueSchedule {
+  queueId: string;
+  queueDate: string; // YYYY-MM-DD
+  entries: QueueEntryWithTask[];
+  totalEstimatedMinutes: number; // 見積もり時間の合計
+  unestimatedCount: number; // 見積もり未設定のエントリ数
+  workingMinutes: number; // 1日の作業時間
+  overloaded: boolean; // 合計が作業時間を超えているか
+  overloadMinutes: number; // 超過分（分）
+  projectedEnd?: string; // 最後のエントリの予定終了時刻
 }
 
 export interface AddToQueueRequest {
+  queueId?: string; // 省略時は既定のキュー
   taskId: string;
+  queueDate?: string; // YYYY-MM-DD（省略時は今日）
+  overrideLimits?: boolean; // キューの上限（WIP制限）を無視する
+}
+
+export interface EnqueueSubtreeRequest {
+  queueId?: string; // 省略時は既定のキュー
+  parentId: string;
+  queueDate?: string; // YYYY-MM-DD（省略時は今日）
+  overrideLimits?: boolean; // キューの上限（WIP制限）を無視する
+}
+
+export type EnqueueSortKey = "created" | "updated" | "title" | "estimate";
+
+export interface EnqueueByFilterRequest {
+  queueId?: string; // 省略時は既定のキュー
+  filter: { q?: string; status?: string; tags?: string[] }; // search_tasks と同じ検索条件
+  sortBy?: EnqueueSortKey; // 省略時は作成日時の古い順
+  descending?: boolean;
+  queueDate?: string; // YYYY-MM-DD（省略時は今日）
+  overrideLimits?: boolean; // キューの上限（WIP制限）を無視する
+}
+
+export type EnqueueSkipReason =
+  | "already_queued"
+  | "has_children"
+  | "archived"
+  | "completed";
+
+export interface SkippedQueueTask {
+  taskId: string;
+  title: string;
+  reason: EnqueueSkipReason;
+}
+
+export interface EnqueueByFilterResult {
+  added: QueueEntry[];
+  skipped: SkippedQueueTask[];
 }
 
 export interface RemoveFromQueueRequest {
+  queueId?: string; // 省略時は既定のキュー
   taskId: string;
   targetStatus: "draft" | "completed";
 }
 
+export interface DeferQueueEntryRequest {
+  queueId?: string; // 省略時は既定のキュー
+  taskId: string;
+  until: string; // YYYY-MM-DD（明日以降）
+}
+
+export interface DeferredTask {
+  taskId: string;
+  queueId: string; // 戻す先のキュー
+  untilDate: string; // YYYY-MM-DD（この日のロールオーバーでキューに戻る）
+  deferredFrom: string; // 延期元のキューの日付
+  deferredAt: string;
+}
+
+export interface DeferredTaskWithTitle extends DeferredTask {
+  taskTitle: string;
+  taskStatus: string;
+}
+
 export interface UpdateQueueRequest {
+  queueId?: string; // 省略時は既定のキュー
   taskId: string;
   newPosition: number;
 }
 
 export interface ReorderQueueRequest {
+  queueId?: string; // 省略時は既定のキュー
   taskIds: string[];
+  queueDate?: string; // YYYY-MM-DD（省略時は今日）
+  overrideLimits?: boolean; // キューの上限（WIP制限）を超えていても並び替える
+}
+
+export interface MoveQueueEntryRequest {
+  queueId?: string; // 省略時は既定のキュー
+  taskId: string;
+  targetQueueId?: string; // 移動先のキュー（省略時は同じキュー）
+  targetDate: string; // YYYY-MM-DD
+  position?: number; // 省略時は末尾
+  overrideLimits?: boolean; // 移動先のキューの上限（WIP制限）を無視する
 }
 
 export interface CompleteAllQueueResponse {
   completedCount: number;
 }
+
+export interface RolloverSettings {
+  dayStartHour: number; // 1日の開始時刻（0〜23）
+  carryOverToTop: boolean; // 持ち越しタスクをキュー先頭に入れるか
+}
+
+export interface QueueLimits {
+  maxEntries?: number; // 1日のキューの最大エントリ数
+  maxTotalMinutes?: number; // 見積もり合計の上限（分）
+  tagCaps: Record<string, number>; // タグ名 → 最大エントリ数
+}
+
+export interface WorkdaySettings {
+  workdayStart?: string; // 作業開始時刻（HH:MM、未設定の場合は現在時刻から計算）
+  workingMinutes: number; // 1日の作業時間（分）
+}
+
+export interface CarriedOverTask {
+  taskId: string;
+  taskTitle: string;
+  fromDate: string; // YYYY-MM-DD
+  slipCount: number; // 持ち越し回数（今回を含む）
+}
+
+// "queue-rolled-over" イベントのペイロードとしても送信される
+export interface RolloverResult {
+  today: string; // YYYY-MM-DD
+  rolledOverDates: string[];
+  carriedOver: CarriedOverTask[];
+  finishedCount: number;
+  activatedTaskIds: string[];
+  restoredTaskIds: string[]; // 延期期限を迎えて今日のキューへ戻したタスク
+  releasedTaskIds: string[]; // 延期期限を迎えたがキューへ戻せずプールに戻したタスク
+}
+
+export interface TaskSlipCount {
+  taskId: string;
+  taskTitle: string;
+  slipCount: number;
+  lastSlippedDate: string; // YYYY-MM-DD
+}
+
+export type QueueOutcome =
+  | "completed"
+  | "archived"
+  | "returned_to_draft"
+  | "carried_over"
+  | "deferred";
+
+export interface QueueHistoryEntry {
+  id: number;
+  queueDate: string; // YYYY-MM-DD
+  taskId: string;
+  taskTitle: string; // 記録時点のタイトル
+  position: number;
+  outcome: QueueOutcome;
+  recordedAt: string;
+}
+
+export interface DailyQueueStats {
+  date: string; // YYYY-MM-DD
+  planned: number;
+  completed: number;
+  returnedToDraft: number;
+  archived: number;
+  carriedOver: number;
+  deferred: number;
+  remaining: number; // まだキューに残っているタスク数
+  completionRate: number; // completed / planned（0.0〜1.0）
+  focusedMinutes: number; // 集中セッションで集中した時間（分）
+}
+
+export interface CompletionStreak {
+  current: number;
+  longest: number;
+  lastCompletedDate?: string;
+}
+
+export interface WeeklyTagTotal {
+  weekStart: string; // 月曜日（YYYY-MM-DD）
+  tag: string;
+  completed: number;
+}
+
+export type SuggestionReasonKind =
+  | "slipped" // 持ち越されたことがある
+  | "in_progress" // 集中セッションの実績あり
+  | "parent_active" // 親タスクが進行中
+  | "stale" // 長期間更新なし
+  | "quick_win" // 見積もりが短い
+  | "waiting"; // 作成からの経過日数のみ
+
+export interface SuggestionReason {
+  kind: SuggestionReasonKind;
+  message: string;
+}
+
+export interface QueueSuggestion {
+  taskId: string;
+  title: string;
+  estimatedMinutes?: number;
+  score: number;
+  reasons: SuggestionReason[]; // 寄与の大きい順
+}
+
+export type FocusSessionState = "running" | "paused" | "finished";
+
+export type FocusPhase = "work" | "break" | "over";
+
+export interface FocusSettings {
+  workMinutes: number; // 作業時間（分、1〜240）
+  breakMinutes: number; // 休憩時間（分、0〜120）
+}
+
+export interface FocusSession {
+  id: number;
+  taskId: string;
+  taskTitle: string;
+  queueDate: string; // YYYY-MM-DD
+  state: FocusSessionState;
+  phase: FocusPhase;
+  workMinutes: number;
+  breakMinutes: number;
+  startedAt: string;
+  endedAt?: string;
+  focusedSeconds: number; // 集中した秒数（作業時間が上限）
+  remainingSeconds: number; // 現在のフェーズの残り秒数
+  completedTask: boolean;
+}
+
+export interface TaskFocusTotal {
+  taskId: string;
+  taskTitle: string;
+  sessionCount: number;
+  focusedMinutes: number;
+}
@@ -1,5 +1,6 @@
 /**
  * タグレスポンス（バックエンドからの返却値）
+ * name は名前空間を含む完全なパス（"work/clientA/billing"）
  */
 export interface Tag {
   id: string;
@@ -7,6 +8,23 @@ export interface Tag {
   color?: string;
   usageCount: number;
   createdAt: string;
+  parentId?: string; // 親タグ（名前空間）のID
+  groupId?: string; // 所属するタググループのID
+  // ステータスごとの使用数（usageCount の内訳）
+  draftCount: number;
+  activeCount: number;
+  completedCount: number;
+  archivedCount: number;
+}
+
+/**
+ * タグツリーのノード（list_tags の返却値）
+ */
+export interface TagTreeNode extends Tag {
+  label: string; // パスの末尾の名前
+  totalUsageCount: number; // 自身と子孫のタグが付いたタスク数
+  aliases: string[]; // タグの別名（名前順）
+  children: TagTreeNode[];
 }
 
 /**
@@ -23,6 +41,100 @@ export interface CreateTagRequest {
 export interface UpdateTagRequest {
   name?: string;
   color?: string;
+  merge?: boolean; // 変更後の名前のタグが既に存在する場合、そのタグへ統合する
+}
+
+/**
+ * タグの別名（タスクへのタグ付けと検索で正式なタグに読み替えられる）
+ */
+export interface TagAlias {
+  alias: string;
+  tagId: string;
+  createdAt: string;
+}
+
+/**
+ * タググループ（exclusive のグループのタグは1つのタスクに1つまで）
+ */
+export interface TagGroup {
+  id: string;
+  name: string;
+  exclusive: boolean;
+  createdAt: string;
+  updatedAt: string;
+}
+
+/**
+ * 所属するタグ付きのタググループ（list_tag_groups の返却値）
+ */
+export interface TagGroupWithTags extends TagGroup {
+  tags: Tag[];
+}
+
+/**
+ * タググループ作成リクエスト
+ */
+export interface CreateTagGroupRequest {
+  name: string;
+  exclusive?: boolean;
+}
+
+/**
+ * タググループ更新リクエスト
+ */
+export interface UpdateTagGroupRequest {
+  name?: string;
+  exclusive?: boolean;
+}
+
+/**
+ * 自動タグ付けルールで判定する項目
+ * （タスクにカスタムフィールドが無いため、カスタムフィールドは判定できない）
+ */
+export type AutoTagTarget = "title" | "description" | "parent_id";
+
+/**
+ * 自動タグ付けルールの一致方法（contains は大文字小文字を区別しない）
+ */
+export type AutoTagMatch = "contains" | "regex" | "equals";
+
+/**
+ * 自動タグ付けルール（一致したタスクの作成・更新時にタグを付ける）
+ */
+export interface AutoTagRule {
+  id: string;
+  name: string;
+  target: AutoTagTarget;
+  matchType: AutoTagMatch;
+  pattern: string;
+  tags: string[]; // 付けるタグ名（名前順）
+  enabled: boolean;
+  createdAt: string;
+  updatedAt: string;
+}
+
+/**
+ * 自動タグ付けルール作成リクエスト
+ */
+export interface CreateAutoTagRuleRequest {
+  name: string;
+  target: AutoTagTarget;
+  matchType: AutoTagMatch;
+  pattern: string;
+  tags: string[]; // 別名も指定できる
+  enabled?: boolean; // 省略時は有効
+}
+
+/**
+ * 自動タグ付けルール更新リクエスト
+ */
+export interface UpdateAutoTagRuleRequest {
+  name?: string;
+  target?: AutoTagTarget;
+  matchType?: AutoTagMatch;
+  pattern?: string;
+  tags?: string[];
+  enabled?: boolean;
 }
 
 /**
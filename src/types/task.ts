@@ -17,6 +17,19 @@ export interface Task {
   createdAt: string;
   updatedAt: string;
   childrenIds: string[]; // 子タスクのIDリスト
+  estimatedMinutes?: number; // 見積もり時間（分）
+  duplicateWarnings?: SimilarTask[]; // 重複の可能性がある既存タスク（checkDuplicates指定時）
+  createdTags?: string[]; // 新規作成されたタグ名（autoCreateTags指定時）
+}
+
+/**
+ * 類似タスク（find_similar_tasks API用）
+ */
+export interface SimilarTask {
+  taskId: string;
+  title: string;
+  status: TaskStatus;
+  score: number; // 類似度（0.0〜1.0）
 }
 
 /**
@@ -25,6 +38,8 @@ export interface Task {
 export interface PaginatedTaskResponse {
   tasks: Task[];
   total: number;
+  nextCursor?: string; // 次ページ取得用カーソル
+  prevCursor?: string; // 前ページ取得用カーソル
 }
 
 /**
@@ -40,9 +55,20 @@ export interface TaskHierarchy {
   parentId?: string;
   createdAt: string;
   updatedAt: string;
+  estimatedMinutes?: number; // 見積もり時間（分）
   children: TaskHierarchy[]; // 子タスクの配列（再帰的）
 }
 
+/**
+ * タスク階層の差分レスポンス（get_task_hierarchy_changes API用）
+ */
+export interface TaskHierarchyDelta {
+  changed: TaskHierarchy[]; // since 以降に変更されたサブツリー
+  removedRootIds: string[]; // プールから外れたルートタスクID
+  movedUnderParentIds: string[]; // 変更された子タスクID（同じIDのルートがあれば取り除く）
+  syncedAt: string; // 次回の since に指定する時刻
+}
+
 /**
  * タスク作成リクエスト
  */
@@ -51,6 +77,7 @@ export interface CreateTaskRequest {
   description?: string;
   tags?: string[];
   parentId?: string;
+  estimatedMinutes?: number; // 見積もり時間（分、1〜1440）
 }
 
 /**
@@ -71,3 +98,36 @@ export interface UpdateTaskRequest {
   tags?: string[];
   parentId?: string;
 }
+
+/**
+ * クイックスイッチャー検索結果（Ctrl+K ジャンプ用）
+ */
+export interface QuickSwitchResult {
+  taskId: string;
+  shortId: string;
+  title: string;
+  status: TaskStatus;
+  tags: string[];
+  score: number;
+  titleHighlights: number[];
+}
+
+/**
+ * 整合性チェックで検出する不整合の種別
+ */
+export type ConsistencyIssueKind =
+  | "active_not_queued" // Activeのタスクがキューに無い
+  | "closed_task_queued" // Completed/Archivedのタスクがキューに残っている
+  | "parent_status_mismatch" // 親タスクのステータスが子タスクと一致しない
+  | "invalid_queue_rank"; // キューの並び順キーが不正
+
+export interface ConsistencyIssue {
+  kind: ConsistencyIssueKind;
+  taskId: string;
+  message: string;
+}
+
+export interface ConsistencyReport {
+  issues: ConsistencyIssue[]; // 修復した場合は修復した不整合
+  repaired: boolean;
+}